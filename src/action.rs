@@ -2,7 +2,14 @@ use {
     super::component_name::ComponentName,
     crate::{
         app_error::AppError,
-        tg::td_enums::{TdChatList, TdMessageReplyToMessage},
+        components::unread_dashboard::UnreadChat,
+        tg::edit_history::DiffSpan,
+        tg::formatting_preview::FormattingPreview,
+        tg::td_enums::{
+            TdBotMenu, TdCallEntry, TdChatList, TdChatPermissions, TdForumTopic, TdFoundMessage,
+            TdMessageReplyToMessage, TdMessageSender, TdParseMode, TdPollEntry, TdScheduledMessage,
+            TdSession,
+        },
     },
     crossterm::event::{KeyCode, KeyModifiers},
     ratatui::layout::Rect,
@@ -109,8 +116,211 @@ pub enum Action {
     /// If `revoke` is true, the message will be deleted for everyone.
     /// If `revoke` is false, the message will be deleted only for the current user.
     DeleteMessages(Vec<i64>, bool),
+    /// ForwardMessages action.
+    /// The first parameter is the `message_ids`, the second parameter is the
+    /// `chat_id` of the destination chat and the third parameter is the
+    /// `send_copy`.
+    /// If `send_copy` is true, the messages are sent without the link to the
+    /// original ones.
+    ForwardMessages(Vec<i64>, i64, bool),
     /// ViewAllMessages action.
     ViewAllMessages,
+    /// ViewMessage action with two `i64`.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// `message_id` that is marked as read.
+    ViewMessage(i64, i64),
+    /// OpenSplitView action with two `i64`.
+    /// The first parameter is the `chat_id` of the left chat and the second
+    /// parameter is the `chat_id` of the right chat.
+    OpenSplitView(i64, i64),
+    /// CloseSplitView action.
+    CloseSplitView,
+    /// SwapSplitFocus action.
+    SwapSplitFocus,
+    /// GetCallbackQueryAnswer action with a `i64` and a `String`.
+    /// The first parameter is the `message_id` of the message with the inline
+    /// keyboard and the second parameter is the `data` of the pressed button.
+    GetCallbackQueryAnswer(i64, String),
+    /// SetChatMuteFor action with a `i64` and a `i32`.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// time, in seconds, the chat is muted for; 0 unmutes the chat.
+    SetChatMuteFor(i64, i32),
+    /// OpenReactionPicker action with a `i64`.
+    /// The parameter is the `message_id` of the message to react to, the
+    /// reactions available for it are requested to TDLib.
+    OpenReactionPicker(i64),
+    /// ShowReactionPicker action with a `i64` and a `Vec<String>`.
+    /// The first parameter is the `message_id` of the message to react to and
+    /// the second parameter is the emoji of the available reactions.
+    ShowReactionPicker(i64, Vec<String>),
+    /// AddReaction action with a `i64` and a `String`.
+    /// The first parameter is the `message_id` and the second parameter is
+    /// the emoji of the reaction.
+    AddReaction(i64, String),
+    /// ShowCallHistory action with a `i64`.
+    /// The parameter is the `chat_id` whose recent calls are requested to
+    /// TDLib.
+    ShowCallHistory(i64),
+    /// ShowCallHistoryPanel action with a `Vec<TdCallEntry>`.
+    /// The parameter is the list of the recent calls of the chat.
+    ShowCallHistoryPanel(Vec<TdCallEntry>),
+    /// ShowChatPolls action with a `i64`.
+    /// The parameter is the `chat_id` whose polls are searched with TDLib.
+    ShowChatPolls(i64),
+    /// ShowChatPollsPanel action with a `Vec<TdPollEntry>`.
+    /// The parameter is the list of the polls of the chat.
+    ShowChatPollsPanel(Vec<TdPollEntry>),
+    /// ShowForumTopics action with a `i64`.
+    /// The parameter is the `chat_id` of the forum whose topics are listed
+    /// with TDLib.
+    ShowForumTopics(i64),
+    /// ShowForumTopicsPanel action with a `Vec<TdForumTopic>`.
+    /// The parameter is the list of the topics of the forum.
+    ShowForumTopicsPanel(Vec<TdForumTopic>),
+    /// OpenBotMenu action with a `i64`.
+    /// The parameter is the `chat_id` of the chat with the bot whose menu is
+    /// got with TDLib.
+    OpenBotMenu(i64),
+    /// ShowBotMenuPanel action with a `TdBotMenu`.
+    /// The parameter is the menu button or the commands of the bot.
+    ShowBotMenuPanel(TdBotMenu),
+    /// OpenForumTopic action with a `i64`.
+    /// The parameter is the `message_thread_id` of the topic the open chat is
+    /// filtered by, `0` to show the messages of all the topics.
+    OpenForumTopic(i64),
+    /// OpenComments action with a `i64`.
+    /// The parameter is the `message_id` of the post of the open channel
+    /// whose discussion thread is opened.
+    OpenComments(i64),
+    /// ActiveSessions action.
+    /// It is used to list the sessions logged in to the account with TDLib.
+    ActiveSessions,
+    /// ShowActiveSessionsPanel action with a `Vec<TdSession>`.
+    /// The parameter is the list of the sessions of the account.
+    ShowActiveSessionsPanel(Vec<TdSession>),
+    /// TerminateSession action with a `i64`.
+    /// The parameter is the identifier of the session to log out.
+    TerminateSession(i64),
+    /// ShowScheduledMessages action with a `i64`.
+    /// The parameter is the `chat_id` whose scheduled messages are listed
+    /// with TDLib.
+    ShowScheduledMessages(i64),
+    /// ShowScheduledMessagesPanel action with a `Vec<TdScheduledMessage>`.
+    /// The parameter is the list of the scheduled messages of the chat.
+    ShowScheduledMessagesPanel(Vec<TdScheduledMessage>),
+    /// SendScheduledNow action with a `i64`.
+    /// The parameter is the `message_id` of the scheduled message of the
+    /// open chat to send immediately.
+    SendScheduledNow(i64),
+    /// ScheduledMessageSent action with a `i64`.
+    /// The parameter is the `message_id` of the scheduled message that has
+    /// been sent, it is removed from the scheduled messages panel.
+    ScheduledMessageSent(i64),
+    /// PreviewFormatting action with a `String` and a `TdParseMode`.
+    /// The parameters are the composed text and the syntax it is parsed
+    /// with by TDLib.
+    PreviewFormatting(String, TdParseMode),
+    /// ShowFormattingPreviewPanel action with a `FormattingPreview`.
+    /// The parameter is the parsed text, or the error of the parsing.
+    ShowFormattingPreviewPanel(FormattingPreview),
+    /// HideFormattingPreview action.
+    /// It is used to hide the formatting preview once the prompt is typed in
+    /// again.
+    HideFormattingPreview,
+    /// ShowChatPermissionsPanel action with a `TdChatPermissions`.
+    /// The parameter is what the current user can do in the chat.
+    ShowChatPermissionsPanel(TdChatPermissions),
+    /// ShowLocationPanel action with a `Vec<String>`.
+    /// The parameter is the coordinates, the status and the map links of a
+    /// location message.
+    ShowLocationPanel(Vec<String>),
+    /// ShowActionAuditPanel action with a `Vec<String>`.
+    /// The parameter is the entries of the audit log of the destructive
+    /// actions, the most recent first.
+    ShowActionAuditPanel(Vec<String>),
+    /// ShowUnreadDashboardPanel action with a `Vec<UnreadChat>`.
+    /// The parameter is the list of the chats with unread messages, sorted by
+    /// their number of unread messages.
+    ShowUnreadDashboardPanel(Vec<UnreadChat>),
+    /// UnreadDashboard action.
+    /// It is used to list the chats with unread messages.
+    UnreadDashboard,
+    /// OpenChat action with a `i64`.
+    /// The parameter is the `chat_id` of the chat to open.
+    OpenChat(i64),
+    /// OpenChatAtMessage action with two `i64`.
+    /// The first parameter is the `chat_id` of the chat to open and the second
+    /// parameter is the `message_id` its history is loaded from.
+    OpenChatAtMessage(i64, i64),
+    /// ShowEditHistoryPanel action with a `String` and an
+    /// `Option<Vec<DiffSpan>>`.
+    /// The first parameter is the current text of an edited message and the
+    /// second parameter is the difference from its previous version, if it is
+    /// known.
+    ShowEditHistoryPanel(String, Option<Vec<DiffSpan>>),
+    /// ShowChatActivityPanel action with a `Vec<String>`.
+    /// The parameter is the sparkline of the activity of the chat and the
+    /// time range it covers.
+    ShowChatActivityPanel(Vec<String>),
+    /// ShowQuizExplanationPanel action with a `String`.
+    /// The parameter is the explanation of an answered quiz.
+    ShowQuizExplanationPanel(String),
+    /// OpenUsername action with a `String`.
+    /// The parameter is the username, without the `@`, of the chat to open.
+    OpenUsername(String),
+    /// GetMe action.
+    /// It is used to resolve the current user.
+    GetMe,
+    /// OpenSavedMessages action.
+    /// It is used to open the chat of the current user with itself.
+    OpenSavedMessages,
+    /// SearchHashtag action with a `String`.
+    /// The parameter is the hashtag, with the `#`, searched in the open chat.
+    SearchHashtag(String),
+    /// ShowSearchResults action with a `String` and a `Vec<String>`.
+    /// The first parameter is the searched text and the second parameter is
+    /// the list of the found messages, one line each.
+    ShowSearchResults(String, Vec<String>),
+    /// ShowConfirmation action with a `String`.
+    /// The parameter is the description of the destructive event waiting for
+    /// a confirmation.
+    ShowConfirmation(String),
+    /// StatusMessage action with a `String`.
+    /// The parameter is a message shown in the status bar, e.g. an error.
+    StatusMessage(String),
+    /// ShowSendAsPicker action with a `Vec<(TdMessageSender, String)>`.
+    /// The parameter is the list of the identities and their names.
+    ShowSendAsPicker(Vec<(TdMessageSender, String)>),
+    /// SetSendAs action with two `i64`.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// identifier of the user or of the chat the messages are sent as.
+    SetSendAs(i64, i64),
+    /// OpenDocument action with a `i32` and a `String`.
+    /// The first parameter is the identifier of the file of a document and
+    /// the second parameter is its name.
+    OpenDocument(i32, String),
+    /// SetOnline action with a `bool`.
+    /// The parameter is whether the user is set online or offline.
+    SetOnline(bool),
+    /// SendDraft action with a `i64`.
+    /// The parameter is the `chat_id` whose draft is sent.
+    SendDraft(i64),
+    /// SaveDraft action with a `i64` and a `String`.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// text saved as the draft of the chat.
+    SaveDraft(i64, String),
+    /// RetrySend action with a `i64` and a `u32`.
+    /// The first parameter is the `message_id` of the message that failed to
+    /// send and the second parameter is the number of the retry attempt.
+    RetrySend(i64, u32),
+    /// ChatWindowSendError action.
+    /// It is used to show the full error of the selected message, when it
+    /// failed to send.
+    ChatWindowSendError,
+    /// ShowSendError action with a `i64`.
+    /// The parameter is the `message_id` of the message whose send error is
+    /// shown.
+    ShowSendError(i64),
 
     /// Focus action with a `ComponentName`.
     FocusComponent(ComponentName),
@@ -118,6 +328,78 @@ pub enum Action {
     UnfocusComponent,
     /// Toggle ChatList action.
     ToggleChatList,
+    /// Toggle mobile layout action.
+    ToggleMobileLayout,
+    /// Toggle high contrast action.
+    ToggleHighContrast,
+    /// ToggleAvatarInitials action.
+    /// It is used to show or hide the avatars as colored initials.
+    ToggleAvatarInitials,
+    /// ToggleUnsupportedPlaceholders action.
+    /// It is used to show or hide the placeholders of the messages whose
+    /// content cannot be rendered.
+    ToggleUnsupportedPlaceholders,
+    /// ToggleSenderTimezone action.
+    /// It is used to show the timestamps of the messages in the time zone of
+    /// their sender or in the local one.
+    ToggleSenderTimezone,
+    /// ToggleEmojiShortcodes action.
+    /// It is used to expand or not the emoji shortcodes typed in the prompt.
+    ToggleEmojiShortcodes,
+    /// ToggleViewCounts action.
+    /// It is used to show or hide the view count under the posts of the
+    /// channels.
+    ToggleViewCounts,
+    /// ToggleCommentCounts action.
+    /// It is used to show or hide the comment count under the posts of the
+    /// channels with a discussion group.
+    ToggleCommentCounts,
+    /// ToggleChannelSignatures action.
+    /// It is used to show or hide the signature of the author under the
+    /// signed posts of the channels.
+    ToggleChannelSignatures,
+    /// ToggleFolderTabs action.
+    /// It is used to show or hide the chat folders as tabs above the chat
+    /// list, all the chats are shown again when they are hidden.
+    ToggleFolderTabs,
+    /// ToggleUnreadOnlyView action.
+    /// It is used to show only the chats with unread messages in the chat
+    /// list, or all the chats again.
+    ToggleUnreadOnlyView,
+    /// ToggleSearchBar action.
+    /// It is used to show or hide the search input docked above the chat
+    /// list.
+    ToggleSearchBar,
+    /// SearchMessages action with a `String`.
+    /// The parameter is the text searched with TDLib in the messages of all
+    /// the chats.
+    SearchMessages(String),
+    /// ShowSearchBarMessages action with a `String` and a
+    /// `Vec<TdFoundMessage>`.
+    /// The first parameter is the searched text and the second parameter is
+    /// the list of the messages found for it.
+    ShowSearchBarMessages(String, Vec<TdFoundMessage>),
+    /// TogglePresenceDisplay action.
+    /// It is used to show or hide the presence of the peer next to the title
+    /// of the private chats.
+    TogglePresenceDisplay,
+    /// ToggleSyntaxHighlight action.
+    /// It is used to color the tokens of the code blocks of the known
+    /// languages, or to render them plain again.
+    ToggleSyntaxHighlight,
+    /// ToggleBigEmoji action.
+    /// It is used to render the messages made only of a few emoji big, or
+    /// as a normal text again.
+    ToggleBigEmoji,
+    /// ToggleActionAudit action.
+    /// It is used to start or stop logging the destructive actions to the
+    /// audit log.
+    ToggleActionAudit,
+    /// ActionAudit action.
+    /// It is used to read the audit log of the destructive actions.
+    ActionAudit,
+    /// Toggle new messages divider action.
+    ToggleNewMessagesDivider,
     /// Increase ChatList size action.
     IncreaseChatListSize,
     /// Decrease ChatList size action.
@@ -143,6 +425,24 @@ pub enum Action {
     ChatListUnselect,
     /// ChatListOpen action.
     ChatListOpen,
+    /// ChatListNextFolder action.
+    /// It is used to filter the chat list by the next folder tab.
+    ChatListNextFolder,
+    /// ChatListPreviousFolder action.
+    /// It is used to filter the chat list by the previous folder tab.
+    ChatListPreviousFolder,
+    /// ToggleSelectedChatMute action.
+    ToggleSelectedChatMute,
+    /// LeaveSelectedChat action.
+    /// It is used to leave the group or the channel selected in the chat list.
+    LeaveSelectedChat,
+    /// LeaveChat action with a `i64`.
+    /// The parameter is the `chat_id` of the group or the channel to leave.
+    LeaveChat(i64),
+    /// SetEmojiStatus action with a `i64`.
+    /// The parameter is the `custom_emoji_id` of the emoji status, `0` to
+    /// clear it.
+    SetEmojiStatus(i64),
 
     /// ChatWindowNext action.
     ChatWindowNext,
@@ -150,6 +450,24 @@ pub enum Action {
     ChatWindowPrevious,
     /// ChatWindowUnselect action.
     ChatWindowUnselect,
+    /// PromptIndent action.
+    /// It is used to indent the text of the prompt.
+    PromptIndent,
+    /// PromptCompleteMention action.
+    /// It is used to complete the `@`-mention before the cursor of the
+    /// prompt.
+    PromptCompleteMention,
+    /// PromptClearText action.
+    /// It is used to clear the text of the prompt, keeping the focus.
+    PromptClearText,
+    /// PromptPreviewFormatting action with a `TdParseMode`.
+    /// It is used to preview the formatting of the text of the prompt, the
+    /// parameter is the syntax it is written with.
+    PromptPreviewFormatting(TdParseMode),
+    /// PromptDiscardReply action.
+    /// It is used to discard the message being replied to or edited in the
+    /// prompt.
+    PromptDiscardReply,
     /// ChatWindowDeleteForEveryone action.
     /// It is used to delete a message for everyone.
     ChatWindowDeleteForEveryone,
@@ -160,6 +478,109 @@ pub enum Action {
     ChatWindowCopy,
     /// ChatWindowEdit action.
     ChatWindowEdit,
+    /// ChatWindowReact action.
+    /// It is used to open the reaction picker for the selected message.
+    ChatWindowReact,
+    /// ChatWindowCallHistory action.
+    /// It is used to show the recent calls of the chat.
+    ChatWindowCallHistory,
+    /// ChatWindowPermissions action.
+    /// It is used to show what the current user can do in the chat.
+    ChatWindowPermissions,
+    /// ChatWindowLocationMap action.
+    /// It is used to show the map links of the selected location message.
+    ChatWindowLocationMap,
+    /// ChatWindowEditHistory action.
+    /// It is used to show the edits of the selected message.
+    ChatWindowEditHistory,
+    /// ChatWindowOpenComments action.
+    /// It is used to open the discussion thread of the selected channel post.
+    ChatWindowOpenComments,
+    /// ChatWindowActivity action.
+    /// It is used to show when the open chat is active.
+    ChatWindowActivity,
+    /// ChatWindowQuizExplanation action.
+    /// It is used to show the explanation of the selected quiz.
+    ChatWindowQuizExplanation,
+    /// ChatWindowOpenDocument action.
+    /// It is used to download and open the selected document.
+    ChatWindowOpenDocument,
+    /// ChatWindowPolls action.
+    /// It is used to list the polls of the open chat.
+    ChatWindowPolls,
+    /// ChatWindowForumTopics action.
+    /// It is used to list the topics of the open forum.
+    ChatWindowForumTopics,
+    /// ChatWindowBotMenu action.
+    /// It is used to show the menu of the bot of the open chat.
+    ChatWindowBotMenu,
+    /// ChatWindowScheduledMessages action.
+    /// It is used to list the scheduled messages of the open chat.
+    ChatWindowScheduledMessages,
+    /// ChatWindowJumpToNewest action.
+    /// It is used to scroll the open chat back to the most recent message,
+    /// e.g. after the messages received while the history was scrolled up.
+    ChatWindowJumpToNewest,
+    /// ChatWindowFirst action.
+    /// It is used to select the first loaded message of the open chat.
+    ChatWindowFirst,
+    /// ChatWindowLast action.
+    /// It is used to select the last message of the open chat.
+    ChatWindowLast,
+    /// ChatWindowPageUp action.
+    /// It is used to select the message a page older than the selected one.
+    ChatWindowPageUp,
+    /// ChatWindowPageDown action.
+    /// It is used to select the message a page newer than the selected one.
+    ChatWindowPageDown,
+    /// ChatWindowTranslate action.
+    /// It is used to translate the selected message to the language of the
+    /// system, or to hide and show its translation again.
+    ChatWindowTranslate,
+    /// TranslateMessage action with a `i64` and a `String`.
+    /// The first parameter is the `message_id` of the message to translate
+    /// with TDLib and the second parameter is the code of the target
+    /// language.
+    TranslateMessage(i64, String),
+    /// ChatWindowNextEntity action.
+    /// It is used to select the next `@username` or `#hashtag` of the
+    /// selected message.
+    ChatWindowNextEntity,
+    /// ChatWindowFollowEntity action.
+    /// It is used to follow the selected entity of the selected message.
+    ChatWindowFollowEntity,
+    /// ChatWindowSendAs action.
+    /// It is used to choose the identity the messages of the chat are sent
+    /// as.
+    ChatWindowSendAs,
+    /// ChatWindowCopyCodeBlock action.
+    /// It is used to copy the first code block of the selected message.
+    ChatWindowCopyCodeBlock,
+    /// CopyCodeBlock action with a `i64` and a `usize`.
+    /// The first parameter is the `message_id` and the second parameter is the
+    /// index of the code block of the message to copy.
+    CopyCodeBlock(i64, usize),
+    /// ChatWindowCopyLinks action.
+    /// It is used to copy the links of the selected message.
+    ChatWindowCopyLinks,
+    /// CopyMessageLinks action with a `i64`.
+    /// The parameter is the `message_id` whose links are copied.
+    CopyMessageLinks(i64),
+    /// CopyVisibleTranscript action.
+    /// It is used to copy the messages visible in the chat as plain text.
+    CopyVisibleTranscript,
+    /// NextAlbumItem action.
+    /// It is used to select the next item of the selected album.
+    NextAlbumItem,
+    /// PreviousAlbumItem action.
+    /// It is used to select the previous item of the selected album.
+    PreviousAlbumItem,
+    /// NextOwnMessage action.
+    /// It is used to select the newer message sent by the current user.
+    NextOwnMessage,
+    /// PreviousOwnMessage action.
+    /// It is used to select the older message sent by the current user.
+    PreviousOwnMessage,
 
     /// EditMessage action with a `String`.
     /// This action is used to edit a message.
@@ -199,6 +620,24 @@ impl FromStr for Action {
             "focus_prompt" => Ok(Action::FocusComponent(ComponentName::Prompt)),
             "unfocus_component" => Ok(Action::UnfocusComponent),
             "toggle_chat_list" => Ok(Action::ToggleChatList),
+            "toggle_mobile_layout" => Ok(Action::ToggleMobileLayout),
+            "toggle_high_contrast" => Ok(Action::ToggleHighContrast),
+            "toggle_avatar_initials" => Ok(Action::ToggleAvatarInitials),
+            "toggle_unsupported_placeholders" => Ok(Action::ToggleUnsupportedPlaceholders),
+            "toggle_sender_timezone" => Ok(Action::ToggleSenderTimezone),
+            "toggle_emoji_shortcodes" => Ok(Action::ToggleEmojiShortcodes),
+            "toggle_view_counts" => Ok(Action::ToggleViewCounts),
+            "toggle_comment_counts" => Ok(Action::ToggleCommentCounts),
+            "toggle_channel_signatures" => Ok(Action::ToggleChannelSignatures),
+            "toggle_folder_tabs" => Ok(Action::ToggleFolderTabs),
+            "toggle_unread_only_view" => Ok(Action::ToggleUnreadOnlyView),
+            "toggle_search_bar" => Ok(Action::ToggleSearchBar),
+            "toggle_presence_display" => Ok(Action::TogglePresenceDisplay),
+            "toggle_syntax_highlight" => Ok(Action::ToggleSyntaxHighlight),
+            "toggle_big_emoji" => Ok(Action::ToggleBigEmoji),
+            "toggle_action_audit" => Ok(Action::ToggleActionAudit),
+            "action_audit" => Ok(Action::ActionAudit),
+            "toggle_new_messages_divider" => Ok(Action::ToggleNewMessagesDivider),
             "increase_chat_list_size" => Ok(Action::IncreaseChatListSize),
             "decrease_chat_list_size" => Ok(Action::DecreaseChatListSize),
             "increase_prompt_size" => Ok(Action::IncreasePromptSize),
@@ -207,6 +646,10 @@ impl FromStr for Action {
             "chat_list_previous" => Ok(Action::ChatListPrevious),
             "chat_list_unselect" => Ok(Action::ChatListUnselect),
             "chat_list_open" => Ok(Action::ChatListOpen),
+            "chat_list_next_folder" => Ok(Action::ChatListNextFolder),
+            "chat_list_previous_folder" => Ok(Action::ChatListPreviousFolder),
+            "chat_list_toggle_mute" => Ok(Action::ToggleSelectedChatMute),
+            "chat_list_leave" => Ok(Action::LeaveSelectedChat),
             "chat_window_next" => Ok(Action::ChatWindowNext),
             "chat_window_previous" => Ok(Action::ChatWindowPrevious),
             "chat_window_unselect" => Ok(Action::ChatWindowUnselect),
@@ -215,6 +658,42 @@ impl FromStr for Action {
             "chat_window_copy" => Ok(Action::ChatWindowCopy),
             "chat_window_edit" => Ok(Action::ChatWindowEdit),
             "chat_window_reply" => Ok(Action::ShowChatWindowReply),
+            "chat_window_react" => Ok(Action::ChatWindowReact),
+            "chat_window_call_history" => Ok(Action::ChatWindowCallHistory),
+            "chat_window_permissions" => Ok(Action::ChatWindowPermissions),
+            "chat_window_location_map" => Ok(Action::ChatWindowLocationMap),
+            "chat_window_quiz_explanation" => Ok(Action::ChatWindowQuizExplanation),
+            "chat_window_activity" => Ok(Action::ChatWindowActivity),
+            "chat_window_edit_history" => Ok(Action::ChatWindowEditHistory),
+            "chat_window_open_comments" => Ok(Action::ChatWindowOpenComments),
+            "chat_window_open_document" => Ok(Action::ChatWindowOpenDocument),
+            "chat_window_polls" => Ok(Action::ChatWindowPolls),
+            "chat_window_forum_topics" => Ok(Action::ChatWindowForumTopics),
+            "chat_window_bot_menu" => Ok(Action::ChatWindowBotMenu),
+            "chat_window_scheduled_messages" => Ok(Action::ChatWindowScheduledMessages),
+            "prompt_preview_markdown" => Ok(Action::PromptPreviewFormatting(TdParseMode::Markdown)),
+            "prompt_preview_html" => Ok(Action::PromptPreviewFormatting(TdParseMode::Html)),
+            "chat_window_jump_to_newest" => Ok(Action::ChatWindowJumpToNewest),
+            "chat_window_first" => Ok(Action::ChatWindowFirst),
+            "chat_window_last" => Ok(Action::ChatWindowLast),
+            "chat_window_page_up" => Ok(Action::ChatWindowPageUp),
+            "chat_window_page_down" => Ok(Action::ChatWindowPageDown),
+            "chat_window_translate" => Ok(Action::ChatWindowTranslate),
+            "chat_window_next_entity" => Ok(Action::ChatWindowNextEntity),
+            "chat_window_follow_entity" => Ok(Action::ChatWindowFollowEntity),
+            "chat_window_send_as" => Ok(Action::ChatWindowSendAs),
+            "copy_visible_transcript" => Ok(Action::CopyVisibleTranscript),
+            "unread_dashboard" => Ok(Action::UnreadDashboard),
+            "active_sessions" => Ok(Action::ActiveSessions),
+            "chat_window_copy_code_block" => Ok(Action::ChatWindowCopyCodeBlock),
+            "chat_window_copy_links" => Ok(Action::ChatWindowCopyLinks),
+            "chat_window_send_error" => Ok(Action::ChatWindowSendError),
+            "chat_window_next_album_item" => Ok(Action::NextAlbumItem),
+            "chat_window_previous_album_item" => Ok(Action::PreviousAlbumItem),
+            "chat_window_next_own_message" => Ok(Action::NextOwnMessage),
+            "chat_window_previous_own_message" => Ok(Action::PreviousOwnMessage),
+            "close_split_view" => Ok(Action::CloseSplitView),
+            "swap_split_focus" => Ok(Action::SwapSplitFocus),
             _ => Err(AppError::InvalidAction(s.to_string())),
         }
     }
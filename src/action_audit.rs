@@ -0,0 +1,183 @@
+use crate::{action::Action, utils::tgt_config_dir};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// The name of the file, in the configuration directory, where the
+/// destructive actions are logged.
+pub const ACTION_AUDIT_FILENAME: &str = "action_audit.log";
+
+/// The maximum size, in bytes, of the audit log. When an entry would exceed
+/// it, the log is rotated to a single backup and a new log is started.
+pub const ACTION_AUDIT_MAX_BYTES: u64 = 64 * 1024;
+
+/// The maximum number of entries shown when the audit log is viewed.
+pub const ACTION_AUDIT_SHOWN_ENTRIES: usize = 100;
+
+/// Get the path of the file where the destructive actions are logged.
+///
+/// # Returns
+/// The path of the file in the default configuration directory.
+pub fn action_audit_path() -> io::Result<PathBuf> {
+    Ok(tgt_config_dir()?.join(ACTION_AUDIT_FILENAME))
+}
+
+/// Get the path of the backup the audit log is rotated to.
+///
+/// # Arguments
+/// * `path` - The path of the audit log.
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
+}
+
+/// Describe an action in the audit log, only the destructive actions are
+/// logged.
+///
+/// # Arguments
+/// * `action` - The action.
+///
+/// # Returns
+/// * `Option<String>` - The description, `None` when the action is not
+///   destructive.
+pub fn audit_entry(action: &Action) -> Option<String> {
+    match action {
+        Action::DeleteMessages(message_ids, revoke) => Some(format!(
+            "Delete the messages {} {}",
+            message_ids
+                .iter()
+                .map(|message_id| message_id.to_string())
+                .collect::<Vec<String>>()
+                .join(", "),
+            if *revoke { "for everyone" } else { "for me" }
+        )),
+        Action::LeaveChat(chat_id) => Some(format!("Leave the chat {}", chat_id)),
+        // Unmuting a chat is not destructive.
+        Action::SetChatMuteFor(chat_id, mute_for) if *mute_for > 0 => {
+            Some(format!("Mute the chat {}", chat_id))
+        }
+        Action::TerminateSession(session_id) => {
+            Some(format!("Terminate the session {}", session_id))
+        }
+        _ => None,
+    }
+}
+
+/// Check whether the audit log is rotated before an entry is appended.
+/// An empty log is never rotated, so an entry bigger than the limit is
+/// still logged.
+///
+/// # Arguments
+/// * `log_len` - The size, in bytes, of the audit log.
+/// * `entry_len` - The size, in bytes, of the entry to append.
+/// * `max_len` - The maximum size, in bytes, of the audit log.
+///
+/// # Returns
+/// * `bool` - Whether the log has to be rotated.
+pub fn needs_rotation(log_len: u64, entry_len: u64, max_len: u64) -> bool {
+    log_len > 0 && log_len + entry_len > max_len
+}
+
+/// Append an entry to the audit log, the entries are never rewritten.
+/// The log is rotated first when the entry would make it exceed `max_len`,
+/// the previous backup is replaced.
+///
+/// # Arguments
+/// * `path` - The path of the audit log.
+/// * `timestamp` - When the action happened, formatted.
+/// * `entry` - The description of the action.
+/// * `max_len` - The maximum size, in bytes, of the audit log.
+///
+/// # Returns
+/// * `io::Result<()>` - An Ok result or an error if the log can not be
+///   written.
+pub fn append(path: &Path, timestamp: &str, entry: &str, max_len: u64) -> io::Result<()> {
+    let line = format!("{} {}\n", timestamp, entry);
+    let log_len = fs::metadata(path).map_or(0, |metadata| metadata.len());
+    if needs_rotation(log_len, line.len() as u64, max_len) {
+        fs::rename(path, rotated_path(path))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// Read the most recent entries of the audit log, including the rotated
+/// backup.
+///
+/// # Arguments
+/// * `path` - The path of the audit log.
+/// * `max_entries` - The maximum number of entries to read.
+///
+/// # Returns
+/// * `Vec<String>` - The entries, the most recent first.
+pub fn read_recent(path: &Path, max_entries: usize) -> Vec<String> {
+    [rotated_path(path), path.to_path_buf()]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| {
+            content
+                .lines()
+                .map(|line| line.to_string())
+                .collect::<Vec<String>>()
+        })
+        .rev()
+        .take(max_entries)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        action::Action,
+        action_audit::{append, audit_entry, needs_rotation, read_recent, rotated_path},
+    };
+    use std::fs;
+
+    #[test]
+    fn test_audit_entry_destructive_actions_only() {
+        assert_eq!(
+            audit_entry(&Action::DeleteMessages(vec![1, 2], true)),
+            Some("Delete the messages 1, 2 for everyone".to_string())
+        );
+        assert_eq!(
+            audit_entry(&Action::SetChatMuteFor(-100, i32::MAX)),
+            Some("Mute the chat -100".to_string())
+        );
+        assert_eq!(audit_entry(&Action::SetChatMuteFor(-100, 0)), None);
+        assert_eq!(audit_entry(&Action::ViewAllMessages), None);
+    }
+
+    #[test]
+    fn test_rotation_boundary() {
+        assert!(!needs_rotation(0, 200, 100));
+        assert!(!needs_rotation(60, 40, 100));
+        assert!(needs_rotation(60, 41, 100));
+
+        let path = std::env::temp_dir().join("tgt_test_action_audit.log");
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(rotated_path(&path));
+        // Each line is 23 bytes: the timestamp, a space, the entry and the
+        // newline.
+        append(&path, "2024-01-01 10:00", "Leave", 46).unwrap();
+        append(&path, "2024-01-01 10:01", "Leave", 46).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), 46);
+        assert!(!rotated_path(&path).exists());
+        // The log is full, the next entry starts a new one.
+        append(&path, "2024-01-01 10:02", "Leave", 46).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), 23);
+        assert_eq!(fs::metadata(rotated_path(&path)).unwrap().len(), 46);
+        assert_eq!(
+            read_recent(&path, 2),
+            vec!["2024-01-01 10:02 Leave", "2024-01-01 10:01 Leave"]
+        );
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(rotated_path(&path)).unwrap();
+    }
+}
@@ -5,9 +5,11 @@ use crate::{
         app_custom::AppConfig, keymap_custom::KeymapConfig, palette_custom::PaletteConfig,
         telegram_custom::TelegramConfig, theme_custom::ThemeConfig,
     },
+    double_key::DoubleKeyTracker,
+    event::ConfirmationGate,
     tg::tg_context::TgContext,
 };
-use ratatui::style::Style;
+use ratatui::style::{Color, Modifier, Style};
 use std::sync::{atomic::AtomicBool, Arc, Mutex, MutexGuard};
 use std::{io, sync::atomic::Ordering};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
@@ -22,6 +24,8 @@ use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 /// If the theme is enabled, it returns the style for the specified attribute in
 /// the specified map in the theme configuration. If the theme is disabled, it
 /// returns the default style.
+/// When the high contrast mode is on, it overrides both of them with the high
+/// contrast style of the attribute.
 ///
 /// # Arguments
 /// * `fn_name` - The name of the function.
@@ -39,19 +43,60 @@ macro_rules! theme_style_generate {
     ($fn_name: ident, $map: ident, $attr_name: ident) => {
         #[inline]
         pub fn $fn_name(&self) -> Style {
-            if self.app_config().theme_enable {
-                self.theme_config()
-                    .$map
-                    .get(stringify!($attr_name))
-                    .unwrap()
-                    .as_style()
+            let theme_style = if self.app_config().theme_enable {
+                Some(
+                    self.theme_config()
+                        .$map
+                        .get(stringify!($attr_name))
+                        .unwrap()
+                        .as_style(),
+                )
             } else {
-                Style::default()
-            }
+                None
+            };
+            effective_style(stringify!($attr_name), theme_style, self.high_contrast())
         }
     };
 }
 
+/// Get the high contrast style of a theme attribute.
+/// The selected items are rendered inverted and the focused borders are
+/// highlighted, everything else is rendered white on black. All the text is
+/// bold.
+///
+/// # Arguments
+/// * `attr_name` - The name of the attribute in the theme configuration.
+///
+/// # Returns
+/// * `Style` - The high contrast style.
+pub fn high_contrast_style(attr_name: &str) -> Style {
+    let style = if attr_name.ends_with("selected") {
+        Style::default().fg(Color::Black).bg(Color::White)
+    } else if attr_name == "border_component_focused" {
+        Style::default().fg(Color::Yellow).bg(Color::Black)
+    } else {
+        Style::default().fg(Color::White).bg(Color::Black)
+    };
+    style.add_modifier(Modifier::BOLD)
+}
+
+/// Get the style used to render a theme attribute.
+///
+/// # Arguments
+/// * `attr_name` - The name of the attribute in the theme configuration.
+/// * `theme_style` - The style of the attribute in the active theme, or `None`
+///   if the theme is disabled.
+/// * `high_contrast` - Whether the high contrast mode is on.
+///
+/// # Returns
+/// * `Style` - The style to render the attribute with.
+pub fn effective_style(attr_name: &str, theme_style: Option<Style>, high_contrast: bool) -> Style {
+    if high_contrast {
+        return high_contrast_style(attr_name);
+    }
+    theme_style.unwrap_or_default()
+}
+
 #[derive(Debug)]
 /// `AppContext` is a struct that represents the main application.
 /// It contains the application configuration, keymap configuration, theme
@@ -84,6 +129,55 @@ pub struct AppContext {
     /// A boolean flag that represents whether the application should quit or
     /// not.
     quit: AtomicBool,
+    /// A boolean flag that represents whether the high contrast mode is on.
+    /// It overrides the active theme until it is turned off.
+    high_contrast: AtomicBool,
+    /// A boolean flag that represents whether the avatars are shown as colored
+    /// initials next to the chat names and the message senders.
+    avatar_initials: AtomicBool,
+    /// A boolean flag that represents whether a placeholder is shown in place
+    /// of the messages whose content cannot be rendered.
+    unsupported_placeholders: AtomicBool,
+    /// A boolean flag that represents whether the timestamps of the messages
+    /// are shown in the time zone of their sender.
+    sender_timezone: AtomicBool,
+    /// A boolean flag that represents whether the emoji shortcodes typed in
+    /// the prompt are expanded to their emoji.
+    emoji_shortcodes: AtomicBool,
+    /// A boolean flag that represents whether the view count is shown under
+    /// the posts of the channels.
+    view_counts: AtomicBool,
+    /// A boolean flag that represents whether the comment count is shown
+    /// under the posts of the channels with a discussion group.
+    comment_counts: AtomicBool,
+    /// A boolean flag that represents whether the signature of the author is
+    /// shown under the signed posts of the channels.
+    channel_signatures: AtomicBool,
+    /// A boolean flag that represents whether the chat folders are shown as
+    /// tabs above the chat list.
+    folder_tabs: AtomicBool,
+    /// A boolean flag that represents whether only the chats with unread
+    /// messages are shown in the chat list.
+    unread_only_view: AtomicBool,
+    /// A boolean flag that represents whether the search bar is docked above
+    /// the chat list.
+    search_bar: AtomicBool,
+    /// A boolean flag that represents whether the presence of the peer is
+    /// shown next to the title of the private chats.
+    presence_display: AtomicBool,
+    /// A boolean flag that represents whether the tokens of the code blocks
+    /// of the known languages are colored.
+    syntax_highlight: AtomicBool,
+    /// A boolean flag that represents whether the messages made only of a
+    /// few emoji are rendered big.
+    big_emoji: AtomicBool,
+    /// A boolean flag that represents whether the destructive actions are
+    /// logged in the audit log.
+    action_audit: AtomicBool,
+    /// The destructive event waiting for a confirmation.
+    confirmation_gate: Mutex<ConfirmationGate>,
+    /// The last key pressed, used to detect the double presses.
+    double_key: Mutex<DoubleKeyTracker>,
     /// The Telegram context.
     tg_context: Arc<TgContext>,
     /// The CLI arguments for the application.
@@ -115,6 +209,21 @@ impl AppContext {
     ) -> Result<Self, io::Error> {
         let (action_tx, action_rx) = tokio::sync::mpsc::unbounded_channel::<Action>();
         let quit = false;
+        let high_contrast = false;
+        let avatar_initials = false;
+        let unsupported_placeholders = true;
+        let sender_timezone = false;
+        let emoji_shortcodes = false;
+        let view_counts = true;
+        let comment_counts = true;
+        let channel_signatures = true;
+        let folder_tabs = false;
+        let unread_only_view = false;
+        let search_bar = false;
+        let presence_display = true;
+        let syntax_highlight = false;
+        let big_emoji = false;
+        let action_audit = false;
         Ok(Self {
             app_config: Mutex::new(app_config),
             keymap_config: Mutex::new(keymap_config),
@@ -124,6 +233,23 @@ impl AppContext {
             action_rx: Mutex::new(action_rx),
             action_tx: Mutex::new(action_tx),
             quit: AtomicBool::new(quit),
+            high_contrast: AtomicBool::new(high_contrast),
+            avatar_initials: AtomicBool::new(avatar_initials),
+            unsupported_placeholders: AtomicBool::new(unsupported_placeholders),
+            sender_timezone: AtomicBool::new(sender_timezone),
+            emoji_shortcodes: AtomicBool::new(emoji_shortcodes),
+            view_counts: AtomicBool::new(view_counts),
+            comment_counts: AtomicBool::new(comment_counts),
+            channel_signatures: AtomicBool::new(channel_signatures),
+            folder_tabs: AtomicBool::new(folder_tabs),
+            unread_only_view: AtomicBool::new(unread_only_view),
+            search_bar: AtomicBool::new(search_bar),
+            presence_display: AtomicBool::new(presence_display),
+            syntax_highlight: AtomicBool::new(syntax_highlight),
+            big_emoji: AtomicBool::new(big_emoji),
+            action_audit: AtomicBool::new(action_audit),
+            confirmation_gate: Mutex::new(ConfirmationGate::default()),
+            double_key: Mutex::new(DoubleKeyTracker::default()),
             tg_context: Arc::new(tg_context),
             cli_args: Mutex::new(cli_args),
         })
@@ -214,6 +340,188 @@ impl AppContext {
     pub fn quit_store(&self, value: bool) {
         self.quit.store(value, Ordering::Release);
     }
+    /// Get the high contrast flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the high contrast flag.
+    pub fn high_contrast(&self) -> bool {
+        self.high_contrast.load(Ordering::Relaxed)
+    }
+    /// Toggle the high contrast flag.
+    pub fn toggle_high_contrast(&self) {
+        self.high_contrast.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the avatar initials flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the avatar initials flag.
+    pub fn avatar_initials(&self) -> bool {
+        self.avatar_initials.load(Ordering::Relaxed)
+    }
+    /// Toggle the avatar initials flag.
+    pub fn toggle_avatar_initials(&self) {
+        self.avatar_initials.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the unsupported placeholders flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the unsupported placeholders flag.
+    pub fn unsupported_placeholders(&self) -> bool {
+        self.unsupported_placeholders.load(Ordering::Relaxed)
+    }
+    /// Toggle the unsupported placeholders flag.
+    pub fn toggle_unsupported_placeholders(&self) {
+        self.unsupported_placeholders
+            .fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the sender timezone flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the sender timezone flag.
+    pub fn sender_timezone(&self) -> bool {
+        self.sender_timezone.load(Ordering::Relaxed)
+    }
+    /// Toggle the sender timezone flag.
+    pub fn toggle_sender_timezone(&self) {
+        self.sender_timezone.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the emoji shortcodes flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the emoji shortcodes flag.
+    pub fn emoji_shortcodes(&self) -> bool {
+        self.emoji_shortcodes.load(Ordering::Relaxed)
+    }
+    /// Toggle the emoji shortcodes flag.
+    pub fn toggle_emoji_shortcodes(&self) {
+        self.emoji_shortcodes.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the view counts flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the view counts flag.
+    pub fn view_counts(&self) -> bool {
+        self.view_counts.load(Ordering::Relaxed)
+    }
+    /// Toggle the view counts flag.
+    pub fn toggle_view_counts(&self) {
+        self.view_counts.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the comment counts flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the comment counts flag.
+    pub fn comment_counts(&self) -> bool {
+        self.comment_counts.load(Ordering::Relaxed)
+    }
+    /// Toggle the comment counts flag.
+    pub fn toggle_comment_counts(&self) {
+        self.comment_counts.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the channel signatures flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the channel signatures flag.
+    pub fn channel_signatures(&self) -> bool {
+        self.channel_signatures.load(Ordering::Relaxed)
+    }
+    /// Toggle the channel signatures flag.
+    pub fn toggle_channel_signatures(&self) {
+        self.channel_signatures.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the folder tabs flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the folder tabs flag.
+    pub fn folder_tabs(&self) -> bool {
+        self.folder_tabs.load(Ordering::Relaxed)
+    }
+    /// Toggle the folder tabs flag.
+    pub fn toggle_folder_tabs(&self) {
+        self.folder_tabs.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the unread only view flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the unread only view flag.
+    pub fn unread_only_view(&self) -> bool {
+        self.unread_only_view.load(Ordering::Relaxed)
+    }
+    /// Toggle the unread only view flag.
+    pub fn toggle_unread_only_view(&self) {
+        self.unread_only_view.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the search bar flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the search bar flag.
+    pub fn search_bar(&self) -> bool {
+        self.search_bar.load(Ordering::Relaxed)
+    }
+    /// Toggle the search bar flag.
+    pub fn toggle_search_bar(&self) {
+        self.search_bar.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the presence display flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the presence display flag.
+    pub fn presence_display(&self) -> bool {
+        self.presence_display.load(Ordering::Relaxed)
+    }
+    /// Toggle the presence display flag.
+    pub fn toggle_presence_display(&self) {
+        self.presence_display.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the syntax highlight flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the syntax highlight flag.
+    pub fn syntax_highlight(&self) -> bool {
+        self.syntax_highlight.load(Ordering::Relaxed)
+    }
+    /// Toggle the syntax highlight flag.
+    pub fn toggle_syntax_highlight(&self) {
+        self.syntax_highlight.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the big emoji flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the big emoji flag.
+    pub fn big_emoji(&self) -> bool {
+        self.big_emoji.load(Ordering::Relaxed)
+    }
+    /// Toggle the big emoji flag.
+    pub fn toggle_big_emoji(&self) {
+        self.big_emoji.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the action audit flag.
+    ///
+    /// # Returns
+    /// * `bool` - The value of the action audit flag.
+    pub fn action_audit(&self) -> bool {
+        self.action_audit.load(Ordering::Relaxed)
+    }
+    /// Toggle the action audit flag.
+    pub fn toggle_action_audit(&self) {
+        self.action_audit.fetch_xor(true, Ordering::Relaxed);
+    }
+    /// Get the confirmation gate.
+    /// This function takes the lock on the confirmation gate and returns it.
+    ///
+    /// # Returns
+    /// * `MutexGuard<'_, ConfirmationGate>` - The confirmation gate.
+    pub fn confirmation_gate(&self) -> MutexGuard<'_, ConfirmationGate> {
+        self.confirmation_gate.lock().unwrap()
+    }
+    /// Get the double key tracker.
+    /// This function takes the lock on the double key tracker and returns it.
+    ///
+    /// # Returns
+    /// * `MutexGuard<'_, DoubleKeyTracker>` - The double key tracker.
+    pub fn double_key(&self) -> MutexGuard<'_, DoubleKeyTracker> {
+        self.double_key.lock().unwrap()
+    }
     /// Get the Telegram context.
     /// This function returns the Telegram context.
     /// The Telegram context is a shared resource and the contained variables are
@@ -290,6 +598,10 @@ impl AppContext {
         chat,
         message_other_reply_content
     );
+    theme_style_generate!(style_chat_code_keyword, chat, code_keyword);
+    theme_style_generate!(style_chat_code_string, chat, code_string);
+    theme_style_generate!(style_chat_code_number, chat, code_number);
+    theme_style_generate!(style_chat_code_comment, chat, code_comment);
 
     // ===== PROMPT =====
     theme_style_generate!(style_prompt, prompt, self);
@@ -342,3 +654,55 @@ impl AppContext {
     theme_style_generate!(style_title_bar_title2, title_bar, title2);
     theme_style_generate!(style_title_bar_title3, title_bar, title3);
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::app_context::{effective_style, high_contrast_style};
+    use ratatui::style::{Color, Modifier, Style};
+
+    #[test]
+    fn test_effective_style_high_contrast_overrides_theme() {
+        let first_theme = Style::default().fg(Color::Red).bg(Color::Blue);
+        let second_theme = Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::ITALIC);
+        for theme_style in [Some(first_theme), Some(second_theme), None] {
+            assert_eq!(
+                effective_style("message_myself_content", theme_style, true),
+                high_contrast_style("message_myself_content")
+            );
+            assert_eq!(
+                effective_style("item_selected", theme_style, true),
+                high_contrast_style("item_selected")
+            );
+        }
+    }
+
+    #[test]
+    fn test_effective_style_without_high_contrast() {
+        let theme_style = Style::default().fg(Color::Red);
+        assert_eq!(
+            effective_style("self", Some(theme_style), false),
+            theme_style
+        );
+        assert_eq!(effective_style("self", None, false), Style::default());
+    }
+
+    #[test]
+    fn test_high_contrast_style() {
+        assert_eq!(
+            high_contrast_style("message_text"),
+            Style::default()
+                .fg(Color::White)
+                .bg(Color::Black)
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            high_contrast_style("message_text_selected"),
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        );
+    }
+}
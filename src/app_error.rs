@@ -10,6 +10,8 @@ use tokio::sync::mpsc::error::SendError;
 /// * Send: A send error.
 /// * Config: A configuration error.
 /// * ConfigFile: A configuration file error.
+/// * InvalidDirectory: A directory that does not exist or is not writable.
+/// * InvalidTimestamp: A timestamp that is not in the expected range.
 pub enum AppError<T> {
     /// It is a wrapper for the `std::io::Error`.
     Io(io::Error),
@@ -26,6 +28,12 @@ pub enum AppError<T> {
     AlreadyBound,
     /// It is an invalid color.
     InvalidColor(String),
+    /// It is an invalid directory. It is used when a directory does not
+    /// exist or it is not writable.
+    InvalidDirectory(String),
+    /// It is an invalid timestamp. It is used when a time that must be in the
+    /// future is in the past.
+    InvalidTimestamp(String),
 }
 impl<T> From<io::Error> for AppError<T> {
     fn from(error: io::Error) -> Self {
@@ -60,6 +68,12 @@ impl<T> Display for AppError<T> {
             Self::InvalidColor(color) => {
                 write!(f, "Invalid color: {}", color)
             }
+            Self::InvalidDirectory(directory) => {
+                write!(f, "Invalid directory: {}", directory)
+            }
+            Self::InvalidTimestamp(timestamp) => {
+                write!(f, "Invalid timestamp: {}", timestamp)
+            }
         }
     }
 }
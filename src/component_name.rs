@@ -10,6 +10,8 @@ pub enum ComponentName {
     ChatList,
     /// The chat.
     Chat,
+    /// The chat parked on the unfocused side of the split view.
+    SplitChat,
     /// The prompt.
     Prompt,
     /// The reply message window.
@@ -26,6 +28,7 @@ impl Display for ComponentName {
             ComponentName::CoreWindow => write!(f, "Core Window"),
             ComponentName::ChatList => write!(f, "Chat List"),
             ComponentName::Chat => write!(f, "Chat"),
+            ComponentName::SplitChat => write!(f, "Split Chat"),
             ComponentName::Prompt => write!(f, "Prompt"),
             ComponentName::TitleBar => write!(f, "Title Bar"),
             ComponentName::StatusBar => write!(f, "Status Bar"),
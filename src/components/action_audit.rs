@@ -0,0 +1,51 @@
+use crate::{app_context::AppContext, components::list_popup::draw_text_popup};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+};
+
+/// `ActionAudit` is a read-only panel that shows the destructive actions
+/// logged in the audit log, it is drawn over the `ChatWindow`.
+pub struct ActionAudit {
+    /// The entries of the audit log, the most recent first.
+    entries: Vec<String>,
+}
+/// Implementation of the `ActionAudit` struct.
+impl ActionAudit {
+    /// Create a new instance of the `ActionAudit` struct.
+    ///
+    /// # Arguments
+    /// * `entries` - The entries of the audit log.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `ActionAudit` struct.
+    pub fn new(entries: Vec<String>) -> Self {
+        ActionAudit { entries }
+    }
+
+    /// Draw the `ActionAudit` centered in the given area, the entries that
+    /// do not fit are cut.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let text = if self.entries.is_empty() {
+            vec![Line::from("No destructive actions logged")]
+        } else {
+            self.entries
+                .iter()
+                .map(|entry| Line::from(entry.clone()))
+                .collect::<Vec<Line>>()
+        };
+        draw_text_popup(
+            frame,
+            area,
+            Constraint::Percentage(90),
+            "Action audit",
+            text,
+            app_context,
+        );
+    }
+}
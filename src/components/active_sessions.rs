@@ -0,0 +1,185 @@
+use crate::{
+    app_context::AppContext, components::list_popup::ListPopup, tg::message_entry::DateTimeEntry,
+    tg::td_enums::TdSession,
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Modifier,
+    text::{Line, Text},
+    widgets::ListItem,
+};
+
+/// Check whether a session can be terminated, the session of this client can
+/// only be closed by logging out.
+///
+/// # Arguments
+/// * `sessions` - The sessions of the account.
+/// * `session_id` - The identifier of the session to terminate.
+///
+/// # Returns
+/// * `bool` - Whether the session is known and it is not the current one.
+pub fn can_terminate(sessions: &[TdSession], session_id: i64) -> bool {
+    sessions
+        .iter()
+        .any(|session| session.id == session_id && !session.is_current)
+}
+
+/// Describe a session in a single line, with its application, its device and
+/// its location.
+///
+/// # Arguments
+/// * `session` - The session.
+///
+/// # Returns
+/// * `String` - The description of the session.
+pub fn session_summary(session: &TdSession) -> String {
+    let mut summary = [&session.application, &session.device, &session.location]
+        .iter()
+        .filter(|part| !part.is_empty())
+        .map(|part| part.as_str())
+        .collect::<Vec<&str>>()
+        .join(" · ");
+    if session.is_current {
+        summary.push_str(" (this device)");
+    }
+    summary
+}
+
+/// `ActiveSessions` is a panel that lists the sessions logged in to the
+/// account, it is drawn over the `ChatWindow` and the highlighted session can
+/// be terminated.
+pub struct ActiveSessions {
+    /// The sessions of the account, the current one first.
+    sessions: ListPopup<TdSession>,
+}
+/// Implementation of the `ActiveSessions` struct.
+impl ActiveSessions {
+    /// Create a new instance of the `ActiveSessions` struct.
+    ///
+    /// # Arguments
+    /// * `sessions` - The sessions of the account.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `ActiveSessions` struct.
+    pub fn new(sessions: Vec<TdSession>) -> Self {
+        ActiveSessions {
+            sessions: ListPopup::new(sessions),
+        }
+    }
+
+    /// Get the highlighted session.
+    pub fn selected(&self) -> Option<&TdSession> {
+        self.sessions.selected()
+    }
+
+    /// Get the identifier of the highlighted session, when it can be
+    /// terminated.
+    pub fn selected_terminable(&self) -> Option<i64> {
+        self.selected()
+            .map(|session| session.id)
+            .filter(|session_id| can_terminate(self.sessions.items(), *session_id))
+    }
+
+    /// Highlight the next session.
+    pub fn next(&mut self) {
+        self.sessions.next();
+    }
+
+    /// Highlight the previous session.
+    pub fn previous(&mut self) {
+        self.sessions.previous();
+    }
+
+    /// Draw the `ActiveSessions` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        // Each session takes two lines, its description and its last
+        // activity.
+        let items = if self.sessions.items().is_empty() {
+            vec![ListItem::new(Line::from("No active sessions"))]
+        } else {
+            self.sessions
+                .items()
+                .iter()
+                .map(|session| {
+                    let last_active = if session.is_current {
+                        "Online".to_string()
+                    } else {
+                        format!(
+                            "Last active {}",
+                            DateTimeEntry::convert_time(session.last_active_date)
+                        )
+                    };
+                    ListItem::new(Text::from(vec![
+                        Line::from(session_summary(session)),
+                        Line::styled(
+                            format!("  {}", last_active),
+                            app_context.style_chat().add_modifier(Modifier::DIM),
+                        ),
+                    ]))
+                })
+                .collect()
+        };
+        self.sessions.draw(
+            frame,
+            area,
+            Constraint::Percentage(80),
+            "Active sessions",
+            items,
+            app_context,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::active_sessions::{can_terminate, session_summary, ActiveSessions},
+        tg::td_enums::TdSession,
+    };
+
+    fn session(id: i64, is_current: bool, application: &str, device: &str) -> TdSession {
+        TdSession {
+            id,
+            is_current,
+            application: application.to_string(),
+            device: device.to_string(),
+            location: "Italy".to_string(),
+            last_active_date: 0,
+        }
+    }
+
+    #[test]
+    fn test_session_summary() {
+        assert_eq!(
+            session_summary(&session(1, true, "tgt 1.0", "Linux")),
+            "tgt 1.0 · Linux · Italy (this device)"
+        );
+        assert_eq!(
+            session_summary(&session(2, false, "Telegram Android 11.2", "")),
+            "Telegram Android 11.2 · Italy"
+        );
+    }
+
+    #[test]
+    fn test_current_session_is_protected() {
+        let sessions = vec![
+            session(1, true, "tgt 1.0", "Linux"),
+            session(2, false, "Telegram Desktop 5.6", "Windows 11"),
+        ];
+        assert!(!can_terminate(&sessions, 1));
+        assert!(can_terminate(&sessions, 2));
+        // The sessions that are not listed are unknown.
+        assert!(!can_terminate(&sessions, 3));
+
+        let mut panel = ActiveSessions::new(sessions);
+        assert_eq!(panel.selected().map(|s| s.id), Some(1));
+        assert_eq!(panel.selected_terminable(), None);
+        panel.next();
+        assert_eq!(panel.selected_terminable(), Some(2));
+    }
+}
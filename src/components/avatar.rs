@@ -0,0 +1,87 @@
+use ratatui::{
+    style::{Color, Style},
+    text::Span,
+};
+
+/// The colors of the avatars, the same ones used by the Telegram clients for
+/// the users and the chats without a profile photo.
+const AVATAR_COLORS: [Color; 7] = [
+    Color::Rgb(0xe1, 0x71, 0x76),
+    Color::Rgb(0xf3, 0x9b, 0x4a),
+    Color::Rgb(0x9c, 0x83, 0xe8),
+    Color::Rgb(0x7b, 0xc8, 0x62),
+    Color::Rgb(0x5c, 0xaf, 0xfa),
+    Color::Rgb(0x65, 0xaa, 0xdd),
+    Color::Rgb(0xee, 0x7a, 0xae),
+];
+
+/// Get the color of the avatar of a user or a chat.
+/// The color only depends on the identifier, so it is the same everywhere
+/// the user or the chat is shown.
+///
+/// # Arguments
+/// * `id` - The identifier of the user or of the chat.
+///
+/// # Returns
+/// * `Color` - The color of the avatar.
+pub fn avatar_color(id: i64) -> Color {
+    AVATAR_COLORS[(id.unsigned_abs() % AVATAR_COLORS.len() as u64) as usize]
+}
+
+/// Get the initials of a name, i.e. the first letter of its first two words.
+///
+/// # Arguments
+/// * `name` - The name of the user or of the chat.
+///
+/// # Returns
+/// * `String` - The uppercase initials, or `?` when the name is empty.
+pub fn avatar_initials(name: &str) -> String {
+    let initials = name
+        .split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect::<String>();
+    if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials
+    }
+}
+
+/// Get the avatar of a user or a chat as a colored block with its initials.
+///
+/// # Arguments
+/// * `id` - The identifier of the user or of the chat.
+/// * `name` - The name of the user or of the chat.
+///
+/// # Returns
+/// * `Span` - The avatar followed by a space.
+pub fn avatar_span(id: i64, name: &str) -> Span<'static> {
+    Span::styled(
+        format!(" {:<2} ", avatar_initials(name)),
+        Style::default().fg(Color::Black).bg(avatar_color(id)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::avatar::{avatar_color, avatar_initials};
+
+    #[test]
+    fn test_avatar_color_deterministic() {
+        for id in [0, 1, 42, -1001234567890, i64::MAX, i64::MIN] {
+            assert_eq!(avatar_color(id), avatar_color(id));
+        }
+        assert_eq!(avatar_color(1), avatar_color(8));
+        assert_ne!(avatar_color(1), avatar_color(2));
+    }
+
+    #[test]
+    fn test_avatar_initials() {
+        assert_eq!(avatar_initials("Pavel Durov"), "PD");
+        assert_eq!(avatar_initials("rust lang users group"), "RL");
+        assert_eq!(avatar_initials("Telegram"), "T");
+        assert_eq!(avatar_initials("  "), "?");
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    app_context::AppContext,
+    components::list_popup::ListPopup,
+    tg::td_enums::{TdBotCommand, TdBotMenu},
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::ListItem,
+};
+
+/// `BotMenu` is a panel that shows the menu of the bot of the open chat, it
+/// is drawn over the `ChatWindow`.
+/// The highlighted command is sent to the bot, the URL of a Web App is only
+/// shown.
+pub struct BotMenu {
+    /// The text and the URL of the Web App of the menu button.
+    web_app: Option<(String, String)>,
+    /// The commands of the bot, empty for a Web App.
+    commands: ListPopup<TdBotCommand>,
+}
+/// Implementation of the `BotMenu` struct.
+impl BotMenu {
+    /// Create a new instance of the `BotMenu` struct.
+    ///
+    /// # Arguments
+    /// * `menu` - The menu of the bot.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `BotMenu` struct.
+    pub fn new(menu: TdBotMenu) -> Self {
+        match menu {
+            TdBotMenu::WebApp { text, url } => BotMenu {
+                web_app: Some((text, url)),
+                commands: ListPopup::new(vec![]),
+            },
+            TdBotMenu::Commands(commands) => BotMenu {
+                web_app: None,
+                commands: ListPopup::new(commands),
+            },
+        }
+    }
+
+    /// Get the highlighted command, with its leading slash.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The command, `None` for a Web App.
+    pub fn selected_command(&self) -> Option<String> {
+        self.commands
+            .selected()
+            .map(|command| format!("/{}", command.command))
+    }
+
+    /// Highlight the next command.
+    pub fn next(&mut self) {
+        self.commands.next();
+    }
+
+    /// Highlight the previous command.
+    pub fn previous(&mut self) {
+        self.commands.previous();
+    }
+
+    /// Draw the `BotMenu` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let (title, items) = match &self.web_app {
+            None => (
+                "Bot commands".to_string(),
+                self.commands
+                    .items()
+                    .iter()
+                    .map(|command| {
+                        ListItem::new(Line::from(vec![
+                            Span::styled(
+                                format!("/{}", command.command),
+                                app_context.style_chat().add_modifier(Modifier::BOLD),
+                            ),
+                            Span::styled(
+                                format!(" {}", command.description),
+                                app_context.style_chat(),
+                            ),
+                        ]))
+                    })
+                    .collect::<Vec<ListItem>>(),
+            ),
+            Some((text, url)) => (
+                text.clone(),
+                vec![
+                    ListItem::new(Line::from("The Web App can not be shown, open it at:")),
+                    ListItem::new(Line::styled(
+                        url.clone(),
+                        app_context.style_chat().add_modifier(Modifier::UNDERLINED),
+                    )),
+                ],
+            ),
+        };
+        self.commands.draw(
+            frame,
+            area,
+            Constraint::Percentage(70),
+            title,
+            items,
+            app_context,
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{
+    app_context::AppContext,
+    components::list_popup::draw_list_popup,
+    tg::{
+        message_entry::DateTimeEntry,
+        td_enums::{TdCallEntry, TdCallOutcome},
+    },
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier},
+    text::Line,
+    widgets::{ListItem, ListState},
+};
+
+/// `CallHistory` is a panel that lists the recent calls of a chat, it is
+/// drawn over the `ChatWindow`.
+pub struct CallHistory {
+    /// The calls of the chat, from the most recent one.
+    entries: Vec<TdCallEntry>,
+}
+/// Implementation of the `CallHistory` struct.
+impl CallHistory {
+    /// Create a new instance of the `CallHistory` struct.
+    ///
+    /// # Arguments
+    /// * `entries` - The calls of the chat, from the most recent one.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `CallHistory` struct.
+    pub fn new(entries: Vec<TdCallEntry>) -> Self {
+        CallHistory { entries }
+    }
+
+    /// Draw the `CallHistory` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let items = if self.entries.is_empty() {
+            vec![ListItem::new(Line::from("No calls"))]
+        } else {
+            self.entries
+                .iter()
+                .map(|entry| {
+                    let style = match entry.outcome {
+                        TdCallOutcome::Missed => app_context
+                            .style_chat()
+                            .fg(Color::Red)
+                            .add_modifier(Modifier::BOLD),
+                        _ => app_context.style_chat(),
+                    };
+                    ListItem::new(Line::styled(call_summary(entry), style))
+                })
+                .collect()
+        };
+        draw_list_popup(
+            frame,
+            area,
+            Constraint::Length(40),
+            "Calls",
+            items,
+            &mut ListState::default(),
+            app_context,
+        );
+    }
+}
+
+/// Describe a call in a single line, with its type, its direction, its date
+/// and its duration or how it ended.
+///
+/// # Arguments
+/// * `entry` - The call to describe.
+///
+/// # Returns
+/// * `String` - The description of the call.
+pub fn call_summary(entry: &TdCallEntry) -> String {
+    let outcome = match entry.outcome {
+        TdCallOutcome::Completed => format!("{}:{:02}", entry.duration / 60, entry.duration % 60),
+        TdCallOutcome::Missed => "Missed".to_string(),
+        TdCallOutcome::Declined => "Declined".to_string(),
+    };
+    format!(
+        "{} {} {} {}",
+        if entry.is_video { "📹" } else { "📞" },
+        if entry.is_outgoing { "↗" } else { "↙" },
+        DateTimeEntry::convert_time(entry.date),
+        outcome
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::call_history::call_summary;
+    use crate::tg::td_enums::{TdCallEntry, TdCallOutcome};
+
+    fn call_entry(is_video: bool, duration: i32, outcome: TdCallOutcome) -> TdCallEntry {
+        TdCallEntry {
+            date: 0,
+            is_outgoing: true,
+            is_video,
+            duration,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_call_summary_outcome() {
+        let completed = call_summary(&call_entry(false, 125, TdCallOutcome::Completed));
+        assert!(completed.starts_with("📞 ↗ "));
+        assert!(completed.ends_with(" 2:05"));
+        let missed = call_summary(&call_entry(true, 0, TdCallOutcome::Missed));
+        assert!(missed.starts_with("📹 ↗ "));
+        assert!(missed.ends_with(" Missed"));
+        let declined = call_summary(&call_entry(false, 0, TdCallOutcome::Declined));
+        assert!(declined.ends_with(" Declined"));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{app_context::AppContext, components::list_popup::draw_text_popup};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+};
+
+/// The number of bars of the sparkline shown in the panel.
+pub const CHAT_ACTIVITY_BUCKETS: usize = 24;
+
+/// The bars of the sparkline, from the lowest to the highest.
+const SPARKLINE_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Build a sparkline of the number of messages over time.
+/// The time between the first and the last timestamp is split in buckets of
+/// the same length, each bar is as high as the number of messages of its
+/// bucket compared to the busiest one.
+///
+/// # Arguments
+/// * `timestamps` - The timestamps of the messages, in seconds, in any order.
+/// * `buckets` - The number of bars of the sparkline.
+///
+/// # Returns
+/// * `String` - The sparkline, a flat line when there are no messages.
+pub fn activity_sparkline(timestamps: &[i64], buckets: usize) -> String {
+    if buckets == 0 {
+        return String::new();
+    }
+    let (Some(first), Some(last)) = (timestamps.iter().min(), timestamps.iter().max()) else {
+        return SPARKLINE_BARS[0].to_string().repeat(buckets);
+    };
+    let span = (last - first) as u128 + 1;
+    let mut counts = vec![0usize; buckets];
+    for timestamp in timestamps {
+        let bucket = ((timestamp - first) as u128 * buckets as u128 / span) as usize;
+        counts[bucket] += 1;
+    }
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    counts
+        .iter()
+        .map(|count| SPARKLINE_BARS[count * (SPARKLINE_BARS.len() - 1) / max])
+        .collect()
+}
+
+/// `ChatActivity` is a panel that shows when the open chat is active, it is
+/// drawn over the `ChatWindow`.
+pub struct ChatActivity {
+    /// The lines of the panel.
+    lines: Vec<String>,
+}
+/// Implementation of the `ChatActivity` struct.
+impl ChatActivity {
+    /// Create a new instance of the `ChatActivity` struct.
+    ///
+    /// # Arguments
+    /// * `lines` - The sparkline and the time range it covers.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `ChatActivity` struct.
+    pub fn new(lines: Vec<String>) -> Self {
+        ChatActivity { lines }
+    }
+
+    /// Draw the `ChatActivity` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let text = self
+            .lines
+            .iter()
+            .map(|line| Line::from(line.clone()))
+            .collect::<Vec<Line>>();
+        draw_text_popup(
+            frame,
+            area,
+            Constraint::Percentage(90),
+            "Activity",
+            text,
+            app_context,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::chat_activity::activity_sparkline;
+
+    #[test]
+    fn test_activity_sparkline_empty_is_flat() {
+        assert_eq!(activity_sparkline(&[], 5), "▁▁▁▁▁");
+        assert_eq!(activity_sparkline(&[], 0), "");
+        assert_eq!(activity_sparkline(&[100], 0), "");
+    }
+
+    #[test]
+    fn test_activity_sparkline_buckets() {
+        // Four buckets of 25 seconds: 3, 0, 1 and 2 messages.
+        let timestamps = [0, 10, 24, 60, 99, 90];
+        assert_eq!(activity_sparkline(&timestamps, 4), "█▁▃▅");
+        // The order of the timestamps does not matter.
+        assert_eq!(activity_sparkline(&[99, 60, 90, 24, 10, 0], 4), "█▁▃▅");
+        // A single message fills the first bucket.
+        assert_eq!(activity_sparkline(&[1700000000], 3), "█▁▁");
+    }
+}
@@ -1,21 +1,30 @@
-use crate::action::Action;
+use crate::action::{Action, Modifiers};
 use crate::app_context::AppContext;
 use crate::component_name::ComponentName::Prompt;
+use crate::components::avatar::avatar_span;
 use crate::components::component_traits::{Component, HandleFocus};
+use crate::components::search_bar::{SearchBar, SearchBarResult};
 use crate::event::Event;
+use crate::tg::chat_folders::ALL_CHATS_FOLDER_ID;
 use crate::tg::message_entry::MessageEntry;
-use ratatui::layout::Rect;
+use crossterm::event::KeyCode;
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::Style;
 use ratatui::symbols::border::PLAIN;
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::block::{Block, Title};
 use ratatui::widgets::Borders;
-use ratatui::widgets::{List, ListDirection, ListState};
+use ratatui::widgets::{List, ListDirection, ListState, Paragraph, Tabs};
 use ratatui::Frame;
 use std::sync::Arc;
 use tdlib_rs::enums::{ChatList, UserStatus};
 use tdlib_rs::types::User;
 use tokio::sync::mpsc::UnboundedSender;
 
+/// The time, in seconds, a chat is muted for when it is muted forever.
+/// Telegram considers any value greater than one year as forever.
+pub const MUTE_FOREVER: i32 = i32::MAX;
+
 #[derive(Debug)]
 pub struct ChatListEntry {
     chat_id: i64,
@@ -28,6 +37,10 @@ pub struct ChatListEntry {
     last_read_inbox_message_id: Option<i64>,
     /// Identifier of the last read outgoing message
     last_read_outbox_message_id: Option<i64>,
+    /// Whether the notifications of the chat are muted
+    is_muted: bool,
+    /// Whether the chat woke up from a snooze and is shown as a reminder
+    is_reminder: bool,
 }
 impl Default for ChatListEntry {
     fn default() -> Self {
@@ -45,6 +58,8 @@ impl ChatListEntry {
             unread_count: 0,
             last_read_inbox_message_id: None,
             last_read_outbox_message_id: None,
+            is_muted: false,
+            is_reminder: false,
         }
     }
 
@@ -72,6 +87,30 @@ impl ChatListEntry {
     pub fn set_last_read_outbox_message_id(&mut self, last_read_outbox_message_id: i64) {
         self.last_read_outbox_message_id = Some(last_read_outbox_message_id);
     }
+    pub fn set_is_muted(&mut self, is_muted: bool) {
+        self.is_muted = is_muted;
+    }
+    pub fn is_muted(&self) -> bool {
+        self.is_muted
+    }
+    /// Whether the chat has unread messages or it is marked as unread.
+    pub fn has_unread(&self) -> bool {
+        self.unread_count > 0 || self.is_marked_as_unread
+    }
+    pub fn set_is_reminder(&mut self, is_reminder: bool) {
+        self.is_reminder = is_reminder;
+    }
+
+    /// Flip the chat between muted forever and unmuted.
+    /// It returns the new time, in seconds, the chat is muted for.
+    pub fn toggle_mute(&mut self) -> i32 {
+        self.is_muted = !self.is_muted;
+        if self.is_muted {
+            MUTE_FOREVER
+        } else {
+            0
+        }
+    }
 
     fn get_text_styled(&self, app_context: &AppContext) -> Text {
         let mut online_symbol = "";
@@ -87,20 +126,39 @@ impl ChatListEntry {
             };
             verificated_symbol = if user.is_verified { "✅" } else { "" };
         }
-        let unread_info = if self.is_marked_as_unread {
+        let muted_symbol = if self.is_muted { "🔇" } else { "" };
+        let reminder_symbol = if self.is_reminder { "⏰ " } else { "" };
+        let unread_info = if self.unread_count > 0 {
             format!("({})", self.unread_count)
         } else {
             "".to_string()
         };
 
+        let avatar = if app_context.avatar_initials() {
+            avatar_span(self.chat_id, &self.chat_name)
+        } else {
+            Span::default()
+        };
+
+        let chat_title_max_width = app_context.app_config().chat_title_max_width;
+        let chat_name = match chat_title_max_width {
+            0 => self.chat_name.clone(),
+            max => {
+                // The shorter titles are padded, so the markers after them
+                // start at the same column.
+                let title = ellipsize(&self.chat_name, max);
+                let padding = usize::from(max).saturating_sub(Span::raw(&title).width());
+                format!("{}{}", title, " ".repeat(padding))
+            }
+        };
+
         let preview_lines = -1;
         let mut entry = Text::default();
         entry.extend(vec![Line::from(vec![
+            avatar,
+            Span::raw(reminder_symbol),
             Span::raw(online_symbol),
-            Span::styled(
-                self.chat_name.clone(),
-                app_context.style_chat_list_item_chat_name(),
-            ),
+            Span::styled(chat_name, app_context.style_chat_list_item_chat_name()),
             Span::raw(" "),
             Span::styled(
                 unread_info,
@@ -108,6 +166,7 @@ impl ChatListEntry {
             ),
             Span::raw(" "),
             Span::raw(verificated_symbol),
+            Span::raw(muted_symbol),
             Span::raw(" | "),
             self.last_message.as_ref().map_or_else(Span::default, |e| {
                 e.timestamp().get_span_styled(app_context)
@@ -117,6 +176,7 @@ impl ChatListEntry {
             e.get_lines_styled_with_style(
                 app_context.style_chat_list_item_message_content(),
                 preview_lines,
+                None,
             )[0]
             .clone()
         }));
@@ -140,6 +200,13 @@ pub struct ChatListWindow {
     chat_list_state: ListState,
     /// Indicates whether the `ChatListWindow` is focused or not.
     focused: bool,
+    /// The folder the chat list was filtered by when it was last drawn.
+    folder_id: i32,
+    /// Whether only the unread chats were shown when the chat list was last
+    /// drawn.
+    unread_only_view: bool,
+    /// The search bar docked above the chat list, when it is shown.
+    search_bar: Option<SearchBar>,
 }
 /// Implementation of the `ChatListWindow` struct.
 impl ChatListWindow {
@@ -156,6 +223,9 @@ impl ChatListWindow {
         let chat_list = vec![];
         let chat_list_state = ListState::default();
         let focused = false;
+        let folder_id = ALL_CHATS_FOLDER_ID;
+        let unread_only_view = false;
+        let search_bar = None;
 
         ChatListWindow {
             app_context,
@@ -164,6 +234,9 @@ impl ChatListWindow {
             chat_list,
             chat_list_state,
             focused,
+            folder_id,
+            unread_only_view,
+            search_bar,
         }
     }
     /// Set the name of the `ChatListWindow`.
@@ -217,31 +290,218 @@ impl ChatListWindow {
     fn unselect(&mut self) {
         self.chat_list_state.select(None);
     }
-    /// Confirm the selection of the chat item in the list.
-    fn confirm_selection(&mut self) {
+    /// Filter the chat list by the next or the previous folder tab.
+    ///
+    /// # Arguments
+    /// * `forward` - Whether the next folder is selected.
+    fn select_folder(&self, forward: bool) {
+        if !self.app_context.folder_tabs() {
+            return;
+        }
+        let folder_id = {
+            let chat_folders = self.app_context.tg_context().chat_folders();
+            if forward {
+                chat_folders.next_id()
+            } else {
+                chat_folders.previous_id()
+            }
+        };
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::SelectFolder(folder_id)).unwrap();
+        }
+    }
+    /// Draw the chat folders as tabs, the first tab shows all the chats.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the tabs.
+    fn draw_folder_tabs(&self, frame: &mut Frame<'_>, area: Rect) {
+        let chat_folders = self.app_context.tg_context().chat_folders();
+        let titles = std::iter::once("All".to_string()).chain(
+            chat_folders
+                .folders()
+                .iter()
+                .map(|(_, title)| title.clone()),
+        );
+        let tabs = Tabs::new(titles)
+            .select(chat_folders.active_index())
+            .style(self.app_context.style_chat_list())
+            .highlight_style(self.app_context.style_chat_list_item_selected());
+        frame.render_widget(tabs, area);
+    }
+    /// Toggle the mute of the selected chat item in the list.
+    /// The chat is updated locally, so that the marker in the list changes
+    /// immediately, and then the new settings are sent to Telegram.
+    fn toggle_selected_chat_mute(&mut self) {
         if let Some(i) = self.chat_list_state.selected() {
-            if let Some(chat) = self.chat_list.get(i) {
+            if let Some(chat) = self.chat_list.get_mut(i) {
+                let mute_for = chat.toggle_mute();
                 self.app_context
                     .tg_context()
-                    .set_open_chat_user(chat.user.clone());
-                self.app_context.tg_context().set_open_chat_id(chat.chat_id);
-                self.app_context.tg_context().clear_open_chat_messages();
+                    .set_chat_mute_for(chat.chat_id, mute_for);
                 self.app_context
                     .action_tx()
-                    .send(Action::FocusComponent(Prompt))
+                    .send(Action::SetChatMuteFor(chat.chat_id, mute_for))
                     .unwrap();
-
-                if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                    self.app_context.tg_context().set_from_message_id(0);
-                    // Load chat history
-                    event_tx.send(Event::GetChatHistory).unwrap();
-
-                    // Mark all unread messages as read
-                    event_tx.send(Event::ViewAllMessages).unwrap();
+            }
+        }
+    }
+    /// Leave the group or the channel selected in the list.
+    /// The chat is removed from the list once it has been left.
+    fn leave_selected_chat(&self) {
+        if let Some(chat) = self
+            .chat_list_state
+            .selected()
+            .and_then(|i| self.chat_list.get(i))
+        {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx.send(Event::LeaveChat(chat.chat_id)).unwrap();
+            }
+        }
+    }
+    /// Handle a key typed in the search bar.
+    /// While nothing is typed the arrows and Enter move through the chat
+    /// list, then through the results of the search. The chats are filtered
+    /// while typing, the messages are searched on Enter.
+    ///
+    /// # Arguments
+    /// * `key_code` - The typed key.
+    /// * `modifiers` - The modifiers of the typed key.
+    fn search_bar_key(&mut self, key_code: KeyCode, modifiers: Modifiers) {
+        let Some(query_is_empty) = self
+            .search_bar
+            .as_ref()
+            .map(|search_bar| search_bar.query().is_empty())
+        else {
+            return;
+        };
+        match key_code {
+            KeyCode::Down if query_is_empty => self.next(),
+            KeyCode::Up if query_is_empty => self.previous(),
+            KeyCode::Enter if query_is_empty => self.confirm_selection(),
+            KeyCode::Enter => self.open_search_result(),
+            _ => {
+                let Some(search_bar) = self.search_bar.as_mut() else {
+                    return;
+                };
+                match key_code {
+                    KeyCode::Char(c) if !modifiers.control && !modifiers.alt => search_bar.push(c),
+                    KeyCode::Backspace => search_bar.pop(),
+                    KeyCode::Down => search_bar.next(),
+                    KeyCode::Up => search_bar.previous(),
+                    _ => {}
                 }
             }
         }
     }
+    /// Open the chat, or the chat at the message, highlighted in the results
+    /// of the search bar. When no result is highlighted the messages are
+    /// searched for the typed text, so a search is sent once per query and
+    /// not on every typed character.
+    fn open_search_result(&self) {
+        let Some(search_bar) = self.search_bar.as_ref() else {
+            return;
+        };
+        let event = match search_bar.selected() {
+            Some(SearchBarResult::Chat(chat_id, _)) => Event::OpenChat(*chat_id),
+            Some(SearchBarResult::Message(message)) => {
+                Event::OpenChatAtMessage(message.chat_id, message.message_id)
+            }
+            None => Event::SearchMessages(search_bar.query().to_string()),
+        };
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(event).unwrap();
+        }
+    }
+    /// Confirm the selection of the chat item in the list.
+    fn confirm_selection(&mut self) {
+        if let Some(i) = self.chat_list_state.selected() {
+            if let Some(chat) = self.chat_list.get(i) {
+                self.open_chat(chat.chat_id, chat.user.clone());
+            }
+        }
+    }
+    /// Open a chat, that may not be in the list, e.g. because it is chosen
+    /// in the unread dashboard or in the results of the search bar.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `user` - The user of the chat, if it is a private chat.
+    fn open_chat(&self, chat_id: i64, user: Option<User>) {
+        self.app_context.tg_context().set_open_chat_user(user);
+        self.app_context.tg_context().set_open_chat_id(chat_id);
+        self.app_context.tg_context().set_open_topic_id(0);
+        self.app_context.tg_context().clear_open_chat_messages();
+        self.app_context
+            .tg_context()
+            .snoozed_chats()
+            .dismiss(chat_id);
+        self.app_context
+            .action_tx()
+            .send(Action::FocusComponent(Prompt))
+            .unwrap();
+
+        let read_on_scroll_to = self.app_context.app_config().read_on_scroll_to;
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            self.app_context.tg_context().set_from_message_id(0);
+            for event in open_chat_events(read_on_scroll_to) {
+                event_tx.send(event).unwrap();
+            }
+        }
+    }
+}
+
+/// Get the events sent when a chat is opened: its history is loaded and, unless
+/// the messages are marked as read only when they are selected, all its
+/// unread messages are marked as read.
+///
+/// # Arguments
+/// * `read_on_scroll_to` - Whether the messages are marked as read only when
+///   they are selected.
+///
+/// # Returns
+/// * `Vec<Event>` - The events to send.
+pub fn open_chat_events(read_on_scroll_to: bool) -> Vec<Event> {
+    if read_on_scroll_to {
+        vec![Event::GetChatHistory]
+    } else {
+        vec![Event::GetChatHistory, Event::ViewAllMessages]
+    }
+}
+
+/// Shorten a chat title to the given width, the cut titles end with an
+/// ellipsis. The title is cut between the graphemes, so that an emoji or a
+/// letter with its accents is never split, and the wide characters count
+/// as two columns.
+///
+/// # Arguments
+/// * `title` - The title of the chat.
+/// * `max` - The maximum width of the title, in columns.
+///
+/// # Returns
+/// * `String` - The title, ellipsized when it is wider than `max`.
+pub fn ellipsize(title: &str, max: u16) -> String {
+    let max = usize::from(max);
+    let title_span = Span::raw(title);
+    if title_span.width() <= max {
+        return title.to_string();
+    }
+    // The ellipsis takes a column.
+    let budget = max.saturating_sub(1);
+    let mut width = 0;
+    let mut ellipsized = String::new();
+    for grapheme in title_span.styled_graphemes(Style::default()) {
+        let grapheme_width = Span::raw(grapheme.symbol).width();
+        if width + grapheme_width > budget {
+            break;
+        }
+        width += grapheme_width;
+        ellipsized.push_str(grapheme.symbol);
+    }
+    if max > 0 {
+        ellipsized.push('…');
+    }
+    ellipsized
 }
 
 /// Implement the `HandleFocus` trait for the `ChatListWindow` struct.
@@ -270,6 +530,22 @@ impl Component for ChatListWindow {
             Action::ChatListPrevious => self.previous(),
             Action::ChatListUnselect => self.unselect(),
             Action::ChatListOpen => self.confirm_selection(),
+            Action::OpenChat(chat_id) => {
+                let user = self.app_context.tg_context().user_of_chat(chat_id);
+                self.open_chat(chat_id, user);
+            }
+            Action::ToggleSelectedChatMute => self.toggle_selected_chat_mute(),
+            Action::LeaveSelectedChat => self.leave_selected_chat(),
+            Action::ChatListNextFolder => self.select_folder(true),
+            Action::ChatListPreviousFolder => self.select_folder(false),
+            Action::Key(key_code, modifiers) if self.search_bar.is_some() => {
+                self.search_bar_key(key_code, modifiers)
+            }
+            Action::ShowSearchBarMessages(query, messages) => {
+                if let Some(search_bar) = self.search_bar.as_mut() {
+                    search_bar.set_messages(&query, messages);
+                }
+            }
             _ => {}
         }
     }
@@ -280,9 +556,41 @@ impl Component for ChatListWindow {
         } else {
             self.app_context.style_chat_list()
         };
+        let unread_only_view = self.app_context.unread_only_view();
         if let Ok(Some(items)) = self.app_context.tg_context().get_chats_index() {
-            self.chat_list = items;
+            self.chat_list = items
+                .into_iter()
+                .filter(|item| !unread_only_view || item.has_unread())
+                .collect();
+        }
+        // The selection is meaningless once the list is filtered by another
+        // folder or by the unread chats.
+        let folder_id = self.app_context.tg_context().chat_folders().active();
+        if folder_id != self.folder_id || unread_only_view != self.unread_only_view {
+            self.folder_id = folder_id;
+            self.unread_only_view = unread_only_view;
+            self.unselect();
+        }
+        if self.app_context.search_bar() != self.search_bar.is_some() {
+            self.search_bar = self.app_context.search_bar().then(SearchBar::new);
         }
+        let area = match self.search_bar.as_ref() {
+            Some(search_bar) => {
+                let [input_area, list_area] =
+                    Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+                search_bar.draw_input(frame, input_area, &self.app_context, self.focused);
+                list_area
+            }
+            None => area,
+        };
+        let area = if self.app_context.folder_tabs() {
+            let [tabs_area, list_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+            self.draw_folder_tabs(frame, tabs_area);
+            list_area
+        } else {
+            area
+        };
         let items = self
             .chat_list
             .iter()
@@ -293,6 +601,29 @@ impl Component for ChatListWindow {
             .borders(Borders::TOP | Borders::LEFT | Borders::BOTTOM)
             .title(Title::from(self.name.as_str()));
 
+        if let Some(search_bar) = self
+            .search_bar
+            .as_mut()
+            .filter(|search_bar| !search_bar.query().is_empty())
+        {
+            let chats = self
+                .chat_list
+                .iter()
+                .map(|chat| (chat.chat_id, chat.chat_name.clone()))
+                .collect::<Vec<_>>();
+            search_bar.draw_results(frame, area, &self.app_context, block, &chats);
+            return Ok(());
+        }
+
+        if unread_only_view && self.chat_list.is_empty() {
+            let placeholder = Paragraph::new("All caught up")
+                .block(block)
+                .style(self.app_context.style_chat_list())
+                .alignment(Alignment::Center);
+            frame.render_widget(placeholder, area);
+            return Ok(());
+        }
+
         let list = List::new(items)
             .block(block)
             .style(self.app_context.style_chat_list())
@@ -305,3 +636,68 @@ impl Component for ChatListWindow {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::chat_list_window::{ellipsize, open_chat_events, ChatListEntry, MUTE_FOREVER},
+        event::Event,
+    };
+
+    #[test]
+    fn test_toggle_mute() {
+        let mut chat = ChatListEntry::new();
+        chat.set_chat_id(42);
+        chat.set_chat_name("Synthetic chat".to_string());
+        assert!(!chat.is_muted());
+
+        assert_eq!(chat.toggle_mute(), MUTE_FOREVER);
+        assert!(chat.is_muted());
+
+        assert_eq!(chat.toggle_mute(), 0);
+        assert!(!chat.is_muted());
+    }
+
+    #[test]
+    fn test_has_unread() {
+        let mut chat = ChatListEntry::new();
+        assert!(!chat.has_unread());
+
+        chat.set_unread_count(3);
+        assert!(chat.has_unread());
+
+        chat.set_unread_count(0);
+        chat.set_is_marked_as_unread(true);
+        assert!(chat.has_unread());
+    }
+
+    #[test]
+    fn test_ellipsize_exact_fit() {
+        assert_eq!(ellipsize("Rust", 4), "Rust");
+        assert_eq!(ellipsize("Rust", 10), "Rust");
+        assert_eq!(ellipsize("Rustaceans", 4), "Rus…");
+        assert_eq!(ellipsize("Rustaceans", 1), "…");
+        assert_eq!(ellipsize("Rustaceans", 0), "");
+    }
+
+    #[test]
+    fn test_ellipsize_wide_characters() {
+        // Each character is two columns wide, the ellipsis fits in the
+        // column left by the character that does not fit.
+        assert_eq!(ellipsize("日本語のチャット", 7), "日本語…");
+        assert_eq!(ellipsize("日本語のチャット", 6), "日本…");
+        assert_eq!(ellipsize("日本語", 6), "日本語");
+        // The accents are kept with their letter.
+        assert_eq!(ellipsize("Cafe\u{301} cre\u{300}me", 5), "Cafe\u{301}…");
+    }
+
+    #[test]
+    fn test_open_chat_events_read_on_scroll_to() {
+        assert_eq!(
+            open_chat_events(false),
+            vec![Event::GetChatHistory, Event::ViewAllMessages]
+        );
+        // Opening the chat does not mark any message as read.
+        assert!(!open_chat_events(true).contains(&Event::ViewAllMessages));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{
+    app_context::AppContext, components::list_popup::draw_list_popup,
+    tg::td_enums::TdChatPermissions,
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier},
+    text::Line,
+    widgets::{ListItem, ListState},
+};
+
+/// `ChatPermissions` is a panel that lists what the current user can do in a
+/// chat, it is drawn over the `ChatWindow`.
+pub struct ChatPermissions {
+    /// The permissions of the current user in the chat.
+    permissions: TdChatPermissions,
+}
+/// Implementation of the `ChatPermissions` struct.
+impl ChatPermissions {
+    /// Create a new instance of the `ChatPermissions` struct.
+    ///
+    /// # Arguments
+    /// * `permissions` - The permissions of the current user in the chat.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `ChatPermissions` struct.
+    pub fn new(permissions: TdChatPermissions) -> Self {
+        ChatPermissions { permissions }
+    }
+
+    /// Draw the `ChatPermissions` centered in the given area.
+    /// The restricted actions are marked and highlighted.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let mut items = self
+            .permissions
+            .entries
+            .iter()
+            .map(|(label, allowed)| {
+                if *allowed {
+                    ListItem::new(Line::styled(
+                        format!("✓ {}", label),
+                        app_context.style_chat(),
+                    ))
+                } else {
+                    ListItem::new(Line::styled(
+                        format!("✗ {}", label),
+                        app_context
+                            .style_chat()
+                            .fg(Color::Red)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                }
+            })
+            .collect::<Vec<ListItem>>();
+        if self.permissions.slow_mode_delay > 0 {
+            items.push(ListItem::new(Line::styled(
+                format!(
+                    "Slow mode: one message every {}s",
+                    self.permissions.slow_mode_delay
+                ),
+                app_context.style_timestamp(),
+            )));
+        }
+
+        draw_list_popup(
+            frame,
+            area,
+            Constraint::Length(40),
+            match self.permissions.restricted().len() {
+                0 => "Permissions".to_string(),
+                n => format!("Permissions ({} restricted)", n),
+            },
+            items,
+            &mut ListState::default(),
+            app_context,
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    app_context::AppContext, components::list_popup::ListPopup, tg::td_enums::TdPollEntry,
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Modifier,
+    text::Line,
+    widgets::ListItem,
+};
+
+/// `ChatPolls` is a panel that lists the polls of a chat, it is drawn over
+/// the `ChatWindow` and the highlighted poll can be jumped to.
+pub struct ChatPolls {
+    /// The polls of the chat, from the most recent one.
+    polls: ListPopup<TdPollEntry>,
+}
+/// Implementation of the `ChatPolls` struct.
+impl ChatPolls {
+    /// Create a new instance of the `ChatPolls` struct.
+    ///
+    /// # Arguments
+    /// * `polls` - The polls of the chat, from the most recent one.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `ChatPolls` struct.
+    pub fn new(polls: Vec<TdPollEntry>) -> Self {
+        ChatPolls {
+            polls: ListPopup::new(polls),
+        }
+    }
+
+    /// Get the identifier of the highlighted poll message.
+    pub fn selected(&self) -> Option<i64> {
+        self.polls.selected().map(|poll| poll.message_id)
+    }
+
+    /// Highlight the next poll.
+    pub fn next(&mut self) {
+        self.polls.next();
+    }
+
+    /// Highlight the previous poll.
+    pub fn previous(&mut self) {
+        self.polls.previous();
+    }
+
+    /// Draw the `ChatPolls` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let items = if self.polls.items().is_empty() {
+            vec![ListItem::new(Line::from("No polls"))]
+        } else {
+            self.polls
+                .items()
+                .iter()
+                .map(|poll| {
+                    let style = if poll.is_closed {
+                        app_context.style_chat().add_modifier(Modifier::DIM)
+                    } else {
+                        app_context.style_chat()
+                    };
+                    ListItem::new(Line::styled(poll_summary(poll), style))
+                })
+                .collect()
+        };
+        self.polls.draw(
+            frame,
+            area,
+            Constraint::Percentage(80),
+            "Polls",
+            items,
+            app_context,
+        );
+    }
+}
+
+/// Describe a poll in a single line, with whether the current user voted,
+/// its question and whether it is closed.
+///
+/// # Arguments
+/// * `poll` - The poll.
+///
+/// # Returns
+/// * `String` - The description of the poll.
+fn poll_summary(poll: &TdPollEntry) -> String {
+    let mut summary = format!(
+        "{} {}",
+        if poll.voted { "●" } else { "○" },
+        poll.question.replace('\n', " ")
+    );
+    if poll.is_closed {
+        summary.push_str(" 🔒");
+    }
+    summary
+}
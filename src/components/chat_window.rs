@@ -1,13 +1,39 @@
 use crate::{
     action::Action,
     app_context::AppContext,
-    components::component_traits::{Component, HandleFocus},
+    component_name::ComponentName,
+    components::{
+        action_audit::ActionAudit,
+        active_sessions::ActiveSessions,
+        bot_menu::BotMenu,
+        call_history::CallHistory,
+        chat_activity::ChatActivity,
+        chat_permissions::ChatPermissions,
+        chat_polls::ChatPolls,
+        component_traits::{Component, HandleFocus},
+        edit_history::EditHistory,
+        formatting_preview::FormattingPreviewPanel,
+        forum_topics::{ForumTopics, ALL_TOPICS_THREAD_ID},
+        location_panel::LocationPanel,
+        quiz_explanation::QuizExplanation,
+        reaction_picker::ReactionPicker,
+        scheduled_messages::ScheduledMessages,
+        search_results::SearchResults,
+        send_as_picker::SendAsPicker,
+        unread_dashboard::UnreadDashboard,
+    },
+    configs::raw::app_raw::{DeleteLastBehavior, ScrolledUpNewMessageBehavior, SelectionStyle},
     event::Event,
-    tg::message_entry::MessageEntry,
+    tg::{
+        message_entry::{group_album, AlbumGroup, MessageEntry},
+        send_error::SendErrorKind,
+    },
 };
 use arboard::Clipboard;
+use crossterm::event::KeyCode;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     symbols::{
         border::{self, Set},
         line,
@@ -15,7 +41,7 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, List, ListDirection, ListItem, ListState, Paragraph},
 };
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 use tokio::sync::mpsc::UnboundedSender;
 
 /// `ChatWindow` is a struct that represents a window for displaying a chat.
@@ -29,10 +55,77 @@ pub struct ChatWindow {
     action_tx: Option<UnboundedSender<Action>>,
     /// A list of message items to be displayed in the `ChatWindow`.
     message_list: Vec<MessageEntry>,
+    /// The groups of the message list, each of them is a single item of the
+    /// list. The messages of an album are in the same group.
+    album_groups: Vec<AlbumGroup>,
+    /// The position of the selected message inside the selected album, from
+    /// the first sent item of the album.
+    album_item: usize,
+    /// The chat whose last read message is kept in `divider_last_read`.
+    divider_chat_id: i64,
+    /// The range of the groups that were visible the last time the
+    /// `ChatWindow` was drawn.
+    visible_range: Range<usize>,
+    /// The last read incoming message of the chat when it was opened. It is
+    /// kept while the chat is open, so that the "New messages" divider does
+    /// not move when the messages are marked as read.
+    divider_last_read: i64,
+    /// The chat and the identifier of its most recent message the last time
+    /// the `ChatWindow` was drawn, to tell the messages received since then.
+    newest_seen: (i64, i64),
+    /// The number of messages received while the history is scrolled up,
+    /// shown on the jump button.
+    new_while_scrolled_up: usize,
     /// The state of the list.
     message_list_state: ListState,
     /// Indicates whether the `ChatWindow` is focused or not.
     focused: bool,
+    /// Indicates whether the `ChatWindow` shows the chat parked on the
+    /// unfocused side of the split view instead of the open chat.
+    split: bool,
+    /// The menu used to choose a reaction for the selected message, it is
+    /// drawn over the messages while it is open.
+    reaction_picker: Option<ReactionPicker>,
+    /// The panel listing the recent calls of the chat, it is drawn over the
+    /// messages while it is open.
+    call_history: Option<CallHistory>,
+    /// The permissions of the current user in the chat, if open.
+    chat_permissions: Option<ChatPermissions>,
+    /// The location of a message and its map links, if open.
+    location_panel: Option<LocationPanel>,
+    /// The destructive actions logged in the audit log, if open.
+    action_audit: Option<ActionAudit>,
+    /// How the formatting of the composed text is rendered, if previewed.
+    formatting_preview: Option<FormattingPreviewPanel>,
+    /// The explanation of an answered quiz, if open.
+    quiz_explanation: Option<QuizExplanation>,
+    /// The activity of the chat over time, if open.
+    chat_activity: Option<ChatActivity>,
+    /// The edits of the selected message, if open.
+    edit_history: Option<EditHistory>,
+    /// The panel listing the messages found by the last hashtag search, it is
+    /// drawn over the messages while it is open.
+    search_results: Option<SearchResults>,
+    /// The entity of the selected message that is followed, `None` until an
+    /// entity is selected, in which case the first one is followed.
+    entity_index: Option<usize>,
+    /// The menu used to choose the identity the messages are sent as, it is
+    /// drawn over the messages while it is open.
+    send_as_picker: Option<SendAsPicker>,
+    /// The chats with unread messages, drawn over the messages while it is
+    /// open.
+    unread_dashboard: Option<UnreadDashboard>,
+    /// The panel that lists the polls of the chat, when it is open.
+    chat_polls: Option<ChatPolls>,
+    /// The panel that lists the topics of the forum, when it is open.
+    forum_topics: Option<ForumTopics>,
+    /// The panel that lists the sessions of the account, when it is open.
+    active_sessions: Option<ActiveSessions>,
+    /// The panel that lists the scheduled messages of the chat, when it is
+    /// open.
+    scheduled_messages: Option<ScheduledMessages>,
+    /// The menu of the bot of the open chat, if open.
+    bot_menu: Option<BotMenu>,
 }
 /// Implementation of the `ChatWindow` struct.
 impl ChatWindow {
@@ -47,15 +140,67 @@ impl ChatWindow {
         let name = "".to_string();
         let action_tx = None;
         let message_list = vec![];
+        let album_groups = vec![];
+        let album_item = 0;
+        let divider_chat_id = 0;
+        let divider_last_read = -1;
+        let newest_seen = (0, 0);
+        let new_while_scrolled_up = 0;
+        let visible_range = 0..0;
         let message_list_state = ListState::default();
         let focused = false;
+        let split = false;
+        let reaction_picker = None;
+        let call_history = None;
+        let chat_permissions = None;
+        let location_panel = None;
+        let action_audit = None;
+        let formatting_preview = None;
+        let quiz_explanation = None;
+        let chat_activity = None;
+        let edit_history = None;
+        let search_results = None;
+        let entity_index = None;
+        let send_as_picker = None;
+        let unread_dashboard = None;
+        let chat_polls = None;
+        let forum_topics = None;
+        let active_sessions = None;
+        let scheduled_messages = None;
+        let bot_menu = None;
         ChatWindow {
             app_context,
             name,
             action_tx,
             message_list,
+            album_groups,
+            album_item,
+            divider_chat_id,
+            divider_last_read,
+            newest_seen,
+            new_while_scrolled_up,
+            visible_range,
             message_list_state,
             focused,
+            split,
+            reaction_picker,
+            call_history,
+            chat_permissions,
+            location_panel,
+            action_audit,
+            formatting_preview,
+            quiz_explanation,
+            chat_activity,
+            edit_history,
+            search_results,
+            entity_index,
+            send_as_picker,
+            unread_dashboard,
+            chat_polls,
+            forum_topics,
+            active_sessions,
+            scheduled_messages,
+            bot_menu,
         }
     }
     /// Set the name of the `ChatWindow`.
@@ -69,12 +214,21 @@ impl ChatWindow {
         self.name = name.as_ref().to_string();
         self
     }
+    /// Make the `ChatWindow` show the chat parked on the unfocused side of
+    /// the split view.
+    ///
+    /// # Returns
+    /// * `Self` - The modified instance of the `ChatWindow`.
+    pub fn with_split(mut self) -> Self {
+        self.split = true;
+        self
+    }
 
     /// Select the next message item in the list.
     fn next(&mut self) {
         let i = match self.message_list_state.selected() {
             Some(i) => {
-                if i == self.message_list.len() / 2 {
+                if i == self.album_groups.len() / 2 {
                     if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
                         event_tx.send(Event::GetChatHistory).unwrap();
                     }
@@ -88,20 +242,23 @@ impl ChatWindow {
             }
             None => 0,
         };
+        self.album_item = 0;
+        self.entity_index = None;
         self.message_list_state.select(Some(i));
+        self.view_selected_message();
     }
 
     /// Select the previous message item in the list.
     fn previous(&mut self) {
         let i = match self.message_list_state.selected() {
             Some(i) => {
-                if i == self.message_list.len() / 2 {
+                if i == self.album_groups.len() / 2 {
                     if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
                         event_tx.send(Event::GetChatHistory).unwrap();
                     }
                 }
 
-                if i >= self.message_list.len() - 1 {
+                if i >= self.album_groups.len() - 1 {
                     i
                 } else {
                     i + 1
@@ -109,53 +266,449 @@ impl ChatWindow {
             }
             None => 0,
         };
+        self.album_item = 0;
+        self.entity_index = None;
         self.message_list_state.select(Some(i));
+        self.view_selected_message();
+    }
+
+    /// Mark the selected message as read when `read_on_scroll_to` is enabled,
+    /// otherwise the messages are already marked as read when the chat is
+    /// opened.
+    fn view_selected_message(&self) {
+        if !self.app_context.app_config().read_on_scroll_to {
+            return;
+        }
+        let Some(message) = self.selected_message() else {
+            return;
+        };
+        let tg_context = self.app_context.tg_context();
+        let chat_id = if self.split {
+            tg_context.split_chat_id()
+        } else {
+            tg_context.open_chat_id()
+        };
+        let unread = tg_context
+            .chats()
+            .get(&chat_id)
+            .is_some_and(|chat| message.id() > chat.last_read_inbox_message_id);
+        if unread {
+            if let Some(event_tx) = tg_context.event_tx().as_ref() {
+                event_tx
+                    .send(Event::ViewMessage(chat_id, message.id()))
+                    .unwrap();
+            }
+        }
     }
 
     /// Unselect the message item in the list.
     fn unselect(&mut self) {
+        self.entity_index = None;
         self.message_list_state.select(None);
     }
 
+    /// Scroll back to the most recent message, the messages received while
+    /// the history was scrolled up are no longer counted.
+    fn jump_to_newest(&mut self) {
+        self.unselect();
+        *self.message_list_state.offset_mut() = 0;
+        self.new_while_scrolled_up = 0;
+    }
+
+    /// Select the message item at the given index of the list, the older
+    /// messages are loaded when it is in the older half of the list.
+    ///
+    /// # Arguments
+    /// * `i` - The index of the message item, clamped to the list.
+    fn select_message(&mut self, i: usize) {
+        if self.album_groups.is_empty() {
+            return;
+        }
+        let i = i.min(self.album_groups.len() - 1);
+        if i >= self.album_groups.len() / 2 {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx.send(Event::GetChatHistory).unwrap();
+            }
+        }
+        self.album_item = 0;
+        self.entity_index = None;
+        self.message_list_state.select(Some(i));
+        self.view_selected_message();
+    }
+
+    /// Get the number of message items scrolled by a page, the ones that
+    /// were visible the last time the `ChatWindow` was drawn.
+    fn page_size(&self) -> usize {
+        self.visible_range.len().max(1)
+    }
+
+    /// Keep the view on the messages being read when messages are received
+    /// while the history is scrolled up, and tell the reader about them
+    /// according to the `scrolled_up_new_message_behavior`.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the drawn chat.
+    fn follow_new_messages(&mut self, chat_id: i64) {
+        let received = if self.newest_seen.0 == chat_id {
+            count_new_messages(&self.message_list, self.newest_seen.1)
+        } else {
+            self.new_while_scrolled_up = 0;
+            0
+        };
+        self.newest_seen = (chat_id, self.message_list.first().map_or(0, |m| m.id()));
+
+        let scrolled_up = self.message_list_state.offset() > 0
+            || self.message_list_state.selected().is_some_and(|i| i > 0);
+        if !scrolled_up {
+            self.new_while_scrolled_up = 0;
+            return;
+        }
+        if received == 0 {
+            return;
+        }
+        // The new messages are at the top of the list, the view is moved by
+        // as many groups so that it does not follow them.
+        let new_groups = self
+            .album_groups
+            .iter()
+            .take_while(|group| group.indices.iter().all(|index| *index < received))
+            .count();
+        *self.message_list_state.offset_mut() += new_groups;
+        if let Some(selected) = self.message_list_state.selected() {
+            self.message_list_state.select(Some(selected + new_groups));
+        }
+
+        let behavior = self
+            .app_context
+            .app_config()
+            .scrolled_up_new_message_behavior;
+        let (pending, status) = scrolled_up_notice(behavior, self.new_while_scrolled_up, received);
+        self.new_while_scrolled_up = pending;
+        if let Some(status) = status {
+            self.app_context
+                .action_tx()
+                .send(Action::StatusMessage(status))
+                .unwrap();
+        }
+    }
+
+    /// Select the next `@username` or `#hashtag` entity of the selected
+    /// message, the selected entity is shown in the status bar.
+    fn next_entity(&mut self) {
+        let Some(entities) = self.selected_message().map(|m| m.entities().to_vec()) else {
+            return;
+        };
+        if entities.is_empty() {
+            return;
+        }
+        let i = self.entity_index.map_or(0, |i| (i + 1) % entities.len());
+        self.entity_index = Some(i);
+        self.app_context
+            .action_tx()
+            .send(Action::StatusMessage(format!("Selected {}", entities[i])))
+            .unwrap();
+    }
+
+    /// Follow the selected entity of the selected message.
+    fn follow_entity(&self) {
+        if let Some(selected) = self.selected_message() {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx
+                    .send(Event::FollowEntity(
+                        selected.id(),
+                        self.entity_index.unwrap_or(0),
+                    ))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Select the newer or the older message sent by the current user.
+    ///
+    /// # Arguments
+    /// * `newer` - Whether the newer message is selected, or the older one.
+    fn select_own_message(&mut self, newer: bool) {
+        let me = self.app_context.tg_context().me();
+        match own_message_group(
+            &self.message_list,
+            &self.album_groups,
+            me,
+            self.message_list_state.selected(),
+            newer,
+        ) {
+            Some(i) => {
+                self.album_item = 0;
+                self.entity_index = None;
+                self.message_list_state.select(Some(i));
+                self.view_selected_message();
+            }
+            None => self
+                .app_context
+                .action_tx()
+                .send(Action::StatusMessage(
+                    "You have not sent any of the loaded messages".to_string(),
+                ))
+                .unwrap(),
+        }
+    }
+
+    /// Select the next item of the selected album.
+    fn next_album_item(&mut self) {
+        if let Some(group) = self.selected_group() {
+            if self.album_item + 1 < group.indices.len() {
+                self.album_item += 1;
+            }
+        }
+    }
+
+    /// Select the previous item of the selected album.
+    fn previous_album_item(&mut self) {
+        self.album_item = self.album_item.saturating_sub(1);
+    }
+
+    /// Get the selected group of the list.
+    fn selected_group(&self) -> Option<&AlbumGroup> {
+        self.album_groups.get(self.message_list_state.selected()?)
+    }
+
+    /// Get the selected message of the list.
+    /// When the selected item is an album, it is the selected item of the
+    /// album.
+    fn selected_message(&self) -> Option<&MessageEntry> {
+        let group = self.selected_group()?;
+        // The messages of the group are stored from the newest to the oldest.
+        let index = group
+            .indices
+            .len()
+            .checked_sub(self.album_item + 1)
+            .and_then(|i| group.indices.get(i))?;
+        self.message_list.get(*index)
+    }
+
+    /// Restore the scroll of the list after a message is sent.
+    /// Depending on the `jump_to_bottom_on_send` flag of the application
+    /// configuration, the view snaps back to the most recent message or it is
+    /// left where the reader was.
+    /// The Saved Messages used as a notepad always stay at the most recent
+    /// message, with the focus on the prompt.
+    fn scroll_after_send(&mut self) {
+        let (jump_to_bottom_on_send, notepad_mode) = {
+            let app_config = self.app_context.app_config();
+            (
+                app_config.jump_to_bottom_on_send,
+                app_config.saved_messages_notepad_mode,
+            )
+        };
+        let notepad = {
+            let tg_context = self.app_context.tg_context();
+            notepad_mode && tg_context.open_chat_id() == tg_context.me()
+        };
+        let (offset, focus_prompt) = after_send(
+            self.message_list_state.offset(),
+            jump_to_bottom_on_send,
+            notepad,
+        );
+        *self.message_list_state.offset_mut() = offset;
+        if jump_to_bottom_on_send || notepad {
+            self.message_list_state.select(None);
+        }
+        if focus_prompt {
+            self.app_context
+                .action_tx()
+                .send(Action::FocusComponent(ComponentName::Prompt))
+                .unwrap();
+        }
+    }
+
+    /// Copy the messages visible on the screen as plain text.
+    fn copy_visible_transcript(&self) {
+        let tg_context = self.app_context.tg_context();
+        // The groups are stored from the newest to the oldest, while the
+        // transcript is read from the oldest to the newest.
+        let messages = self.album_groups[self.visible_range.clone()]
+            .iter()
+            .rev()
+            .flat_map(|group| group.indices.iter().rev())
+            .map(|index| {
+                let message_entry = &self.message_list[*index];
+                (
+                    tg_context
+                        .try_name_from_chats_or_users(message_entry.sender_id())
+                        .unwrap_or_default(),
+                    message_entry.message_content_to_string(),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+        if let Ok(mut clipboard) = Clipboard::new() {
+            clipboard.set_text(transcript(&messages)).unwrap();
+        }
+    }
+
     /// Delete the selected message item in the list.
     ///
     /// # Arguments
     /// * `revoke` - A boolean flag indicating whether the message should be revoked or not.
     fn delete_selected(&mut self, revoke: bool) {
-        if let Some(selected) = self.message_list_state.selected() {
+        if let Some(selected) = self.selected_message() {
             if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
-                let sender_id = self.message_list[selected].sender_id();
+                let sender_id = selected.sender_id();
                 if sender_id != self.app_context.tg_context().me() {
                     return;
                 }
-                let message_id = self.message_list[selected].id();
+                // The message is removed once the deletion is confirmed.
                 event_tx
-                    .send(Event::DeleteMessages(vec![message_id], revoke))
+                    .send(Event::DeleteMessages(vec![selected.id()], revoke))
                     .unwrap();
-                self.app_context.tg_context().delete_message(message_id);
             }
         }
     }
 
     /// Copy the selected message item in the list.
     fn copy_selected(&self) {
-        if let Some(selected) = self.message_list_state.selected() {
-            let message = self.message_list[selected].message_content_to_string();
+        if let Some(selected) = self.selected_message() {
+            let message = selected.message_content_to_string();
             if let Ok(mut clipboard) = Clipboard::new() {
                 clipboard.set_text(message).unwrap();
             }
         }
     }
 
+    /// Translate the selected message to the language of the system, the
+    /// translation of a message already translated is hidden or shown again.
+    /// Only the messages of the open chat are translated, not the ones of the
+    /// split chat.
+    fn translate_selected(&self) {
+        if self.split {
+            return;
+        }
+        let Some(selected) = self.selected_message() else {
+            return;
+        };
+        let event = if selected.translation().is_some() {
+            Event::ToggleTranslation(selected.id())
+        } else {
+            let to_language_code = self
+                .app_context
+                .telegram_config()
+                .system_language_code
+                .clone();
+            Event::TranslateMessage(selected.id(), to_language_code)
+        };
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(event).unwrap();
+        }
+    }
+
+    /// Copy the first code block of the selected message item in the list.
+    fn copy_selected_code_block(&self) {
+        if let Some(selected) = self.selected_message() {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx
+                    .send(Event::CopyCodeBlock(selected.id(), 0))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Copy the content of a code block of a message of the open chat.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the message.
+    /// * `block_index` - The index of the code block of the message.
+    fn copy_code_block(&self, message_id: i64, block_index: usize) {
+        let code = self
+            .message_list
+            .iter()
+            .find(|message| message.id() == message_id)
+            .and_then(|message| message.code_block_content(block_index))
+            .map(str::to_string);
+        if let Some(code) = code {
+            if let Ok(mut clipboard) = Clipboard::new() {
+                clipboard.set_text(code).unwrap();
+            }
+        }
+    }
+
+    /// Copy the links of the selected message item in the list.
+    fn copy_selected_links(&self) {
+        if let Some(selected) = self.selected_message() {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx
+                    .send(Event::CopyMessageLinks(selected.id()))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Copy the links of a message of the open chat, one per line.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the message.
+    fn copy_message_links(&self, message_id: i64) {
+        let links = self
+            .message_list
+            .iter()
+            .find(|message| message.id() == message_id)
+            .map(|message| message.links().join("\n"))
+            .unwrap_or_default();
+        if links.is_empty() {
+            self.app_context
+                .action_tx()
+                .send(Action::StatusMessage(
+                    "The message has no links".to_string(),
+                ))
+                .unwrap();
+            return;
+        }
+        if let Ok(mut clipboard) = Clipboard::new() {
+            clipboard.set_text(links).unwrap();
+        }
+    }
+
+    /// Show the full error of the selected message item in the list.
+    fn show_selected_send_error(&self) {
+        if let Some(selected) = self.selected_message() {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx.send(Event::ShowSendError(selected.id())).unwrap();
+            }
+        }
+    }
+
+    /// Show the full error of a message of the open chat that failed to send.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the message.
+    fn show_send_error(&self, message_id: i64) {
+        let status = match self
+            .message_list
+            .iter()
+            .find(|message| message.id() == message_id)
+            .and_then(|message| message.send_error())
+        {
+            Some(error) => {
+                let kind = match error.kind() {
+                    SendErrorKind::Transient => "Transient",
+                    SendErrorKind::Permanent => "Permanent",
+                };
+                format!("{} error {}: {}", kind, error.code, error.message)
+            }
+            None => "The message did not fail to send".to_string(),
+        };
+        self.app_context
+            .action_tx()
+            .send(Action::StatusMessage(status))
+            .unwrap();
+    }
+
     /// Edit the selected message item in the list.
     fn edit_selected(&self) {
-        if let Some(selected) = self.message_list_state.selected() {
-            let sender_id = self.message_list[selected].sender_id();
+        if let Some(selected) = self.selected_message() {
+            let sender_id = selected.sender_id();
             if sender_id != self.app_context.tg_context().me() {
                 return;
             }
-            let message = self.message_list[selected].message_content_to_string();
-            let message_id = self.message_list[selected].id();
+            let message = selected.message_content_to_string();
+            let message_id = selected.id();
             if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
                 event_tx
                     .send(Event::EditMessage(message_id, message))
@@ -164,11 +717,329 @@ impl ChatWindow {
         }
     }
 
+    /// Ask for the reactions available for the selected message item in the
+    /// list, the reaction picker is opened when they are received.
+    fn react_selected(&self) {
+        if let Some(selected) = self.selected_message() {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx
+                    .send(Event::OpenReactionPicker(selected.id()))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Open the reaction picker for the given message.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the message.
+    /// * `available` - The emoji of the reactions available for the message.
+    fn open_reaction_picker(&mut self, message_id: i64, available: Vec<String>) {
+        if let Some(message) = self.message_list.iter().find(|m| m.id() == message_id) {
+            self.reaction_picker = Some(ReactionPicker::new(
+                message_id,
+                available,
+                message.reactions(),
+            ));
+        }
+    }
+
+    /// Add the reaction highlighted in the reaction picker and close it.
+    fn choose_reaction(&mut self) {
+        if let Some(picker) = self.reaction_picker.take() {
+            if let Some(entry) = picker.selected() {
+                if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                    event_tx
+                        .send(Event::AddReaction(picker.message_id(), entry.emoji.clone()))
+                        .unwrap();
+                }
+            }
+        }
+    }
+
+    /// Ask for the explanation of the selected quiz, the panel is opened when
+    /// it is received.
+    fn show_quiz_explanation(&self) {
+        if let Some(selected) = self.selected_message() {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx
+                    .send(Event::ShowQuizExplanation(selected.id()))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Download and open the file of the selected document message.
+    fn open_selected_document(&self) {
+        if let Some(selected) = self.selected_message() {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx.send(Event::OpenDocument(selected.id())).unwrap();
+            }
+        }
+    }
+
+    /// Ask for the map links of the selected location message, the panel is
+    /// opened when they are received.
+    fn show_location_map(&self) {
+        if let Some(selected) = self.selected_message() {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx
+                    .send(Event::ShowLiveLocationMap(selected.id()))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Ask for the permissions of the current user in the chat, the panel is
+    /// opened when they are received.
+    fn show_chat_permissions(&self) {
+        let tg_context = self.app_context.tg_context();
+        let chat_id = if self.split {
+            tg_context.split_chat_id()
+        } else {
+            tg_context.open_chat_id()
+        };
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx.send(Event::ShowChatPermissions(chat_id)).unwrap();
+        }
+    }
+
+    /// Ask for the edits of the selected message, the panel is opened when
+    /// they are received.
+    fn show_edit_history(&self) {
+        if let Some(selected) = self.selected_message() {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx
+                    .send(Event::ShowEditHistory(selected.id()))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Open the discussion thread of the selected channel post, the posts
+    /// without a discussion group are ignored.
+    fn open_comments(&self) {
+        if self.split {
+            return;
+        }
+        if let Some(selected) = self.selected_message() {
+            if selected.comment_count().is_none() {
+                return;
+            }
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx.send(Event::OpenComments(selected.id())).unwrap();
+            }
+        }
+    }
+
+    /// Ask for the activity of the chat, the panel is opened when it is
+    /// received.
+    fn show_chat_activity(&self) {
+        let tg_context = self.app_context.tg_context();
+        let chat_id = if self.split {
+            tg_context.split_chat_id()
+        } else {
+            tg_context.open_chat_id()
+        };
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx.send(Event::ShowChatActivity(chat_id)).unwrap();
+        }
+    }
+
+    /// Ask for the recent calls of the chat, the call history is opened when
+    /// they are received.
+    fn show_call_history(&self) {
+        let tg_context = self.app_context.tg_context();
+        let chat_id = if self.split {
+            tg_context.split_chat_id()
+        } else {
+            tg_context.open_chat_id()
+        };
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx.send(Event::ShowCallHistory(chat_id)).unwrap();
+        }
+    }
+
+    /// Ask for the polls of the chat, the panel is opened when they are
+    /// received.
+    fn show_chat_polls(&self) {
+        let tg_context = self.app_context.tg_context();
+        let chat_id = if self.split {
+            tg_context.split_chat_id()
+        } else {
+            tg_context.open_chat_id()
+        };
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx.send(Event::ShowChatPolls(chat_id)).unwrap();
+        }
+    }
+
+    /// Ask for the topics of the open forum, the panel is opened when they
+    /// are received.
+    /// The topics filter the open chat only, not the split chat.
+    fn show_forum_topics(&self) {
+        if self.split {
+            return;
+        }
+        let tg_context = self.app_context.tg_context();
+        let chat_id = tg_context.open_chat_id();
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx.send(Event::ShowForumTopics(chat_id)).unwrap();
+        }
+    }
+
+    /// Ask for the scheduled messages of the open chat, the panel is opened
+    /// when they are received.
+    /// They are sent now in the open chat only, not in the split chat.
+    fn show_scheduled_messages(&self) {
+        if self.split {
+            return;
+        }
+        let tg_context = self.app_context.tg_context();
+        let chat_id = tg_context.open_chat_id();
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx
+                .send(Event::ShowScheduledMessages(chat_id))
+                .unwrap();
+        }
+    }
+
+    /// Ask for the menu of the bot of the open chat, the panel is opened when
+    /// it is received.
+    fn show_bot_menu(&self) {
+        if self.split {
+            return;
+        }
+        let tg_context = self.app_context.tg_context();
+        let chat_id = tg_context.open_chat_id();
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx.send(Event::OpenBotMenu(chat_id)).unwrap();
+        }
+    }
+
+    /// Send the command highlighted in the bot menu to the bot and close the
+    /// menu, a Web App is only shown.
+    fn send_selected_bot_command(&mut self) {
+        let Some(command) = self.bot_menu.as_ref().and_then(|m| m.selected_command()) else {
+            return;
+        };
+        self.bot_menu = None;
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::SendMessage(command, None)).unwrap();
+        }
+    }
+
+    /// Send the message highlighted in the scheduled messages panel now, it
+    /// is removed from the panel, that stays open, once it has been sent.
+    fn send_selected_scheduled_now(&mut self) {
+        let Some(message_id) = self
+            .scheduled_messages
+            .as_ref()
+            .and_then(|s| s.selected())
+            .map(|m| m.message_id)
+        else {
+            return;
+        };
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::SendScheduledNow(message_id)).unwrap();
+        }
+    }
+
+    /// Filter the messages by the topic highlighted in the topics panel and
+    /// close it.
+    fn open_forum_topic(&mut self) {
+        let Some(message_thread_id) = self.forum_topics.take().and_then(|t| t.selected()) else {
+            return;
+        };
+        self.message_list_state.select(None);
+        self.app_context
+            .action_tx()
+            .send(Action::OpenForumTopic(message_thread_id))
+            .unwrap();
+    }
+
+    /// Ask to terminate the session highlighted in the sessions panel, the
+    /// panel is closed and the termination is confirmed first.
+    /// The current session is kept open.
+    fn terminate_selected_session(&mut self) {
+        let Some(sessions) = self
+            .active_sessions
+            .as_ref()
+            .filter(|s| s.selected().is_some())
+        else {
+            return;
+        };
+        let Some(session_id) = sessions.selected_terminable() else {
+            self.app_context
+                .action_tx()
+                .send(Action::StatusMessage(
+                    "The current session can not be terminated".to_string(),
+                ))
+                .unwrap();
+            return;
+        };
+        self.active_sessions = None;
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx.send(Event::TerminateSession(session_id)).unwrap();
+        }
+    }
+
+    /// Select the poll highlighted in the polls panel and close it.
+    /// When the poll is not loaded yet, the history is loaded up to it.
+    fn jump_to_poll(&mut self) {
+        let Some(message_id) = self.chat_polls.take().and_then(|p| p.selected()) else {
+            return;
+        };
+        let position = self.album_groups.iter().position(|group| {
+            group
+                .indices
+                .iter()
+                .any(|index| self.message_list[*index].id() == message_id)
+        });
+        match position {
+            Some(i) => {
+                self.album_item = 0;
+                self.entity_index = None;
+                self.message_list_state.select(Some(i));
+                self.view_selected_message();
+            }
+            None => {
+                let chat_id = self.app_context.tg_context().open_chat_id();
+                self.app_context
+                    .action_tx()
+                    .send(Action::OpenChatAtMessage(chat_id, message_id))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Send messages to the open chat as the identity highlighted in the send
+    /// as picker and close it.
+    fn choose_send_as(&mut self) {
+        if let Some(sender) = self.send_as_picker.take().and_then(|p| p.selected()) {
+            let tg_context = self.app_context.tg_context();
+            if let Some(event_tx) = tg_context.event_tx().as_ref() {
+                event_tx
+                    .send(Event::SetSendAs(tg_context.open_chat_id(), sender.id()))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// Open the chat highlighted in the unread dashboard and close it.
+    fn open_unread_chat(&mut self) {
+        if let Some(chat_id) = self.unread_dashboard.take().and_then(|d| d.selected()) {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx.send(Event::OpenChat(chat_id)).unwrap();
+            }
+        }
+    }
+
     /// Reply to the selected message item in the list.
     fn reply_selected(&self) {
-        if let Some(selected) = self.message_list_state.selected() {
-            let message_id = self.message_list[selected].id();
-            let text = self.message_list[selected].message_content_to_string();
+        if let Some(selected) = self.selected_message() {
+            let message_id = selected.id();
+            let text = selected.message_content_to_string();
             if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
                 event_tx
                     .send(Event::ReplyMessage(message_id, text))
@@ -178,6 +1049,305 @@ impl ChatWindow {
     }
 }
 
+/// Compute the actions to perform after a message of the open chat is
+/// deleted. When the chat becomes empty, the next chat of the chat list is
+/// opened if the `delete_last_behavior` asks so.
+///
+/// # Arguments
+/// * `remaining` - The number of messages left in the open chat, `0` only
+///   when its history is empty on the TDLib side and not just the loaded
+///   messages.
+/// * `delete_last_behavior` - What happens when the last message is deleted.
+///
+/// # Returns
+/// * `Vec<Action>` - The actions to perform, in order.
+pub fn actions_after_delete(
+    remaining: usize,
+    delete_last_behavior: DeleteLastBehavior,
+) -> Vec<Action> {
+    match delete_last_behavior {
+        DeleteLastBehavior::NextChat if remaining == 0 => vec![
+            Action::FocusComponent(ComponentName::ChatList),
+            Action::ChatListNext,
+            Action::ChatListOpen,
+        ],
+        _ => vec![],
+    }
+}
+
+/// Compute the offset of the message list after a message is sent.
+/// The list is rendered from the bottom to the top, so an offset of `0` means
+/// that the most recent message is visible.
+///
+/// # Arguments
+/// * `offset` - The offset of the message list before sending the message.
+/// * `jump_to_bottom_on_send` - A boolean flag indicating whether the view
+///   should snap back to the most recent message.
+///
+/// # Returns
+/// * `usize` - The offset of the message list after sending the message.
+fn offset_after_send(offset: usize, jump_to_bottom_on_send: bool) -> usize {
+    if jump_to_bottom_on_send {
+        0
+    } else {
+        offset
+    }
+}
+
+/// Compute how the view is restored after a message is sent.
+/// When the chat is used as a notepad, the view stays at the most recent
+/// message and the prompt keeps the focus, skipping any other navigation.
+///
+/// # Arguments
+/// * `offset` - The offset of the message list before sending the message.
+/// * `jump_to_bottom_on_send` - A boolean flag indicating whether the view
+///   should snap back to the most recent message.
+/// * `notepad` - A boolean flag indicating whether the chat is used as a
+///   notepad.
+///
+/// # Returns
+/// * `(usize, bool)` - The offset of the message list after sending the
+///   message and whether the prompt takes the focus.
+fn after_send(offset: usize, jump_to_bottom_on_send: bool, notepad: bool) -> (usize, bool) {
+    if notepad {
+        (0, true)
+    } else {
+        (offset_after_send(offset, jump_to_bottom_on_send), false)
+    }
+}
+
+/// Compute the width the text of the messages is wrapped to.
+///
+/// # Arguments
+/// * `pane` - The width available to the text of the messages.
+/// * `cap` - The maximum width of the text, `0` for no maximum.
+///
+/// # Returns
+/// * `u16` - The width of the text, it never exceeds the available width.
+fn effective_content_width(pane: u16, cap: u16) -> u16 {
+    if cap == 0 {
+        pane
+    } else {
+        cap.min(pane)
+    }
+}
+
+/// Compute the offset of the message list that keeps a margin of lines
+/// between the selected message and the edges of the list.
+/// The list is rendered from the bottom to the top, so the lines below the
+/// selected message are the ones of the messages from the offset to it.
+///
+/// # Arguments
+/// * `offset` - The offset of the message list.
+/// * `selected` - The index of the selected message.
+/// * `item_heights` - The height of each item of the list.
+/// * `height` - The height of the list.
+/// * `margin` - The number of lines to keep above and below the selected
+///   message, it is clamped to less than half of the height.
+///
+/// # Returns
+/// * `usize` - The offset of the message list.
+fn scroll_offset(
+    offset: usize,
+    selected: usize,
+    item_heights: &[usize],
+    height: usize,
+    margin: usize,
+) -> usize {
+    if selected >= item_heights.len() {
+        return offset;
+    }
+    let margin = margin.min(height.saturating_sub(1) / 2);
+    let mut offset = offset.min(selected);
+    // Keep the margin below the selected message.
+    while offset > 0 && item_heights[offset..selected].iter().sum::<usize>() < margin {
+        offset -= 1;
+    }
+    // Keep the margin above the selected message.
+    while offset < selected
+        && item_heights[offset..=selected].iter().sum::<usize>() + margin > height
+    {
+        offset += 1;
+    }
+    offset
+}
+
+/// Compute the range of the items that are visible in the list.
+/// The items are rendered from the offset until the height of the list is
+/// filled, the last one can be visible only in part.
+///
+/// # Arguments
+/// * `offset` - The offset of the list.
+/// * `item_heights` - The height of each item of the list.
+/// * `height` - The height of the list.
+///
+/// # Returns
+/// * `Range<usize>` - The range of the visible items.
+fn visible_range(offset: usize, item_heights: &[usize], height: usize) -> Range<usize> {
+    let start = offset.min(item_heights.len());
+    let mut end = start;
+    let mut filled = 0;
+    while end < item_heights.len() && filled < height {
+        filled += item_heights[end];
+        end += 1;
+    }
+    start..end
+}
+
+/// Assemble the transcript of a list of messages.
+/// Every message is on its own line, prefixed by the name of its sender.
+///
+/// # Arguments
+/// * `messages` - The sender name and the text of each message, from the
+///   oldest to the newest.
+///
+/// # Returns
+/// * `String` - The transcript.
+fn transcript(messages: &[(String, String)]) -> String {
+    messages
+        .iter()
+        .map(|(sender, text)| format!("{}: {}", sender, text))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Get the index of the oldest unread message, the "New messages" divider is
+/// rendered right above it.
+/// The messages are stored from the newest to the oldest. There is no divider
+/// when all the messages are read or when the last read message is not loaded.
+///
+/// # Arguments
+/// * `msgs` - The messages of the chat, from the newest to the oldest.
+/// * `last_read` - The identifier of the last read incoming message.
+///
+/// # Returns
+/// * `Option<usize>` - The index of the oldest unread message, if any.
+fn new_message_divider_index(msgs: &[MessageEntry], last_read: i64) -> Option<usize> {
+    let first_read = msgs.iter().position(|m| m.id() <= last_read)?;
+    first_read.checked_sub(1)
+}
+
+/// Count the messages received after the most recent one seen.
+///
+/// # Arguments
+/// * `msgs` - The messages of the chat, from the newest to the oldest.
+/// * `newest_seen` - The identifier of the most recent message seen, `0`
+///   when no message was seen yet.
+///
+/// # Returns
+/// * `usize` - The number of messages newer than the one seen.
+fn count_new_messages(msgs: &[MessageEntry], newest_seen: i64) -> usize {
+    if newest_seen == 0 {
+        return 0;
+    }
+    msgs.iter().take_while(|m| m.id() > newest_seen).count()
+}
+
+/// Find the group with a message sent by the current user that follows the
+/// selected one, wrapping at the ends of the history.
+///
+/// # Arguments
+/// * `msgs` - The messages of the chat, from the newest to the oldest.
+/// * `groups` - The groups of the messages, from the newest to the oldest.
+/// * `me` - The identifier of the current user.
+/// * `selected` - The selected group, `None` when the view is at the bottom.
+/// * `newer` - Whether the newer message is found, or the older one.
+///
+/// # Returns
+/// * `Option<usize>` - The group to select, `None` when the current user has
+///   not sent any of the messages.
+fn own_message_group(
+    msgs: &[MessageEntry],
+    groups: &[AlbumGroup],
+    me: i64,
+    selected: Option<usize>,
+    newer: bool,
+) -> Option<usize> {
+    let len = groups.len();
+    // Nothing selected is just below the newest message.
+    let start = selected.unwrap_or(if newer { 0 } else { len.saturating_sub(1) });
+    (1..=len)
+        .map(|step| {
+            if newer {
+                (start + len - step) % len
+            } else {
+                (start + step) % len
+            }
+        })
+        .find(|i| {
+            groups[*i]
+                .indices
+                .iter()
+                .any(|index| msgs[*index].sender_id() == me)
+        })
+}
+
+/// Tell the reader about the messages received while the history is
+/// scrolled up.
+///
+/// # Arguments
+/// * `behavior` - The configured behavior.
+/// * `pending` - The number of messages already counted on the jump button.
+/// * `received` - The number of messages just received.
+///
+/// # Returns
+/// * `(usize, Option<String>)` - The number of messages counted on the jump
+///   button and the status message to flash, if any.
+fn scrolled_up_notice(
+    behavior: ScrolledUpNewMessageBehavior,
+    pending: usize,
+    received: usize,
+) -> (usize, Option<String>) {
+    match behavior {
+        ScrolledUpNewMessageBehavior::JumpButton => (pending + received, None),
+        ScrolledUpNewMessageBehavior::FlashStatusBar => (
+            0,
+            Some(match received {
+                1 => "1 new message ↓".to_string(),
+                n => format!("{} new messages ↓", n),
+            }),
+        ),
+        ScrolledUpNewMessageBehavior::Nothing => (0, None),
+    }
+}
+
+/// Get the body of an album: its items one after the other, with the selected
+/// one marked, followed by the captions of the album.
+///
+/// # Arguments
+/// * `album` - The messages of the album, from the first sent one.
+/// * `selected_item` - The position of the selected item, if any.
+/// * `content_style` - The style of the content.
+/// * `wrap_width` - The width at which the content is wrapped.
+/// * `syntax_highlight` - The application context, used for the styles of the
+///   highlighted code blocks, `None` to not highlight them.
+///
+/// # Returns
+/// * `Vec<Line>` - The lines of the body.
+fn album_body(
+    album: &[&MessageEntry],
+    selected_item: Option<usize>,
+    content_style: Style,
+    wrap_width: i32,
+    syntax_highlight: Option<&AppContext>,
+) -> Vec<Line<'static>> {
+    let mut body = Vec::new();
+    for (i, message_entry) in album.iter().enumerate() {
+        let mut lines =
+            message_entry.get_lines_styled_with_style(content_style, wrap_width, syntax_highlight);
+        if selected_item == Some(i) {
+            if let Some(line) = lines.first_mut() {
+                line.spans.insert(0, Span::styled("▶ ", content_style));
+            }
+        }
+        body.extend(lines);
+    }
+    for message_entry in album {
+        body.extend(message_entry.get_caption_lines_styled_with_style(content_style, wrap_width));
+    }
+    body
+}
+
 /// Implement the `HandleFocus` trait for the `ChatWindow` struct.
 /// This trait allows the `ChatListWindow` to be focused or unfocused.
 impl HandleFocus for ChatWindow {
@@ -188,6 +1358,23 @@ impl HandleFocus for ChatWindow {
     /// Set the `focused` flag for the `ChatWindow`.
     fn unfocus(&mut self) {
         self.focused = false;
+        self.reaction_picker = None;
+        self.call_history = None;
+        self.chat_permissions = None;
+        self.location_panel = None;
+        self.action_audit = None;
+        self.formatting_preview = None;
+        self.quiz_explanation = None;
+        self.chat_activity = None;
+        self.edit_history = None;
+        self.search_results = None;
+        self.send_as_picker = None;
+        self.unread_dashboard = None;
+        self.chat_polls = None;
+        self.forum_topics = None;
+        self.active_sessions = None;
+        self.scheduled_messages = None;
+        self.bot_menu = None;
     }
 }
 
@@ -199,15 +1386,234 @@ impl Component for ChatWindow {
     }
 
     fn update(&mut self, action: Action) {
+        if let Action::HideFormattingPreview = action {
+            self.formatting_preview = None;
+            return;
+        }
+        if let Action::ScheduledMessageSent(message_id) = action {
+            if let Some(scheduled) = self.scheduled_messages.as_mut() {
+                scheduled.remove(message_id);
+            }
+            return;
+        }
+        if self.call_history.is_some()
+            || self.chat_permissions.is_some()
+            || self.location_panel.is_some()
+            || self.action_audit.is_some()
+            || self.formatting_preview.is_some()
+            || self.quiz_explanation.is_some()
+            || self.chat_activity.is_some()
+            || self.edit_history.is_some()
+            || self.search_results.is_some()
+        {
+            if let Action::Key(KeyCode::Esc, _) | Action::ChatWindowUnselect = action {
+                self.call_history = None;
+                self.chat_permissions = None;
+                self.location_panel = None;
+                self.action_audit = None;
+                self.formatting_preview = None;
+                self.quiz_explanation = None;
+                self.chat_activity = None;
+                self.edit_history = None;
+                self.search_results = None;
+            }
+            return;
+        }
+        if let Some(dashboard) = self.unread_dashboard.as_mut() {
+            match action {
+                Action::ChatWindowNext => dashboard.next(),
+                Action::ChatWindowPrevious => dashboard.previous(),
+                Action::Key(KeyCode::Enter, _) => self.open_unread_chat(),
+                Action::Key(KeyCode::Esc, _) | Action::ChatWindowUnselect => {
+                    self.unread_dashboard = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+        if let Some(polls) = self.chat_polls.as_mut() {
+            match action {
+                Action::ChatWindowNext => polls.next(),
+                Action::ChatWindowPrevious => polls.previous(),
+                Action::Key(KeyCode::Enter, _) => self.jump_to_poll(),
+                Action::Key(KeyCode::Esc, _) | Action::ChatWindowUnselect => {
+                    self.chat_polls = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+        if let Some(sessions) = self.active_sessions.as_mut() {
+            match action {
+                Action::ChatWindowNext => sessions.next(),
+                Action::ChatWindowPrevious => sessions.previous(),
+                Action::Key(KeyCode::Enter, _) => self.terminate_selected_session(),
+                Action::Key(KeyCode::Esc, _) | Action::ChatWindowUnselect => {
+                    self.active_sessions = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+        if let Some(scheduled) = self.scheduled_messages.as_mut() {
+            match action {
+                Action::ChatWindowNext => scheduled.next(),
+                Action::ChatWindowPrevious => scheduled.previous(),
+                Action::Key(KeyCode::Enter, _) => self.send_selected_scheduled_now(),
+                Action::Key(KeyCode::Esc, _) | Action::ChatWindowUnselect => {
+                    self.scheduled_messages = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+        if let Some(menu) = self.bot_menu.as_mut() {
+            match action {
+                Action::ChatWindowNext => menu.next(),
+                Action::ChatWindowPrevious => menu.previous(),
+                Action::Key(KeyCode::Enter, _) => self.send_selected_bot_command(),
+                Action::Key(KeyCode::Esc, _) | Action::ChatWindowUnselect => {
+                    self.bot_menu = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+        if let Some(topics) = self.forum_topics.as_mut() {
+            match action {
+                Action::ChatWindowNext => topics.next(),
+                Action::ChatWindowPrevious => topics.previous(),
+                Action::Key(KeyCode::Enter, _) => self.open_forum_topic(),
+                Action::Key(KeyCode::Esc, _) | Action::ChatWindowUnselect => {
+                    self.forum_topics = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+        if let Some(picker) = self.send_as_picker.as_mut() {
+            match action {
+                Action::ChatWindowNext => picker.next(),
+                Action::ChatWindowPrevious => picker.previous(),
+                Action::Key(KeyCode::Enter, _) => self.choose_send_as(),
+                Action::Key(KeyCode::Esc, _) | Action::ChatWindowUnselect => {
+                    self.send_as_picker = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+        if let Some(picker) = self.reaction_picker.as_mut() {
+            match action {
+                Action::ChatWindowNext => picker.next(),
+                Action::ChatWindowPrevious => picker.previous(),
+                Action::Key(KeyCode::Enter, _) => self.choose_reaction(),
+                Action::Key(KeyCode::Esc, _) | Action::ChatWindowUnselect => {
+                    self.reaction_picker = None;
+                }
+                _ => {}
+            }
+            return;
+        }
         match action {
             Action::ChatWindowNext => self.next(),
             Action::ChatWindowPrevious => self.previous(),
             Action::ChatWindowUnselect => self.unselect(),
+            Action::NextAlbumItem => self.next_album_item(),
+            Action::PreviousAlbumItem => self.previous_album_item(),
+            Action::NextOwnMessage => self.select_own_message(true),
+            Action::PreviousOwnMessage => self.select_own_message(false),
             Action::ChatWindowDeleteForEveryone => self.delete_selected(true),
             Action::ChatWindowDeleteForMe => self.delete_selected(false),
             Action::ChatWindowCopy => self.copy_selected(),
+            Action::CopyVisibleTranscript => self.copy_visible_transcript(),
+            Action::ChatWindowCopyCodeBlock => self.copy_selected_code_block(),
+            Action::CopyCodeBlock(message_id, block_index) => {
+                self.copy_code_block(message_id, block_index)
+            }
+            Action::ChatWindowCopyLinks => self.copy_selected_links(),
+            Action::CopyMessageLinks(message_id) => self.copy_message_links(message_id),
+            Action::ChatWindowSendError => self.show_selected_send_error(),
+            Action::ShowSendError(message_id) => self.show_send_error(message_id),
             Action::ChatWindowEdit => self.edit_selected(),
             Action::ShowChatWindowReply => self.reply_selected(),
+            Action::ChatWindowReact => self.react_selected(),
+            Action::ChatWindowCallHistory => self.show_call_history(),
+            Action::ChatWindowPermissions => self.show_chat_permissions(),
+            Action::ChatWindowLocationMap => self.show_location_map(),
+            Action::ChatWindowQuizExplanation => self.show_quiz_explanation(),
+            Action::ChatWindowActivity => self.show_chat_activity(),
+            Action::ChatWindowEditHistory => self.show_edit_history(),
+            Action::ChatWindowOpenComments => self.open_comments(),
+            Action::ChatWindowOpenDocument => self.open_selected_document(),
+            Action::ChatWindowPolls => self.show_chat_polls(),
+            Action::ChatWindowForumTopics => self.show_forum_topics(),
+            Action::ChatWindowBotMenu => self.show_bot_menu(),
+            Action::ChatWindowScheduledMessages => self.show_scheduled_messages(),
+            Action::ChatWindowJumpToNewest => self.jump_to_newest(),
+            Action::ChatWindowFirst => self.select_message(usize::MAX),
+            Action::ChatWindowLast => self.select_message(0),
+            Action::ChatWindowPageUp => {
+                let selected = self.message_list_state.selected().unwrap_or(0);
+                self.select_message(selected.saturating_add(self.page_size()))
+            }
+            Action::ChatWindowPageDown => {
+                let selected = self.message_list_state.selected().unwrap_or(0);
+                self.select_message(selected.saturating_sub(self.page_size()))
+            }
+            Action::ChatWindowTranslate => self.translate_selected(),
+            Action::ChatWindowNextEntity => self.next_entity(),
+            Action::ChatWindowFollowEntity => self.follow_entity(),
+            Action::ShowSendAsPicker(senders) => {
+                self.send_as_picker = Some(SendAsPicker::new(senders))
+            }
+            Action::ShowUnreadDashboardPanel(chats) => {
+                self.unread_dashboard = Some(UnreadDashboard::new(chats))
+            }
+            Action::ShowSearchResults(query, results) => {
+                self.search_results = Some(SearchResults::new(query, results))
+            }
+            Action::ShowChatPollsPanel(polls) => self.chat_polls = Some(ChatPolls::new(polls)),
+            Action::ShowActiveSessionsPanel(sessions) => {
+                self.active_sessions = Some(ActiveSessions::new(sessions))
+            }
+            Action::ShowForumTopicsPanel(topics) if !self.split => {
+                self.forum_topics = Some(ForumTopics::new(topics))
+            }
+            Action::ShowScheduledMessagesPanel(scheduled) if !self.split => {
+                self.scheduled_messages = Some(ScheduledMessages::new(scheduled))
+            }
+            Action::ShowBotMenuPanel(menu) if !self.split => {
+                self.bot_menu = Some(BotMenu::new(menu))
+            }
+            Action::ShowCallHistoryPanel(entries) => {
+                self.call_history = Some(CallHistory::new(entries))
+            }
+            Action::ShowChatPermissionsPanel(permissions) => {
+                self.chat_permissions = Some(ChatPermissions::new(permissions))
+            }
+            Action::ShowLocationPanel(lines) => {
+                self.location_panel = Some(LocationPanel::new(lines))
+            }
+            Action::ShowActionAuditPanel(entries) => {
+                self.action_audit = Some(ActionAudit::new(entries))
+            }
+            Action::ShowFormattingPreviewPanel(preview) => {
+                self.formatting_preview = Some(FormattingPreviewPanel::new(preview))
+            }
+            Action::ShowQuizExplanationPanel(explanation) => {
+                self.quiz_explanation = Some(QuizExplanation::new(explanation))
+            }
+            Action::ShowChatActivityPanel(lines) => {
+                self.chat_activity = Some(ChatActivity::new(lines))
+            }
+            Action::ShowEditHistoryPanel(current, diff) => {
+                self.edit_history = Some(EditHistory::new(current, diff))
+            }
+            Action::ShowReactionPicker(message_id, available) => {
+                self.open_reaction_picker(message_id, available)
+            }
+            Action::SendMessage(..) => self.scroll_after_send(),
             _ => {}
         }
     }
@@ -217,8 +1623,39 @@ impl Component for ChatWindow {
             self.message_list_state.select(None);
         }
 
-        self.message_list
-            .clone_from(&self.app_context.tg_context().open_chat_messages());
+        let tg_context = self.app_context.tg_context();
+        let chat_id = if self.split {
+            self.message_list
+                .clone_from(&tg_context.split_chat_messages());
+            tg_context.split_chat_id()
+        } else {
+            self.message_list
+                .clone_from(&tg_context.open_chat_messages());
+            // The new messages of the other topics are received as well.
+            let open_topic_id = tg_context.open_topic_id();
+            if open_topic_id != ALL_TOPICS_THREAD_ID {
+                self.message_list
+                    .retain(|message| message.message_thread_id() == open_topic_id);
+            }
+            tg_context.open_chat_id()
+        };
+        let (last_read_inbox_message_id, last_read_outbox_message_id) =
+            tg_context.chats().get(&chat_id).map_or((-1, -1), |chat| {
+                (
+                    chat.last_read_inbox_message_id,
+                    chat.last_read_outbox_message_id,
+                )
+            });
+        let (chat_name, chat_status) = if self.split {
+            (tg_context.name_from_chats(chat_id), String::new())
+        } else if !self.app_context.presence_display() {
+            (tg_context.name_of_open_chat_id(), String::new())
+        } else {
+            (
+                tg_context.name_of_open_chat_id(),
+                tg_context.open_chat_user_status(),
+            )
+        };
 
         let chat_layout = Layout::default()
             .direction(Direction::Vertical)
@@ -237,48 +1674,106 @@ impl Component for ChatWindow {
             self.app_context.style_chat()
         };
 
+        if self.divider_chat_id != chat_id {
+            self.divider_chat_id = chat_id;
+            self.divider_last_read = last_read_inbox_message_id;
+        }
+        let divider_index = if self.app_context.app_config().show_new_messages_divider {
+            new_message_divider_index(&self.message_list, self.divider_last_read)
+        } else {
+            None
+        };
+
         let mut is_unread_outbox = true;
         let mut is_unread_inbox = true;
-        let wrap_width = (area.width / 2) as i32;
-        let items = self.message_list.iter().map(|message_entry| {
-            let (myself, name_style, content_style, alignment) = if message_entry.sender_id()
-                == self.app_context.tg_context().me()
-            {
-                if message_entry.id() == self.app_context.tg_context().last_read_outbox_message_id()
-                {
-                    is_unread_outbox = false;
-                }
-                (
-                    true,
-                    self.app_context.style_chat_message_myself_name(),
-                    self.app_context.style_chat_message_myself_content(),
-                    Alignment::Right,
+        let wrap_width = effective_content_width(
+            area.width / 2,
+            self.app_context.app_config().message_content_width,
+        ) as i32;
+        self.album_groups = group_album(&self.message_list);
+        self.follow_new_messages(chat_id);
+        let selected = self.message_list_state.selected();
+        let album_item = self.album_item;
+        let selection_style = self.app_context.app_config().selection_style;
+        let items = self.album_groups.iter().enumerate().map(|(i, group)| {
+            // The album is rendered under the header of its first sent message.
+            let album = group
+                .indices
+                .iter()
+                .rev()
+                .map(|index| &self.message_list[*index])
+                .collect::<Vec<&MessageEntry>>();
+            let message_entry = album[0];
+            let (myself, name_style, content_style, alignment) =
+                if message_entry.sender_id() == self.app_context.tg_context().me() {
+                    if album.iter().any(|m| m.id() == last_read_outbox_message_id) {
+                        is_unread_outbox = false;
+                    }
+                    (
+                        true,
+                        self.app_context.style_chat_message_myself_name(),
+                        self.app_context.style_chat_message_myself_content(),
+                        Alignment::Right,
+                    )
+                } else {
+                    if album.iter().any(|m| m.id() == last_read_inbox_message_id) {
+                        is_unread_inbox = false;
+                    }
+                    (
+                        false,
+                        self.app_context.style_chat_message_other_name(),
+                        self.app_context.style_chat_message_other_content(),
+                        Alignment::Left,
+                    )
+                };
+            let text = if album.len() == 1 {
+                message_entry.get_text_styled(
+                    myself,
+                    &self.app_context,
+                    is_unread_outbox,
+                    name_style,
+                    content_style,
+                    wrap_width,
                 )
             } else {
-                if message_entry.id() == self.app_context.tg_context().last_read_inbox_message_id()
-                {
-                    is_unread_inbox = false;
-                }
-                (
-                    false,
-                    self.app_context.style_chat_message_other_name(),
-                    self.app_context.style_chat_message_other_content(),
-                    Alignment::Left,
-                )
-            };
-            ListItem::new(
-                message_entry
-                    .get_text_styled(
-                        myself,
-                        &self.app_context,
-                        is_unread_outbox,
-                        name_style,
+                let selected_item = (selected == Some(i)).then_some(album_item);
+                message_entry.get_text_styled_with_body(
+                    myself,
+                    &self.app_context,
+                    is_unread_outbox,
+                    name_style,
+                    wrap_width,
+                    album_body(
+                        &album,
+                        selected_item,
                         content_style,
                         wrap_width,
-                    )
-                    .alignment(alignment),
-            )
+                        self.app_context
+                            .syntax_highlight()
+                            .then_some(self.app_context.as_ref()),
+                    ),
+                )
+            };
+            let mut text = text.alignment(alignment);
+            if selection_style == SelectionStyle::Brackets && selected == Some(i) {
+                if let Some(first) = text.lines.first_mut() {
+                    first.spans.insert(0, Span::raw("[ "));
+                }
+                if let Some(last) = text.lines.last_mut() {
+                    last.push_span(Span::raw(" ]"));
+                }
+            }
+            if divider_index.is_some_and(|index| group.indices.contains(&index)) {
+                text.lines.insert(
+                    0,
+                    Line::styled("─── New messages ───", self.app_context.style_timestamp())
+                        .alignment(Alignment::Center),
+                );
+            }
+            ListItem::new(text)
         });
+        let items = items.collect::<Vec<ListItem>>();
+        let item_heights = items.iter().map(ListItem::height).collect::<Vec<usize>>();
 
         let block = Block::new()
             .border_set(border)
@@ -288,9 +1783,21 @@ impl Component for ChatWindow {
         let list = List::new(items)
             .block(block)
             .style(self.app_context.style_chat())
-            .highlight_style(self.app_context.style_item_selected())
             .repeat_highlight_symbol(true)
             .direction(ListDirection::BottomToTop);
+        let list = match selection_style {
+            SelectionStyle::Highlight | SelectionStyle::Brackets => {
+                list.highlight_style(self.app_context.style_item_selected())
+            }
+            SelectionStyle::Reverse => list.highlight_style(
+                self.app_context
+                    .style_item_selected()
+                    .add_modifier(Modifier::REVERSED),
+            ),
+            SelectionStyle::LeftBar => list
+                .highlight_style(self.app_context.style_item_selected())
+                .highlight_symbol("▌"),
+        };
 
         let border_header = Set {
             top_left: line::NORMAL.horizontal_down,
@@ -305,24 +1812,333 @@ impl Component for ChatWindow {
             .title(self.name.as_str());
         let header = Paragraph::new(Line::from(vec![
             Span::styled(
-                self.app_context
-                    .tg_context()
-                    .name_of_open_chat_id()
-                    .unwrap_or_default(),
+                chat_name.unwrap_or_default(),
                 self.app_context.style_chat_chat_name(),
             ),
             Span::raw(" "),
-            Span::styled(
-                self.app_context.tg_context().open_chat_user_status(),
-                self.app_context.style_timestamp(),
-            ),
+            Span::styled(chat_status, self.app_context.style_timestamp()),
         ]))
         .block(block_header)
         .alignment(Alignment::Center);
 
         frame.render_widget(header, chat_layout[0]);
+        // The list has only the top border.
+        let list_height = chat_layout[1].height.saturating_sub(1) as usize;
+        if let Some(selected) = selected {
+            *self.message_list_state.offset_mut() = scroll_offset(
+                self.message_list_state.offset(),
+                selected,
+                &item_heights,
+                list_height,
+                self.app_context.app_config().scroll_margin as usize,
+            );
+        }
         frame.render_stateful_widget(list, chat_layout[1], &mut self.message_list_state);
+        if self.new_while_scrolled_up > 0 {
+            let label = format!(" {} new ↓ ", self.new_while_scrolled_up);
+            let width = (label.chars().count() as u16).min(chat_layout[1].width);
+            let button_area = Rect {
+                x: chat_layout[1].right().saturating_sub(width + 1),
+                y: chat_layout[1].bottom().saturating_sub(1),
+                width,
+                height: 1,
+            };
+            frame.render_widget(
+                Paragraph::new(Span::styled(label, self.app_context.style_item_selected())),
+                button_area,
+            );
+        }
+        self.visible_range =
+            visible_range(self.message_list_state.offset(), &item_heights, list_height);
+        if !self.split {
+            // The newest visible message is saved with the view state.
+            let scroll_message_id = if self.message_list_state.offset() == 0 {
+                0
+            } else {
+                self.album_groups
+                    .get(self.visible_range.start)
+                    .and_then(|group| group.indices.first())
+                    .and_then(|i| self.message_list.get(*i))
+                    .map_or(0, |message| message.id())
+            };
+            self.app_context
+                .tg_context()
+                .set_scroll_message_id(scroll_message_id);
+        }
+        if let Some(picker) = self.reaction_picker.as_mut() {
+            picker.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(call_history) = self.call_history.as_ref() {
+            call_history.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(chat_permissions) = self.chat_permissions.as_ref() {
+            chat_permissions.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(location_panel) = self.location_panel.as_ref() {
+            location_panel.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(action_audit) = self.action_audit.as_ref() {
+            action_audit.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(formatting_preview) = self.formatting_preview.as_ref() {
+            formatting_preview.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(quiz_explanation) = self.quiz_explanation.as_ref() {
+            quiz_explanation.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(chat_activity) = self.chat_activity.as_ref() {
+            chat_activity.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(edit_history) = self.edit_history.as_ref() {
+            edit_history.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(search_results) = self.search_results.as_ref() {
+            search_results.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(picker) = self.send_as_picker.as_mut() {
+            picker.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(dashboard) = self.unread_dashboard.as_mut() {
+            dashboard.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(polls) = self.chat_polls.as_mut() {
+            polls.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(topics) = self.forum_topics.as_mut() {
+            topics.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(sessions) = self.active_sessions.as_mut() {
+            sessions.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(scheduled) = self.scheduled_messages.as_mut() {
+            scheduled.draw(frame, chat_layout[1], &self.app_context);
+        }
+        if let Some(menu) = self.bot_menu.as_mut() {
+            menu.draw(frame, chat_layout[1], &self.app_context);
+        }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::action::Action;
+    use crate::component_name::ComponentName;
+    use crate::components::chat_window::{
+        actions_after_delete, after_send, count_new_messages, effective_content_width,
+        new_message_divider_index, offset_after_send, own_message_group, scroll_offset,
+        scrolled_up_notice, transcript, visible_range,
+    };
+    use crate::configs::raw::app_raw::{DeleteLastBehavior, ScrolledUpNewMessageBehavior};
+    use crate::tg::message_entry::{group_album, MessageEntry};
+
+    #[test]
+    fn test_offset_after_send_jump_to_bottom() {
+        assert_eq!(offset_after_send(0, true), 0);
+        assert_eq!(offset_after_send(42, true), 0);
+    }
+
+    #[test]
+    fn test_offset_after_send_stay_in_place() {
+        assert_eq!(offset_after_send(0, false), 0);
+        assert_eq!(offset_after_send(42, false), 42);
+    }
+
+    #[test]
+    fn test_effective_content_width() {
+        // No cap uses the whole width.
+        assert_eq!(effective_content_width(120, 0), 120);
+        assert_eq!(effective_content_width(0, 0), 0);
+        assert_eq!(effective_content_width(120, 80), 80);
+        assert_eq!(effective_content_width(120, 120), 120);
+        // The cap never exceeds the width of the pane.
+        assert_eq!(effective_content_width(60, 80), 60);
+        assert_eq!(effective_content_width(0, 80), 0);
+    }
+
+    #[test]
+    fn test_after_send_notepad_mode() {
+        // The notepad stays at the bottom with the focus on the prompt.
+        assert_eq!(after_send(42, false, true), (0, true));
+        assert_eq!(after_send(42, true, true), (0, true));
+        // Otherwise only the `jump_to_bottom_on_send` flag is followed.
+        assert_eq!(after_send(42, false, false), (42, false));
+        assert_eq!(after_send(42, true, false), (0, false));
+    }
+
+    fn message_entries(ids: &[i64]) -> Vec<MessageEntry> {
+        ids.iter().map(|id| MessageEntry::with_id(*id)).collect()
+    }
+
+    #[test]
+    fn test_new_message_divider_index() {
+        let messages = message_entries(&[50, 40, 30, 20, 10]);
+        assert_eq!(new_message_divider_index(&messages, 30), Some(1));
+        assert_eq!(new_message_divider_index(&messages, 10), Some(3));
+        assert_eq!(new_message_divider_index(&messages, 35), Some(1));
+    }
+
+    #[test]
+    fn test_own_message_group_mixed_history() {
+        let me = 7;
+        // From the newest to the oldest.
+        let msgs = vec![
+            MessageEntry::with_sender(6, 2),
+            MessageEntry::with_sender(5, me),
+            MessageEntry::with_sender(4, 3),
+            MessageEntry::with_sender(3, 2),
+            MessageEntry::with_sender(2, me),
+            MessageEntry::with_sender(1, 3),
+        ];
+        let groups = group_album(&msgs);
+        // From the bottom, the older one is the most recent own message.
+        assert_eq!(own_message_group(&msgs, &groups, me, None, false), Some(1));
+        assert_eq!(
+            own_message_group(&msgs, &groups, me, Some(1), false),
+            Some(4)
+        );
+        // The older one wraps to the newest.
+        assert_eq!(
+            own_message_group(&msgs, &groups, me, Some(4), false),
+            Some(1)
+        );
+        assert_eq!(
+            own_message_group(&msgs, &groups, me, Some(4), true),
+            Some(1)
+        );
+        // The newer one wraps to the oldest.
+        assert_eq!(
+            own_message_group(&msgs, &groups, me, Some(1), true),
+            Some(4)
+        );
+        assert_eq!(
+            own_message_group(&msgs, &groups, me, Some(2), true),
+            Some(1)
+        );
+        assert_eq!(own_message_group(&msgs, &groups, 42, Some(2), true), None);
+        assert_eq!(own_message_group(&[], &[], me, None, true), None);
+    }
+
+    #[test]
+    fn test_count_new_messages() {
+        let messages = message_entries(&[60, 55, 50, 40]);
+        assert_eq!(count_new_messages(&messages, 50), 2);
+        assert_eq!(count_new_messages(&messages, 60), 0);
+        // Nothing was seen yet, e.g. the history is being loaded.
+        assert_eq!(count_new_messages(&messages, 0), 0);
+    }
+
+    #[test]
+    fn test_scrolled_up_notice() {
+        // The jump button keeps counting until the reader jumps back.
+        assert_eq!(
+            scrolled_up_notice(ScrolledUpNewMessageBehavior::JumpButton, 0, 2),
+            (2, None)
+        );
+        assert_eq!(
+            scrolled_up_notice(ScrolledUpNewMessageBehavior::JumpButton, 2, 1),
+            (3, None)
+        );
+        assert_eq!(
+            scrolled_up_notice(ScrolledUpNewMessageBehavior::FlashStatusBar, 0, 1),
+            (0, Some("1 new message ↓".to_string()))
+        );
+        assert_eq!(
+            scrolled_up_notice(ScrolledUpNewMessageBehavior::FlashStatusBar, 0, 3),
+            (0, Some("3 new messages ↓".to_string()))
+        );
+        assert_eq!(
+            scrolled_up_notice(ScrolledUpNewMessageBehavior::Nothing, 0, 3),
+            (0, None)
+        );
+    }
+
+    #[test]
+    fn test_new_message_divider_index_all_read() {
+        let messages = message_entries(&[50, 40, 30]);
+        assert_eq!(new_message_divider_index(&messages, 50), None);
+        assert_eq!(new_message_divider_index(&messages, 60), None);
+    }
+
+    #[test]
+    fn test_new_message_divider_index_last_read_not_loaded() {
+        let messages = message_entries(&[50, 40, 30]);
+        assert_eq!(new_message_divider_index(&messages, 10), None);
+        assert_eq!(new_message_divider_index(&[], 10), None);
+    }
+
+    #[test]
+    fn test_visible_range() {
+        let item_heights = [2, 3, 2, 4, 1];
+        assert_eq!(visible_range(0, &item_heights, 5), 0..2);
+        assert_eq!(visible_range(1, &item_heights, 6), 1..4);
+        assert_eq!(visible_range(3, &item_heights, 100), 3..5);
+        assert_eq!(visible_range(10, &item_heights, 5), 5..5);
+        assert_eq!(visible_range(0, &[], 5), 0..0);
+    }
+
+    #[test]
+    fn test_transcript_of_visible_window() {
+        // The window shows the items 1 and 2 of a chat stored from the newest
+        // to the oldest message.
+        let chat = [
+            ("Alice", "see you"),
+            ("Bob", "at 9?"),
+            ("Alice", "tomorrow"),
+            ("Bob", "hi"),
+        ];
+        let item_heights = [2, 2, 2, 2];
+        let window = visible_range(1, &item_heights, 4);
+        let messages = chat[window]
+            .iter()
+            .rev()
+            .map(|(sender, text)| (sender.to_string(), text.to_string()))
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(transcript(&messages), "Alice: tomorrow\nBob: at 9?");
+        assert_eq!(transcript(&[]), "");
+    }
+
+    #[test]
+    fn test_actions_after_delete_next_chat() {
+        assert_eq!(
+            actions_after_delete(0, DeleteLastBehavior::NextChat),
+            vec![
+                Action::FocusComponent(ComponentName::ChatList),
+                Action::ChatListNext,
+                Action::ChatListOpen,
+            ]
+        );
+        assert!(actions_after_delete(3, DeleteLastBehavior::NextChat).is_empty());
+    }
+
+    #[test]
+    fn test_actions_after_delete_keep_open() {
+        assert!(actions_after_delete(0, DeleteLastBehavior::KeepOpen).is_empty());
+        assert!(actions_after_delete(3, DeleteLastBehavior::KeepOpen).is_empty());
+    }
+
+    #[test]
+    fn test_scroll_offset_keeps_margin() {
+        let item_heights = [2, 2, 2, 2, 2, 2, 2, 2];
+        // Without a margin the list is not scrolled.
+        assert_eq!(scroll_offset(0, 3, &item_heights, 8, 0), 0);
+        // The selected message is too close to the top edge.
+        assert_eq!(scroll_offset(0, 3, &item_heights, 8, 2), 1);
+        // The selected message is too close to the bottom edge.
+        assert_eq!(scroll_offset(3, 3, &item_heights, 8, 2), 2);
+        // The most recent message has no lines below it.
+        assert_eq!(scroll_offset(0, 0, &item_heights, 8, 2), 0);
+    }
+
+    #[test]
+    fn test_scroll_offset_margin_clamped() {
+        let item_heights = [1, 1, 1, 1, 1, 1, 1, 1];
+        // The margin is clamped to less than half of the height.
+        assert_eq!(scroll_offset(0, 4, &item_heights, 5, 100), 2);
+        assert_eq!(scroll_offset(4, 4, &item_heights, 5, 100), 2);
+        assert_eq!(scroll_offset(0, 4, &item_heights, 0, 100), 4);
+        // The selected message out of the list does not scroll it.
+        assert_eq!(scroll_offset(3, 8, &item_heights, 5, 1), 3);
+    }
+}
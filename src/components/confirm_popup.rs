@@ -0,0 +1,154 @@
+use crate::{
+    app_context::AppContext,
+    components::{active_sessions::session_summary, list_popup::draw_text_popup},
+    event::Event,
+    tg::tg_context::TgContext,
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+};
+
+/// Describe what a destructive event does, it is shown to the user
+/// before the event is handled.
+///
+/// # Arguments
+/// * `event` - The event.
+/// * `tg_context` - The Telegram context, used to describe the chats.
+///
+/// # Returns
+/// * `Option<String>` - The description, or `None` when the event is not
+///   destructive.
+pub fn destructive_description(event: &Event, tg_context: &TgContext) -> Option<String> {
+    match event {
+        Event::LeaveChat(chat_id) => Some(leave_chat_description(
+            &tg_context
+                .name_from_chats(*chat_id)
+                .unwrap_or_else(|| chat_id.to_string()),
+            tg_context.is_chat_owner(*chat_id),
+        )),
+        Event::SendDraft(chat_id) => Some(format!(
+            "Send the draft to {}",
+            tg_context
+                .name_from_chats(*chat_id)
+                .unwrap_or_else(|| chat_id.to_string())
+        )),
+        Event::TerminateSession(session_id) => Some(format!(
+            "Terminate the session {}",
+            tg_context
+                .active_session(*session_id)
+                .map(|session| session_summary(&session))
+                .unwrap_or_else(|| session_id.to_string())
+        )),
+        Event::DeleteMessages(message_ids, revoke) => Some(format!(
+            "Delete {} message{} {}",
+            message_ids.len(),
+            if message_ids.len() == 1 { "" } else { "s" },
+            if *revoke {
+                "for everyone"
+            } else {
+                "only for you"
+            }
+        )),
+        _ => None,
+    }
+}
+
+/// Describe what leaving a chat does.
+/// The owner is warned that leaving may require transferring the ownership.
+///
+/// # Arguments
+/// * `title` - The title of the chat.
+/// * `is_owner` - Whether the current user is the owner of the chat.
+///
+/// # Returns
+/// * `String` - The description.
+fn leave_chat_description(title: &str, is_owner: bool) -> String {
+    if is_owner {
+        format!(
+            "Leave {} (you are its owner, leaving may require to transfer the ownership first)",
+            title
+        )
+    } else {
+        format!("Leave {}", title)
+    }
+}
+
+/// `ConfirmPopup` asks the user to confirm a destructive event, it is drawn
+/// over the whole `CoreWindow`.
+pub struct ConfirmPopup {
+    /// What will happen when the event is confirmed.
+    description: String,
+}
+/// Implementation of the `ConfirmPopup` struct.
+impl ConfirmPopup {
+    /// Create a new instance of the `ConfirmPopup` struct.
+    ///
+    /// # Arguments
+    /// * `description` - What will happen when the event is confirmed.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `ConfirmPopup` struct.
+    pub fn new(description: String) -> Self {
+        ConfirmPopup { description }
+    }
+
+    /// Draw the `ConfirmPopup` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `CoreWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let text = vec![
+            Line::from(format!("{}?", self.description)),
+            Line::from("Press y to confirm or any other key to cancel"),
+        ];
+        draw_text_popup(
+            frame,
+            area,
+            Constraint::Percentage(60),
+            "Confirm",
+            text,
+            app_context,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::confirm_popup::{destructive_description, leave_chat_description},
+        event::Event,
+        tg::tg_context::TgContext,
+    };
+
+    #[test]
+    fn test_leave_chat_description_owner_warning() {
+        assert_eq!(leave_chat_description("Rust", false), "Leave Rust");
+        let description = leave_chat_description("Rust", true);
+        assert!(description.starts_with("Leave Rust"));
+        assert!(description.contains("transfer the ownership"));
+    }
+
+    #[test]
+    fn test_destructive_description() {
+        let tg_context = TgContext::default();
+        assert_eq!(
+            destructive_description(&Event::DeleteMessages(vec![1, 2], true), &tg_context),
+            Some("Delete 2 messages for everyone".to_string())
+        );
+        assert_eq!(
+            destructive_description(&Event::LeaveChat(-100), &tg_context),
+            Some("Leave -100".to_string())
+        );
+        assert_eq!(
+            destructive_description(&Event::TerminateSession(42), &tg_context),
+            Some("Terminate the session 42".to_string())
+        );
+        assert_eq!(
+            destructive_description(&Event::GetChatHistory, &tg_context),
+            None
+        );
+    }
+}
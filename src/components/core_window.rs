@@ -1,5 +1,5 @@
 use crate::{
-    action::Action,
+    action::{Action, Modifiers},
     app_context::AppContext,
     app_error::AppError,
     component_name::ComponentName,
@@ -7,18 +7,199 @@ use crate::{
         chat_list_window::ChatListWindow,
         chat_window::ChatWindow,
         component_traits::{Component, HandleFocus},
+        confirm_popup::ConfirmPopup,
         prompt_window::PromptWindow,
     },
-    components::{MAX_CHAT_LIST_SIZE, MAX_PROMPT_SIZE, MIN_CHAT_LIST_SIZE, MIN_PROMPT_SIZE},
-    configs::custom::keymap_custom::ActionBinding,
+    components::{
+        MAX_CHAT_LIST_SIZE, MAX_PROMPT_SIZE, MIN_CHAT_LIST_SIZE, MIN_PROMPT_SIZE,
+        MOBILE_LAYOUT_WIDTH,
+    },
+    configs::{
+        custom::keymap_custom::ActionBinding,
+        raw::app_raw::{BackspaceAction, ComposeEscapeAction, HomeEndBehavior, TabBehavior},
+    },
     event::Event,
 };
+use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use std::{collections::HashMap, io, sync::Arc};
 use tokio::sync::mpsc::UnboundedSender;
 
 use super::reply_message::ReplyMessage;
 
+/// `SplitSide` is an enum that represents a side of the split view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitSide {
+    /// The left side of the split view.
+    Left,
+    /// The right side of the split view.
+    Right,
+}
+/// Implementation of the `SplitSide` enum.
+impl SplitSide {
+    /// Get the opposite side of the split view.
+    ///
+    /// # Returns
+    /// * `SplitSide` - The opposite side.
+    pub fn other(self) -> Self {
+        match self {
+            SplitSide::Left => SplitSide::Right,
+            SplitSide::Right => SplitSide::Left,
+        }
+    }
+}
+
+/// `MobilePane` is an enum that represents the pane shown by the mobile
+/// layout, which shows either the chat list or the open chat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MobilePane {
+    /// The chat list is shown.
+    ChatList,
+    /// The open chat, with the reply message and the prompt, is shown.
+    Chat,
+}
+/// Implementation of the `MobilePane` enum.
+impl MobilePane {
+    /// Get the pane shown after the given action. Focusing the chat or the
+    /// prompt, e.g. when a chat is opened, shows the open chat; unfocusing or
+    /// focusing the chat list goes back to the chat list.
+    ///
+    /// # Arguments
+    /// * `action` - The action handled by the `CoreWindow`.
+    ///
+    /// # Returns
+    /// * `MobilePane` - The pane to show.
+    pub fn after(self, action: &Action) -> Self {
+        match action {
+            Action::FocusComponent(ComponentName::Chat | ComponentName::Prompt) => MobilePane::Chat,
+            Action::FocusComponent(ComponentName::ChatList) | Action::UnfocusComponent => {
+                MobilePane::ChatList
+            }
+            _ => self,
+        }
+    }
+}
+
+/// Get the action dispatched when Backspace is pressed.
+/// The prompt uses Backspace to edit its text, so nothing is dispatched
+/// while it is focused.
+///
+/// # Arguments
+/// * `backspace_action` - What the Backspace key is configured to do.
+/// * `focused` - The focused component, if any.
+///
+/// # Returns
+/// * `Option<Action>` - The action to dispatch, if any.
+pub fn backspace_action(
+    backspace_action: BackspaceAction,
+    focused: Option<ComponentName>,
+) -> Option<Action> {
+    match (backspace_action, focused) {
+        (_, Some(ComponentName::Prompt)) | (BackspaceAction::Noop, _) => None,
+        (BackspaceAction::NavigateBack, Some(ComponentName::Chat)) => {
+            Some(Action::FocusComponent(ComponentName::ChatList))
+        }
+        (BackspaceAction::NavigateBack, Some(ComponentName::ChatList)) => {
+            Some(Action::UnfocusComponent)
+        }
+        (BackspaceAction::CloseOverlay, Some(ComponentName::Chat)) => Some(Action::Key(
+            KeyCode::Esc,
+            Modifiers::from(KeyModifiers::NONE),
+        )),
+        _ => None,
+    }
+}
+
+/// Get the action dispatched when Home or End is pressed.
+/// The prompt uses them to move to the start and the end of the line, so
+/// nothing is dispatched while it is focused, and the chat list is not
+/// affected.
+///
+/// # Arguments
+/// * `home_end_behavior` - What the Home and End keys are configured to do.
+/// * `focused` - The focused component, if any.
+/// * `key_code` - The pressed key, either `KeyCode::Home` or `KeyCode::End`.
+///
+/// # Returns
+/// * `Option<Action>` - The action to dispatch, if any.
+pub fn home_end_action(
+    home_end_behavior: HomeEndBehavior,
+    focused: Option<ComponentName>,
+    key_code: KeyCode,
+) -> Option<Action> {
+    if focused != Some(ComponentName::Chat) {
+        return None;
+    }
+    match (home_end_behavior, key_code) {
+        (HomeEndBehavior::JumpToEdge, KeyCode::Home) => Some(Action::ChatWindowFirst),
+        (HomeEndBehavior::JumpToEdge, KeyCode::End) => Some(Action::ChatWindowLast),
+        (HomeEndBehavior::ScrollPage, KeyCode::Home) => Some(Action::ChatWindowPageUp),
+        (HomeEndBehavior::ScrollPage, KeyCode::End) => Some(Action::ChatWindowPageDown),
+        _ => None,
+    }
+}
+
+/// Get the action dispatched when Tab is pressed.
+/// Completing a mention and indenting only make sense in the prompt, so the
+/// Tab key moves the focus to the next component everywhere else.
+///
+/// # Arguments
+/// * `tab_behavior` - What the Tab key is configured to do.
+/// * `focused` - The focused component, if any.
+///
+/// # Returns
+/// * `Action` - The action to dispatch.
+pub fn tab_action(tab_behavior: TabBehavior, focused: Option<ComponentName>) -> Action {
+    match (tab_behavior, focused) {
+        (TabBehavior::MentionCompletion, Some(ComponentName::Prompt)) => {
+            Action::PromptCompleteMention
+        }
+        (TabBehavior::Indent, Some(ComponentName::Prompt)) => Action::PromptIndent,
+        (_, Some(ComponentName::ChatList)) => Action::FocusComponent(ComponentName::Chat),
+        (_, Some(ComponentName::Chat)) => Action::FocusComponent(ComponentName::Prompt),
+        _ => Action::FocusComponent(ComponentName::ChatList),
+    }
+}
+
+/// Get the action dispatched instead of unfocusing the prompt when Esc is
+/// pressed, the focus is moved as usual everywhere else.
+///
+/// # Arguments
+/// * `compose_escape_action` - What the Esc key is configured to do in the
+///   prompt.
+/// * `focused` - The focused component, if any.
+///
+/// # Returns
+/// * `Option<Action>` - The action to dispatch, `None` to unfocus.
+pub fn compose_escape_action(
+    compose_escape_action: ComposeEscapeAction,
+    focused: Option<ComponentName>,
+) -> Option<Action> {
+    match (compose_escape_action, focused) {
+        (ComposeEscapeAction::ClearText, Some(ComponentName::Prompt)) => {
+            Some(Action::PromptClearText)
+        }
+        (ComposeEscapeAction::DiscardReply, Some(ComponentName::Prompt)) => {
+            Some(Action::PromptDiscardReply)
+        }
+        _ => None,
+    }
+}
+
+/// Check whether the mobile layout is used.
+///
+/// # Arguments
+/// * `mobile_layout` - The layout chosen by the user, `None` when it has
+///   never been toggled.
+/// * `width` - The width of the `CoreWindow`.
+///
+/// # Returns
+/// * `bool` - The chosen layout, or whether the width is below
+///   `MOBILE_LAYOUT_WIDTH` when no layout has been chosen.
+pub fn mobile_layout_enabled(mobile_layout: Option<bool>, width: u16) -> bool {
+    mobile_layout.unwrap_or(width < MOBILE_LAYOUT_WIDTH)
+}
+
 /// `CoreWindow` is a struct that represents the core window of the application.
 /// It is responsible for managing the layout and rendering of the core window.
 pub struct CoreWindow {
@@ -47,6 +228,24 @@ pub struct CoreWindow {
     focused: bool,
     /// Indicates whether the reply message should be shown.
     show_reply_message: bool,
+    /// Indicates whether two chats are shown side by side.
+    split_view: bool,
+    /// The side of the split view that receives the input. The open chat is
+    /// always drawn on this side, the parked chat on the other one.
+    split_focus: SplitSide,
+    /// The layout chosen by the user, `None` to use the mobile layout only
+    /// on narrow terminals.
+    mobile_layout: Option<bool>,
+    /// The pane shown by the mobile layout.
+    mobile_pane: MobilePane,
+    /// The width of the area of the last draw.
+    area_width: u16,
+    /// The popup asking to confirm a destructive event, it is `None` when
+    /// there is nothing to confirm.
+    confirm_popup: Option<ConfirmPopup>,
+    /// The action that replaces the unfocus bound to the Esc key just
+    /// pressed in the prompt, see `compose_escape_action`.
+    compose_escape: Option<Action>,
 }
 
 impl CoreWindow {
@@ -71,6 +270,13 @@ impl CoreWindow {
                     .with_name(ComponentName::Chat.to_string())
                     .new_boxed(),
             ),
+            (
+                ComponentName::SplitChat,
+                ChatWindow::new(Arc::clone(&app_context))
+                    .with_name(ComponentName::SplitChat.to_string())
+                    .with_split()
+                    .new_boxed(),
+            ),
             (
                 ComponentName::Prompt,
                 PromptWindow::new(Arc::clone(&app_context))
@@ -97,6 +303,13 @@ impl CoreWindow {
         let component_focused = None;
         let focused = true;
         let show_reply_message = false;
+        let split_view = false;
+        let split_focus = SplitSide::Left;
+        let mobile_layout = None;
+        let mobile_pane = MobilePane::ChatList;
+        let area_width = 0;
+        let confirm_popup = None;
+        let compose_escape = None;
 
         CoreWindow {
             app_context,
@@ -110,6 +323,13 @@ impl CoreWindow {
             component_focused,
             focused,
             show_reply_message,
+            split_view,
+            split_focus,
+            mobile_layout,
+            mobile_pane,
+            area_width,
+            confirm_popup,
+            compose_escape,
         }
     }
     /// Set the name of the `CoreWindow`.
@@ -136,6 +356,10 @@ impl CoreWindow {
     pub fn toggle_chat_list(&mut self) {
         self.size_chat_list = if self.size_chat_list == 0 { 20 } else { 0 };
     }
+    /// Toggle the mobile layout, overriding the choice based on the width.
+    pub fn toggle_mobile_layout(&mut self) {
+        self.mobile_layout = Some(!mobile_layout_enabled(self.mobile_layout, self.area_width));
+    }
     /// Increase the size of the chat list component.
     pub fn increase_chat_list_size(&mut self) {
         if self.size_chat_list == MAX_CHAT_LIST_SIZE {
@@ -164,7 +388,144 @@ impl CoreWindow {
         }
         self.size_prompt -= 1;
     }
+
+    /// Whether the keys are typed in the search bar docked above the focused
+    /// chat list.
+    fn search_bar_typing(&self) -> bool {
+        self.component_focused == Some(ComponentName::ChatList) && self.app_context.search_bar()
+    }
+
+    /// Draw the components in the layout in use, i.e. the mobile layout,
+    /// the split view or the chat list next to the open chat.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `CoreWindow`.
+    fn draw_layout(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        self.area_width = area.width;
+        if mobile_layout_enabled(self.mobile_layout, area.width) {
+            return match self.mobile_pane {
+                MobilePane::ChatList => self
+                    .components
+                    .get_mut(&ComponentName::ChatList)
+                    .unwrap_or_else(|| {
+                        panic!("Failed to get component: {}", ComponentName::ChatList)
+                    })
+                    .draw(frame, area),
+                MobilePane::Chat => self.draw_open_chat(frame, area),
+            };
+        }
+
+        let core_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(self.size_chat_list),
+                Constraint::Percentage(100 - self.size_chat_list),
+            ])
+            .split(area);
+
+        self.components
+            .get_mut(&ComponentName::ChatList)
+            .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::ChatList))
+            .draw(frame, core_layout[0])?;
+
+        if !self.split_view {
+            return self.draw_open_chat(frame, core_layout[1]);
+        }
+
+        // Only the focused side has the reply message and the prompt.
+        let (left, right) = split_view_areas(core_layout[1]);
+        let (focused_area, parked_area) = match self.split_focus {
+            SplitSide::Left => (left, right),
+            SplitSide::Right => (right, left),
+        };
+        self.draw_open_chat(frame, focused_area)?;
+        self.components
+            .get_mut(&ComponentName::SplitChat)
+            .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::SplitChat))
+            .draw(frame, parked_area)?;
+
+        Ok(())
+    }
+
+    /// Answer the popup asking to confirm a destructive event.
+    /// The `y` key confirms the event, any other key cancels it.
+    ///
+    /// # Arguments
+    /// * `key_code` - The key code of the pressed key.
+    fn answer_confirmation(&mut self, key_code: KeyCode) {
+        self.confirm_popup = None;
+        let mut confirmation_gate = self.app_context.confirmation_gate();
+        if key_code != KeyCode::Char('y') {
+            confirmation_gate.cancel();
+            return;
+        }
+        if let Some(event) = confirmation_gate.confirm() {
+            if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+                event_tx.send(event).unwrap();
+            }
+        }
+    }
+
+    /// Draw the open chat, the reply message and the prompt in the given
+    /// area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area where the components are drawn.
+    fn draw_open_chat(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
+        let sub_core_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(1),
+                {
+                    if self.show_reply_message {
+                        Constraint::Length(self.size_message_reply)
+                    } else {
+                        Constraint::Length(0)
+                    }
+                },
+                Constraint::Length(self.size_prompt),
+            ])
+            .split(area);
+
+        self.components
+            .get_mut(&ComponentName::Chat)
+            .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Chat))
+            .draw(frame, sub_core_layout[0])?;
+
+        if self.show_reply_message {
+            self.components
+                .get_mut(&ComponentName::ReplyMessage)
+                .unwrap_or_else(|| {
+                    panic!("Failed to get component: {}", ComponentName::ReplyMessage)
+                })
+                .draw(frame, sub_core_layout[1])?;
+        }
+        self.components
+            .get_mut(&ComponentName::Prompt)
+            .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Prompt))
+            .draw(frame, sub_core_layout[2])?;
+
+        Ok(())
+    }
 }
+
+/// Split the given area into the two message areas of the split view.
+///
+/// # Arguments
+/// * `area` - The area to split.
+///
+/// # Returns
+/// * `(Rect, Rect)` - The left and the right areas.
+pub fn split_view_areas(area: Rect) -> (Rect, Rect) {
+    let split_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    (split_layout[0], split_layout[1])
+}
+
 /// Implement the `HandleFocus` trait for the `CoreWindow` struct.
 /// This trait allows the `CoreWindow` to be focused or unfocused.
 impl HandleFocus for CoreWindow {
@@ -189,12 +550,28 @@ impl Component for CoreWindow {
     }
 
     fn handle_events(&mut self, event: Option<Event>) -> Result<Option<Action>, AppError<Action>> {
+        // The keys are typed in the search bar, it handles them as
+        // `Action::Key`.
+        if self.search_bar_typing() {
+            return Ok(Some(Action::Unknown));
+        }
         let binding = self.app_context.keymap_config();
         let map = binding.get_map_of(self.component_focused);
-        if let Some(action_binding) = map.get(&event.unwrap()) {
+        if let Some(action_binding) = map.get(&event.unwrap().normalized()) {
             match action_binding {
-                ActionBinding::Single { action, .. } => {
-                    return Ok(Some(action.clone()));
+                ActionBinding::Single {
+                    action,
+                    double_action,
+                    ..
+                } => {
+                    // The key press has been recorded before the event
+                    // reached the components.
+                    return Ok(Some(match double_action {
+                        Some(double_action) if self.app_context.double_key().last_was_double() => {
+                            double_action.clone()
+                        }
+                        _ => action.clone(),
+                    }));
                 }
                 ActionBinding::Multiple(_map_event_action) => {
                     tracing::warn!("Multiple action bindings are not supported yet. They are supported only for default key bindings because there are not the app context to handle them here.");
@@ -206,7 +583,63 @@ impl Component for CoreWindow {
     }
 
     fn update(&mut self, action: Action) {
+        if self.confirm_popup.is_some() {
+            // The popup takes every key until it is answered.
+            if let Action::Key(key_code, _) = action {
+                self.answer_confirmation(key_code);
+            }
+            return;
+        }
+        self.mobile_pane = self.mobile_pane.after(&action);
+        if let Action::Key(key_code, _) = action {
+            // The Esc key reaches the `CoreWindow` before the action it is
+            // bound to.
+            self.compose_escape = if key_code == KeyCode::Esc {
+                let configured = self.app_context.app_config().compose_escape_action;
+                compose_escape_action(configured, self.component_focused)
+            } else {
+                None
+            };
+        }
         match action {
+            Action::ShowConfirmation(description) => {
+                self.confirm_popup = Some(ConfirmPopup::new(description));
+            }
+            Action::Key(KeyCode::Backspace, _) if !self.search_bar_typing() => {
+                let configured = self.app_context.app_config().backspace_action;
+                if let Some(action) = backspace_action(configured, self.component_focused) {
+                    self.action_tx
+                        .as_ref()
+                        .unwrap_or_else(|| panic!("Failed to get action_tx on CoreWindow"))
+                        .send(action)
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send the Backspace action from CoreWindow")
+                        });
+                }
+            }
+            Action::Key(key_code @ (KeyCode::Home | KeyCode::End), _)
+                if !self.search_bar_typing() =>
+            {
+                let configured = self.app_context.app_config().home_end_behavior;
+                if let Some(action) = home_end_action(configured, self.component_focused, key_code)
+                {
+                    self.action_tx
+                        .as_ref()
+                        .unwrap_or_else(|| panic!("Failed to get action_tx on CoreWindow"))
+                        .send(action)
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send the Home/End action from CoreWindow")
+                        });
+                }
+            }
+            Action::Key(KeyCode::Tab, _) => {
+                let configured = self.app_context.app_config().tab_behavior;
+                self.action_tx
+                    .as_ref()
+                    .unwrap_or_else(|| panic!("Failed to get action_tx on CoreWindow"))
+                    .send(tab_action(configured, self.component_focused))
+                    .unwrap_or_else(|_| panic!("Failed to send the Tab action from CoreWindow"));
+            }
             Action::FocusComponent(component_name) => {
                 self.component_focused = Some(component_name);
                 self.components
@@ -218,6 +651,18 @@ impl Component for CoreWindow {
                     .filter(|(name, _)| *name != &component_name)
                     .for_each(|(_, component)| component.unfocus());
             }
+            Action::UnfocusComponent if self.compose_escape.is_some() => {
+                if let Some(action) = self.compose_escape.take() {
+                    self.action_tx
+                        .as_ref()
+                        .unwrap_or_else(|| panic!("Failed to get action_tx on CoreWindow"))
+                        .send(action)
+                        .unwrap_or_else(|_| {
+                            panic!("Failed to send the Esc action from CoreWindow")
+                        });
+                }
+                return;
+            }
             Action::UnfocusComponent => {
                 self.component_focused = None;
                 self.show_reply_message = false;
@@ -228,6 +673,9 @@ impl Component for CoreWindow {
             Action::ToggleChatList => {
                 self.toggle_chat_list();
             }
+            Action::ToggleMobileLayout => {
+                self.toggle_mobile_layout();
+            }
             Action::IncreaseChatListSize => {
                 self.increase_chat_list_size();
             }
@@ -241,7 +689,9 @@ impl Component for CoreWindow {
                 self.decrease_size_prompt();
             }
             Action::TryQuit => {
-                if self.component_focused != Some(ComponentName::Prompt) {
+                if self.component_focused != Some(ComponentName::Prompt)
+                    && !self.search_bar_typing()
+                {
                     self.action_tx
                         .as_ref()
                         .unwrap_or_else(|| panic!("Failed to get action_tx on CoreWindow"))
@@ -255,6 +705,93 @@ impl Component for CoreWindow {
             Action::HideChatWindowReply => {
                 self.show_reply_message = false;
             }
+            Action::OpenSplitView(..) => {
+                self.split_view = true;
+                self.split_focus = SplitSide::Left;
+            }
+            Action::CloseSplitView => {
+                self.app_context.tg_context().clear_split_chat();
+                self.split_view = false;
+            }
+            Action::SwapSplitFocus => {
+                if self.split_view {
+                    self.app_context.tg_context().swap_split_chat();
+                    self.split_focus = self.split_focus.other();
+                }
+            }
+            Action::CopyVisibleTranscript
+            | Action::CopyCodeBlock(..)
+            | Action::CopyMessageLinks(..)
+            | Action::ShowSendError(..) => {
+                // The transcript, the code blocks, the links and the send
+                // errors are always taken from the open chat, even when the
+                // focus is on another component.
+                if self.component_focused != Some(ComponentName::Chat) {
+                    self.components
+                        .get_mut(&ComponentName::Chat)
+                        .unwrap_or_else(|| {
+                            panic!("Failed to get component: {}", ComponentName::Chat)
+                        })
+                        .update(action.clone());
+                }
+            }
+            Action::HideFormattingPreview
+            | Action::ShowActionAuditPanel(..)
+            | Action::ShowActiveSessionsPanel(..)
+            | Action::ShowBotMenuPanel(..)
+            | Action::ShowCallHistoryPanel(..)
+            | Action::ShowChatActivityPanel(..)
+            | Action::ShowChatPermissionsPanel(..)
+            | Action::ShowChatPollsPanel(..)
+            | Action::ShowForumTopicsPanel(..)
+            | Action::ShowEditHistoryPanel(..)
+            | Action::ShowFormattingPreviewPanel(..)
+            | Action::ShowLocationPanel(..)
+            | Action::ShowQuizExplanationPanel(..)
+            | Action::ShowScheduledMessagesPanel(..)
+            | Action::ShowSearchResults(..)
+            | Action::ShowSendAsPicker(..)
+            | Action::ShowUnreadDashboardPanel(..) => {
+                // The panels are requested by the open chat, they are shown
+                // there even if the focus moved meanwhile.
+                if self.component_focused != Some(ComponentName::Chat) {
+                    self.components
+                        .get_mut(&ComponentName::Chat)
+                        .unwrap_or_else(|| {
+                            panic!("Failed to get component: {}", ComponentName::Chat)
+                        })
+                        .update(action.clone());
+                }
+            }
+            Action::ShowSearchBarMessages(..) | Action::OpenChat(..) => {
+                // The messages may be found after the focus left the chat
+                // list, and the chats are opened by the chat list also when
+                // they are chosen in a panel of the chat.
+                if self.component_focused != Some(ComponentName::ChatList) {
+                    self.components
+                        .get_mut(&ComponentName::ChatList)
+                        .unwrap_or_else(|| {
+                            panic!("Failed to get component: {}", ComponentName::ChatList)
+                        })
+                        .update(action.clone());
+                }
+            }
+            Action::Quit if self.component_focused != Some(ComponentName::Prompt) => {
+                // The text typed in the prompt is kept when it is unfocused,
+                // it is flushed as a draft when quitting.
+                self.components
+                    .get_mut(&ComponentName::Prompt)
+                    .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Prompt))
+                    .update(action.clone());
+            }
+            Action::SendMessage(..) => {
+                // The chat window is not focused while the prompt is sending
+                // a message, so it is notified explicitly to restore its scroll.
+                self.components
+                    .get_mut(&ComponentName::Chat)
+                    .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Chat))
+                    .update(action.clone());
+            }
             _ => {}
         }
 
@@ -267,52 +804,208 @@ impl Component for CoreWindow {
     }
 
     fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect) -> io::Result<()> {
-        let core_layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(self.size_chat_list),
-                Constraint::Percentage(100 - self.size_chat_list),
-            ])
-            .split(area);
+        self.draw_layout(frame, area)?;
+        if let Some(confirm_popup) = self.confirm_popup.as_ref() {
+            confirm_popup.draw(frame, area, &self.app_context);
+        }
+        Ok(())
+    }
+}
 
-        self.components
-            .get_mut(&ComponentName::ChatList)
-            .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::ChatList))
-            .draw(frame, core_layout[0])?;
+#[cfg(test)]
+mod tests {
+    use crate::{
+        action::Action,
+        component_name::ComponentName,
+        components::core_window::{
+            backspace_action, compose_escape_action, home_end_action, mobile_layout_enabled,
+            split_view_areas, tab_action, MobilePane, SplitSide,
+        },
+        configs::raw::app_raw::{
+            BackspaceAction, ComposeEscapeAction, HomeEndBehavior, TabBehavior,
+        },
+    };
+    use crossterm::event::KeyCode;
+    use ratatui::layout::Rect;
 
-        let sub_core_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Fill(1),
-                {
-                    if self.show_reply_message {
-                        Constraint::Length(self.size_message_reply)
-                    } else {
-                        Constraint::Length(0)
-                    }
-                },
-                Constraint::Length(self.size_prompt),
-            ])
-            .split(core_layout[1]);
+    #[test]
+    fn test_split_view_areas() {
+        let (left, right) = split_view_areas(Rect::new(0, 0, 100, 30));
+        assert_eq!(left, Rect::new(0, 0, 50, 30));
+        assert_eq!(right, Rect::new(50, 0, 50, 30));
+    }
 
-        self.components
-            .get_mut(&ComponentName::Chat)
-            .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Chat))
-            .draw(frame, sub_core_layout[0])?;
+    #[test]
+    fn test_split_side_other() {
+        assert_eq!(SplitSide::Left.other(), SplitSide::Right);
+        assert_eq!(SplitSide::Right.other(), SplitSide::Left);
+    }
 
-        if self.show_reply_message {
-            self.components
-                .get_mut(&ComponentName::ReplyMessage)
-                .unwrap_or_else(|| {
-                    panic!("Failed to get component: {}", ComponentName::ReplyMessage)
-                })
-                .draw(frame, sub_core_layout[1])?;
+    #[test]
+    fn test_mobile_pane_switches() {
+        let pane = MobilePane::ChatList;
+        // Opening a chat focuses the prompt.
+        let pane = pane.after(&Action::FocusComponent(ComponentName::Prompt));
+        assert_eq!(pane, MobilePane::Chat);
+        let pane = pane.after(&Action::ChatWindowNext);
+        assert_eq!(pane, MobilePane::Chat);
+        let pane = pane.after(&Action::UnfocusComponent);
+        assert_eq!(pane, MobilePane::ChatList);
+        let pane = pane.after(&Action::FocusComponent(ComponentName::Chat));
+        assert_eq!(pane, MobilePane::Chat);
+        let pane = pane.after(&Action::FocusComponent(ComponentName::ChatList));
+        assert_eq!(pane, MobilePane::ChatList);
+    }
+
+    #[test]
+    fn test_mobile_layout_enabled() {
+        assert!(mobile_layout_enabled(None, 40));
+        assert!(!mobile_layout_enabled(None, 120));
+        assert!(mobile_layout_enabled(Some(true), 120));
+        assert!(!mobile_layout_enabled(Some(false), 40));
+    }
+
+    #[test]
+    fn test_backspace_action_routing() {
+        assert_eq!(
+            backspace_action(BackspaceAction::NavigateBack, Some(ComponentName::Chat)),
+            Some(Action::FocusComponent(ComponentName::ChatList))
+        );
+        assert_eq!(
+            backspace_action(BackspaceAction::NavigateBack, Some(ComponentName::ChatList)),
+            Some(Action::UnfocusComponent)
+        );
+        assert!(matches!(
+            backspace_action(BackspaceAction::CloseOverlay, Some(ComponentName::Chat)),
+            Some(Action::Key(KeyCode::Esc, _))
+        ));
+        assert_eq!(
+            backspace_action(BackspaceAction::CloseOverlay, Some(ComponentName::ChatList)),
+            None
+        );
+        assert_eq!(
+            backspace_action(BackspaceAction::Noop, Some(ComponentName::Chat)),
+            None
+        );
+        // The prompt keeps using Backspace to edit its text.
+        for configured in [
+            BackspaceAction::NavigateBack,
+            BackspaceAction::CloseOverlay,
+            BackspaceAction::Noop,
+        ] {
+            assert_eq!(
+                backspace_action(configured, Some(ComponentName::Prompt)),
+                None
+            );
         }
-        self.components
-            .get_mut(&ComponentName::Prompt)
-            .unwrap_or_else(|| panic!("Failed to get component: {}", ComponentName::Prompt))
-            .draw(frame, sub_core_layout[2])?;
+    }
 
-        Ok(())
+    #[test]
+    fn test_home_end_action_routing() {
+        let chat = Some(ComponentName::Chat);
+        assert_eq!(
+            home_end_action(HomeEndBehavior::JumpToEdge, chat, KeyCode::Home),
+            Some(Action::ChatWindowFirst)
+        );
+        assert_eq!(
+            home_end_action(HomeEndBehavior::JumpToEdge, chat, KeyCode::End),
+            Some(Action::ChatWindowLast)
+        );
+        assert_eq!(
+            home_end_action(HomeEndBehavior::ScrollPage, chat, KeyCode::Home),
+            Some(Action::ChatWindowPageUp)
+        );
+        assert_eq!(
+            home_end_action(HomeEndBehavior::ScrollPage, chat, KeyCode::End),
+            Some(Action::ChatWindowPageDown)
+        );
+        assert_eq!(
+            home_end_action(HomeEndBehavior::LineStartEnd, chat, KeyCode::Home),
+            None
+        );
+        // The prompt keeps using Home and End to move in the line, and the
+        // chat list is not affected.
+        for configured in [
+            HomeEndBehavior::JumpToEdge,
+            HomeEndBehavior::LineStartEnd,
+            HomeEndBehavior::ScrollPage,
+        ] {
+            for focused in [
+                Some(ComponentName::Prompt),
+                Some(ComponentName::ChatList),
+                None,
+            ] {
+                assert_eq!(home_end_action(configured, focused, KeyCode::Home), None);
+                assert_eq!(home_end_action(configured, focused, KeyCode::End), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compose_escape_action_routing() {
+        assert_eq!(
+            compose_escape_action(ComposeEscapeAction::ClearText, Some(ComponentName::Prompt)),
+            Some(Action::PromptClearText)
+        );
+        assert_eq!(
+            compose_escape_action(
+                ComposeEscapeAction::DiscardReply,
+                Some(ComponentName::Prompt)
+            ),
+            Some(Action::PromptDiscardReply)
+        );
+        assert_eq!(
+            compose_escape_action(ComposeEscapeAction::Unfocus, Some(ComponentName::Prompt)),
+            None
+        );
+        // Outside the prompt the Esc key always unfocuses.
+        for configured in [
+            ComposeEscapeAction::Unfocus,
+            ComposeEscapeAction::ClearText,
+            ComposeEscapeAction::DiscardReply,
+        ] {
+            for focused in [
+                Some(ComponentName::ChatList),
+                Some(ComponentName::Chat),
+                None,
+            ] {
+                assert_eq!(compose_escape_action(configured, focused), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tab_action_routing() {
+        assert_eq!(
+            tab_action(TabBehavior::MentionCompletion, Some(ComponentName::Prompt)),
+            Action::PromptCompleteMention
+        );
+        assert_eq!(
+            tab_action(TabBehavior::Indent, Some(ComponentName::Prompt)),
+            Action::PromptIndent
+        );
+        assert_eq!(
+            tab_action(TabBehavior::CycleFocus, Some(ComponentName::Prompt)),
+            Action::FocusComponent(ComponentName::ChatList)
+        );
+        // Outside the prompt the Tab key always cycles the focus.
+        for configured in [
+            TabBehavior::CycleFocus,
+            TabBehavior::MentionCompletion,
+            TabBehavior::Indent,
+        ] {
+            assert_eq!(
+                tab_action(configured, Some(ComponentName::ChatList)),
+                Action::FocusComponent(ComponentName::Chat)
+            );
+            assert_eq!(
+                tab_action(configured, Some(ComponentName::Chat)),
+                Action::FocusComponent(ComponentName::Prompt)
+            );
+            assert_eq!(
+                tab_action(configured, None),
+                Action::FocusComponent(ComponentName::ChatList)
+            );
+        }
     }
 }
@@ -0,0 +1,86 @@
+use crate::{
+    app_context::AppContext, components::list_popup::draw_text_popup, tg::edit_history::DiffSpan,
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier},
+    text::{Line, Span},
+};
+
+/// `EditHistory` is a panel that shows the current text of an edited message
+/// and, when its previous version is known, what the edit changed, it is
+/// drawn over the `ChatWindow`.
+pub struct EditHistory {
+    /// The current text of the message.
+    current: String,
+    /// The difference from the previous version of the message, if it is
+    /// known.
+    diff: Option<Vec<DiffSpan>>,
+}
+/// Implementation of the `EditHistory` struct.
+impl EditHistory {
+    /// Create a new instance of the `EditHistory` struct.
+    ///
+    /// # Arguments
+    /// * `current` - The current text of the message.
+    /// * `diff` - The difference from the previous version, if it is known.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `EditHistory` struct.
+    pub fn new(current: String, diff: Option<Vec<DiffSpan>>) -> Self {
+        EditHistory { current, diff }
+    }
+
+    /// Draw the `EditHistory` centered in the given area.
+    /// The removed words are struck through in red and the added ones are
+    /// green.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let mut text = self
+            .current
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect::<Vec<Line>>();
+        match self.diff.as_ref() {
+            Some(diff) => {
+                text.push(Line::from(""));
+                text.push(Line::styled("Changes:", app_context.style_timestamp()));
+                let spans = diff
+                    .iter()
+                    .flat_map(|span| {
+                        let span = match span {
+                            DiffSpan::Same(words) => Span::raw(words.clone()),
+                            DiffSpan::Added(words) => Span::styled(
+                                words.clone(),
+                                app_context.style_chat().fg(Color::Green),
+                            ),
+                            DiffSpan::Removed(words) => Span::styled(
+                                words.clone(),
+                                app_context
+                                    .style_chat()
+                                    .fg(Color::Red)
+                                    .add_modifier(Modifier::CROSSED_OUT),
+                            ),
+                        };
+                        [span, Span::raw(" ")]
+                    })
+                    .collect::<Vec<Span>>();
+                text.push(Line::from(spans));
+            }
+            None => text.push(Line::styled("(edited)", app_context.style_timestamp())),
+        }
+
+        draw_text_popup(
+            frame,
+            area,
+            Constraint::Percentage(80),
+            "Edit history",
+            text,
+            app_context,
+        );
+    }
+}
@@ -0,0 +1,146 @@
+/// The built-in table of the emoji shortcodes, without the colons.
+const SHORTCODES: [(&str, &str); 40] = [
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("angry", "😠"),
+    ("blush", "😊"),
+    ("broken_heart", "💔"),
+    ("clap", "👏"),
+    ("cry", "😢"),
+    ("eyes", "👀"),
+    ("fire", "🔥"),
+    ("grin", "😁"),
+    ("heart", "❤️"),
+    ("heart_eyes", "😍"),
+    ("hugs", "🤗"),
+    ("joy", "😂"),
+    ("kiss", "😘"),
+    ("laughing", "😆"),
+    ("ok_hand", "👌"),
+    ("party", "🥳"),
+    ("pray", "🙏"),
+    ("rage", "😡"),
+    ("rocket", "🚀"),
+    ("rofl", "🤣"),
+    ("scream", "😱"),
+    ("see_no_evil", "🙈"),
+    ("shrug", "🤷"),
+    ("sleeping", "😴"),
+    ("smile", "😄"),
+    ("smiley", "😃"),
+    ("smirk", "😏"),
+    ("sob", "😭"),
+    ("sparkles", "✨"),
+    ("star", "⭐"),
+    ("sunglasses", "😎"),
+    ("tada", "🎉"),
+    ("thinking", "🤔"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("wave", "👋"),
+    ("wink", "😉"),
+    ("zap", "⚡"),
+];
+
+/// Get the emoji of a shortcode.
+///
+/// # Arguments
+/// * `shortcode` - The shortcode, without the colons.
+///
+/// # Returns
+/// * `Option<&'static str>` - The emoji, or `None` when the shortcode is not
+///   in the table.
+pub fn shortcode_emoji(shortcode: &str) -> Option<&'static str> {
+    SHORTCODES
+        .iter()
+        .find(|(code, _)| *code == shortcode)
+        .map(|(_, emoji)| *emoji)
+}
+
+/// Expand the `:shortcode:` of a text to their emoji, the unknown shortcodes
+/// are left as they are.
+///
+/// # Arguments
+/// * `text` - The text.
+///
+/// # Returns
+/// * `String` - The text with the known shortcodes expanded.
+pub fn expand_shortcodes(text: &str) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(':') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after
+            .find(':')
+            .and_then(|end| Some((end, shortcode_emoji(&after[..end])?)))
+        {
+            Some((end, emoji)) => {
+                expanded.push_str(emoji);
+                rest = &after[end + 1..];
+            }
+            None => {
+                // The colon can be the start of a later shortcode.
+                expanded.push(':');
+                rest = after;
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Find the shortcode completed by a character typed at the cursor: a
+/// closing colon completes `:shortcode:` and a space completes `:shortcode`.
+///
+/// # Arguments
+/// * `before_cursor` - The text of the line before the cursor.
+/// * `typed` - The character that is being typed.
+///
+/// # Returns
+/// * `Option<(usize, &'static str)>` - The number of characters before the
+///   cursor that are replaced and the emoji, or `None` when nothing is
+///   expanded.
+pub fn shortcode_at_cursor(before_cursor: &str, typed: char) -> Option<(usize, &'static str)> {
+    if typed != ':' && typed != ' ' {
+        return None;
+    }
+    let (start, shortcode) = before_cursor.rsplit_once(':')?;
+    // The shortcode starts at the beginning of a word.
+    if !start.is_empty() && !start.ends_with(char::is_whitespace) {
+        return None;
+    }
+    let emoji = shortcode_emoji(shortcode)?;
+    Some((shortcode.chars().count() + 1, emoji))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::emoji_shortcodes::{expand_shortcodes, shortcode_at_cursor};
+
+    #[test]
+    fn test_expand_shortcodes_known() {
+        assert_eq!(expand_shortcodes(":smile:"), "😄");
+        assert_eq!(
+            expand_shortcodes("great job :tada: :+1:"),
+            "great job 🎉 👍"
+        );
+        assert_eq!(expand_shortcodes("at 10:30 :fire:"), "at 10:30 🔥");
+    }
+
+    #[test]
+    fn test_expand_shortcodes_unknown() {
+        assert_eq!(expand_shortcodes(":not_an_emoji:"), ":not_an_emoji:");
+        assert_eq!(expand_shortcodes("a: b :c"), "a: b :c");
+        assert_eq!(expand_shortcodes("::smile:"), ":😄");
+    }
+
+    #[test]
+    fn test_shortcode_at_cursor() {
+        assert_eq!(shortcode_at_cursor("hi :smile", ':'), Some((6, "😄")));
+        assert_eq!(shortcode_at_cursor(":wave", ' '), Some((5, "👋")));
+        assert_eq!(shortcode_at_cursor("hi :unknown", ':'), None);
+        assert_eq!(shortcode_at_cursor("10:smile", ':'), None);
+        assert_eq!(shortcode_at_cursor("hi :smile", 'x'), None);
+    }
+}
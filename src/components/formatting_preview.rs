@@ -0,0 +1,95 @@
+use crate::{app_context::AppContext, tg::formatting_preview::FormattingPreview};
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// `FormattingPreviewPanel` is a transient panel that shows how the
+/// formatting of the composed text is rendered once it is sent, it is drawn
+/// over the `ChatWindow` until the prompt is typed in again.
+pub struct FormattingPreviewPanel {
+    /// The parsed text, or the error of the parsing.
+    preview: FormattingPreview,
+}
+/// Implementation of the `FormattingPreviewPanel` struct.
+impl FormattingPreviewPanel {
+    /// Create a new instance of the `FormattingPreviewPanel` struct.
+    ///
+    /// # Arguments
+    /// * `preview` - The parsed text, or the error of the parsing.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `FormattingPreviewPanel` struct.
+    pub fn new(preview: FormattingPreview) -> Self {
+        FormattingPreviewPanel { preview }
+    }
+
+    /// Get the lines of the panel: the text without the formatting syntax,
+    /// then a legend of its formatted pieces, each one rendered with its
+    /// style. A parse error is shown in place of the legend.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context, used for the styles.
+    fn lines(&self, app_context: &AppContext) -> Vec<Line<'static>> {
+        match &self.preview {
+            FormattingPreview::Error(e) => vec![Line::styled(
+                format!("⚠ {}", e),
+                app_context
+                    .style_chat()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+            )],
+            FormattingPreview::Parsed { text, entities } => {
+                let mut lines = text
+                    .lines()
+                    .map(|line| Line::from(line.to_string()))
+                    .collect::<Vec<Line>>();
+                lines.push(Line::default());
+                if entities.is_empty() {
+                    lines.push(Line::styled(
+                        "No formatting detected",
+                        app_context.style_chat().add_modifier(Modifier::DIM),
+                    ));
+                }
+                lines.extend(entities.iter().map(|entity| {
+                    Line::from(vec![
+                        Span::raw(format!("{}: ", entity.kind.label())),
+                        Span::styled(entity.text.clone(), entity.kind.style()),
+                    ])
+                }));
+                lines
+            }
+        }
+    }
+
+    /// Draw the `FormattingPreviewPanel` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let lines = self.lines(app_context);
+        let [popup_area] = Layout::vertical([Constraint::Length(lines.len() as u16 + 2)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup_area] = Layout::horizontal([Constraint::Percentage(80)])
+            .flex(Flex::Center)
+            .areas(popup_area);
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .border_style(app_context.style_border_component_focused())
+                    .title("Formatting preview"),
+            )
+            .style(app_context.style_chat())
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(paragraph, popup_area);
+    }
+}
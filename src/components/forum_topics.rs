@@ -0,0 +1,160 @@
+use crate::{
+    app_context::AppContext, components::list_popup::ListPopup, tg::td_enums::TdForumTopic,
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Modifier,
+    text::Line,
+    widgets::ListItem,
+};
+
+/// The identifier used for the entry that shows the messages of all the
+/// topics, TDLib never uses it for a message thread.
+pub const ALL_TOPICS_THREAD_ID: i64 = 0;
+
+/// `ForumTopics` is a panel that lists the topics of a forum supergroup, it
+/// is drawn over the `ChatWindow` and the highlighted topic filters the
+/// messages shown.
+pub struct ForumTopics {
+    /// The topics of the forum, the first entry shows all the topics.
+    topics: ListPopup<TdForumTopic>,
+}
+/// Implementation of the `ForumTopics` struct.
+impl ForumTopics {
+    /// Create a new instance of the `ForumTopics` struct.
+    ///
+    /// # Arguments
+    /// * `topics` - The topics of the forum.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `ForumTopics` struct.
+    pub fn new(topics: Vec<TdForumTopic>) -> Self {
+        let all_topics = TdForumTopic {
+            message_thread_id: ALL_TOPICS_THREAD_ID,
+            name: "All topics".to_string(),
+            unread_count: 0,
+            is_closed: false,
+        };
+        ForumTopics {
+            topics: ListPopup::new(std::iter::once(all_topics).chain(topics).collect()),
+        }
+    }
+
+    /// Get the identifier of the message thread of the highlighted topic,
+    /// `ALL_TOPICS_THREAD_ID` for the entry that shows all the topics.
+    pub fn selected(&self) -> Option<i64> {
+        self.topics.selected().map(|topic| topic.message_thread_id)
+    }
+
+    /// Highlight the next topic.
+    pub fn next(&mut self) {
+        self.topics.next();
+    }
+
+    /// Highlight the previous topic.
+    pub fn previous(&mut self) {
+        self.topics.previous();
+    }
+
+    /// Draw the `ForumTopics` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let items = self
+            .topics
+            .items()
+            .iter()
+            .map(|topic| {
+                let style = if topic.is_closed {
+                    app_context.style_chat().add_modifier(Modifier::DIM)
+                } else {
+                    app_context.style_chat()
+                };
+                ListItem::new(Line::styled(topic_summary(topic), style))
+            })
+            .collect::<Vec<_>>();
+        self.topics.draw(
+            frame,
+            area,
+            Constraint::Percentage(60),
+            "Topics",
+            items,
+            app_context,
+        );
+    }
+}
+
+/// Describe a topic in a single line, with its name, its number of unread
+/// messages and whether it is closed.
+///
+/// # Arguments
+/// * `topic` - The topic.
+///
+/// # Returns
+/// * `String` - The description of the topic.
+pub fn topic_summary(topic: &TdForumTopic) -> String {
+    let mut summary = topic.name.clone();
+    if topic.unread_count > 0 {
+        summary.push_str(&format!(" ({})", topic.unread_count));
+    }
+    if topic.is_closed {
+        summary.push_str(" 🔒");
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::forum_topics::{topic_summary, ForumTopics, ALL_TOPICS_THREAD_ID},
+        tg::td_enums::TdForumTopic,
+    };
+
+    fn topic(
+        message_thread_id: i64,
+        name: &str,
+        unread_count: i32,
+        is_closed: bool,
+    ) -> TdForumTopic {
+        TdForumTopic {
+            message_thread_id,
+            name: name.to_string(),
+            unread_count,
+            is_closed,
+        }
+    }
+
+    #[test]
+    fn test_topic_summary() {
+        assert_eq!(topic_summary(&topic(1, "General", 0, false)), "General");
+        assert_eq!(
+            topic_summary(&topic(2, "Releases", 12, false)),
+            "Releases (12)"
+        );
+        assert_eq!(topic_summary(&topic(3, "Archive", 0, true)), "Archive 🔒");
+        assert_eq!(
+            topic_summary(&topic(4, "Old bugs", 3, true)),
+            "Old bugs (3) 🔒"
+        );
+    }
+
+    #[test]
+    fn test_selected_topic() {
+        let mut topics = ForumTopics::new(vec![
+            topic(1048576, "General", 0, false),
+            topic(42, "Releases", 12, false),
+        ]);
+        assert_eq!(topics.selected(), Some(ALL_TOPICS_THREAD_ID));
+        topics.next();
+        assert_eq!(topics.selected(), Some(1048576));
+        topics.next();
+        assert_eq!(topics.selected(), Some(42));
+        topics.next();
+        assert_eq!(topics.selected(), Some(ALL_TOPICS_THREAD_ID));
+        topics.previous();
+        assert_eq!(topics.selected(), Some(42));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::app_context::AppContext;
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+/// Get the block of a popup, with its borders and its title.
+///
+/// # Arguments
+/// * `title` - The title of the popup.
+/// * `app_context` - The application context, used for the styles.
+///
+/// # Returns
+/// * `Block` - The block of the popup.
+fn popup_block<'a>(title: impl Into<Line<'a>>, app_context: &AppContext) -> Block<'a> {
+    Block::new()
+        .borders(Borders::ALL)
+        .border_style(app_context.style_border_component_focused())
+        .title(title.into())
+}
+
+/// Draw a list in a popup centered in the given area, as high as its items.
+///
+/// # Arguments
+/// * `frame` - The frame to draw on.
+/// * `area` - The area the popup is drawn over.
+/// * `width` - The width of the popup.
+/// * `title` - The title of the popup.
+/// * `items` - The items of the list.
+/// * `state` - The state of the list, with the highlighted item.
+/// * `app_context` - The application context, used for the styles.
+pub fn draw_list_popup<'a>(
+    frame: &mut ratatui::Frame<'_>,
+    area: Rect,
+    width: Constraint,
+    title: impl Into<Line<'a>>,
+    items: Vec<ListItem<'a>>,
+    state: &mut ListState,
+    app_context: &AppContext,
+) {
+    let height = items.iter().map(ListItem::height).sum::<usize>().max(1) as u16;
+    let [popup_area] = Layout::vertical([Constraint::Length(height + 2)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [popup_area] = Layout::horizontal([width])
+        .flex(Flex::Center)
+        .areas(popup_area);
+
+    let list = List::new(items)
+        .block(popup_block(title, app_context))
+        .style(app_context.style_chat())
+        .highlight_style(app_context.style_item_selected());
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, state);
+}
+
+/// Draw a text in a popup centered in the given area, the lines are wrapped
+/// and the popup is as high as the wrapped text.
+///
+/// # Arguments
+/// * `frame` - The frame to draw on.
+/// * `area` - The area the popup is drawn over.
+/// * `width` - The width of the popup.
+/// * `title` - The title of the popup.
+/// * `text` - The lines of the text.
+/// * `app_context` - The application context, used for the styles.
+pub fn draw_text_popup<'a>(
+    frame: &mut ratatui::Frame<'_>,
+    area: Rect,
+    width: Constraint,
+    title: impl Into<Line<'a>>,
+    text: Vec<Line<'a>>,
+    app_context: &AppContext,
+) {
+    let [popup_area] = Layout::horizontal([width]).flex(Flex::Center).areas(area);
+    // One more line is left every line of the width of the popup.
+    let inner_width = popup_area.width.saturating_sub(2).max(1) as usize;
+    let height = text
+        .iter()
+        .map(|line| line.width() / inner_width + 1)
+        .sum::<usize>() as u16;
+    let [popup_area] = Layout::vertical([Constraint::Length(height + 2)])
+        .flex(Flex::Center)
+        .areas(popup_area);
+
+    let paragraph = Paragraph::new(text)
+        .block(popup_block(title, app_context))
+        .style(app_context.style_chat())
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
+
+/// `ListPopup` holds the items of a panel drawn as a list over the
+/// `ChatWindow`, with the highlighted one.
+/// The highlight wraps around at both ends of the list.
+pub struct ListPopup<T> {
+    /// The items of the list.
+    items: Vec<T>,
+    /// The state of the list.
+    state: ListState,
+}
+/// Implementation of the `ListPopup` struct.
+impl<T> ListPopup<T> {
+    /// Create a new instance of the `ListPopup` struct, the first item is
+    /// highlighted.
+    ///
+    /// # Arguments
+    /// * `items` - The items of the list.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `ListPopup` struct.
+    pub fn new(items: Vec<T>) -> Self {
+        let mut state = ListState::default();
+        if !items.is_empty() {
+            state.select(Some(0));
+        }
+        ListPopup { items, state }
+    }
+
+    /// Get the items of the list.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Get the highlighted item.
+    pub fn selected(&self) -> Option<&T> {
+        self.state.selected().and_then(|i| self.items.get(i))
+    }
+
+    /// Remove the first item that matches from the list, the highlight stays
+    /// on the same item, or moves to the one that takes the place of the
+    /// removed one.
+    ///
+    /// # Arguments
+    /// * `f` - Whether an item is the one to remove.
+    ///
+    /// # Returns
+    /// * `Option<T>` - The removed item, `None` when no item matches.
+    pub fn remove(&mut self, f: impl Fn(&T) -> bool) -> Option<T> {
+        let i = self.items.iter().position(f)?;
+        let item = self.items.remove(i);
+        let selected = self
+            .state
+            .selected()
+            .filter(|_| !self.items.is_empty())
+            .map(|s| {
+                if s > i {
+                    s - 1
+                } else {
+                    s.min(self.items.len() - 1)
+                }
+            });
+        self.state.select(selected);
+        Some(item)
+    }
+
+    /// Highlight the next item.
+    pub fn next(&mut self) {
+        if !self.items.is_empty() {
+            let i = self
+                .state
+                .selected()
+                .map_or(0, |i| (i + 1) % self.items.len());
+            self.state.select(Some(i));
+        }
+    }
+
+    /// Highlight the previous item.
+    pub fn previous(&mut self) {
+        if !self.items.is_empty() {
+            let i = self
+                .state
+                .selected()
+                .map_or(0, |i| (i + self.items.len() - 1) % self.items.len());
+            self.state.select(Some(i));
+        }
+    }
+
+    /// Draw the `ListPopup` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `width` - The width of the popup.
+    /// * `title` - The title of the popup.
+    /// * `items` - How the items are shown, or what is shown in their place
+    ///   when the list is empty.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw<'a>(
+        &mut self,
+        frame: &mut ratatui::Frame<'_>,
+        area: Rect,
+        width: Constraint,
+        title: impl Into<Line<'a>>,
+        items: Vec<ListItem<'a>>,
+        app_context: &AppContext,
+    ) {
+        draw_list_popup(
+            frame,
+            area,
+            width,
+            title,
+            items,
+            &mut self.state,
+            app_context,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::list_popup::ListPopup;
+
+    #[test]
+    fn test_list_popup_wraps_around() {
+        let mut popup = ListPopup::new(vec![1, 2, 3]);
+        assert_eq!(popup.selected(), Some(&1));
+        popup.previous();
+        assert_eq!(popup.selected(), Some(&3));
+        popup.next();
+        assert_eq!(popup.selected(), Some(&1));
+        popup.next();
+        assert_eq!(popup.selected(), Some(&2));
+    }
+
+    #[test]
+    fn test_list_popup_empty() {
+        let mut popup = ListPopup::<i32>::new(vec![]);
+        popup.next();
+        popup.previous();
+        assert_eq!(popup.selected(), None);
+        assert_eq!(popup.remove(|_| true), None);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{app_context::AppContext, components::list_popup::draw_text_popup};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+};
+
+/// `LocationPanel` is a panel that shows the location of a message with the
+/// links to open it on a map, it is drawn over the `ChatWindow`.
+pub struct LocationPanel {
+    /// The lines of the panel.
+    lines: Vec<String>,
+}
+/// Implementation of the `LocationPanel` struct.
+impl LocationPanel {
+    /// Create a new instance of the `LocationPanel` struct.
+    ///
+    /// # Arguments
+    /// * `lines` - The coordinates, the status and the map links.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `LocationPanel` struct.
+    pub fn new(lines: Vec<String>) -> Self {
+        LocationPanel { lines }
+    }
+
+    /// Draw the `LocationPanel` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let text = self
+            .lines
+            .iter()
+            .map(|line| Line::from(line.clone()))
+            .collect::<Vec<Line>>();
+        draw_text_popup(
+            frame,
+            area,
+            Constraint::Percentage(90),
+            "Location",
+            text,
+            app_context,
+        );
+    }
+}
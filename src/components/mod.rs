@@ -1,15 +1,38 @@
 pub const SMALL_AREA_WIDTH: u16 = 100;
 pub const SMALL_AREA_HEIGHT: u16 = 20;
+pub const MOBILE_LAYOUT_WIDTH: u16 = 60;
 pub const MAX_CHAT_LIST_SIZE: u16 = 25;
 pub const MIN_CHAT_LIST_SIZE: u16 = 10;
 pub const MAX_PROMPT_SIZE: u16 = 20;
 pub const MIN_PROMPT_SIZE: u16 = 3;
 
+pub mod action_audit;
+pub mod active_sessions;
+pub mod avatar;
+pub mod bot_menu;
+pub mod call_history;
+pub mod chat_activity;
 pub mod chat_list_window;
+pub mod chat_permissions;
+pub mod chat_polls;
 pub mod chat_window;
 pub mod component_traits;
+pub mod confirm_popup;
 pub mod core_window;
+pub mod edit_history;
+pub mod emoji_shortcodes;
+pub mod formatting_preview;
+pub mod forum_topics;
+pub mod list_popup;
+pub mod location_panel;
 pub mod prompt_window;
+pub mod quiz_explanation;
+pub mod reaction_picker;
 pub mod reply_message;
+pub mod scheduled_messages;
+pub mod search_bar;
+pub mod search_results;
+pub mod send_as_picker;
 pub mod status_bar;
 pub mod title_bar;
+pub mod unread_dashboard;
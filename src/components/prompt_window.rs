@@ -2,9 +2,12 @@ use crate::{
     action::{Action, Modifiers},
     app_context::AppContext,
     component_name::ComponentName,
-    components::component_traits::{Component, HandleFocus},
+    components::{
+        component_traits::{Component, HandleFocus},
+        emoji_shortcodes::{expand_shortcodes, shortcode_at_cursor},
+    },
     event::Event,
-    tg::td_enums::TdMessageReplyToMessage,
+    tg::td_enums::{TdMessageReplyToMessage, TdParseMode},
 };
 use arboard::Clipboard;
 use crossterm::event::KeyCode;
@@ -89,6 +92,10 @@ struct Input {
     /// The mode of the input.
     /// It is used to keep track of the mode of the input.
     mode: Mode,
+    /// The timestamp, in seconds, of the last change of the text.
+    /// It is used to clear the text abandoned for the
+    /// `compose_auto_clear_timeout`.
+    last_edit: i32,
 }
 /// Implement the `Input` struct.
 impl Input {
@@ -426,6 +433,77 @@ impl Input {
             }
         }
     }
+    /// Clear the text, keeping the message being replied to or edited.
+    fn clear_text(&mut self) {
+        self.text = vec![vec![]];
+        self.set_prompt_size_to_one_focused();
+    }
+    /// Discard the message being replied to or edited, the text of an edit
+    /// is the edited message so it is cleared too.
+    ///
+    /// # Returns
+    /// * `bool` - Whether there was a reply or an edit to discard.
+    fn discard_reply(&mut self) -> bool {
+        match self.mode {
+            Mode::Normal => return false,
+            Mode::Edit(_) => self.clear_text(),
+            Mode::Reply(_) => {}
+        }
+        self.mode = Mode::Normal;
+        true
+    }
+    /// Get the text of the current line before the cursor.
+    fn text_before_cursor(&self) -> String {
+        self.text[self.cursor.1][..self.cursor.0]
+            .iter()
+            .map(|cell| cell.c)
+            .collect()
+    }
+    /// Insert a character, expanding the shortcode it completes before the
+    /// cursor to its emoji.
+    /// The closing colon of a shortcode is replaced by the emoji, while a
+    /// space is kept after it.
+    ///
+    /// # Arguments
+    /// * `c` - The character to insert.
+    fn expand_shortcode(&mut self, c: char) {
+        match shortcode_at_cursor(&self.text_before_cursor(), c) {
+            Some((replaced, emoji)) => {
+                for _ in 0..replaced {
+                    self.backspace();
+                }
+                self.paste(emoji.to_string());
+                if c == ' ' {
+                    self.insert(c);
+                }
+            }
+            None => self.insert(c),
+        }
+    }
+    /// Take the text abandoned for the timeout, the input is cleared and the
+    /// message being replied to is discarded.
+    /// The text of an edit is the edited message, so it is never taken.
+    ///
+    /// # Arguments
+    /// * `now` - The current timestamp, in seconds.
+    /// * `timeout` - The inactivity, in seconds, after which the text is
+    ///   abandoned, `0` to never take it.
+    ///
+    /// # Returns
+    /// * `Option<String>` - The abandoned text, to be saved as a draft.
+    fn take_abandoned(&mut self, now: i32, timeout: i32) -> Option<String> {
+        if timeout <= 0
+            || matches!(self.mode, Mode::Edit(_))
+            || self.text.iter().all(|line| line.is_empty())
+            || now - self.last_edit < timeout
+        {
+            return None;
+        }
+        let text = self.text_to_string();
+        self.clear_text();
+        self.mode = Mode::Normal;
+        Some(text)
+    }
     /// Edit a message.
     ///
     /// # Arguments
@@ -451,22 +529,27 @@ impl Input {
 
     /// Send a message.
     /// The message is sent to the main event loop for processing.
+    /// When the emoji shortcodes are enabled, the ones that were not expanded
+    /// while typing, e.g. because they were pasted, are expanded.
     ///
     /// # Arguments
     /// * `app_context` - An Arc wrapped AppContext struct.
     fn send_message(&mut self, app_context: Arc<AppContext>) {
+        let text = if app_context.emoji_shortcodes() {
+            expand_shortcodes(&self.text_to_string())
+        } else {
+            self.text_to_string()
+        };
         if let Some(event_tx) = app_context.tg_context().event_tx().as_ref() {
             match self.mode {
                 Mode::Normal => {
-                    event_tx
-                        .send(Event::SendMessage(self.text_to_string(), None))
-                        .unwrap();
+                    event_tx.send(Event::SendMessage(text, None)).unwrap();
                     self.text = vec![vec![]];
                     self.set_prompt_size_to_one_focused();
                 }
                 Mode::Edit(message_id) => {
                     event_tx
-                        .send(Event::SendMessageEdited(message_id, self.text_to_string()))
+                        .send(Event::SendMessageEdited(message_id, text))
                         .unwrap();
                     self.text = vec![vec![]];
                     self.set_prompt_size_to_one_focused();
@@ -475,7 +558,7 @@ impl Input {
                 Mode::Reply(message_id) => {
                     event_tx
                         .send(Event::SendMessage(
-                            self.text_to_string(),
+                            text,
                             Some(TdMessageReplyToMessage {
                                 chat_id: 0, // This must be  0 and not `app_context.tg_context().open_chat_id()` because the tdlib (maybe from the version 1.8.29 or before)  is able to know the chat id from the message_id; it will infer the chat id from the message_id.
                                 message_id,
@@ -515,6 +598,7 @@ impl Default for Input {
             correct_prompt_size: 0,
             is_restored: true,
             mode: Mode::Normal,
+            last_edit: 0,
         }
     }
 }
@@ -534,6 +618,12 @@ pub struct PromptWindow {
     focused_keys: Vec<Event>,
     /// The current input of the `PromptWindow`.
     input: Input,
+    /// The paste that exceeds the maximum paste size and is waiting for
+    /// confirmation before being inserted into the input.
+    pending_paste: Option<String>,
+    /// Whether the formatting of the input is previewed, the preview is
+    /// hidden on the next key press.
+    formatting_previewed: bool,
 }
 /// Implement the `PromptWindow` struct.
 impl PromptWindow {
@@ -554,6 +644,8 @@ impl PromptWindow {
         );
 
         let input = Input::default();
+        let pending_paste = None;
+        let formatting_previewed = false;
 
         PromptWindow {
             app_context,
@@ -562,6 +654,8 @@ impl PromptWindow {
             focused,
             focused_keys,
             input,
+            pending_paste,
+            formatting_previewed,
         }
     }
     /// Set the name of the `PromptWindow`.
@@ -586,6 +680,126 @@ impl PromptWindow {
             self.input.area_input = area_input;
         }
     }
+    /// Paste text into the input of the `PromptWindow`.
+    /// If the text exceeds the maximum paste size, it is kept aside until the
+    /// user confirms it.
+    ///
+    /// # Arguments
+    /// * `text` - The text to paste.
+    fn paste(&mut self, text: String) {
+        let max_paste_size = self.app_context.app_config().max_paste_size;
+        if exceeds_max_paste_size(&text, max_paste_size) {
+            self.pending_paste = Some(text);
+        } else {
+            self.input.unselect_all();
+            self.input.paste(text);
+        }
+    }
+    /// Save the text abandoned for the `compose_auto_clear_timeout` as the
+    /// draft of the open chat and clear the input.
+    fn auto_clear(&mut self) {
+        let now = chrono::Utc::now().timestamp() as i32;
+        let timeout = self.app_context.app_config().compose_auto_clear_timeout;
+        let reply = matches!(self.input.mode, Mode::Reply(_));
+        let Some(text) = self.input.take_abandoned(now, timeout) else {
+            return;
+        };
+        let tg_context = self.app_context.tg_context();
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx
+                .send(Event::SaveDraft(tg_context.open_chat_id(), text))
+                .unwrap();
+        }
+        if let Some(tx) = self.action_tx.as_ref() {
+            if reply {
+                tx.send(Action::HideChatWindowReply).unwrap();
+            }
+            tx.send(Action::StatusMessage(
+                "The message has been saved as a draft".to_string(),
+            ))
+            .unwrap();
+        }
+    }
+    /// Queue the text of the prompt as the draft of the open chat, it is
+    /// flushed before the TDLib client is closed.
+    fn flush_draft(&mut self) {
+        let text = self.input.text_to_string();
+        let tg_context = self.app_context.tg_context();
+        let chat_id = tg_context.open_chat_id();
+        if text.trim().is_empty() || chat_id == 0 {
+            return;
+        }
+        if let Some(event_tx) = tg_context.event_tx().as_ref() {
+            event_tx.send(Event::SaveDraft(chat_id, text)).unwrap();
+        }
+    }
+    /// Handle a key pressed while a paste is waiting for confirmation.
+    /// The `Enter` key inserts the paste, any other key discards it.
+    ///
+    /// # Arguments
+    /// * `key_code` - The key code of the pressed key.
+    fn confirm_pending_paste(&mut self, key_code: KeyCode) {
+        if let Some(text) = self.pending_paste.take() {
+            if key_code == KeyCode::Enter {
+                self.input.unselect_all();
+                self.input.paste(text);
+            }
+        }
+    }
+    /// Ask to parse the formatting of the input, to preview how it is
+    /// rendered before it is sent. The input is kept as it is.
+    ///
+    /// # Arguments
+    /// * `parse_mode` - The syntax the input is written with.
+    fn preview_formatting(&mut self, parse_mode: TdParseMode) {
+        let text = self.input.text_to_string();
+        if text.trim().is_empty() {
+            return;
+        }
+        if let Some(event_tx) = self.app_context.tg_context().event_tx().as_ref() {
+            event_tx
+                .send(Event::PreviewFormatting(text, parse_mode))
+                .unwrap();
+            self.formatting_previewed = true;
+        }
+    }
+}
+
+/// Check whether a paste exceeds the maximum paste size.
+///
+/// # Arguments
+/// * `text` - The pasted text.
+/// * `max_paste_size` - The maximum number of characters that can be pasted
+///   without confirmation, 0 disables the guard.
+///
+/// # Returns
+/// * `bool` - Whether the paste needs to be confirmed.
+/// The text inserted when the prompt is indented.
+const INDENT: &str = "    ";
+
+/// Get the text that completes the `@`-mention before the cursor.
+///
+/// # Arguments
+/// * `before_cursor` - The text of the line before the cursor.
+/// * `usernames` - The sorted usernames that can be mentioned.
+///
+/// # Returns
+/// * `Option<String>` - The rest of the first matching username followed by
+///   a space, or `None` when the cursor is not after a mention or nothing
+///   matches.
+fn complete_mention(before_cursor: &str, usernames: &[String]) -> Option<String> {
+    let word = before_cursor.rsplit(char::is_whitespace).next()?;
+    let prefix = word.strip_prefix('@')?.to_lowercase();
+    usernames
+        .iter()
+        .find(|username| {
+            username.len() > prefix.len() && username.to_lowercase().starts_with(&prefix)
+        })
+        .map(|username| format!("{} ", &username[prefix.len()..]))
+}
+
+fn exceeds_max_paste_size(text: &str, max_paste_size: usize) -> bool {
+    max_paste_size != 0 && text.chars().count() > max_paste_size
 }
 
 /// Implement the `HandleFocus` trait for the `PromptWindow` struct.
@@ -600,6 +814,7 @@ impl HandleFocus for PromptWindow {
         self.focused = false;
         self.input.mode = Mode::Normal;
         self.input.text = vec![vec![]];
+        self.pending_paste = None;
     }
 }
 
@@ -612,7 +827,21 @@ impl Component for PromptWindow {
     }
 
     fn update(&mut self, action: Action) {
+        if matches!(action, Action::Key(..) | Action::Paste(_)) {
+            self.input.last_edit = chrono::Utc::now().timestamp() as i32;
+            if self.formatting_previewed {
+                self.formatting_previewed = false;
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(Action::HideFormattingPreview).unwrap();
+                }
+            }
+        }
         match action {
+            Action::Render => self.auto_clear(),
+            Action::Quit => self.flush_draft(),
+            Action::Key(key_code, _) if self.pending_paste.is_some() => {
+                self.confirm_pending_paste(key_code);
+            }
             Action::Key(key_code, modifiers) => match (key_code, modifiers) {
                 (KeyCode::Home, ..)
                 | (
@@ -705,7 +934,7 @@ impl Component for PromptWindow {
                 (KeyCode::Char('v'), Modifiers { control: true, .. }) => {
                     if let Ok(mut clipboard) = Clipboard::new() {
                         if let Ok(text) = clipboard.get_text() {
-                            self.input.paste(text);
+                            self.paste(text);
                         }
                     }
                 }
@@ -744,7 +973,11 @@ impl Component for PromptWindow {
                     },
                 ) => {
                     self.input.unselect_all();
-                    self.input.insert(c);
+                    if self.app_context.emoji_shortcodes() {
+                        self.input.expand_shortcode(c);
+                    } else {
+                        self.input.insert(c);
+                    }
                 }
 
                 (KeyCode::Backspace, ..) => {
@@ -784,8 +1017,36 @@ impl Component for PromptWindow {
                 _ => {}
             },
             Action::Paste(text) => {
+                self.paste(text);
+            }
+            Action::PromptIndent => {
                 self.input.unselect_all();
-                self.input.paste(text);
+                self.input.paste(INDENT.to_string());
+            }
+            Action::PromptCompleteMention => {
+                let usernames = self.app_context.tg_context().open_chat_usernames();
+                if let Some(completion) =
+                    complete_mention(&self.input.text_before_cursor(), &usernames)
+                {
+                    self.input.unselect_all();
+                    self.input.paste(completion);
+                }
+            }
+            Action::PromptClearText => {
+                self.input.clear_text();
+            }
+            Action::PromptPreviewFormatting(parse_mode) => {
+                self.preview_formatting(parse_mode);
+            }
+            Action::PromptDiscardReply => {
+                let action = if self.input.discard_reply() {
+                    Action::HideChatWindowReply
+                } else {
+                    Action::UnfocusComponent
+                };
+                if let Some(tx) = self.action_tx.as_ref() {
+                    tx.send(action).unwrap();
+                }
             }
             Action::EditMessage(message_id, message) => {
                 self.input.edit_message(message_id, message);
@@ -831,28 +1092,63 @@ impl Component for PromptWindow {
             })
             .collect::<Vec<Line>>();
 
+        let chat_id = self.app_context.tg_context().open_chat_id();
         let (text, style_text, style_border_focused) = if self.focused {
             self.input.restore_prompt_size();
-            (
-                text,
-                self.app_context.style_prompt(),
-                self.app_context.style_border_component_focused(),
-            )
+            // The placeholder of the open chat, if any, is shown while
+            // nothing is typed.
+            let placeholder = if self.input.text.iter().all(|line| line.is_empty()) {
+                self.app_context
+                    .tg_context()
+                    .compose_placeholders()
+                    .get(chat_id)
+                    .map(|text| text.to_string())
+            } else {
+                None
+            };
+            match placeholder {
+                Some(placeholder) => (
+                    vec![Line::styled(
+                        placeholder,
+                        self.app_context.style_prompt_message_preview_text(),
+                    )],
+                    self.app_context.style_prompt(),
+                    self.app_context.style_border_component_focused(),
+                ),
+                None => (
+                    text,
+                    self.app_context.style_prompt(),
+                    self.app_context.style_border_component_focused(),
+                ),
+            }
         } else {
             self.input.set_prompt_size_to_one_unfocused();
+            let default = format!(
+                "Press {} to send a message",
+                self.focused_keys
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" or ")
+            );
             (
-                vec![Line::from(format!(
-                    "Press {} to send a message",
-                    self.focused_keys
-                        .iter()
-                        .map(|e| e.to_string())
-                        .collect::<Vec<String>>()
-                        .join(" or ")
-                ))],
+                vec![Line::from(
+                    self.app_context
+                        .tg_context()
+                        .compose_placeholders()
+                        .resolve(chat_id, &default),
+                )],
                 self.app_context.style_prompt_message_preview_text(),
                 self.app_context.style_prompt(),
             )
         };
+        let text = match self.pending_paste.as_ref() {
+            Some(pending_paste) => vec![Line::from(format!(
+                "Paste {} characters? Press enter to confirm or any other key to discard",
+                pending_paste.chars().count()
+            ))],
+            None => text,
+        };
 
         let block = Block::new()
             .border_set(collapsed_top_and_left_border_set)
@@ -873,3 +1169,84 @@ impl Component for PromptWindow {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::components::prompt_window::{
+        complete_mention, exceeds_max_paste_size, Input, InputCell, Mode,
+    };
+
+    fn input(text: &str, last_edit: i32) -> Input {
+        let mut input = Input {
+            last_edit,
+            ..Input::default()
+        };
+        input.text = vec![text
+            .chars()
+            .map(|c| InputCell { c, selected: false })
+            .collect()];
+        input
+    }
+
+    #[test]
+    fn test_exceeds_max_paste_size_triggers_guard() {
+        assert!(exceeds_max_paste_size("hello world", 5));
+        assert!(exceeds_max_paste_size("ààààà", 4));
+    }
+
+    #[test]
+    fn test_exceeds_max_paste_size_within_limit() {
+        assert!(!exceeds_max_paste_size("hello", 5));
+        assert!(!exceeds_max_paste_size("", 5));
+    }
+
+    #[test]
+    fn test_exceeds_max_paste_size_disabled() {
+        assert!(!exceeds_max_paste_size(&"a".repeat(100_000), 0));
+    }
+
+    #[test]
+    fn test_complete_mention() {
+        let usernames = vec!["durov".to_string(), "rustlang".to_string()];
+        assert_eq!(
+            complete_mention("hi @Du", &usernames),
+            Some("rov ".to_string())
+        );
+        assert_eq!(
+            complete_mention("@", &usernames),
+            Some("durov ".to_string())
+        );
+        assert_eq!(complete_mention("hi @durov", &usernames), None);
+        assert_eq!(complete_mention("hi du", &usernames), None);
+        assert_eq!(complete_mention("hi @go", &usernames), None);
+    }
+
+    #[test]
+    fn test_take_abandoned_after_timeout() {
+        let mut input = input("see you at", 1000);
+        assert_eq!(input.take_abandoned(1059, 60), None);
+        assert_eq!(
+            input.take_abandoned(1060, 60),
+            Some("see you at".to_string())
+        );
+        assert!(input.text.iter().all(|line| line.is_empty()));
+        // The cleared input is not saved again.
+        assert_eq!(input.take_abandoned(2000, 60), None);
+
+        let mut input = input("ok", 1000);
+        input.mode = Mode::Reply(42);
+        assert_eq!(input.take_abandoned(1060, 60), Some("ok".to_string()));
+        assert!(matches!(input.mode, Mode::Normal));
+    }
+
+    #[test]
+    fn test_take_abandoned_kept() {
+        // The auto clear is disabled.
+        assert_eq!(input("see you at", 1000).take_abandoned(100_000, 0), None);
+        // The text of an edit is the edited message.
+        let mut input = input("fixed typo", 1000);
+        input.mode = Mode::Edit(42);
+        assert_eq!(input.take_abandoned(1060, 60), None);
+        assert_eq!(input.text_to_string(), "fixed typo");
+    }
+}
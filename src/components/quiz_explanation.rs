@@ -0,0 +1,47 @@
+use crate::{app_context::AppContext, components::list_popup::draw_text_popup};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+};
+
+/// `QuizExplanation` is a panel that shows the explanation of an answered
+/// quiz, it is drawn over the `ChatWindow`.
+pub struct QuizExplanation {
+    /// The explanation of the quiz.
+    explanation: String,
+}
+/// Implementation of the `QuizExplanation` struct.
+impl QuizExplanation {
+    /// Create a new instance of the `QuizExplanation` struct.
+    ///
+    /// # Arguments
+    /// * `explanation` - The explanation of the quiz.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `QuizExplanation` struct.
+    pub fn new(explanation: String) -> Self {
+        QuizExplanation { explanation }
+    }
+
+    /// Draw the `QuizExplanation` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let text = self
+            .explanation
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect::<Vec<Line>>();
+        draw_text_popup(
+            frame,
+            area,
+            Constraint::Percentage(60),
+            "Explanation",
+            text,
+            app_context,
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{
+    app_context::AppContext, components::list_popup::ListPopup,
+    tg::message_entry::MessageReactionEntry,
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::ListItem,
+};
+
+/// `ReactionPickerEntry` is a reaction that can be chosen in the
+/// `ReactionPicker`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReactionPickerEntry {
+    /// The emoji of the reaction.
+    pub emoji: String,
+    /// Whether the current user already reacted with this emoji.
+    pub is_chosen: bool,
+}
+
+/// `ReactionPicker` is a small menu that shows the reactions available for a
+/// message, it is drawn over the `ChatWindow`.
+pub struct ReactionPicker {
+    /// The identifier of the message the reaction is added to.
+    message_id: i64,
+    /// The reactions that can be chosen.
+    entries: ListPopup<ReactionPickerEntry>,
+}
+/// Implementation of the `ReactionPicker` struct.
+impl ReactionPicker {
+    /// Create a new instance of the `ReactionPicker` struct.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the message the reaction is added
+    ///   to.
+    /// * `available` - The emoji of the reactions available for the message.
+    /// * `reactions` - The reactions already added to the message, used to
+    ///   mark the ones given by the current user.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `ReactionPicker` struct.
+    pub fn new(
+        message_id: i64,
+        available: Vec<String>,
+        reactions: &[MessageReactionEntry],
+    ) -> Self {
+        let entries = available
+            .into_iter()
+            .map(|emoji| ReactionPickerEntry {
+                is_chosen: reactions.iter().any(|r| r.is_chosen && r.emoji == emoji),
+                emoji,
+            })
+            .collect::<Vec<ReactionPickerEntry>>();
+        ReactionPicker {
+            message_id,
+            entries: ListPopup::new(entries),
+        }
+    }
+
+    /// Get the identifier of the message the reaction is added to.
+    pub fn message_id(&self) -> i64 {
+        self.message_id
+    }
+
+    /// Get the reactions that can be chosen.
+    pub fn entries(&self) -> &[ReactionPickerEntry] {
+        self.entries.items()
+    }
+
+    /// Get the highlighted reaction.
+    pub fn selected(&self) -> Option<&ReactionPickerEntry> {
+        self.entries.selected()
+    }
+
+    /// Highlight the next reaction.
+    pub fn next(&mut self) {
+        self.entries.next();
+    }
+
+    /// Highlight the previous reaction.
+    pub fn previous(&mut self) {
+        self.entries.previous();
+    }
+
+    /// Draw the `ReactionPicker` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let items = self
+            .entries
+            .items()
+            .iter()
+            .map(|entry| {
+                ListItem::new(Line::from(vec![
+                    Span::raw(entry.emoji.clone()),
+                    Span::raw(" "),
+                    Span::raw(if entry.is_chosen { "✓" } else { "" }),
+                ]))
+                .style(if entry.is_chosen {
+                    app_context.style_chat().add_modifier(Modifier::BOLD)
+                } else {
+                    app_context.style_chat()
+                })
+            })
+            .collect();
+        self.entries.draw(
+            frame,
+            area,
+            Constraint::Length(16),
+            "React",
+            items,
+            app_context,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::reaction_picker::{ReactionPicker, ReactionPickerEntry};
+    use crate::tg::message_entry::MessageReactionEntry;
+
+    fn reaction(emoji: &str, is_chosen: bool) -> MessageReactionEntry {
+        MessageReactionEntry {
+            emoji: emoji.to_string(),
+            total_count: 1,
+            is_chosen,
+        }
+    }
+
+    #[test]
+    fn test_reaction_picker_marks_chosen() {
+        let picker = ReactionPicker::new(
+            1,
+            vec!["👍".to_string(), "❤️".to_string(), "🔥".to_string()],
+            &[reaction("❤️", true), reaction("🔥", false)],
+        );
+        assert_eq!(
+            picker.entries(),
+            &[
+                ReactionPickerEntry {
+                    emoji: "👍".to_string(),
+                    is_chosen: false,
+                },
+                ReactionPickerEntry {
+                    emoji: "❤️".to_string(),
+                    is_chosen: true,
+                },
+                ReactionPickerEntry {
+                    emoji: "🔥".to_string(),
+                    is_chosen: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reaction_picker_navigation() {
+        let mut picker = ReactionPicker::new(1, vec!["👍".to_string(), "❤️".to_string()], &[]);
+        assert_eq!(picker.selected().unwrap().emoji, "👍");
+        picker.next();
+        assert_eq!(picker.selected().unwrap().emoji, "❤️");
+        picker.next();
+        assert_eq!(picker.selected().unwrap().emoji, "👍");
+        picker.previous();
+        assert_eq!(picker.selected().unwrap().emoji, "❤️");
+    }
+
+    #[test]
+    fn test_reaction_picker_empty() {
+        let mut picker = ReactionPicker::new(1, vec![], &[]);
+        picker.next();
+        assert!(picker.selected().is_none());
+    }
+}
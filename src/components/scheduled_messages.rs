@@ -0,0 +1,169 @@
+use crate::{
+    app_context::AppContext,
+    components::list_popup::ListPopup,
+    tg::message_entry::DateTimeEntry,
+    tg::td_enums::{TdScheduledMessage, TdSchedulingState},
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier},
+    text::Line,
+    widgets::ListItem,
+};
+
+/// Describe a scheduled message in a single line, with when it is sent and
+/// its text.
+///
+/// # Arguments
+/// * `message` - The scheduled message.
+///
+/// # Returns
+/// * `String` - The description of the scheduled message.
+pub fn scheduled_summary(message: &TdScheduledMessage) -> String {
+    let send_time = match message.state {
+        TdSchedulingState::SendAtDate(send_date) => DateTimeEntry::convert_time(send_date),
+        TdSchedulingState::SendWhenOnline => "when online".to_string(),
+    };
+    format!("{} · {}", send_time, message.text.replace('\n', " "))
+}
+
+/// `ScheduledMessages` is a panel that lists the scheduled messages of a
+/// chat, it is drawn over the `ChatWindow` and the highlighted message can be
+/// sent immediately.
+pub struct ScheduledMessages {
+    /// The scheduled messages of the chat, the next to be sent first.
+    messages: ListPopup<TdScheduledMessage>,
+}
+/// Implementation of the `ScheduledMessages` struct.
+impl ScheduledMessages {
+    /// Create a new instance of the `ScheduledMessages` struct.
+    ///
+    /// # Arguments
+    /// * `messages` - The scheduled messages of the chat.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `ScheduledMessages` struct.
+    pub fn new(messages: Vec<TdScheduledMessage>) -> Self {
+        ScheduledMessages {
+            messages: ListPopup::new(messages),
+        }
+    }
+
+    /// Get the highlighted scheduled message.
+    pub fn selected(&self) -> Option<&TdScheduledMessage> {
+        self.messages.selected()
+    }
+
+    /// Remove a scheduled message from the list because it has been sent.
+    ///
+    /// # Arguments
+    /// * `message_id` - The identifier of the sent message.
+    ///
+    /// # Returns
+    /// * `Option<TdScheduledMessage>` - The removed message, `None` when it
+    ///   is not in the list.
+    pub fn remove(&mut self, message_id: i64) -> Option<TdScheduledMessage> {
+        self.messages.remove(|m| m.message_id == message_id)
+    }
+
+    /// Highlight the next scheduled message.
+    pub fn next(&mut self) {
+        self.messages.next();
+    }
+
+    /// Highlight the previous scheduled message.
+    pub fn previous(&mut self) {
+        self.messages.previous();
+    }
+
+    /// Draw the `ScheduledMessages` centered in the given area.
+    /// The messages whose send time has passed are highlighted.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let now = chrono::Utc::now().timestamp() as i32;
+        let items = if self.messages.items().is_empty() {
+            vec![ListItem::new(Line::from("No scheduled messages"))]
+        } else {
+            self.messages
+                .items()
+                .iter()
+                .map(|message| {
+                    if message.is_past_due(now) {
+                        ListItem::new(Line::styled(
+                            format!("⚠ {}", scheduled_summary(message)),
+                            app_context
+                                .style_chat()
+                                .fg(Color::Red)
+                                .add_modifier(Modifier::BOLD),
+                        ))
+                    } else {
+                        ListItem::new(Line::from(scheduled_summary(message)))
+                    }
+                })
+                .collect()
+        };
+        self.messages.draw(
+            frame,
+            area,
+            Constraint::Percentage(80),
+            "Scheduled messages",
+            items,
+            app_context,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::scheduled_messages::{scheduled_summary, ScheduledMessages},
+        tg::td_enums::{TdScheduledMessage, TdSchedulingState},
+    };
+
+    fn scheduled(message_id: i64, text: &str, state: TdSchedulingState) -> TdScheduledMessage {
+        TdScheduledMessage {
+            message_id,
+            text: text.to_string(),
+            state,
+        }
+    }
+
+    #[test]
+    fn test_scheduled_summary_when_online() {
+        assert_eq!(
+            scheduled_summary(&scheduled(
+                1,
+                "See you\ntomorrow",
+                TdSchedulingState::SendWhenOnline
+            )),
+            "when online · See you tomorrow"
+        );
+    }
+
+    #[test]
+    fn test_send_now_removes_the_sent_message() {
+        let mut panel = ScheduledMessages::new(vec![
+            scheduled(1, "a", TdSchedulingState::SendWhenOnline),
+            scheduled(2, "b", TdSchedulingState::SendAtDate(0)),
+            scheduled(3, "c", TdSchedulingState::SendAtDate(100)),
+        ]);
+        panel.next();
+        // The highlighted message is the one sent.
+        let message_id = panel.selected().map(|m| m.message_id);
+        assert_eq!(message_id, Some(2));
+        // It stays in the panel until it has been sent.
+        assert_eq!(panel.remove(2).map(|m| m.text), Some("b".to_string()));
+        assert_eq!(panel.selected().map(|m| m.message_id), Some(3));
+        assert!(panel.remove(2).is_none());
+        // Removing a message above the highlight keeps the highlight on the
+        // same message.
+        assert_eq!(panel.remove(1).map(|m| m.message_id), Some(1));
+        assert_eq!(panel.selected().map(|m| m.message_id), Some(3));
+        assert_eq!(panel.remove(3).map(|m| m.message_id), Some(3));
+        assert!(panel.selected().is_none());
+    }
+}
@@ -0,0 +1,262 @@
+use crate::{app_context::AppContext, tg::td_enums::TdFoundMessage};
+use ratatui::{
+    layout::Rect,
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// `SearchBarResult` is an entry of the results of the `SearchBar`.
+pub enum SearchBarResult {
+    /// A chat whose title matches, with its identifier and its title.
+    Chat(i64, String),
+    /// A message that matches, found in any chat.
+    Message(TdFoundMessage),
+}
+
+/// Assemble the results of the `SearchBar`: the chats whose title contains
+/// the query, ignoring the case, followed by the messages found by Telegram.
+///
+/// # Arguments
+/// * `query` - The searched text.
+/// * `chats` - The identifiers and the titles of the chats of the chat list.
+/// * `messages` - The messages found for the query.
+///
+/// # Returns
+/// * `Vec<SearchBarResult>` - The chats first, then the messages, each in the
+///   given order.
+pub fn unified_results(
+    query: &str,
+    chats: &[(i64, String)],
+    messages: &[TdFoundMessage],
+) -> Vec<SearchBarResult> {
+    let query = query.to_lowercase();
+    chats
+        .iter()
+        .filter(|(_, title)| title.to_lowercase().contains(&query))
+        .map(|(chat_id, title)| SearchBarResult::Chat(*chat_id, title.clone()))
+        .chain(messages.iter().cloned().map(SearchBarResult::Message))
+        .collect()
+}
+
+/// `SearchBar` is a search input docked above the `ChatListWindow`, the
+/// chats and the messages matching the typed text are listed in place of the
+/// chat list.
+pub struct SearchBar {
+    /// The typed text.
+    query: String,
+    /// The messages found for the typed text.
+    messages: Vec<TdFoundMessage>,
+    /// The results of the search, updated on every draw.
+    results: Vec<SearchBarResult>,
+    /// The state of the list of the results.
+    state: ListState,
+}
+impl Default for SearchBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+/// Implementation of the `SearchBar` struct.
+impl SearchBar {
+    /// Create a new instance of the `SearchBar` struct.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `SearchBar` struct.
+    pub fn new() -> Self {
+        SearchBar {
+            query: String::new(),
+            messages: vec![],
+            results: vec![],
+            state: ListState::default(),
+        }
+    }
+
+    /// Get the typed text.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Type a character, the found messages belong to the previous text and
+    /// are dropped.
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.messages.clear();
+        self.state.select(None);
+    }
+
+    /// Delete the last typed character.
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.messages.clear();
+        self.state.select(None);
+    }
+
+    /// Set the messages found for a text, they are ignored when the text has
+    /// changed in the meantime.
+    ///
+    /// # Arguments
+    /// * `query` - The searched text.
+    /// * `messages` - The messages found for the text.
+    pub fn set_messages(&mut self, query: &str, messages: Vec<TdFoundMessage>) {
+        if query == self.query {
+            self.messages = messages;
+        }
+    }
+
+    /// Get the highlighted result.
+    pub fn selected(&self) -> Option<&SearchBarResult> {
+        self.state.selected().and_then(|i| self.results.get(i))
+    }
+
+    /// Highlight the next result.
+    pub fn next(&mut self) {
+        if !self.results.is_empty() {
+            let i = self
+                .state
+                .selected()
+                .map_or(0, |i| (i + 1) % self.results.len());
+            self.state.select(Some(i));
+        }
+    }
+
+    /// Highlight the previous result.
+    pub fn previous(&mut self) {
+        if !self.results.is_empty() {
+            let i = self
+                .state
+                .selected()
+                .map_or(0, |i| (i + self.results.len() - 1) % self.results.len());
+            self.state.select(Some(i));
+        }
+    }
+
+    /// Draw the typed text.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the input.
+    /// * `app_context` - The application context, used for the styles.
+    /// * `focused` - Whether the `ChatListWindow` is focused.
+    pub fn draw_input(
+        &self,
+        frame: &mut ratatui::Frame<'_>,
+        area: Rect,
+        app_context: &AppContext,
+        focused: bool,
+    ) {
+        let border_style = if focused {
+            app_context.style_border_component_focused()
+        } else {
+            app_context.style_chat_list()
+        };
+        let input = Paragraph::new(format!("🔍 {}", self.query))
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+                    .title("Search"),
+            )
+            .style(app_context.style_chat_list());
+        frame.render_widget(input, area);
+    }
+
+    /// Update the results with the chats of the chat list and draw them.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the chat list.
+    /// * `app_context` - The application context, used for the styles.
+    /// * `block` - The block of the chat list.
+    /// * `chats` - The identifiers and the titles of the chats of the chat
+    ///   list.
+    pub fn draw_results(
+        &mut self,
+        frame: &mut ratatui::Frame<'_>,
+        area: Rect,
+        app_context: &AppContext,
+        block: Block<'_>,
+        chats: &[(i64, String)],
+    ) {
+        self.results = unified_results(&self.query, chats, &self.messages);
+        if self
+            .state
+            .selected()
+            .is_some_and(|i| i >= self.results.len())
+        {
+            self.state.select(None);
+        }
+
+        let items = if self.results.is_empty() {
+            vec![ListItem::new(Line::from("No results"))]
+        } else {
+            self.results
+                .iter()
+                .map(|result| match result {
+                    SearchBarResult::Chat(_, title) => ListItem::new(Line::from(title.clone())),
+                    SearchBarResult::Message(message) => ListItem::new(Line::from(format!(
+                        "💬 {} · {}",
+                        message.chat_title,
+                        message.text.replace('\n', " ")
+                    ))),
+                })
+                .collect()
+        };
+        let list = List::new(items)
+            .block(block)
+            .style(app_context.style_chat_list())
+            .highlight_style(app_context.style_chat_list_item_selected());
+
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::search_bar::{unified_results, SearchBarResult},
+        tg::td_enums::TdFoundMessage,
+    };
+
+    fn found_message(chat_id: i64, message_id: i64, text: &str) -> TdFoundMessage {
+        TdFoundMessage {
+            chat_id,
+            message_id,
+            chat_title: format!("Chat {}", chat_id),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_unified_results_chats_then_messages() {
+        let chats = vec![
+            (1, "Rust Italia".to_string()),
+            (2, "Family".to_string()),
+            (3, "rustaceans".to_string()),
+        ];
+        let messages = vec![
+            found_message(2, 20, "Mom: rust on the bike"),
+            found_message(4, 40, "Bob: I love Rust"),
+        ];
+
+        assert_eq!(
+            unified_results("RUST", &chats, &messages),
+            vec![
+                SearchBarResult::Chat(1, "Rust Italia".to_string()),
+                SearchBarResult::Chat(3, "rustaceans".to_string()),
+                SearchBarResult::Message(found_message(2, 20, "Mom: rust on the bike")),
+                SearchBarResult::Message(found_message(4, 40, "Bob: I love Rust")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unified_results_without_matches() {
+        let chats = vec![(1, "Rust Italia".to_string())];
+        assert!(unified_results("go", &chats, &[]).is_empty());
+        assert_eq!(
+            unified_results("go", &chats, &[found_message(1, 10, "Ann: go")]),
+            vec![SearchBarResult::Message(found_message(1, 10, "Ann: go"))]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{app_context::AppContext, components::list_popup::draw_list_popup};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::{ListItem, ListState},
+};
+
+/// `SearchResults` is a panel that lists the messages found by a search, it
+/// is drawn over the `ChatWindow`.
+pub struct SearchResults {
+    /// The searched text, used as the title of the panel.
+    query: String,
+    /// The found messages, one line each, from the most recent one.
+    results: Vec<String>,
+}
+/// Implementation of the `SearchResults` struct.
+impl SearchResults {
+    /// Create a new instance of the `SearchResults` struct.
+    ///
+    /// # Arguments
+    /// * `query` - The searched text.
+    /// * `results` - The found messages, one line each.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `SearchResults` struct.
+    pub fn new(query: String, results: Vec<String>) -> Self {
+        SearchResults { query, results }
+    }
+
+    /// Draw the `SearchResults` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let items = if self.results.is_empty() {
+            vec![ListItem::new(Line::from("No messages found"))]
+        } else {
+            self.results
+                .iter()
+                .map(|result| ListItem::new(Line::from(result.clone())))
+                .collect()
+        };
+        draw_list_popup(
+            frame,
+            area,
+            Constraint::Percentage(80),
+            self.query.as_str(),
+            items,
+            &mut ListState::default(),
+            app_context,
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    app_context::AppContext, components::list_popup::ListPopup, tg::td_enums::TdMessageSender,
+};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::ListItem,
+};
+
+/// `SendAsPicker` is a small menu that shows the identities that can be used
+/// to send messages to the open chat, it is drawn over the `ChatWindow`.
+pub struct SendAsPicker {
+    /// The identities and their names.
+    senders: ListPopup<(TdMessageSender, String)>,
+}
+/// Implementation of the `SendAsPicker` struct.
+impl SendAsPicker {
+    /// Create a new instance of the `SendAsPicker` struct.
+    ///
+    /// # Arguments
+    /// * `senders` - The identities that can be chosen and their names.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `SendAsPicker` struct.
+    pub fn new(senders: Vec<(TdMessageSender, String)>) -> Self {
+        SendAsPicker {
+            senders: ListPopup::new(senders),
+        }
+    }
+
+    /// Get the highlighted identity.
+    pub fn selected(&self) -> Option<TdMessageSender> {
+        self.senders.selected().map(|(sender, _)| *sender)
+    }
+
+    /// Highlight the next identity.
+    pub fn next(&mut self) {
+        self.senders.next();
+    }
+
+    /// Highlight the previous identity.
+    pub fn previous(&mut self) {
+        self.senders.previous();
+    }
+
+    /// Draw the `SendAsPicker` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let items = if self.senders.items().is_empty() {
+            vec![ListItem::new(Line::from("No identities"))]
+        } else {
+            self.senders
+                .items()
+                .iter()
+                .map(|(_, name)| ListItem::new(Line::from(name.clone())))
+                .collect()
+        };
+        self.senders.draw(
+            frame,
+            area,
+            Constraint::Length(32),
+            "Send as",
+            items,
+            app_context,
+        );
+    }
+}
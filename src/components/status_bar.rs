@@ -14,6 +14,21 @@ use {
     tokio::sync::mpsc::UnboundedSender,
 };
 
+/// The frames of the spinner shown while a long operation is running.
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Get the frame of the spinner to show at the given time, it advances every
+/// 100 milliseconds.
+///
+/// # Arguments
+/// * `millis` - The current timestamp, in milliseconds.
+///
+/// # Returns
+/// * `char` - The frame of the spinner.
+fn spinner_frame(millis: i64) -> char {
+    SPINNER_FRAMES[(millis / 100).rem_euclid(SPINNER_FRAMES.len() as i64) as usize]
+}
+
 /// `StatusBar` is a struct that represents a status bar.
 /// It is responsible for managing the layout and rendering of the status bar.
 pub struct StatusBar {
@@ -29,6 +44,9 @@ pub struct StatusBar {
     terminal_area: Rect,
     /// The last key pressed.
     last_key: Event,
+    /// A message about the outcome of the last action, e.g. an error. It is
+    /// cleared when the next key is pressed.
+    status_message: Option<String>,
 }
 /// Implementation of `StatusBar` struct.
 impl StatusBar {
@@ -45,6 +63,7 @@ impl StatusBar {
         let terminal_area = Rect::default();
         let last_key = Event::Unknown;
         let focused = false;
+        let status_message = None;
 
         StatusBar {
             app_context,
@@ -53,6 +72,7 @@ impl StatusBar {
             terminal_area,
             last_key,
             focused,
+            status_message,
         }
     }
     /// Set the name of the `StatusBar`.
@@ -93,7 +113,11 @@ impl Component for StatusBar {
             Action::UpdateArea(area) => {
                 self.terminal_area = area;
             }
-            Action::Key(key, modifiers) => self.last_key = Event::Key(key, modifiers.into()),
+            Action::Key(key, modifiers) => {
+                self.last_key = Event::Key(key, modifiers.into());
+                self.status_message = None;
+            }
+            Action::StatusMessage(message) => self.status_message = Some(message),
             _ => {}
         }
     }
@@ -104,7 +128,7 @@ impl Component for StatusBar {
             .tg_context()
             .name_of_open_chat_id()
             .unwrap_or_default();
-        let text = vec![Line::from(vec![
+        let mut spans = vec![
             Span::styled(
                 "Press ",
                 self.app_context.style_status_bar_message_quit_text(),
@@ -151,7 +175,35 @@ impl Component for StatusBar {
                 self.terminal_area.height.to_string(),
                 self.app_context.style_status_bar_size_info_numbers(),
             ),
-        ])];
+        ];
+        let operation = {
+            let pending_operations = self.app_context.tg_context().pending_operations();
+            pending_operations
+                .current()
+                .map(|label| match pending_operations.len() {
+                    1 => label.to_string(),
+                    n => format!("{} (+{})", label, n - 1),
+                })
+        };
+        if let Some(operation) = operation {
+            spans.push(Span::raw("     "));
+            spans.push(Span::styled(
+                format!(
+                    "{} {}",
+                    spinner_frame(chrono::Utc::now().timestamp_millis()),
+                    operation
+                ),
+                self.app_context.style_status_bar_press_key_key(),
+            ));
+        }
+        if let Some(status_message) = self.status_message.as_ref() {
+            spans.push(Span::raw("     "));
+            spans.push(Span::styled(
+                status_message.clone(),
+                self.app_context.style_status_bar_press_key_key(),
+            ));
+        }
+        let text = vec![Line::from(spans)];
 
         let paragraph = Paragraph::new(text)
             .block(Block::new().title(self.name.as_str()).borders(Borders::ALL))
@@ -87,7 +87,7 @@ impl Component for TitleBar {
 
         let name: Vec<char> = self.name.chars().collect::<Vec<char>>();
         // Span::raw(" - A TUI for Telegram"),
-        let text = vec![Line::from(vec![
+        let mut text = vec![Line::from(vec![
             Span::styled(
                 name[0].to_string(),
                 self.app_context.style_title_bar_title1(),
@@ -117,6 +117,12 @@ impl Component for TitleBar {
             Span::styled("a", self.app_context.style_title_bar_title3()),
             Span::styled("m", self.app_context.style_title_bar_title1()),
         ])];
+        if let Some(emoji) = self.app_context.tg_context().emoji_status().as_ref() {
+            text[0].push_span(Span::styled(
+                format!(" {}", emoji),
+                self.app_context.style_title_bar_title1(),
+            ));
+        }
         let block = Block::new().borders(Borders::ALL);
         let paragraph = Paragraph::new(text)
             .block(block.clone())
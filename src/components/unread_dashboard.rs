@@ -0,0 +1,134 @@
+use crate::{app_context::AppContext, components::list_popup::ListPopup};
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::ListItem,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// `UnreadChat` is a chat with unread messages shown in the
+/// `UnreadDashboard`.
+pub struct UnreadChat {
+    /// The identifier of the chat.
+    pub chat_id: i64,
+    /// The title of the chat.
+    pub title: String,
+    /// The number of unread messages of the chat.
+    pub unread_count: i32,
+    /// The text of the last message of the chat.
+    pub preview: String,
+}
+
+/// Sort the chats with unread messages from the one with the most unread
+/// messages, the chats with the same number keep their order.
+///
+/// # Arguments
+/// * `chats` - The chats with unread messages, in the order of the chat list.
+///
+/// # Returns
+/// * `Vec<UnreadChat>` - The sorted chats.
+pub fn sort_by_unread_count(mut chats: Vec<UnreadChat>) -> Vec<UnreadChat> {
+    chats.sort_by(|a, b| b.unread_count.cmp(&a.unread_count));
+    chats
+}
+
+/// `UnreadDashboard` is a panel that lists the chats with unread messages,
+/// it is drawn over the `ChatWindow` and the highlighted chat can be opened.
+pub struct UnreadDashboard {
+    /// The chats with unread messages.
+    chats: ListPopup<UnreadChat>,
+}
+/// Implementation of the `UnreadDashboard` struct.
+impl UnreadDashboard {
+    /// Create a new instance of the `UnreadDashboard` struct.
+    ///
+    /// # Arguments
+    /// * `chats` - The chats with unread messages, sorted by unread count.
+    ///
+    /// # Returns
+    /// * `Self` - The new instance of the `UnreadDashboard` struct.
+    pub fn new(chats: Vec<UnreadChat>) -> Self {
+        UnreadDashboard {
+            chats: ListPopup::new(chats),
+        }
+    }
+
+    /// Get the identifier of the highlighted chat.
+    pub fn selected(&self) -> Option<i64> {
+        self.chats.selected().map(|chat| chat.chat_id)
+    }
+
+    /// Highlight the next chat.
+    pub fn next(&mut self) {
+        self.chats.next();
+    }
+
+    /// Highlight the previous chat.
+    pub fn previous(&mut self) {
+        self.chats.previous();
+    }
+
+    /// Draw the `UnreadDashboard` centered in the given area.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame to draw on.
+    /// * `area` - The area of the `ChatWindow`.
+    /// * `app_context` - The application context, used for the styles.
+    pub fn draw(&mut self, frame: &mut ratatui::Frame<'_>, area: Rect, app_context: &AppContext) {
+        let items = if self.chats.items().is_empty() {
+            vec![ListItem::new(Line::from("All caught up"))]
+        } else {
+            self.chats
+                .items()
+                .iter()
+                .map(|chat| {
+                    ListItem::new(Line::from(format!(
+                        "({}) {}: {}",
+                        chat.unread_count,
+                        chat.title,
+                        chat.preview.replace('\n', " ")
+                    )))
+                })
+                .collect()
+        };
+        self.chats.draw(
+            frame,
+            area,
+            Constraint::Percentage(80),
+            "Unread",
+            items,
+            app_context,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::unread_dashboard::{sort_by_unread_count, UnreadChat};
+
+    fn unread_chat(chat_id: i64, unread_count: i32) -> UnreadChat {
+        UnreadChat {
+            chat_id,
+            title: format!("Chat {}", chat_id),
+            unread_count,
+            preview: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_sort_by_unread_count() {
+        let chats = vec![
+            unread_chat(1, 2),
+            unread_chat(2, 10),
+            unread_chat(3, 2),
+            unread_chat(4, 5),
+        ];
+        let ids = sort_by_unread_count(chats)
+            .iter()
+            .map(|chat| chat.chat_id)
+            .collect::<Vec<i64>>();
+        // The chats with the same count keep the order of the chat list.
+        assert_eq!(ids, vec![2, 4, 1, 3]);
+        assert!(sort_by_unread_count(vec![]).is_empty());
+    }
+}
@@ -1,6 +1,15 @@
 use crate::{
     app_error::AppError,
-    configs::{self, config_file::ConfigFile, config_type::ConfigType, raw::app_raw::AppRaw},
+    configs::{
+        self,
+        config_file::ConfigFile,
+        config_type::ConfigType,
+        raw::app_raw::{
+            AppRaw, BackspaceAction, ComposeEscapeAction, DeleteLastBehavior, HomeEndBehavior,
+            ReplyPreviewMode, ScrolledUpNewMessageBehavior, SelectionStyle, TabBehavior,
+            TimestampGranularity,
+        },
+    },
 };
 use std::path::Path;
 
@@ -25,6 +34,79 @@ pub struct AppConfig {
     pub take_api_id_from_telegram_config: bool,
     /// Take the API HASH from the Telegram configuration.
     pub take_api_hash_from_telegram_config: bool,
+    /// Snap the chat back to the most recent message after sending.
+    pub jump_to_bottom_on_send: bool,
+    /// The maximum number of characters pasted without confirmation, 0 disables the guard.
+    pub max_paste_size: usize,
+    /// Show a divider at the last read message when opening a chat.
+    pub show_new_messages_divider: bool,
+    /// The maximum number of times a message that failed to send is retried
+    /// before being marked as failed. The value 0 disables the retries.
+    pub send_max_retries: u32,
+    /// The time, in milliseconds, to wait before the first retry of a message
+    /// that failed to send. It doubles at every following attempt.
+    pub send_retry_backoff_ms: u32,
+    /// What happens when the last message of the open chat is deleted.
+    pub delete_last_behavior: DeleteLastBehavior,
+    /// Whether the destructive actions, like deleting messages, ask for confirmation.
+    pub confirm_destructive: bool,
+    /// The number of lines of context kept above and below the selected message.
+    pub scroll_margin: u16,
+    /// What the Backspace key does when the prompt is not focused.
+    pub backspace_action: BackspaceAction,
+    /// The maximum number of reactions shown under a message, 0 to show all.
+    pub max_reactions_shown: usize,
+    /// How the selected message is highlighted.
+    pub selection_style: SelectionStyle,
+    /// Whether to ask to send the draft of a chat when its reminder is due.
+    pub draft_reminder_prompt: bool,
+    /// What the Tab key does.
+    pub tab_behavior: TabBehavior,
+    /// Whether the messages are marked as read only when they are selected.
+    pub read_on_scroll_to: bool,
+    /// The maximum number of TDLib requests in flight, downloads and resends
+    /// included, 0 means no limit.
+    pub max_concurrent_requests: usize,
+    /// What the Esc key does in the prompt.
+    pub compose_escape_action: ComposeEscapeAction,
+    /// Whether the Saved Messages are used as a notepad: after each send the
+    /// prompt keeps the focus and the view stays at the most recent message.
+    pub saved_messages_notepad_mode: bool,
+    /// The inactivity, in seconds, after which the user is set offline, `0` to
+    /// never be set offline.
+    pub auto_away_timeout: i32,
+    /// The maximum width the text of the messages is wrapped to, `0` to use
+    /// the whole width of the messages.
+    pub message_content_width: u16,
+    /// The number of messages requested for each page of the history, when
+    /// scrolling back in a chat.
+    pub history_page_size: i32,
+    /// How precise the times of the messages are.
+    pub timestamp_granularity: TimestampGranularity,
+    /// The inactivity, in seconds, after which a half-typed message is saved as
+    /// a draft and the compose box is cleared, 0 to never clear it.
+    pub compose_auto_clear_timeout: i32,
+    /// What happens when a message arrives in the open chat while the history is
+    /// scrolled up.
+    pub scrolled_up_new_message_behavior: ScrolledUpNewMessageBehavior,
+    /// How the messages that a message replies to are previewed above it.
+    pub reply_preview_mode: ReplyPreviewMode,
+    /// The minimum time, in milliseconds, between two renders of the user interface,
+    /// the updates received in between are drawn together. 0 renders on every tick.
+    pub render_interval: u32,
+    /// The maximum width, in columns, of the chat titles in the chat list, `0` to
+    /// show the whole titles.
+    pub chat_title_max_width: u16,
+    /// The maximum time, in milliseconds, between two presses of the same key for
+    /// them to trigger its double press action, `0` to disable the double presses.
+    pub double_key_window: u32,
+    /// The maximum time, in milliseconds, the pending drafts are flushed and
+    /// the TDLib client is closed for when quitting, so that a hung TDLib does
+    /// not block the exit.
+    pub shutdown_timeout: u32,
+    /// What the Home and End keys do in the chat, the prompt always uses them
+    /// to move to the start and the end of the line.
+    pub home_end_behavior: HomeEndBehavior,
 }
 /// The application configuration implementation.
 impl AppConfig {
@@ -86,6 +168,95 @@ impl ConfigFile for AppConfig {
                 {
                     self.take_api_hash_from_telegram_config = take_api_hash_from_telegram_config;
                 }
+                if let Some(jump_to_bottom_on_send) = other.jump_to_bottom_on_send {
+                    self.jump_to_bottom_on_send = jump_to_bottom_on_send;
+                }
+                if let Some(max_paste_size) = other.max_paste_size {
+                    self.max_paste_size = max_paste_size;
+                }
+                if let Some(show_new_messages_divider) = other.show_new_messages_divider {
+                    self.show_new_messages_divider = show_new_messages_divider;
+                }
+                if let Some(send_max_retries) = other.send_max_retries {
+                    self.send_max_retries = send_max_retries;
+                }
+                if let Some(send_retry_backoff_ms) = other.send_retry_backoff_ms {
+                    self.send_retry_backoff_ms = send_retry_backoff_ms;
+                }
+                if let Some(delete_last_behavior) = other.delete_last_behavior {
+                    self.delete_last_behavior = delete_last_behavior;
+                }
+                if let Some(confirm_destructive) = other.confirm_destructive {
+                    self.confirm_destructive = confirm_destructive;
+                }
+                if let Some(scroll_margin) = other.scroll_margin {
+                    self.scroll_margin = scroll_margin;
+                }
+                if let Some(backspace_action) = other.backspace_action {
+                    self.backspace_action = backspace_action;
+                }
+                if let Some(max_reactions_shown) = other.max_reactions_shown {
+                    self.max_reactions_shown = max_reactions_shown;
+                }
+                if let Some(selection_style) = other.selection_style {
+                    self.selection_style = selection_style;
+                }
+                if let Some(draft_reminder_prompt) = other.draft_reminder_prompt {
+                    self.draft_reminder_prompt = draft_reminder_prompt;
+                }
+                if let Some(tab_behavior) = other.tab_behavior {
+                    self.tab_behavior = tab_behavior;
+                }
+                if let Some(read_on_scroll_to) = other.read_on_scroll_to {
+                    self.read_on_scroll_to = read_on_scroll_to;
+                }
+                if let Some(max_concurrent_requests) = other.max_concurrent_requests {
+                    self.max_concurrent_requests = max_concurrent_requests;
+                }
+                if let Some(compose_escape_action) = other.compose_escape_action {
+                    self.compose_escape_action = compose_escape_action;
+                }
+                if let Some(saved_messages_notepad_mode) = other.saved_messages_notepad_mode {
+                    self.saved_messages_notepad_mode = saved_messages_notepad_mode;
+                }
+                if let Some(auto_away_timeout) = other.auto_away_timeout {
+                    self.auto_away_timeout = auto_away_timeout;
+                }
+                if let Some(message_content_width) = other.message_content_width {
+                    self.message_content_width = message_content_width;
+                }
+                if let Some(history_page_size) = other.history_page_size {
+                    self.history_page_size = history_page_size;
+                }
+                if let Some(timestamp_granularity) = other.timestamp_granularity {
+                    self.timestamp_granularity = timestamp_granularity;
+                }
+                if let Some(compose_auto_clear_timeout) = other.compose_auto_clear_timeout {
+                    self.compose_auto_clear_timeout = compose_auto_clear_timeout;
+                }
+                if let Some(scrolled_up_new_message_behavior) =
+                    other.scrolled_up_new_message_behavior
+                {
+                    self.scrolled_up_new_message_behavior = scrolled_up_new_message_behavior;
+                }
+                if let Some(reply_preview_mode) = other.reply_preview_mode {
+                    self.reply_preview_mode = reply_preview_mode;
+                }
+                if let Some(render_interval) = other.render_interval {
+                    self.render_interval = render_interval;
+                }
+                if let Some(chat_title_max_width) = other.chat_title_max_width {
+                    self.chat_title_max_width = chat_title_max_width;
+                }
+                if let Some(double_key_window) = other.double_key_window {
+                    self.double_key_window = double_key_window;
+                }
+                if let Some(shutdown_timeout) = other.shutdown_timeout {
+                    self.shutdown_timeout = shutdown_timeout;
+                }
+                if let Some(home_end_behavior) = other.home_end_behavior {
+                    self.home_end_behavior = home_end_behavior;
+                }
                 self.clone()
             }
         }
@@ -111,6 +282,35 @@ impl From<AppRaw> for AppConfig {
             theme_filename: raw.theme_filename.unwrap(),
             take_api_id_from_telegram_config: raw.take_api_id_from_telegram_config.unwrap(),
             take_api_hash_from_telegram_config: raw.take_api_hash_from_telegram_config.unwrap(),
+            jump_to_bottom_on_send: raw.jump_to_bottom_on_send.unwrap(),
+            max_paste_size: raw.max_paste_size.unwrap(),
+            show_new_messages_divider: raw.show_new_messages_divider.unwrap(),
+            send_max_retries: raw.send_max_retries.unwrap(),
+            send_retry_backoff_ms: raw.send_retry_backoff_ms.unwrap(),
+            delete_last_behavior: raw.delete_last_behavior.unwrap(),
+            confirm_destructive: raw.confirm_destructive.unwrap(),
+            scroll_margin: raw.scroll_margin.unwrap(),
+            backspace_action: raw.backspace_action.unwrap(),
+            max_reactions_shown: raw.max_reactions_shown.unwrap(),
+            selection_style: raw.selection_style.unwrap(),
+            draft_reminder_prompt: raw.draft_reminder_prompt.unwrap(),
+            tab_behavior: raw.tab_behavior.unwrap(),
+            read_on_scroll_to: raw.read_on_scroll_to.unwrap(),
+            max_concurrent_requests: raw.max_concurrent_requests.unwrap(),
+            compose_escape_action: raw.compose_escape_action.unwrap(),
+            saved_messages_notepad_mode: raw.saved_messages_notepad_mode.unwrap(),
+            auto_away_timeout: raw.auto_away_timeout.unwrap(),
+            message_content_width: raw.message_content_width.unwrap(),
+            history_page_size: raw.history_page_size.unwrap(),
+            timestamp_granularity: raw.timestamp_granularity.unwrap(),
+            compose_auto_clear_timeout: raw.compose_auto_clear_timeout.unwrap(),
+            scrolled_up_new_message_behavior: raw.scrolled_up_new_message_behavior.unwrap(),
+            reply_preview_mode: raw.reply_preview_mode.unwrap(),
+            render_interval: raw.render_interval.unwrap(),
+            chat_title_max_width: raw.chat_title_max_width.unwrap(),
+            double_key_window: raw.double_key_window.unwrap(),
+            shutdown_timeout: raw.shutdown_timeout.unwrap(),
+            home_end_behavior: raw.home_end_behavior.unwrap(),
         }
     }
 }
@@ -118,7 +318,13 @@ impl From<AppRaw> for AppConfig {
 #[cfg(test)]
 mod tests {
     use crate::configs::{
-        config_file::ConfigFile, custom::app_custom::AppConfig, raw::app_raw::AppRaw,
+        config_file::ConfigFile,
+        custom::app_custom::AppConfig,
+        raw::app_raw::{
+            AppRaw, BackspaceAction, ComposeEscapeAction, DeleteLastBehavior, HomeEndBehavior,
+            ReplyPreviewMode, ScrolledUpNewMessageBehavior, SelectionStyle, TabBehavior,
+            TimestampGranularity,
+        },
     };
 
     #[test]
@@ -133,6 +339,47 @@ mod tests {
         assert_eq!(app_config.theme_filename, "theme.toml");
         assert!(app_config.take_api_id_from_telegram_config);
         assert!(app_config.take_api_hash_from_telegram_config);
+        assert!(app_config.jump_to_bottom_on_send);
+        assert_eq!(app_config.max_paste_size, 0);
+        assert!(app_config.show_new_messages_divider);
+        assert_eq!(app_config.send_max_retries, 3);
+        assert_eq!(app_config.send_retry_backoff_ms, 1000);
+        assert_eq!(
+            app_config.delete_last_behavior,
+            DeleteLastBehavior::KeepOpen
+        );
+        assert!(!app_config.confirm_destructive);
+        assert_eq!(app_config.scroll_margin, 0);
+        assert_eq!(app_config.backspace_action, BackspaceAction::Noop);
+        assert_eq!(app_config.max_reactions_shown, 0);
+        assert_eq!(app_config.selection_style, SelectionStyle::Highlight);
+        assert!(!app_config.draft_reminder_prompt);
+        assert_eq!(app_config.tab_behavior, TabBehavior::CycleFocus);
+        assert!(!app_config.read_on_scroll_to);
+        assert_eq!(app_config.max_concurrent_requests, 0);
+        assert_eq!(
+            app_config.compose_escape_action,
+            ComposeEscapeAction::Unfocus
+        );
+        assert!(!app_config.saved_messages_notepad_mode);
+        assert_eq!(app_config.auto_away_timeout, 0);
+        assert_eq!(app_config.message_content_width, 0);
+        assert_eq!(app_config.history_page_size, 50);
+        assert_eq!(
+            app_config.timestamp_granularity,
+            TimestampGranularity::Minute
+        );
+        assert_eq!(app_config.compose_auto_clear_timeout, 0);
+        assert_eq!(
+            app_config.scrolled_up_new_message_behavior,
+            ScrolledUpNewMessageBehavior::JumpButton
+        );
+        assert_eq!(app_config.reply_preview_mode, ReplyPreviewMode::Inline);
+        assert_eq!(app_config.render_interval, 0);
+        assert_eq!(app_config.chat_title_max_width, 0);
+        assert_eq!(app_config.double_key_window, 300);
+        assert_eq!(app_config.shutdown_timeout, 3000);
+        assert_eq!(app_config.home_end_behavior, HomeEndBehavior::LineStartEnd);
     }
 
     #[test]
@@ -147,6 +394,35 @@ mod tests {
             theme_filename: Some("test".to_string()),
             take_api_id_from_telegram_config: Some(true),
             take_api_hash_from_telegram_config: Some(true),
+            jump_to_bottom_on_send: Some(true),
+            max_paste_size: Some(0),
+            show_new_messages_divider: Some(true),
+            send_max_retries: Some(3),
+            send_retry_backoff_ms: Some(1000),
+            delete_last_behavior: Some(DeleteLastBehavior::KeepOpen),
+            confirm_destructive: Some(false),
+            scroll_margin: Some(0),
+            backspace_action: Some(BackspaceAction::Noop),
+            max_reactions_shown: Some(0),
+            selection_style: Some(SelectionStyle::Highlight),
+            draft_reminder_prompt: Some(false),
+            tab_behavior: Some(TabBehavior::CycleFocus),
+            read_on_scroll_to: Some(false),
+            max_concurrent_requests: Some(0),
+            compose_escape_action: Some(ComposeEscapeAction::Unfocus),
+            saved_messages_notepad_mode: Some(false),
+            auto_away_timeout: Some(0),
+            message_content_width: Some(0),
+            history_page_size: Some(50),
+            timestamp_granularity: Some(TimestampGranularity::Minute),
+            compose_auto_clear_timeout: Some(0),
+            scrolled_up_new_message_behavior: Some(ScrolledUpNewMessageBehavior::JumpButton),
+            reply_preview_mode: Some(ReplyPreviewMode::Inline),
+            render_interval: Some(0),
+            chat_title_max_width: Some(0),
+            double_key_window: Some(300),
+            shutdown_timeout: Some(3000),
+            home_end_behavior: Some(HomeEndBehavior::LineStartEnd),
         };
         let app_config = AppConfig::from(app_raw);
         assert!(app_config.mouse_support);
@@ -170,6 +446,35 @@ mod tests {
             theme_filename: Some("test".to_string()),
             take_api_id_from_telegram_config: Some(true),
             take_api_hash_from_telegram_config: Some(true),
+            jump_to_bottom_on_send: Some(true),
+            max_paste_size: Some(0),
+            show_new_messages_divider: Some(true),
+            send_max_retries: Some(3),
+            send_retry_backoff_ms: Some(1000),
+            delete_last_behavior: Some(DeleteLastBehavior::KeepOpen),
+            confirm_destructive: Some(false),
+            scroll_margin: Some(0),
+            backspace_action: Some(BackspaceAction::Noop),
+            max_reactions_shown: Some(0),
+            selection_style: Some(SelectionStyle::Highlight),
+            draft_reminder_prompt: Some(false),
+            tab_behavior: Some(TabBehavior::CycleFocus),
+            read_on_scroll_to: Some(false),
+            max_concurrent_requests: Some(0),
+            compose_escape_action: Some(ComposeEscapeAction::Unfocus),
+            saved_messages_notepad_mode: Some(false),
+            auto_away_timeout: Some(0),
+            message_content_width: Some(0),
+            history_page_size: Some(50),
+            timestamp_granularity: Some(TimestampGranularity::Minute),
+            compose_auto_clear_timeout: Some(0),
+            scrolled_up_new_message_behavior: Some(ScrolledUpNewMessageBehavior::JumpButton),
+            reply_preview_mode: Some(ReplyPreviewMode::Inline),
+            render_interval: Some(0),
+            chat_title_max_width: Some(0),
+            double_key_window: Some(300),
+            shutdown_timeout: Some(3000),
+            home_end_behavior: Some(HomeEndBehavior::LineStartEnd),
         });
         let app_raw = AppRaw {
             mouse_support: Some(false),
@@ -181,6 +486,35 @@ mod tests {
             theme_filename: None,
             take_api_id_from_telegram_config: None,
             take_api_hash_from_telegram_config: None,
+            jump_to_bottom_on_send: None,
+            max_paste_size: None,
+            show_new_messages_divider: None,
+            send_max_retries: None,
+            send_retry_backoff_ms: None,
+            delete_last_behavior: None,
+            confirm_destructive: None,
+            scroll_margin: None,
+            backspace_action: None,
+            max_reactions_shown: None,
+            selection_style: None,
+            draft_reminder_prompt: None,
+            tab_behavior: None,
+            read_on_scroll_to: None,
+            max_concurrent_requests: None,
+            compose_escape_action: None,
+            saved_messages_notepad_mode: None,
+            auto_away_timeout: None,
+            message_content_width: None,
+            history_page_size: None,
+            timestamp_granularity: None,
+            compose_auto_clear_timeout: None,
+            scrolled_up_new_message_behavior: None,
+            reply_preview_mode: None,
+            render_interval: None,
+            chat_title_max_width: None,
+            double_key_window: None,
+            shutdown_timeout: None,
+            home_end_behavior: None,
         };
         app_config = app_config.merge(Some(app_raw));
         assert!(!app_config.mouse_support);
@@ -210,6 +544,35 @@ mod tests {
             theme_filename: None,
             take_api_id_from_telegram_config: None,
             take_api_hash_from_telegram_config: None,
+            jump_to_bottom_on_send: None,
+            max_paste_size: None,
+            show_new_messages_divider: None,
+            send_max_retries: None,
+            send_retry_backoff_ms: None,
+            delete_last_behavior: None,
+            confirm_destructive: None,
+            scroll_margin: None,
+            backspace_action: None,
+            max_reactions_shown: None,
+            selection_style: None,
+            draft_reminder_prompt: None,
+            tab_behavior: None,
+            read_on_scroll_to: None,
+            max_concurrent_requests: None,
+            compose_escape_action: None,
+            saved_messages_notepad_mode: None,
+            auto_away_timeout: None,
+            message_content_width: None,
+            history_page_size: None,
+            timestamp_granularity: None,
+            compose_auto_clear_timeout: None,
+            scrolled_up_new_message_behavior: None,
+            reply_preview_mode: None,
+            render_interval: None,
+            chat_title_max_width: None,
+            double_key_window: None,
+            shutdown_timeout: None,
+            home_end_behavior: None,
         };
         app_config = app_config.merge(Some(app_raw));
         assert!(app_config.mouse_support);
@@ -221,6 +584,47 @@ mod tests {
         assert_eq!(app_config.theme_filename, "theme.toml");
         assert!(app_config.take_api_id_from_telegram_config);
         assert!(app_config.take_api_hash_from_telegram_config);
+        assert!(app_config.jump_to_bottom_on_send);
+        assert_eq!(app_config.max_paste_size, 0);
+        assert!(app_config.show_new_messages_divider);
+        assert_eq!(app_config.send_max_retries, 3);
+        assert_eq!(app_config.send_retry_backoff_ms, 1000);
+        assert_eq!(
+            app_config.delete_last_behavior,
+            DeleteLastBehavior::KeepOpen
+        );
+        assert!(!app_config.confirm_destructive);
+        assert_eq!(app_config.scroll_margin, 0);
+        assert_eq!(app_config.backspace_action, BackspaceAction::Noop);
+        assert_eq!(app_config.max_reactions_shown, 0);
+        assert_eq!(app_config.selection_style, SelectionStyle::Highlight);
+        assert!(!app_config.draft_reminder_prompt);
+        assert_eq!(app_config.tab_behavior, TabBehavior::CycleFocus);
+        assert!(!app_config.read_on_scroll_to);
+        assert_eq!(app_config.max_concurrent_requests, 0);
+        assert_eq!(
+            app_config.compose_escape_action,
+            ComposeEscapeAction::Unfocus
+        );
+        assert!(!app_config.saved_messages_notepad_mode);
+        assert_eq!(app_config.auto_away_timeout, 0);
+        assert_eq!(app_config.message_content_width, 0);
+        assert_eq!(app_config.history_page_size, 50);
+        assert_eq!(
+            app_config.timestamp_granularity,
+            TimestampGranularity::Minute
+        );
+        assert_eq!(app_config.compose_auto_clear_timeout, 0);
+        assert_eq!(
+            app_config.scrolled_up_new_message_behavior,
+            ScrolledUpNewMessageBehavior::JumpButton
+        );
+        assert_eq!(app_config.reply_preview_mode, ReplyPreviewMode::Inline);
+        assert_eq!(app_config.render_interval, 0);
+        assert_eq!(app_config.chat_title_max_width, 0);
+        assert_eq!(app_config.double_key_window, 300);
+        assert_eq!(app_config.shutdown_timeout, 3000);
+        assert_eq!(app_config.home_end_behavior, HomeEndBehavior::LineStartEnd);
     }
 
     #[test]
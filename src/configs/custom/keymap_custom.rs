@@ -10,6 +10,7 @@ use crate::{
     },
     event::Event,
 };
+use crossterm::event::KeyCode;
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     path::Path,
@@ -25,12 +26,119 @@ pub enum ActionBinding {
     Single {
         action: Action,
         description: Option<String>,
+        /// The action of two rapid presses of the key, the first press still
+        /// triggers `action`.
+        double_action: Option<Action>,
     },
     /// A multiple action binding.
     /// It is used to bind multiple keys to an action.
     Multiple(HashMap<Event, ActionBinding>),
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// The keyboard layout of the bindings.
+/// The bindings are written with the logical characters, e.g. `j` to select
+/// the next message, and they are remapped to the characters typed with the
+/// layout, so that Dvorak and Colemak users do not have to rewrite them all.
+pub struct Layout {
+    /// The typed character of each remapped logical character.
+    remap: HashMap<char, char>,
+}
+/// The conversion from the raw layout to the layout.
+/// The entries that are not a single character on both sides are ignored.
+impl From<HashMap<String, String>> for Layout {
+    fn from(raw: HashMap<String, String>) -> Self {
+        let mut remap = HashMap::new();
+        for (logical, typed) in raw {
+            let mut logical_chars = logical.chars();
+            let mut typed_chars = typed.chars();
+            match (
+                logical_chars.next(),
+                logical_chars.next(),
+                typed_chars.next(),
+                typed_chars.next(),
+            ) {
+                (Some(l), None, Some(t), None) => {
+                    remap.insert(l, t);
+                }
+                _ => tracing::warn!("Invalid layout remap {:?} = {:?}", logical, typed),
+            }
+        }
+        Self { remap }
+    }
+}
+
+/// Remap a character of a binding with the keyboard layout.
+///
+/// # Arguments
+/// * `c` - The logical character of the binding.
+/// * `layout` - The keyboard layout.
+///
+/// # Returns
+/// * `char` - The character typed with the layout, `c` itself when it is not
+///   remapped.
+pub fn remap_char(c: char, layout: &Layout) -> char {
+    layout.remap.get(&c).copied().unwrap_or(c)
+}
+
+/// Remap the character of a key of a binding, e.g. `ctrl+j`, with the
+/// keyboard layout, the modifiers and the named keys are kept.
+///
+/// # Arguments
+/// * `key` - The key, as it is written in the configuration file.
+/// * `layout` - The keyboard layout.
+///
+/// # Returns
+/// * `String` - The remapped key.
+fn remap_key(key: &str, layout: &Layout) -> String {
+    let (modifiers, name) = match key.rsplit_once('+') {
+        Some((modifiers, name)) => (Some(modifiers), name),
+        None => (None, key),
+    };
+    let mut chars = name.chars();
+    let name = match (chars.next(), chars.next()) {
+        (Some(c), None) => remap_char(c, layout).to_string(),
+        _ => name.to_string(),
+    };
+    match modifiers {
+        Some(modifiers) => format!("{}+{}", modifiers, name),
+        None => name,
+    }
+}
+
+/// Remap the characters of the bindings already parsed with the keyboard
+/// layout.
+///
+/// # Arguments
+/// * `bindings` - The bindings.
+/// * `layout` - The keyboard layout.
+///
+/// # Returns
+/// * `HashMap<Event, ActionBinding>` - The remapped bindings.
+fn remap_bindings(
+    bindings: HashMap<Event, ActionBinding>,
+    layout: &Layout,
+) -> HashMap<Event, ActionBinding> {
+    bindings
+        .into_iter()
+        .map(|(event, binding)| {
+            let event = match event {
+                Event::Key(KeyCode::Char(c), modifiers) => {
+                    Event::Key(KeyCode::Char(remap_char(c, layout)), modifiers).normalized()
+                }
+                event => event,
+            };
+            let binding = match binding {
+                ActionBinding::Multiple(map) => {
+                    ActionBinding::Multiple(remap_bindings(map, layout))
+                }
+                binding => binding,
+            };
+            (event, binding)
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// The kind of keymap.
 /// It is used to check for conflicts in the keymaps.
@@ -118,12 +226,14 @@ impl KeymapConfig {
     /// # Arguments
     /// * `keymaps` - A vector of keymap entries.
     /// * `kind` - The kind of keymap.
+    /// * `layout` - The keyboard layout the keys are remapped with.
     ///
     /// # Returns
     /// A hashmap of event and action binding.
     fn keymaps_vec_to_map(
         keymaps: Vec<KeymapEntry>,
         kind: KeymapKind,
+        layout: &Layout,
     ) -> HashMap<Event, ActionBinding> {
         let mut hashmap = HashMap::new();
 
@@ -131,7 +241,7 @@ impl KeymapConfig {
             let event: Vec<Event> = keymap
                 .keys
                 .iter()
-                .map(|k| match Event::from_str(k) {
+                .map(|k| match Event::from_str(&remap_key(k, layout)) {
                     Ok(e) => e,
                     Err(e) => {
                         if let AppError::InvalidEvent(err) = e {
@@ -170,6 +280,41 @@ impl KeymapConfig {
                 std::process::exit(1);
             }
 
+            let double_action = match keymap.double_command.as_deref().map(Action::from_str) {
+                None => None,
+                Some(Ok(a)) => Some(a),
+                Some(Err(e)) => {
+                    if let AppError::InvalidAction(err) = e {
+                        tracing::warn!(err);
+                    }
+                    tracing::warn!(
+                        "Some actions were not recognized in {:?} section for double_command: {:?}",
+                        kind,
+                        keymap.double_command
+                    );
+                    Self::print_config_file_error(
+                        "double_command",
+                        Vec::from([keymap.double_command.unwrap_or_default()]),
+                    );
+                    std::process::exit(1);
+                }
+            };
+            // The double presses are detected for the single keys only, the
+            // sequences of keys already are chords.
+            let single_key = match event.as_slice() {
+                [key] => Some(key.clone()),
+                _ => {
+                    if double_action.is_some() {
+                        tracing::warn!(
+                            "The double_command is ignored for the multiple keys {:?} in {:?} section",
+                            keymap.keys,
+                            kind
+                        );
+                    }
+                    None
+                }
+            };
+
             let description = keymap.description.clone();
 
             if let Err(AppError::AlreadyBound) =
@@ -184,6 +329,12 @@ impl KeymapConfig {
                         .collect::<Vec<String>>(),
                     kind
                 );
+            } else if let Some(ActionBinding::Single {
+                double_action: bound_double_action,
+                ..
+            }) = single_key.and_then(|key| hashmap.get_mut(&key))
+            {
+                *bound_double_action = double_action;
             }
         }
         hashmap
@@ -230,6 +381,7 @@ impl KeymapConfig {
                         e.insert(ActionBinding::Single {
                             action,
                             description,
+                            double_action: None,
                         });
                     }
                 }
@@ -340,11 +492,20 @@ impl ConfigFile for KeymapConfig {
             None => self.clone(),
             Some(other) => {
                 tracing::info!("Merging keymap config");
+                let layout = Layout::from(other.layout.unwrap_or_default());
+                // The bindings of the default configuration are written with
+                // the logical characters too.
+                self.core_window = remap_bindings(std::mem::take(&mut self.core_window), &layout);
+                self.chat_list = remap_bindings(std::mem::take(&mut self.chat_list), &layout);
+                self.chat = remap_bindings(std::mem::take(&mut self.chat), &layout);
+                self.prompt = remap_bindings(std::mem::take(&mut self.prompt), &layout);
                 // It is important that the default keymap is merged first.
                 // The other keymaps can override the default keymap, but the
                 // default keymap can not override the other keymaps.
                 if let Some(default) = other.core_window {
-                    for (k, v) in Self::keymaps_vec_to_map(default.keymap, KeymapKind::CoreWindow) {
+                    for (k, v) in
+                        Self::keymaps_vec_to_map(default.keymap, KeymapKind::CoreWindow, &layout)
+                    {
                         if self.core_window.insert(k.clone(), v).is_some() {
                             tracing::warn!(
                                     "Keymap entry {:?} is already present in the default section, you are overriding it",
@@ -354,7 +515,9 @@ impl ConfigFile for KeymapConfig {
                     }
                 }
                 if let Some(chat_list) = other.chat_list {
-                    for (k, v) in Self::keymaps_vec_to_map(chat_list.keymap, KeymapKind::ChatList) {
+                    for (k, v) in
+                        Self::keymaps_vec_to_map(chat_list.keymap, KeymapKind::ChatList, &layout)
+                    {
                         if self.chat_list.insert(k.clone(), v).is_some() {
                             tracing::warn!(
                                     "Keymap entry {:?} is already present in the chat list section, you are overriding it",
@@ -364,7 +527,7 @@ impl ConfigFile for KeymapConfig {
                     }
                 }
                 if let Some(chat) = other.chat {
-                    for (k, v) in Self::keymaps_vec_to_map(chat.keymap, KeymapKind::Chat) {
+                    for (k, v) in Self::keymaps_vec_to_map(chat.keymap, KeymapKind::Chat, &layout) {
                         if self.chat.insert(k.clone(), v).is_some() {
                             tracing::warn!(
                                     "Keymap entry {:?} is already present in the chat section, you are overriding it",
@@ -374,7 +537,9 @@ impl ConfigFile for KeymapConfig {
                     }
                 }
                 if let Some(prompt) = other.prompt {
-                    for (k, v) in Self::keymaps_vec_to_map(prompt.keymap, KeymapKind::Prompt) {
+                    for (k, v) in
+                        Self::keymaps_vec_to_map(prompt.keymap, KeymapKind::Prompt, &layout)
+                    {
                         if self.prompt.insert(k.clone(), v).is_some() {
                             tracing::warn!(
                                     "Keymap entry {:?} is already present in the prompt section, you are overriding it",
@@ -404,12 +569,17 @@ impl Default for KeymapConfig {
 /// configuration.
 impl From<KeymapRaw> for KeymapConfig {
     fn from(raw: KeymapRaw) -> Self {
-        let core_window =
-            Self::keymaps_vec_to_map(raw.core_window.unwrap().keymap, KeymapKind::CoreWindow);
+        let layout = Layout::from(raw.layout.unwrap_or_default());
+        let core_window = Self::keymaps_vec_to_map(
+            raw.core_window.unwrap().keymap,
+            KeymapKind::CoreWindow,
+            &layout,
+        );
         let chat_list =
-            Self::keymaps_vec_to_map(raw.chat_list.unwrap().keymap, KeymapKind::ChatList);
-        let chat = Self::keymaps_vec_to_map(raw.chat.unwrap().keymap, KeymapKind::Chat);
-        let prompt = Self::keymaps_vec_to_map(raw.prompt.unwrap().keymap, KeymapKind::Prompt);
+            Self::keymaps_vec_to_map(raw.chat_list.unwrap().keymap, KeymapKind::ChatList, &layout);
+        let chat = Self::keymaps_vec_to_map(raw.chat.unwrap().keymap, KeymapKind::Chat, &layout);
+        let prompt =
+            Self::keymaps_vec_to_map(raw.prompt.unwrap().keymap, KeymapKind::Prompt, &layout);
         Self::check_duplicates(&core_window, &chat_list, &chat, &prompt);
         Self {
             core_window,
@@ -427,14 +597,115 @@ mod tests {
             action::Action,
             configs::{
                 config_file::ConfigFile,
-                custom::keymap_custom::{ActionBinding, KeymapConfig},
+                custom::keymap_custom::{
+                    remap_char, remap_key, ActionBinding, KeymapConfig, Layout,
+                },
                 raw::keymap_raw::{KeymapEntry, KeymapMode, KeymapRaw},
             },
             event::Event,
         },
-        std::str::FromStr,
+        std::{collections::HashMap, str::FromStr},
     };
 
+    fn dvorak() -> Layout {
+        Layout::from(HashMap::from([
+            ("j".to_string(), "h".to_string()),
+            ("k".to_string(), "t".to_string()),
+            ("invalid".to_string(), "x".to_string()),
+        ]))
+    }
+
+    #[test]
+    fn test_remap_char() {
+        let layout = dvorak();
+        assert_eq!(remap_char('j', &layout), 'h');
+        assert_eq!(remap_char('k', &layout), 't');
+        // The characters that are not in the table pass through unchanged.
+        assert_eq!(remap_char('q', &layout), 'q');
+        assert_eq!(remap_char('i', &layout), 'i');
+        assert_eq!(remap_char('j', &Layout::default()), 'j');
+    }
+
+    #[test]
+    fn test_remap_key() {
+        let layout = dvorak();
+        assert_eq!(remap_key("j", &layout), "h");
+        assert_eq!(remap_key("ctrl+k", &layout), "ctrl+t");
+        assert_eq!(remap_key("ctrl+alt+j", &layout), "ctrl+alt+h");
+        assert_eq!(remap_key("esc", &layout), "esc");
+        assert_eq!(remap_key("alt+left", &layout), "alt+left");
+    }
+
+    #[test]
+    fn test_keymap_config_merge_layout() {
+        let mut keymap_config = KeymapConfig::from(KeymapRaw {
+            core_window: Some(KeymapMode {
+                keymap: vec![KeymapEntry {
+                    keys: vec!["j".to_string()],
+                    command: "render".to_string(),
+                    description: None,
+                    double_command: None,
+                }],
+            }),
+            chat_list: Some(KeymapMode { keymap: vec![] }),
+            chat: Some(KeymapMode { keymap: vec![] }),
+            prompt: Some(KeymapMode { keymap: vec![] }),
+            layout: None,
+        });
+        keymap_config = keymap_config.merge(Some(KeymapRaw {
+            core_window: Some(KeymapMode {
+                keymap: vec![KeymapEntry {
+                    keys: vec!["ctrl+k".to_string()],
+                    command: "quit".to_string(),
+                    description: None,
+                    double_command: None,
+                }],
+            }),
+            chat_list: None,
+            chat: None,
+            prompt: None,
+            layout: Some(HashMap::from([
+                ("j".to_string(), "h".to_string()),
+                ("k".to_string(), "t".to_string()),
+            ])),
+        }));
+        assert!(keymap_config
+            .core_window
+            .contains_key(&Event::from_str("h").unwrap()));
+        assert!(keymap_config
+            .core_window
+            .contains_key(&Event::from_str("ctrl+t").unwrap()));
+        assert!(!keymap_config
+            .core_window
+            .contains_key(&Event::from_str("j").unwrap()));
+    }
+
+    #[test]
+    fn test_keymap_config_double_command() {
+        let keymap_config = KeymapConfig::from(KeymapRaw {
+            core_window: Some(KeymapMode { keymap: vec![] }),
+            chat_list: Some(KeymapMode { keymap: vec![] }),
+            chat: Some(KeymapMode {
+                keymap: vec![KeymapEntry {
+                    keys: vec!["g".to_string()],
+                    command: "chat_window_previous".to_string(),
+                    description: None,
+                    double_command: Some("chat_window_jump_to_newest".to_string()),
+                }],
+            }),
+            prompt: Some(KeymapMode { keymap: vec![] }),
+            layout: None,
+        });
+        assert_eq!(
+            keymap_config.chat.get(&Event::from_str("g").unwrap()),
+            Some(&ActionBinding::Single {
+                action: Action::ChatWindowPrevious,
+                description: None,
+                double_action: Some(Action::ChatWindowJumpToNewest),
+            })
+        );
+    }
+
     #[test]
     fn test_keymap_config_default() {
         let keymap_config = KeymapConfig::default();
@@ -451,6 +722,7 @@ mod tests {
             chat_list: Some(KeymapMode { keymap: vec![] }),
             chat: Some(KeymapMode { keymap: vec![] }),
             prompt: Some(KeymapMode { keymap: vec![] }),
+            layout: None,
         };
         let keymap_config = KeymapConfig::from(keymap_raw);
         assert_eq!(keymap_config.core_window.len(), 0);
@@ -467,11 +739,13 @@ mod tests {
                     keys: vec!["q".to_string()],
                     command: "quit".to_string(),
                     description: None,
+                    double_command: None,
                 }],
             }),
             chat_list: Some(KeymapMode { keymap: vec![] }),
             chat: Some(KeymapMode { keymap: vec![] }),
             prompt: Some(KeymapMode { keymap: vec![] }),
+            layout: None,
         };
         let keymap_config = KeymapConfig::from(keymap_raw);
         assert_eq!(keymap_config.core_window.len(), 1);
@@ -488,11 +762,13 @@ mod tests {
                     keys: vec!["q".to_string()],
                     command: "quit".to_string(),
                     description: None,
+                    double_command: None,
                 }],
             }),
             chat_list: Some(KeymapMode { keymap: vec![] }),
             chat: Some(KeymapMode { keymap: vec![] }),
             prompt: Some(KeymapMode { keymap: vec![] }),
+            layout: None,
         };
         let mut keymap_config = KeymapConfig::from(keymap_raw);
         let keymap_raw = KeymapRaw {
@@ -501,11 +777,13 @@ mod tests {
                     keys: vec!["q".to_string()],
                     command: "render".to_string(),
                     description: None,
+                    double_command: None,
                 }],
             }),
             chat_list: Some(KeymapMode { keymap: vec![] }),
             chat: Some(KeymapMode { keymap: vec![] }),
             prompt: Some(KeymapMode { keymap: vec![] }),
+            layout: None,
         };
         keymap_config = keymap_config.merge(Some(keymap_raw));
         assert_eq!(keymap_config.core_window.len(), 1);
@@ -520,7 +798,8 @@ mod tests {
                 .clone(),
             ActionBinding::Single {
                 action: Action::from_str("render").unwrap(),
-                description: None
+                description: None,
+                double_action: None
             }
         );
     }
@@ -538,6 +817,7 @@ mod tests {
             chat_list: Some(KeymapMode { keymap: vec![] }),
             chat: Some(KeymapMode { keymap: vec![] }),
             prompt: Some(KeymapMode { keymap: vec![] }),
+            layout: None,
         };
         keymap_config = keymap_config.merge(Some(keymap_raw));
         assert_eq!(keymap_config.core_window.len(), 15);
@@ -49,6 +49,52 @@ impl TelegramConfig {
             &configs::custom::default_config_telegram_file_path()?,
         ))
     }
+    /// Set the directory where the database and the files of TDLib are
+    /// stored. TDLib reads it once per client, so it is used after a restart.
+    ///
+    /// # Arguments
+    /// * `database_dir` - The path of the directory, it must exist and be
+    ///   writable.
+    ///
+    /// # Returns
+    /// * `Result<(), AppError>` - An Ok result or an error if the directory is
+    ///   not valid.
+    pub fn set_database_dir(&mut self, database_dir: String) -> Result<(), AppError<()>> {
+        validate_database_dir(Path::new(&database_dir))?;
+        self.database_dir = database_dir;
+        Ok(())
+    }
+}
+/// Check that the directory where the database is stored exists and is
+/// writable.
+///
+/// # Arguments
+/// * `path` - The path of the directory.
+///
+/// # Returns
+/// * `Result<(), AppError>` - An Ok result or an error if the directory is
+///   not valid.
+pub fn validate_database_dir(path: &Path) -> Result<(), AppError<()>> {
+    if !path.is_dir() {
+        return Err(AppError::InvalidDirectory(format!(
+            "{} does not exist",
+            path.display()
+        )));
+    }
+    // The permissions are not enough to know whether the directory is
+    // writable by the current user, so a file is created and removed.
+    let probe = path.join(".tgt_write_probe");
+    match std::fs::File::create(&probe) {
+        Ok(_) => {
+            std::fs::remove_file(&probe)?;
+            Ok(())
+        }
+        Err(e) => Err(AppError::InvalidDirectory(format!(
+            "{} is not writable: {}",
+            path.display(),
+            e
+        ))),
+    }
 }
 /// The implementation of the configuration file for telegram.
 impl ConfigFile for TelegramConfig {
@@ -160,12 +206,44 @@ impl From<TelegramRaw> for TelegramConfig {
 mod tests {
     use crate::{
         configs::{
-            config_file::ConfigFile, custom::telegram_custom::TelegramConfig,
+            config_file::ConfigFile,
+            custom::telegram_custom::{validate_database_dir, TelegramConfig},
             raw::telegram_raw::TelegramRaw,
         },
         utils,
     };
 
+    #[test]
+    fn test_validate_database_dir() {
+        let dir = std::env::temp_dir().join("tgt_test_validate_database_dir");
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(validate_database_dir(&dir).is_ok());
+        assert!(!dir.join(".tgt_write_probe").exists());
+
+        let file = dir.join("file");
+        std::fs::write(&file, "").unwrap();
+        assert!(validate_database_dir(&file).is_err());
+        assert!(validate_database_dir(&dir.join("missing")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_set_database_dir() {
+        let mut telegram_config = TelegramConfig::default();
+        let database_dir = telegram_config.database_dir.clone();
+        assert!(telegram_config
+            .set_database_dir("/tgt/missing/directory".to_string())
+            .is_err());
+        assert_eq!(telegram_config.database_dir, database_dir);
+
+        let dir = std::env::temp_dir().join("tgt_test_set_database_dir");
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = dir.to_string_lossy().to_string();
+        assert!(telegram_config.set_database_dir(dir.clone()).is_ok());
+        assert_eq!(telegram_config.database_dir, dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_telegram_config_default() {
         let telegram_config = TelegramConfig::default();
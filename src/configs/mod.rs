@@ -4,6 +4,7 @@ use config::File;
 use config::FileFormat;
 use serde::de::DeserializeOwned;
 use std::path::Path;
+use std::{fs, io};
 
 pub mod custom;
 pub mod raw;
@@ -47,3 +48,144 @@ where
 {
     deserialize_to_config::<R>(file_path).map(|s| s.into())
 }
+
+/// Set the value of a top level key of a TOML document, the line of the key
+/// is replaced if it exists and appended otherwise.
+/// The other lines, like the comments, are kept as they are.
+///
+/// # Arguments
+/// * `content` - The TOML document.
+/// * `key` - The key to set.
+/// * `value` - The value of the key, already formatted as TOML.
+///
+/// # Returns
+/// The TOML document with the key set.
+pub fn set_toml_value(content: &str, key: &str, value: &str) -> String {
+    let entry = format!("{} = {}", key, value);
+    let mut found = false;
+    let mut lines = content
+        .lines()
+        .map(|line| {
+            let is_key = line
+                .split_once('=')
+                .is_some_and(|(k, _)| k.trim() == key && !line.trim_start().starts_with('#'));
+            if is_key && !found {
+                found = true;
+                entry.clone()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<String>>();
+    if !found {
+        lines.push(entry);
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Format a string as a TOML basic string, escaping the quotes, the
+/// backslashes and the control characters.
+///
+/// # Arguments
+/// * `value` - The string to format.
+///
+/// # Returns
+/// The quoted TOML string.
+pub fn toml_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", u32::from(c))),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Persist the value of a top level key in a configuration file, the file is
+/// created if it does not exist.
+///
+/// # Arguments
+/// * `file_path` - The path to the configuration file.
+/// * `key` - The key to set.
+/// * `value` - The value of the key, already formatted as TOML.
+///
+/// # Returns
+/// An Ok result or an error if the file cannot be read or written.
+pub fn persist_toml_value(file_path: &Path, key: &str, value: &str) -> Result<(), AppError<()>> {
+    let content = match fs::read_to_string(file_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    fs::write(file_path, set_toml_value(&content, key, value))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configs::{
+        deserialize_to_config, persist_toml_value,
+        raw::app_raw::{AppRaw, SelectionStyle},
+        set_toml_value, toml_string,
+    };
+
+    #[test]
+    fn test_set_toml_value() {
+        let content = "# selection_style = \"brackets\"\nmouse_support = true\n";
+        assert_eq!(
+            set_toml_value(content, "selection_style", "\"left_bar\""),
+            "# selection_style = \"brackets\"\nmouse_support = true\nselection_style = \"left_bar\"\n"
+        );
+        let content = "mouse_support = true\nselection_style = \"reverse\"\n";
+        assert_eq!(
+            set_toml_value(content, "selection_style", "\"left_bar\""),
+            "mouse_support = true\nselection_style = \"left_bar\"\n"
+        );
+    }
+
+    #[test]
+    fn test_toml_string() {
+        assert_eq!(toml_string("/home/me/.tgt"), "\"/home/me/.tgt\"");
+        assert_eq!(toml_string("C:\\tg \"db\""), "\"C:\\\\tg \\\"db\\\"\"");
+        // The combining marks stay as they are, the control characters are
+        // escaped.
+        assert_eq!(toml_string("e\u{301}\u{7}\n"), "\"e\u{301}\\u0007\\n\"");
+    }
+
+    #[test]
+    fn test_selection_style_cycle_persisted() {
+        let dir = std::env::temp_dir().join(format!(
+            "tgt_test_selection_style_cycle_persisted_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("app.toml");
+        let mut selection_style = SelectionStyle::default();
+        for expected in [
+            SelectionStyle::Reverse,
+            SelectionStyle::LeftBar,
+            SelectionStyle::Brackets,
+            SelectionStyle::Highlight,
+        ] {
+            selection_style = selection_style.next();
+            assert_eq!(selection_style, expected);
+            persist_toml_value(
+                &path,
+                "selection_style",
+                &format!("\"{}\"", selection_style),
+            )
+            .unwrap();
+            let raw = deserialize_to_config::<AppRaw>(&path).unwrap();
+            assert_eq!(raw.selection_style, Some(expected));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
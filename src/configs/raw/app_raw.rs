@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::fmt::{self, Display, Formatter};
 
 #[derive(Clone, Debug, Deserialize)]
 /// The raw application configuration.
@@ -26,4 +27,298 @@ pub struct AppRaw {
     /// A boolean flag that represents whether the API_HASH should be taken from
     /// the Telegram configuration or from environment variables `API_HASH`.
     pub take_api_hash_from_telegram_config: Option<bool>,
+    /// A boolean flag that represents whether sending a message should snap the
+    /// chat view back to the most recent message or leave the reader in place.
+    pub jump_to_bottom_on_send: Option<bool>,
+    /// The maximum number of characters that can be pasted into the prompt
+    /// without asking for confirmation. The value 0 disables the guard.
+    pub max_paste_size: Option<usize>,
+    /// A boolean flag that represents whether a "New messages" divider should
+    /// be shown at the last read message when opening a chat with unread messages.
+    pub show_new_messages_divider: Option<bool>,
+    /// The maximum number of times a message that failed to send is retried
+    /// before being marked as failed.
+    pub send_max_retries: Option<u32>,
+    /// The time, in milliseconds, to wait before the first retry of a message
+    /// that failed to send.
+    pub send_retry_backoff_ms: Option<u32>,
+    /// What happens when the last message of the open chat is deleted, either
+    /// `keep_open` or `next_chat`.
+    pub delete_last_behavior: Option<DeleteLastBehavior>,
+    /// Whether the destructive actions, like deleting messages, ask for
+    /// confirmation before being performed.
+    pub confirm_destructive: Option<bool>,
+    /// The number of lines of context kept above and below the selected message.
+    /// It is clamped to less than the height of the message list.
+    pub scroll_margin: Option<u16>,
+    /// What the Backspace key does when the prompt is not focused.
+    pub backspace_action: Option<BackspaceAction>,
+    /// The maximum number of reactions shown under a message, the others are
+    /// collapsed into a `+N` indicator. It is `0` to show all the reactions.
+    pub max_reactions_shown: Option<usize>,
+    /// How the selected message is highlighted.
+    pub selection_style: Option<SelectionStyle>,
+    /// Whether to ask to send the draft of a chat when its reminder is due.
+    pub draft_reminder_prompt: Option<bool>,
+    /// What the Tab key does: `cycle_focus`, `mention_completion` or `indent`.
+    pub tab_behavior: Option<TabBehavior>,
+    /// Whether the messages are marked as read only when they are selected, instead of when the
+    /// chat is opened.
+    pub read_on_scroll_to: Option<bool>,
+    /// The maximum number of TDLib requests in flight, downloads and resends included, 0 means
+    /// no limit.
+    pub max_concurrent_requests: Option<usize>,
+    /// What the Esc key does in the prompt: `unfocus`, `clear_text` or `discard_reply`.
+    pub compose_escape_action: Option<ComposeEscapeAction>,
+    /// Whether the Saved Messages are used as a notepad.
+    pub saved_messages_notepad_mode: Option<bool>,
+    /// The inactivity, in seconds, after which the user is set offline.
+    pub auto_away_timeout: Option<i32>,
+    /// The maximum width the text of the messages is wrapped to.
+    pub message_content_width: Option<u16>,
+    /// The number of messages requested for each page of the history.
+    pub history_page_size: Option<i32>,
+    /// How precise the times of the messages are.
+    pub timestamp_granularity: Option<TimestampGranularity>,
+    /// The inactivity, in seconds, after which a half-typed message is saved as
+    /// a draft and the compose box is cleared.
+    pub compose_auto_clear_timeout: Option<i32>,
+    /// What happens when a message arrives in the open chat while the history is
+    /// scrolled up.
+    pub scrolled_up_new_message_behavior: Option<ScrolledUpNewMessageBehavior>,
+    /// How the messages that a message replies to are previewed above it.
+    pub reply_preview_mode: Option<ReplyPreviewMode>,
+    /// The minimum time, in milliseconds, between two renders of the user interface.
+    pub render_interval: Option<u32>,
+    /// The maximum width of the chat titles in the chat list.
+    pub chat_title_max_width: Option<u16>,
+    /// The maximum time, in milliseconds, between the two presses of a double press.
+    pub double_key_window: Option<u32>,
+    /// The maximum time, in milliseconds, the pending drafts are flushed and
+    /// the TDLib client is closed for when quitting.
+    pub shutdown_timeout: Option<u32>,
+    /// What the Home and End keys do in the chat.
+    pub home_end_behavior: Option<HomeEndBehavior>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+/// What happens when the last message of the open chat is deleted.
+pub enum DeleteLastBehavior {
+    /// Keep the empty chat open.
+    #[default]
+    KeepOpen,
+    /// Open the next chat of the chat list.
+    NextChat,
+}
+/// Implement the `Display` trait for `DeleteLastBehavior`.
+impl Display for DeleteLastBehavior {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DeleteLastBehavior::KeepOpen => write!(f, "keep_open"),
+            DeleteLastBehavior::NextChat => write!(f, "next_chat"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+/// What the Backspace key does when the prompt is not focused.
+pub enum BackspaceAction {
+    /// Go back to the previous component: the chat list from the chat, and
+    /// nothing from the chat list.
+    NavigateBack,
+    /// Close the panel open over the chat.
+    CloseOverlay,
+    /// Do nothing.
+    #[default]
+    Noop,
+}
+/// Implement the `Display` trait for `BackspaceAction`.
+impl Display for BackspaceAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BackspaceAction::NavigateBack => write!(f, "navigate_back"),
+            BackspaceAction::CloseOverlay => write!(f, "close_overlay"),
+            BackspaceAction::Noop => write!(f, "noop"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+/// What the Home and End keys do, the prompt always uses them to move to the
+/// start and the end of the line.
+pub enum HomeEndBehavior {
+    /// Select the first or the last loaded message of the chat.
+    JumpToEdge,
+    /// Only move to the start and the end of the line in the prompt.
+    #[default]
+    LineStartEnd,
+    /// Scroll the messages of the chat by a page.
+    ScrollPage,
+}
+/// Implement the `Display` trait for `HomeEndBehavior`.
+impl Display for HomeEndBehavior {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            HomeEndBehavior::JumpToEdge => write!(f, "jump_to_edge"),
+            HomeEndBehavior::LineStartEnd => write!(f, "line_start_end"),
+            HomeEndBehavior::ScrollPage => write!(f, "scroll_page"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+/// What the Tab key does.
+pub enum TabBehavior {
+    /// Move the focus to the next component.
+    #[default]
+    CycleFocus,
+    /// Complete the `@`-mention before the cursor of the prompt.
+    MentionCompletion,
+    /// Indent the text of the prompt.
+    Indent,
+}
+/// Implement the `Display` trait for `TabBehavior`.
+impl Display for TabBehavior {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TabBehavior::CycleFocus => write!(f, "cycle_focus"),
+            TabBehavior::MentionCompletion => write!(f, "mention_completion"),
+            TabBehavior::Indent => write!(f, "indent"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+/// What the Esc key does in the prompt.
+pub enum ComposeEscapeAction {
+    /// Move the focus away from the prompt, back to the navigation mode.
+    #[default]
+    Unfocus,
+    /// Clear the text of the prompt and keep the focus.
+    ClearText,
+    /// Discard the message being replied to or edited.
+    DiscardReply,
+}
+/// Implement the `Display` trait for `ComposeEscapeAction`.
+impl Display for ComposeEscapeAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ComposeEscapeAction::Unfocus => write!(f, "unfocus"),
+            ComposeEscapeAction::ClearText => write!(f, "clear_text"),
+            ComposeEscapeAction::DiscardReply => write!(f, "discard_reply"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+/// How precise the times of the messages are.
+pub enum TimestampGranularity {
+    /// The hours and the minutes, with the date only for the messages older
+    /// than yesterday.
+    #[default]
+    Minute,
+    /// The hours, the minutes and the seconds, with the date only for the
+    /// messages older than yesterday.
+    Second,
+    /// The full date and time, with the seconds.
+    DateTime,
+}
+/// Implement the `Display` trait for `TimestampGranularity`.
+impl Display for TimestampGranularity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TimestampGranularity::Minute => write!(f, "minute"),
+            TimestampGranularity::Second => write!(f, "second"),
+            TimestampGranularity::DateTime => write!(f, "date_time"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+/// What happens when a message arrives in the open chat while the history is
+/// scrolled up, the view always stays on the messages being read.
+pub enum ScrolledUpNewMessageBehavior {
+    /// Show a "N new ↓" button that counts the new messages.
+    #[default]
+    JumpButton,
+    /// Flash the new messages in the status bar.
+    FlashStatusBar,
+    /// Do nothing.
+    Nothing,
+}
+/// Implement the `Display` trait for `ScrolledUpNewMessageBehavior`.
+impl Display for ScrolledUpNewMessageBehavior {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScrolledUpNewMessageBehavior::JumpButton => write!(f, "jump_button"),
+            ScrolledUpNewMessageBehavior::FlashStatusBar => write!(f, "flash_status_bar"),
+            ScrolledUpNewMessageBehavior::Nothing => write!(f, "nothing"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+/// How a reply previews the message it replies to, the same mode is used by
+/// every reply in the view.
+pub enum ReplyPreviewMode {
+    /// Show the sender and the quoted text above the reply.
+    #[default]
+    Inline,
+    /// Show only "↩ sender" above the reply.
+    Compact,
+}
+/// Implement the `Display` trait for `ReplyPreviewMode`.
+impl Display for ReplyPreviewMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplyPreviewMode::Inline => write!(f, "inline"),
+            ReplyPreviewMode::Compact => write!(f, "compact"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+/// How the selected message is highlighted.
+pub enum SelectionStyle {
+    /// Highlight the message like the other selected items.
+    #[default]
+    Highlight,
+    /// Reverse the colors of the message.
+    Reverse,
+    /// Mark the message with a bar on its left.
+    LeftBar,
+    /// Enclose the message in brackets.
+    Brackets,
+}
+/// Implementation of the `SelectionStyle` enum.
+impl SelectionStyle {
+    /// Get the style that follows this one when the styles are cycled.
+    pub fn next(self) -> Self {
+        match self {
+            SelectionStyle::Highlight => SelectionStyle::Reverse,
+            SelectionStyle::Reverse => SelectionStyle::LeftBar,
+            SelectionStyle::LeftBar => SelectionStyle::Brackets,
+            SelectionStyle::Brackets => SelectionStyle::Highlight,
+        }
+    }
+}
+/// Implement the `Display` trait for `SelectionStyle`.
+impl Display for SelectionStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectionStyle::Highlight => write!(f, "highlight"),
+            SelectionStyle::Reverse => write!(f, "reverse"),
+            SelectionStyle::LeftBar => write!(f, "left_bar"),
+            SelectionStyle::Brackets => write!(f, "brackets"),
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Clone, Debug, Deserialize)]
 /// The command keymap configuration.
@@ -9,6 +10,11 @@ pub struct KeymapEntry {
     /// The command to execute.
     /// It must be a valid command.
     pub command: String, // Action
+    /// The command to execute when the key is pressed twice rapidly, within
+    /// the `double_key_window` of the application configuration.
+    /// It must be a valid command, and it is only used for the single keys.
+    /// The first press still executes the `command`.
+    pub double_command: Option<String>, // Action
     /// The description of the command.
     pub description: Option<String>,
 }
@@ -32,4 +38,7 @@ pub struct KeymapRaw {
     pub chat: Option<KeymapMode>,
     /// The keymap for the chat edit mode.
     pub prompt: Option<KeymapMode>,
+    /// The characters of the bindings remapped to the ones typed with the
+    /// keyboard layout, e.g. `"j" = "h"` on Dvorak.
+    pub layout: Option<HashMap<String, String>>,
 }
@@ -0,0 +1,113 @@
+use crate::event::Event;
+use std::time::Instant;
+
+/// Check whether a key press is the second one of a double press.
+///
+/// # Arguments
+/// * `prev_ms` - When the same key was previously pressed, in milliseconds.
+/// * `now_ms` - When the key is pressed, in milliseconds.
+/// * `window` - The maximum time, in milliseconds, between the two presses
+///   of a double press, `0` to disable the double presses.
+///
+/// # Returns
+/// * `bool` - Whether the two presses are a double press.
+pub fn is_double(prev_ms: u64, now_ms: u64, window: u32) -> bool {
+    window > 0 && now_ms >= prev_ms && now_ms - prev_ms <= u64::from(window)
+}
+
+#[derive(Debug)]
+/// `DoubleKeyTracker` remembers the last key pressed, so that two rapid
+/// presses of the same key can trigger a different action from a single
+/// press.
+pub struct DoubleKeyTracker {
+    /// The monotonic base of the press times.
+    start: Instant,
+    /// The last key pressed and when, in milliseconds since `start`. It is
+    /// `None` after a double press, so that a third press starts a new pair.
+    last: Option<(Event, u64)>,
+    /// Whether the last key press completed a double press.
+    double: bool,
+}
+/// Implementation of the `Default` trait for the `DoubleKeyTracker` struct.
+impl Default for DoubleKeyTracker {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            last: None,
+            double: false,
+        }
+    }
+}
+/// Implementation of the `DoubleKeyTracker` struct.
+impl DoubleKeyTracker {
+    /// Record a key press.
+    ///
+    /// # Arguments
+    /// * `event` - The key event, normalized.
+    /// * `now` - When the key is pressed.
+    /// * `window` - The maximum time, in milliseconds, between the two
+    ///   presses of a double press.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the press completed a double press.
+    pub fn press(&mut self, event: Event, now: Instant, window: u32) -> bool {
+        let now_ms = now.saturating_duration_since(self.start).as_millis() as u64;
+        self.double = matches!(
+            &self.last,
+            Some((last, prev_ms)) if *last == event && is_double(*prev_ms, now_ms, window)
+        );
+        self.last = if self.double {
+            None
+        } else {
+            Some((event, now_ms))
+        };
+        self.double
+    }
+
+    /// Get whether the last key press completed a double press.
+    pub fn last_was_double(&self) -> bool {
+        self.double
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        double_key::{is_double, DoubleKeyTracker},
+        event::Event,
+    };
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_is_double_timing_boundary() {
+        assert!(is_double(1000, 1000, 300));
+        assert!(is_double(1000, 1300, 300));
+        // A slow second press is a single press.
+        assert!(!is_double(1000, 1301, 300));
+        // The window 0 disables the double presses.
+        assert!(!is_double(1000, 1000, 0));
+        // Presses given out of order are not a double press.
+        assert!(!is_double(1000, 999, 300));
+    }
+
+    #[test]
+    fn test_tracker_pairs_the_presses() {
+        let g = Event::Key(KeyCode::Char('g'), KeyModifiers::NONE);
+        let j = Event::Key(KeyCode::Char('j'), KeyModifiers::NONE);
+        let mut tracker = DoubleKeyTracker::default();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        assert!(!tracker.press(g.clone(), at(0), 300));
+        assert!(tracker.press(g.clone(), at(100), 300));
+        assert!(tracker.last_was_double());
+        // A third press starts a new pair.
+        assert!(!tracker.press(g.clone(), at(200), 300));
+        assert!(!tracker.last_was_double());
+        // Another key in between breaks the pair.
+        assert!(!tracker.press(j, at(250), 300));
+        assert!(!tracker.press(g.clone(), at(300), 300));
+        // Two slow presses are two singles.
+        assert!(!tracker.press(g, at(700), 300));
+    }
+}
@@ -1,5 +1,9 @@
 use crate::app_error::AppError;
-use crate::tg::td_enums::{TdChatList, TdMessageReplyToMessage};
+use crate::configs::raw::app_raw::{
+    BackspaceAction, ComposeEscapeAction, DeleteLastBehavior, HomeEndBehavior, ReplyPreviewMode,
+    ScrolledUpNewMessageBehavior, TabBehavior, TimestampGranularity,
+};
+use crate::tg::td_enums::{TdChatList, TdMessageReplyToMessage, TdParseMode};
 use crossterm::event::{KeyCode, KeyModifiers, MouseEvent};
 use ratatui::layout::Rect;
 use std::fmt::{self, Display, Formatter};
@@ -29,6 +33,10 @@ pub enum Event {
     FocusLost,
     /// Focus Gained event.
     FocusGained,
+    /// Quit event.
+    /// The pending drafts are flushed and the view state is saved before the
+    /// TDLib client is closed, a second quit skips the flush.
+    Quit,
 
     /// Update area event with a `Rect` struct.
     UpdateArea(Rect),
@@ -60,8 +68,399 @@ pub enum Event {
     /// If `revoke` is true, the message will be deleted for everyone.
     /// If `revoke` is false, the message will be deleted only for the current user.
     DeleteMessages(Vec<i64>, bool),
+    /// Forward messages event with a `Vec<i64>`, a `i64` and a `bool`.
+    /// The first parameter is the `message_ids` of the messages of the open
+    /// chat, the second parameter is the `chat_id` of the destination chat
+    /// and the third parameter is the `send_copy`.
+    /// If `send_copy` is true, the messages are sent without the link to the
+    /// original ones.
+    ForwardMessages(Vec<i64>, i64, bool),
     /// View all messages event.
     ViewAllMessages,
+    /// View message event with two `i64`.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// `message_id` that is marked as read, with the older ones.
+    ViewMessage(i64, i64),
+    /// Set read on scroll to event with a `bool`.
+    /// If it is true, the messages are marked as read only when they are
+    /// selected, instead of all at once when the chat is opened.
+    SetReadOnScrollTo(bool),
+
+    /// Set jump to bottom on send event with a `bool`.
+    /// If it is true, sending a message snaps the chat back to the most recent
+    /// message, otherwise the view is left where it was.
+    SetJumpToBottomOnSend(bool),
+    /// Open split view event with two `i64`.
+    /// The first parameter is the `chat_id` of the left chat and the second
+    /// parameter is the `chat_id` of the right chat.
+    /// The left chat is focused and receives the input.
+    OpenSplitView(i64, i64),
+    /// Close split view event.
+    CloseSplitView,
+    /// Swap split focus event.
+    /// It is used to switch which side of the split view receives the input.
+    SwapSplitFocus,
+    /// Press keyboard button event with a `i64` and two `usize`.
+    /// The first parameter is the `message_id` of the message with the
+    /// keyboard, the second and the third parameters are the `row` and the
+    /// `col` of the pressed button.
+    PressKeyboardButton(i64, usize, usize),
+    /// Toggle high contrast event.
+    /// It switches the rendering to a high contrast palette with bolder text,
+    /// overriding the active theme until it is turned off.
+    ToggleHighContrast,
+    /// Toggle avatar initials event.
+    /// The avatars are shown as colored initials next to the chat names and
+    /// the message senders.
+    ToggleAvatarInitials,
+    /// Toggle unsupported placeholders event.
+    /// A placeholder is shown in place of the messages whose content cannot
+    /// be rendered.
+    ToggleUnsupportedPlaceholders,
+    /// Toggle sender timezone event.
+    /// The timestamps of the messages are shown in the time zone of their
+    /// sender, when known, instead of the local one.
+    ToggleSenderTimezone,
+    /// Toggle emoji shortcodes event.
+    /// The `:shortcode:` typed in the prompt are expanded to their emoji.
+    ToggleEmojiShortcodes,
+    /// Toggle view counts event.
+    /// The view count is shown under the posts of the channels.
+    ToggleViewCounts,
+    /// Toggle comment counts event.
+    /// The comment count is shown under the posts of the channels with a
+    /// discussion group.
+    ToggleCommentCounts,
+    /// Open comments event with a `i64`.
+    /// The parameter is the `message_id` of the channel post whose
+    /// discussion thread is opened.
+    OpenComments(i64),
+    /// Toggle channel signatures event.
+    /// The signature of the author is shown under the signed posts of the
+    /// channels.
+    ToggleChannelSignatures,
+    /// Toggle folder tabs event.
+    /// The chat folders of the user are shown as tabs above the chat list.
+    ToggleFolderTabs,
+    /// Toggle unread only view event.
+    /// Only the chats with unread messages, or marked as unread, are shown in
+    /// the chat list.
+    ToggleUnreadOnlyView,
+    /// Toggle search bar event.
+    /// A search input is docked above the chat list, the chats and the
+    /// messages matching the typed text are listed in place of the chat list.
+    ToggleSearchBar,
+    /// Search messages event with a `String`.
+    /// The parameter is the text searched in the messages of all the chats.
+    SearchMessages(String),
+    /// Select folder event with a `i32`.
+    /// The parameter is the identifier of the folder the chat list is
+    /// filtered by, `0` to show all the chats.
+    SelectFolder(i32),
+    /// Toggle presence display event.
+    /// The peer of a private chat is shown as online or with when it was
+    /// last seen next to the title of the chat.
+    TogglePresenceDisplay,
+    /// Toggle syntax highlight event.
+    /// The tokens of the code blocks are colored when the language of the
+    /// block is known, the other blocks are rendered plain.
+    ToggleSyntaxHighlight,
+    /// Toggle big emoji event.
+    /// The messages made only of up to a few emoji are rendered spaced out
+    /// and emphasized, the messages that mix text and emoji are unchanged.
+    ToggleBigEmoji,
+    /// Toggle action audit event.
+    /// While it is enabled, the destructive actions, e.g. deleting messages,
+    /// leaving or muting chats, are appended with their time to a local
+    /// audit log.
+    ToggleActionAudit,
+    /// Show action audit event.
+    /// It shows the most recent entries of the audit log in a read-only
+    /// panel.
+    ShowActionAudit,
+    /// Set max paste size event with a `usize`.
+    /// Pastes longer than this number of characters ask for confirmation
+    /// before being inserted into the prompt. The value 0 disables the guard.
+    SetMaxPasteSize(usize),
+    /// Toggle selected chat mute event.
+    /// It flips the chat highlighted in the chat list between muted forever
+    /// and unmuted.
+    ToggleSelectedChatMute,
+    /// Next album item event.
+    /// It selects the next item of the album selected in the chat.
+    NextAlbumItem,
+    /// Previous album item event.
+    /// It selects the previous item of the album selected in the chat.
+    PrevAlbumItem,
+    /// Next own message event.
+    /// It selects the newer message sent by the current user in the open
+    /// chat, wrapping to the oldest one.
+    NextOwnMessage,
+    /// Previous own message event.
+    /// It selects the older message sent by the current user in the open
+    /// chat, wrapping to the newest one.
+    PrevOwnMessage,
+    /// Set database directory event with a `String`.
+    /// It is the directory where TDLib stores its database and files, it is
+    /// used on the next restart of the client or account switch.
+    SetDatabaseDirectory(String),
+    /// Toggle new messages divider event.
+    /// It shows or hides the "New messages" divider rendered at the last read
+    /// message of the open chat.
+    ToggleNewMessagesDivider,
+    /// Copy visible transcript event.
+    /// It copies the sender and the text of the messages visible in the open
+    /// chat to the clipboard.
+    CopyVisibleTranscript,
+    /// Set send retry policy event with two `u32`.
+    /// The first parameter is the maximum number of retries of a message that
+    /// failed to send and the second parameter is the backoff, in
+    /// milliseconds, before the first retry; it doubles at every attempt.
+    SetSendRetryPolicy(u32, u32),
+    /// Send retry event with a `i64` and a `u32`.
+    /// The first parameter is the `message_id` of the message that failed to
+    /// send and the second parameter is the number of the retry attempt.
+    SendRetry(i64, u32),
+    /// Show send error event with a `i64`.
+    /// The parameter is the `message_id` of the message that failed to send,
+    /// the full error is shown.
+    ShowSendError(i64),
+    /// Toggle mobile layout event.
+    /// It switches between the default layout and the single-pane layout,
+    /// which shows either the chat list or the open chat. The single-pane
+    /// layout is used by default on narrow terminals.
+    ToggleMobileLayout,
+    /// Open reaction picker event with a `i64`.
+    /// The parameter is the `message_id` of the message to react to, the
+    /// picker shows the reactions the chat allows for it.
+    OpenReactionPicker(i64),
+    /// Add reaction event with a `i64` and a `String`.
+    /// The first parameter is the `message_id` and the second parameter is
+    /// the emoji of the reaction.
+    AddReaction(i64, String),
+    /// Show call history event with a `i64`.
+    /// The parameter is the `chat_id` whose recent voice and video calls are
+    /// listed in a panel.
+    ShowCallHistory(i64),
+    /// Show chat permissions event with a `i64`.
+    /// The parameter is the `chat_id` whose permissions of the current user
+    /// and slow mode delay are listed in a panel.
+    ShowChatPermissions(i64),
+    /// Open saved messages event.
+    /// It opens the chat of the current user with itself.
+    OpenSavedMessages,
+    /// Show live location map event with a `i64`.
+    /// The parameter is the `message_id` of the location message whose
+    /// coordinates and map links are shown in a panel.
+    ShowLiveLocationMap(i64),
+    /// Show unread dashboard event.
+    /// It lists the chats with unread messages, from the one with the most.
+    ShowUnreadDashboard,
+    /// Open chat event with a `i64`.
+    /// The parameter is the `chat_id` of the chat to open.
+    OpenChat(i64),
+    /// Open chat at message event with two `i64`.
+    /// The first parameter is the `chat_id` of the chat to open and the second
+    /// parameter is the `message_id` its history is loaded from.
+    OpenChatAtMessage(i64, i64),
+    /// Show edit history event with a `i64`.
+    /// The parameter is the `message_id` of an edited message, its current
+    /// text is shown with the changes of its last edit when they are known.
+    ShowEditHistory(i64),
+    /// Show chat activity event with a `i64`.
+    /// The parameter is the `chat_id` whose loaded history is summarized in a
+    /// sparkline of the number of messages over time.
+    ShowChatActivity(i64),
+    /// Show quiz explanation event with a `i64`.
+    /// The parameter is the `message_id` of the quiz whose explanation is
+    /// shown in a panel, once it is answered.
+    ShowQuizExplanation(i64),
+    /// Open document event with a `i64`.
+    /// The parameter is the `message_id` of a document message, its file is
+    /// downloaded and opened with the default application.
+    OpenDocument(i64),
+    /// Show chat polls event with a `i64`.
+    /// The parameter is the `chat_id` whose polls are listed in a panel, with
+    /// whether the current user voted and whether they are closed.
+    ShowChatPolls(i64),
+    /// Show forum topics event with a `i64`.
+    /// The parameter is the `chat_id` of a forum supergroup whose topics are
+    /// listed in a panel, with their unread messages and whether they are
+    /// closed.
+    ShowForumTopics(i64),
+    /// Open bot menu event with a `i64`.
+    /// The parameter is the `chat_id` of a private chat with a bot, whose
+    /// menu button or commands are shown in a panel.
+    OpenBotMenu(i64),
+    /// Show active sessions event.
+    /// It lists the sessions logged in to the account, with their
+    /// application and their device, the current one is marked.
+    ShowActiveSessions,
+    /// Terminate session event with a `i64`.
+    /// The parameter is the identifier of a session of the account that is
+    /// logged out. The current session can not be terminated.
+    TerminateSession(i64),
+    /// Show scheduled messages event with a `i64`.
+    /// The parameter is the `chat_id` whose scheduled messages are listed in
+    /// a panel, with their send times.
+    ShowScheduledMessages(i64),
+    /// Send scheduled now event with a `i64`.
+    /// The parameter is the `message_id` of a scheduled message of the open
+    /// chat that is sent immediately.
+    SendScheduledNow(i64),
+    /// Preview formatting event with a `String` and a `TdParseMode`.
+    /// The first parameter is the composed text and the second parameter is
+    /// the syntax it is written with, the text is parsed and its formatted
+    /// pieces are shown in a panel before it is sent.
+    PreviewFormatting(String, TdParseMode),
+    /// Operation started event with a `String`.
+    /// The parameter is the label of a long operation that started, it is
+    /// shown with a spinner in the status bar until it finishes.
+    OperationStarted(String),
+    /// Operation finished event with a `String`.
+    /// The parameter is the label of a long operation that finished.
+    OperationFinished(String),
+    /// Set max reactions shown event with a `usize`.
+    /// The parameter is the maximum number of reactions shown under a
+    /// message, `0` to show all.
+    SetMaxReactionsShown(usize),
+    /// Cycle selection style event.
+    /// It rotates how the selected message is highlighted and saves it in the
+    /// configuration.
+    CycleSelectionStyle,
+    /// Set delete last behavior event with a `DeleteLastBehavior`.
+    /// It chooses whether the empty chat is kept open or the next chat is
+    /// opened when the last message of the open chat is deleted.
+    SetDeleteLastBehavior(DeleteLastBehavior),
+    /// Set backspace action event with a `BackspaceAction`.
+    /// It chooses what the Backspace key does when the prompt is not focused.
+    SetBackspaceAction(BackspaceAction),
+    /// Set home end behavior event with a `HomeEndBehavior`.
+    /// It chooses what the Home and End keys do in the chat, the prompt
+    /// always uses them to move to the start and the end of the line.
+    SetHomeEndBehavior(HomeEndBehavior),
+    /// Set tab behavior event with a `TabBehavior`.
+    /// It chooses what the Tab key does in the prompt, elsewhere it always
+    /// moves the focus to the next component.
+    SetTabBehavior(TabBehavior),
+    /// Set timestamp granularity event with a `TimestampGranularity`.
+    /// It chooses how precise the times of the messages are.
+    SetTimestampGranularity(TimestampGranularity),
+    /// Set saved messages notepad mode event with a `bool`.
+    /// The parameter is whether the prompt keeps the focus and the view stays
+    /// at the most recent message after each send in the Saved Messages.
+    SetSavedMessagesNotepadMode(bool),
+    /// Set compose escape action event with a `ComposeEscapeAction`.
+    /// The parameter is what the Esc key does in the prompt.
+    SetComposeEscapeAction(ComposeEscapeAction),
+    /// Follow entity event with a `i64` and a `usize`.
+    /// The first parameter is the `message_id` and the second parameter is
+    /// the index of the `@username` or `#hashtag` entity of the message: a
+    /// username opens its chat and a hashtag is searched in the open chat.
+    FollowEntity(i64, usize),
+    /// Set send as event with two `i64`.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// identifier of the user or of the channel the messages of the chat are
+    /// sent as.
+    SetSendAs(i64, i64),
+    /// Snooze chat event with a `i64` and a `i32`.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// timestamp when the chat is shown again at the top of the chat list.
+    SnoozeChat(i64, i32),
+    /// Set compose placeholder event with a `i64` and a `String`.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// text shown in the empty compose box of the chat, an empty string
+    /// restores the default one.
+    SetComposePlaceholder(i64, String),
+    /// Set draft reminder event with a `i64` and a `i32`.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// timestamp when the draft of the chat is shown again.
+    SetDraftReminder(i64, i32),
+    /// Send draft event with a `i64`.
+    /// The parameter is the `chat_id` whose draft is sent, it is asked when a
+    /// draft reminder is due.
+    SendDraft(i64),
+    /// Copy code block event with a `i64` and a `usize`.
+    /// The first parameter is the `message_id` and the second parameter is the
+    /// index of the code block of the message to copy.
+    CopyCodeBlock(i64, usize),
+    /// Copy message links event with a `i64`.
+    /// The parameter is the `message_id` whose links are copied, one per line.
+    CopyMessageLinks(i64),
+    /// Set confirm destructive event with a `bool`.
+    /// When it is `true` the destructive events wait for a confirmation
+    /// before being handled.
+    SetConfirmDestructive(bool),
+    /// Leave chat event with a `i64`.
+    /// The parameter is the `chat_id` of the group or the channel to leave.
+    LeaveChat(i64),
+    /// Set emoji status event with a `i64`.
+    /// The parameter is the `custom_emoji_id` of the emoji status, `0` to
+    /// clear it.
+    SetEmojiStatus(i64),
+    /// Set scroll margin event with a `u16`.
+    /// The parameter is the number of lines of context kept above and below
+    /// the selected message.
+    SetScrollMargin(u16),
+    /// Set max concurrent requests event with a `usize`.
+    /// The parameter is the maximum number of TDLib requests in flight, the
+    /// other requests wait. The value 0 disables the limit.
+    SetMaxConcurrentRequests(usize),
+    /// Set auto away timeout event with a `i32`.
+    /// The parameter is the inactivity, in seconds, after which the user is
+    /// set offline, it is set online again on the next activity. The value 0
+    /// disables it.
+    SetAutoAwayTimeout(i32),
+    /// Set message content width event with a `u16`.
+    /// The parameter is the maximum width the text of the messages is wrapped
+    /// to, `0` to use the whole width of the messages.
+    SetMessageContentWidth(u16),
+    /// Set history page size event with a `i32`.
+    /// The parameter is the number of messages requested for each page of the
+    /// history when scrolling back, it is clamped to the range TDLib allows.
+    SetHistoryPageSize(i32),
+    /// Set compose auto clear timeout event with a `i32`.
+    /// The parameter is the inactivity, in seconds, after which a half-typed
+    /// message is saved as a draft and the compose box is cleared, `0` to
+    /// never clear it.
+    SetComposeAutoClearTimeout(i32),
+    /// Save draft event with a `i64` and a `String`.
+    /// The first parameter is the `chat_id` and the second parameter is the
+    /// text saved as the draft of the chat.
+    SaveDraft(i64, String),
+    /// Set scrolled up new message behavior event with a
+    /// `ScrolledUpNewMessageBehavior`.
+    /// It chooses what happens when a message arrives in the open chat while
+    /// the history is scrolled up.
+    SetScrolledUpNewMessageBehavior(ScrolledUpNewMessageBehavior),
+    /// Set reply preview mode event with a `ReplyPreviewMode`.
+    /// It chooses whether the replies show the quoted text above them or
+    /// only the sender of the message they reply to.
+    SetReplyPreviewMode(ReplyPreviewMode),
+    /// Set render interval event with a `u32`.
+    /// The parameter is the minimum time, in milliseconds, between two
+    /// renders of the user interface, the updates received in between are
+    /// drawn together. `0` renders on every event.
+    SetRenderInterval(u32),
+    /// Set chat title max width event with a `u16`.
+    /// The parameter is the maximum width, in columns, of the chat titles in
+    /// the chat list, the longer ones are ellipsized. `0` shows the whole
+    /// titles.
+    SetChatTitleMaxWidth(u16),
+    /// Set double key window event with a `u32`.
+    /// The parameter is the maximum time, in milliseconds, between two
+    /// presses of the same key for them to trigger the double press action of
+    /// its binding. `0` disables the double presses.
+    SetDoubleKeyWindow(u32),
+    /// Translate message event with a `i64` and a `String`.
+    /// The first parameter is the `message_id` of a message of the open chat
+    /// and the second parameter is the code of the language it is translated
+    /// to, the translation is shown beneath the original text.
+    TranslateMessage(i64, String),
+    /// Toggle translation event with a `i64`.
+    /// The parameter is the `message_id` whose translation is hidden or shown
+    /// again.
+    ToggleTranslation(i64),
 }
 /// Implement the `Event` enum.
 impl Event {
@@ -74,7 +473,7 @@ impl Event {
     /// # Returns
     /// * `Result<Event, AppError>` - An event or an error.
     pub fn event_with_modifiers(s: &str, modifiers: KeyModifiers) -> Result<Event, AppError<()>> {
-        match s {
+        match s.to_ascii_lowercase().as_str() {
             "backspace" => Ok(Event::Key(KeyCode::Backspace, modifiers)),
             "enter" => Ok(Event::Key(KeyCode::Enter, modifiers)),
             "left" => Ok(Event::Key(KeyCode::Left, modifiers)),
@@ -91,6 +490,8 @@ impl Event {
             "insert" => Ok(Event::Key(KeyCode::Insert, modifiers)),
             "null" => Ok(Event::Key(KeyCode::Null, modifiers)),
             "esc" => Ok(Event::Key(KeyCode::Esc, modifiers)),
+            // The `+` separates the modifiers, so it has a name.
+            "plus" => Ok(Event::Key(KeyCode::Char('+'), modifiers)),
             "f1" => Ok(Event::Key(KeyCode::F(1), modifiers)),
             "f2" => Ok(Event::Key(KeyCode::F(2), modifiers)),
             "f3" => Ok(Event::Key(KeyCode::F(3), modifiers)),
@@ -103,18 +504,148 @@ impl Event {
             "f10" => Ok(Event::Key(KeyCode::F(10), modifiers)),
             "f11" => Ok(Event::Key(KeyCode::F(11), modifiers)),
             "f12" => Ok(Event::Key(KeyCode::F(12), modifiers)),
-            e => {
-                if e.len() == 1 && e.chars().next().unwrap().is_ascii() {
-                    Ok(Event::Key(
-                        KeyCode::Char(e.chars().next().unwrap()),
-                        modifiers,
-                    ))
-                } else {
-                    Err(AppError::InvalidEvent(e.to_string()))
+            _ => {
+                // Any single character is a key, an uppercase letter is the
+                // same as the lowercase one with the Shift modifier.
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(Event::Key(KeyCode::Char(c), modifiers).normalized()),
+                    _ => Err(AppError::InvalidEvent(s.to_string())),
                 }
             }
         }
     }
+    /// Get the event in the form used by the key bindings.
+    /// A letter typed with Shift is reported by the terminal as the uppercase
+    /// letter, with or without the Shift modifier, so it is turned into the
+    /// lowercase letter with the Shift modifier. The other events are left
+    /// unchanged.
+    ///
+    /// # Returns
+    /// * `Event` - The normalized event.
+    pub fn normalized(self) -> Event {
+        match self {
+            Event::Key(KeyCode::Char(c), modifiers) if c.is_uppercase() => {
+                let mut lowercase = c.to_lowercase();
+                match (lowercase.next(), lowercase.next()) {
+                    (Some(l), None) => {
+                        Event::Key(KeyCode::Char(l), modifiers | KeyModifiers::SHIFT)
+                    }
+                    _ => Event::Key(KeyCode::Char(c), modifiers),
+                }
+            }
+            event => event,
+        }
+    }
+    /// Whether the event must be confirmed before being handled.
+    /// Leaving a chat is always confirmed, the other destructive events only
+    /// when the confirmation is enabled.
+    ///
+    /// # Arguments
+    /// * `confirm_destructive` - Whether the destructive events need a
+    ///   confirmation.
+    pub fn needs_confirmation(&self, confirm_destructive: bool) -> bool {
+        match self {
+            Event::LeaveChat(_) | Event::SendDraft(_) | Event::TerminateSession(_) => true,
+            Event::DeleteMessages(..) => confirm_destructive,
+            _ => false,
+        }
+    }
+}
+
+/// The modifiers of a key event with their names, in the order they are
+/// written before the key.
+const MODIFIER_NAMES: [(KeyModifiers, &str); 6] = [
+    (KeyModifiers::CONTROL, "Ctrl"),
+    (KeyModifiers::ALT, "Alt"),
+    (KeyModifiers::SHIFT, "Shift"),
+    (KeyModifiers::SUPER, "Super"),
+    (KeyModifiers::META, "Meta"),
+    (KeyModifiers::HYPER, "Hyper"),
+];
+
+/// Get the name of a key code, the same one used in the keymap.
+/// The keys that can not be bound fall back to their debug representation.
+///
+/// # Arguments
+/// * `key` - The key code.
+///
+/// # Returns
+/// * `String` - The name of the key.
+fn key_code_name(key: &KeyCode) -> String {
+    match key {
+        KeyCode::Char('+') => "plus".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("f{}", n),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Home => "home".to_string(),
+        KeyCode::End => "end".to_string(),
+        KeyCode::PageUp => "page_up".to_string(),
+        KeyCode::PageDown => "page_down".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::BackTab => "back_tab".to_string(),
+        KeyCode::Delete => "delete".to_string(),
+        KeyCode::Insert => "insert".to_string(),
+        KeyCode::Null => "null".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        key => format!("{:?}", key),
+    }
+}
+
+#[derive(Debug, Default)]
+/// `ConfirmationGate` holds a destructive event until the user confirms or
+/// cancels it.
+pub struct ConfirmationGate {
+    /// The event waiting for a confirmation.
+    pending: Option<Event>,
+    /// The event that has been confirmed, it passes through the gate once.
+    confirmed: Option<Event>,
+}
+/// Implementation of the `ConfirmationGate` struct.
+impl ConfirmationGate {
+    /// Let an event through the gate, or hold it when it needs a
+    /// confirmation.
+    ///
+    /// # Arguments
+    /// * `event` - The event.
+    /// * `enabled` - Whether the destructive events need a confirmation.
+    ///
+    /// # Returns
+    /// * `Option<Event>` - The event to handle, or `None` when it is held.
+    pub fn filter(&mut self, event: Event, enabled: bool) -> Option<Event> {
+        if self.confirmed.as_ref() == Some(&event) {
+            self.confirmed = None;
+            return Some(event);
+        }
+        if !event.needs_confirmation(enabled) {
+            return Some(event);
+        }
+        self.pending = Some(event);
+        None
+    }
+    /// Get the event waiting for a confirmation.
+    pub fn pending(&self) -> Option<&Event> {
+        self.pending.as_ref()
+    }
+    /// Confirm the event waiting for a confirmation.
+    ///
+    /// # Returns
+    /// * `Option<Event>` - The confirmed event, it must be sent again to be
+    ///   handled.
+    pub fn confirm(&mut self) -> Option<Event> {
+        let event = self.pending.take()?;
+        self.confirmed = Some(event.clone());
+        Some(event)
+    }
+    /// Discard the event waiting for a confirmation.
+    pub fn cancel(&mut self) {
+        self.pending = None;
+    }
 }
 
 /// Implement the `FromStr` trait for `Event`.
@@ -127,7 +658,7 @@ impl FromStr for Event {
             let key = modifiers[modifiers.len() - 1];
             let modifiers = modifiers[..modifiers.len() - 1]
                 .iter()
-                .map(|m| match *m {
+                .map(|m| match m.to_ascii_lowercase().as_str() {
                     "ctrl" => KeyModifiers::CONTROL,
                     "alt" => KeyModifiers::ALT,
                     "shift" => KeyModifiers::SHIFT,
@@ -150,27 +681,20 @@ impl Display for Event {
         match self {
             Event::Unknown => write!(f, "Unknown"),
             Event::Init => write!(f, "Init"),
+            Event::Quit => write!(f, "Quit"),
             Event::Render => write!(f, "Render"),
             Event::Resize(width, height) => {
                 write!(f, "Resize({}, {})", width, height)
             }
             Event::Key(key, modifiers) => {
-                let k = if let KeyCode::Char(c) = key {
-                    c.to_string()
-                } else {
-                    format!("{:?}", key)
-                };
-
-                match *modifiers {
-                    KeyModifiers::NONE => write!(f, "{}", k),
-                    KeyModifiers::CONTROL => write!(f, "Ctrl+{}", k),
-                    KeyModifiers::ALT => write!(f, "Alt+{}", k),
-                    KeyModifiers::SHIFT => write!(f, "Shift+{}", k),
-                    KeyModifiers::SUPER => write!(f, "Super+{}", k),
-                    KeyModifiers::META => write!(f, "Meta+{}", k),
-                    KeyModifiers::HYPER => write!(f, "Hyper+{}", k),
-                    _ => write!(f, "{:?}+{}", modifiers, k),
+                // The modifiers are always written in the same order, so that
+                // the result can be parsed back.
+                for (modifier, name) in MODIFIER_NAMES {
+                    if modifiers.contains(modifier) {
+                        write!(f, "{}+", name)?;
+                    }
                 }
+                write!(f, "{}", key_code_name(key))
             }
             Event::Mouse(mouse) => write!(f, "Mouse({:?})", mouse),
             Event::UpdateArea(area) => write!(f, "UpdateArea({:?})", area),
@@ -193,6 +717,13 @@ impl Display for Event {
             Event::DeleteMessages(message_ids, revoke) => {
                 write!(f, "DeleteMessages({:?}, {})", message_ids, revoke)
             }
+            Event::ForwardMessages(message_ids, chat_id, send_copy) => {
+                write!(
+                    f,
+                    "ForwardMessages({:?}, {}, {})",
+                    message_ids, chat_id, send_copy
+                )
+            }
             Event::EditMessage(message_id, text) => {
                 write!(f, "EditMessage({}, {})", message_id, text)
             }
@@ -202,6 +733,1094 @@ impl Display for Event {
             Event::ViewAllMessages => {
                 write!(f, "ViewAllMessages")
             }
+            Event::ViewMessage(chat_id, message_id) => {
+                write!(f, "ViewMessage({}, {})", chat_id, message_id)
+            }
+            Event::SetReadOnScrollTo(read_on_scroll_to) => {
+                write!(f, "SetReadOnScrollTo({})", read_on_scroll_to)
+            }
+            Event::SetJumpToBottomOnSend(jump_to_bottom_on_send) => {
+                write!(f, "SetJumpToBottomOnSend({})", jump_to_bottom_on_send)
+            }
+            Event::OpenSplitView(left_chat_id, right_chat_id) => {
+                write!(f, "OpenSplitView({}, {})", left_chat_id, right_chat_id)
+            }
+            Event::CloseSplitView => write!(f, "CloseSplitView"),
+            Event::SwapSplitFocus => write!(f, "SwapSplitFocus"),
+            Event::PressKeyboardButton(message_id, row, col) => {
+                write!(f, "PressKeyboardButton({}, {}, {})", message_id, row, col)
+            }
+            Event::ToggleHighContrast => write!(f, "ToggleHighContrast"),
+            Event::ToggleAvatarInitials => write!(f, "ToggleAvatarInitials"),
+            Event::ToggleUnsupportedPlaceholders => write!(f, "ToggleUnsupportedPlaceholders"),
+            Event::ToggleSenderTimezone => write!(f, "ToggleSenderTimezone"),
+            Event::ToggleEmojiShortcodes => write!(f, "ToggleEmojiShortcodes"),
+            Event::ToggleViewCounts => write!(f, "ToggleViewCounts"),
+            Event::ToggleCommentCounts => write!(f, "ToggleCommentCounts"),
+            Event::OpenComments(message_id) => write!(f, "OpenComments({})", message_id),
+            Event::ToggleChannelSignatures => write!(f, "ToggleChannelSignatures"),
+            Event::ToggleFolderTabs => write!(f, "ToggleFolderTabs"),
+            Event::ToggleUnreadOnlyView => write!(f, "ToggleUnreadOnlyView"),
+            Event::ToggleSearchBar => write!(f, "ToggleSearchBar"),
+            Event::SearchMessages(query) => write!(f, "SearchMessages({})", query),
+            Event::SelectFolder(folder_id) => write!(f, "SelectFolder({})", folder_id),
+            Event::TogglePresenceDisplay => write!(f, "TogglePresenceDisplay"),
+            Event::ToggleSyntaxHighlight => write!(f, "ToggleSyntaxHighlight"),
+            Event::ToggleBigEmoji => write!(f, "ToggleBigEmoji"),
+            Event::ToggleActionAudit => write!(f, "ToggleActionAudit"),
+            Event::ShowActionAudit => write!(f, "ShowActionAudit"),
+            Event::SetMaxPasteSize(max_paste_size) => {
+                write!(f, "SetMaxPasteSize({})", max_paste_size)
+            }
+            Event::ToggleSelectedChatMute => write!(f, "ToggleSelectedChatMute"),
+            Event::NextAlbumItem => write!(f, "NextAlbumItem"),
+            Event::PrevAlbumItem => write!(f, "PrevAlbumItem"),
+            Event::NextOwnMessage => write!(f, "NextOwnMessage"),
+            Event::PrevOwnMessage => write!(f, "PrevOwnMessage"),
+            Event::SetDatabaseDirectory(database_dir) => {
+                write!(f, "SetDatabaseDirectory({})", database_dir)
+            }
+            Event::ToggleNewMessagesDivider => write!(f, "ToggleNewMessagesDivider"),
+            Event::CopyVisibleTranscript => write!(f, "CopyVisibleTranscript"),
+            Event::SetSendRetryPolicy(max_retries, backoff_ms) => {
+                write!(f, "SetSendRetryPolicy({}, {})", max_retries, backoff_ms)
+            }
+            Event::SendRetry(message_id, attempt) => {
+                write!(f, "SendRetry({}, {})", message_id, attempt)
+            }
+            Event::ShowSendError(message_id) => write!(f, "ShowSendError({})", message_id),
+            Event::ToggleMobileLayout => write!(f, "ToggleMobileLayout"),
+            Event::OpenReactionPicker(message_id) => {
+                write!(f, "OpenReactionPicker({})", message_id)
+            }
+            Event::AddReaction(message_id, emoji) => {
+                write!(f, "AddReaction({}, {})", message_id, emoji)
+            }
+            Event::ShowCallHistory(chat_id) => write!(f, "ShowCallHistory({})", chat_id),
+            Event::ShowChatPermissions(chat_id) => write!(f, "ShowChatPermissions({})", chat_id),
+            Event::OpenSavedMessages => write!(f, "OpenSavedMessages"),
+            Event::ShowLiveLocationMap(message_id) => {
+                write!(f, "ShowLiveLocationMap({})", message_id)
+            }
+            Event::ShowUnreadDashboard => write!(f, "ShowUnreadDashboard"),
+            Event::OpenChat(chat_id) => write!(f, "OpenChat({})", chat_id),
+            Event::OpenChatAtMessage(chat_id, message_id) => {
+                write!(f, "OpenChatAtMessage({}, {})", chat_id, message_id)
+            }
+            Event::ShowEditHistory(message_id) => write!(f, "ShowEditHistory({})", message_id),
+            Event::ShowChatActivity(chat_id) => write!(f, "ShowChatActivity({})", chat_id),
+            Event::ShowQuizExplanation(message_id) => {
+                write!(f, "ShowQuizExplanation({})", message_id)
+            }
+            Event::OpenDocument(message_id) => write!(f, "OpenDocument({})", message_id),
+            Event::ShowChatPolls(chat_id) => write!(f, "ShowChatPolls({})", chat_id),
+            Event::ShowForumTopics(chat_id) => write!(f, "ShowForumTopics({})", chat_id),
+            Event::OpenBotMenu(chat_id) => write!(f, "OpenBotMenu({})", chat_id),
+            Event::ShowActiveSessions => write!(f, "ShowActiveSessions"),
+            Event::TerminateSession(session_id) => write!(f, "TerminateSession({})", session_id),
+            Event::ShowScheduledMessages(chat_id) => {
+                write!(f, "ShowScheduledMessages({})", chat_id)
+            }
+            Event::SendScheduledNow(message_id) => write!(f, "SendScheduledNow({})", message_id),
+            Event::PreviewFormatting(text, parse_mode) => {
+                write!(f, "PreviewFormatting({}, {})", text, parse_mode)
+            }
+            Event::OperationStarted(label) => write!(f, "OperationStarted({})", label),
+            Event::OperationFinished(label) => write!(f, "OperationFinished({})", label),
+            Event::SetMaxReactionsShown(max_reactions_shown) => {
+                write!(f, "SetMaxReactionsShown({})", max_reactions_shown)
+            }
+            Event::CycleSelectionStyle => write!(f, "CycleSelectionStyle"),
+            Event::SetDeleteLastBehavior(delete_last_behavior) => {
+                write!(f, "SetDeleteLastBehavior({})", delete_last_behavior)
+            }
+            Event::SetBackspaceAction(backspace_action) => {
+                write!(f, "SetBackspaceAction({})", backspace_action)
+            }
+            Event::SetHomeEndBehavior(home_end_behavior) => {
+                write!(f, "SetHomeEndBehavior({})", home_end_behavior)
+            }
+            Event::SetTabBehavior(tab_behavior) => {
+                write!(f, "SetTabBehavior({})", tab_behavior)
+            }
+            Event::SetTimestampGranularity(timestamp_granularity) => {
+                write!(f, "SetTimestampGranularity({})", timestamp_granularity)
+            }
+            Event::SetSavedMessagesNotepadMode(saved_messages_notepad_mode) => {
+                write!(
+                    f,
+                    "SetSavedMessagesNotepadMode({})",
+                    saved_messages_notepad_mode
+                )
+            }
+            Event::SetComposeEscapeAction(compose_escape_action) => {
+                write!(f, "SetComposeEscapeAction({})", compose_escape_action)
+            }
+            Event::FollowEntity(message_id, entity_index) => {
+                write!(f, "FollowEntity({}, {})", message_id, entity_index)
+            }
+            Event::SetSendAs(chat_id, sender_chat_id) => {
+                write!(f, "SetSendAs({}, {})", chat_id, sender_chat_id)
+            }
+            Event::SnoozeChat(chat_id, wake_at) => {
+                write!(f, "SnoozeChat({}, {})", chat_id, wake_at)
+            }
+            Event::SetComposePlaceholder(chat_id, text) => {
+                write!(f, "SetComposePlaceholder({}, {})", chat_id, text)
+            }
+            Event::SetDraftReminder(chat_id, wake_at) => {
+                write!(f, "SetDraftReminder({}, {})", chat_id, wake_at)
+            }
+            Event::SendDraft(chat_id) => write!(f, "SendDraft({})", chat_id),
+            Event::CopyCodeBlock(message_id, block_index) => {
+                write!(f, "CopyCodeBlock({}, {})", message_id, block_index)
+            }
+            Event::CopyMessageLinks(message_id) => write!(f, "CopyMessageLinks({})", message_id),
+            Event::SetConfirmDestructive(confirm_destructive) => {
+                write!(f, "SetConfirmDestructive({})", confirm_destructive)
+            }
+            Event::LeaveChat(chat_id) => write!(f, "LeaveChat({})", chat_id),
+            Event::SetEmojiStatus(custom_emoji_id) => {
+                write!(f, "SetEmojiStatus({})", custom_emoji_id)
+            }
+            Event::SetMaxConcurrentRequests(max_concurrent_requests) => {
+                write!(f, "SetMaxConcurrentRequests({})", max_concurrent_requests)
+            }
+            Event::SetAutoAwayTimeout(auto_away_timeout) => {
+                write!(f, "SetAutoAwayTimeout({})", auto_away_timeout)
+            }
+            Event::SetScrollMargin(scroll_margin) => {
+                write!(f, "SetScrollMargin({})", scroll_margin)
+            }
+            Event::SetMessageContentWidth(message_content_width) => {
+                write!(f, "SetMessageContentWidth({})", message_content_width)
+            }
+            Event::SetHistoryPageSize(history_page_size) => {
+                write!(f, "SetHistoryPageSize({})", history_page_size)
+            }
+            Event::SetComposeAutoClearTimeout(compose_auto_clear_timeout) => {
+                write!(
+                    f,
+                    "SetComposeAutoClearTimeout({})",
+                    compose_auto_clear_timeout
+                )
+            }
+            Event::SaveDraft(chat_id, text) => write!(f, "SaveDraft({}, {})", chat_id, text),
+            Event::SetScrolledUpNewMessageBehavior(scrolled_up_new_message_behavior) => {
+                write!(
+                    f,
+                    "SetScrolledUpNewMessageBehavior({})",
+                    scrolled_up_new_message_behavior
+                )
+            }
+            Event::SetReplyPreviewMode(reply_preview_mode) => {
+                write!(f, "SetReplyPreviewMode({})", reply_preview_mode)
+            }
+            Event::SetRenderInterval(render_interval) => {
+                write!(f, "SetRenderInterval({})", render_interval)
+            }
+            Event::SetChatTitleMaxWidth(chat_title_max_width) => {
+                write!(f, "SetChatTitleMaxWidth({})", chat_title_max_width)
+            }
+            Event::SetDoubleKeyWindow(double_key_window) => {
+                write!(f, "SetDoubleKeyWindow({})", double_key_window)
+            }
+            Event::TranslateMessage(message_id, to_language_code) => {
+                write!(f, "TranslateMessage({}, {})", message_id, to_language_code)
+            }
+            Event::ToggleTranslation(message_id) => {
+                write!(f, "ToggleTranslation({})", message_id)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        configs::raw::app_raw::{
+            BackspaceAction, ComposeEscapeAction, DeleteLastBehavior, HomeEndBehavior,
+            ReplyPreviewMode, ScrolledUpNewMessageBehavior, TabBehavior, TimestampGranularity,
+        },
+        event::{ConfirmationGate, Event},
+        tg::td_enums::TdParseMode,
+    };
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_display_quit() {
+        assert_eq!(Event::Quit.to_string(), "Quit");
+    }
+
+    #[test]
+    fn test_display_set_jump_to_bottom_on_send() {
+        assert_eq!(
+            Event::SetJumpToBottomOnSend(true).to_string(),
+            "SetJumpToBottomOnSend(true)"
+        );
+        assert_eq!(
+            Event::SetJumpToBottomOnSend(false).to_string(),
+            "SetJumpToBottomOnSend(false)"
+        );
+    }
+
+    #[test]
+    fn test_display_split_view() {
+        assert_eq!(
+            Event::OpenSplitView(1, 2).to_string(),
+            "OpenSplitView(1, 2)"
+        );
+        assert_eq!(Event::CloseSplitView.to_string(), "CloseSplitView");
+        assert_eq!(Event::SwapSplitFocus.to_string(), "SwapSplitFocus");
+    }
+
+    #[test]
+    fn test_display_press_keyboard_button() {
+        assert_eq!(
+            Event::PressKeyboardButton(42, 1, 2).to_string(),
+            "PressKeyboardButton(42, 1, 2)"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_high_contrast() {
+        assert_eq!(Event::ToggleHighContrast.to_string(), "ToggleHighContrast");
+    }
+
+    #[test]
+    fn test_display_set_max_paste_size() {
+        assert_eq!(Event::SetMaxPasteSize(0).to_string(), "SetMaxPasteSize(0)");
+        assert_eq!(
+            Event::SetMaxPasteSize(4096).to_string(),
+            "SetMaxPasteSize(4096)"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_selected_chat_mute() {
+        assert_eq!(
+            Event::ToggleSelectedChatMute.to_string(),
+            "ToggleSelectedChatMute"
+        );
+    }
+
+    #[test]
+    fn test_display_album_items() {
+        assert_eq!(Event::NextAlbumItem.to_string(), "NextAlbumItem");
+        assert_eq!(Event::PrevAlbumItem.to_string(), "PrevAlbumItem");
+    }
+
+    #[test]
+    fn test_display_own_messages() {
+        assert_eq!(Event::NextOwnMessage.to_string(), "NextOwnMessage");
+        assert_eq!(Event::PrevOwnMessage.to_string(), "PrevOwnMessage");
+    }
+
+    #[test]
+    fn test_display_set_database_directory() {
+        assert_eq!(
+            Event::SetDatabaseDirectory("/mnt/tgt/db".to_string()).to_string(),
+            "SetDatabaseDirectory(/mnt/tgt/db)"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_new_messages_divider() {
+        assert_eq!(
+            Event::ToggleNewMessagesDivider.to_string(),
+            "ToggleNewMessagesDivider"
+        );
+    }
+
+    #[test]
+    fn test_display_copy_visible_transcript() {
+        assert_eq!(
+            Event::CopyVisibleTranscript.to_string(),
+            "CopyVisibleTranscript"
+        );
+    }
+
+    #[test]
+    fn test_display_set_send_retry_policy() {
+        assert_eq!(
+            Event::SetSendRetryPolicy(3, 500).to_string(),
+            "SetSendRetryPolicy(3, 500)"
+        );
+    }
+
+    #[test]
+    fn test_display_send_retry() {
+        assert_eq!(Event::SendRetry(42, 1).to_string(), "SendRetry(42, 1)");
+    }
+
+    #[test]
+    fn test_display_show_send_error() {
+        assert_eq!(Event::ShowSendError(42).to_string(), "ShowSendError(42)");
+    }
+
+    #[test]
+    fn test_display_toggle_mobile_layout() {
+        assert_eq!(Event::ToggleMobileLayout.to_string(), "ToggleMobileLayout");
+    }
+
+    #[test]
+    fn test_display_open_reaction_picker() {
+        assert_eq!(
+            Event::OpenReactionPicker(42).to_string(),
+            "OpenReactionPicker(42)"
+        );
+    }
+
+    #[test]
+    fn test_display_add_reaction() {
+        assert_eq!(
+            Event::AddReaction(42, "👍".to_string()).to_string(),
+            "AddReaction(42, 👍)"
+        );
+    }
+
+    #[test]
+    fn test_display_show_chat_permissions() {
+        assert_eq!(
+            Event::ShowChatPermissions(-100).to_string(),
+            "ShowChatPermissions(-100)"
+        );
+    }
+
+    #[test]
+    fn test_display_show_call_history() {
+        assert_eq!(
+            Event::ShowCallHistory(42).to_string(),
+            "ShowCallHistory(42)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_delete_last_behavior() {
+        assert_eq!(
+            Event::SetDeleteLastBehavior(DeleteLastBehavior::KeepOpen).to_string(),
+            "SetDeleteLastBehavior(keep_open)"
+        );
+        assert_eq!(
+            Event::SetDeleteLastBehavior(DeleteLastBehavior::NextChat).to_string(),
+            "SetDeleteLastBehavior(next_chat)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_backspace_action() {
+        assert_eq!(
+            Event::SetBackspaceAction(BackspaceAction::NavigateBack).to_string(),
+            "SetBackspaceAction(navigate_back)"
+        );
+        assert_eq!(
+            Event::SetBackspaceAction(BackspaceAction::CloseOverlay).to_string(),
+            "SetBackspaceAction(close_overlay)"
+        );
+        assert_eq!(
+            Event::SetBackspaceAction(BackspaceAction::Noop).to_string(),
+            "SetBackspaceAction(noop)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_home_end_behavior() {
+        assert_eq!(
+            Event::SetHomeEndBehavior(HomeEndBehavior::JumpToEdge).to_string(),
+            "SetHomeEndBehavior(jump_to_edge)"
+        );
+        assert_eq!(
+            Event::SetHomeEndBehavior(HomeEndBehavior::LineStartEnd).to_string(),
+            "SetHomeEndBehavior(line_start_end)"
+        );
+        assert_eq!(
+            Event::SetHomeEndBehavior(HomeEndBehavior::ScrollPage).to_string(),
+            "SetHomeEndBehavior(scroll_page)"
+        );
+    }
+
+    #[test]
+    fn test_display_follow_entity() {
+        assert_eq!(
+            Event::FollowEntity(42, 1).to_string(),
+            "FollowEntity(42, 1)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_send_as() {
+        assert_eq!(
+            Event::SetSendAs(42, -100).to_string(),
+            "SetSendAs(42, -100)"
+        );
+    }
+
+    #[test]
+    fn test_display_snooze_chat() {
+        assert_eq!(
+            Event::SnoozeChat(42, 1700000000).to_string(),
+            "SnoozeChat(42, 1700000000)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_compose_placeholder() {
+        assert_eq!(
+            Event::SetComposePlaceholder(42, "Reply to the ticket".to_string()).to_string(),
+            "SetComposePlaceholder(42, Reply to the ticket)"
+        );
+        assert_eq!(
+            Event::SetComposePlaceholder(42, String::new()).to_string(),
+            "SetComposePlaceholder(42, )"
+        );
+    }
+
+    #[test]
+    fn test_display_copy_code_block() {
+        assert_eq!(
+            Event::CopyCodeBlock(42, 1).to_string(),
+            "CopyCodeBlock(42, 1)"
+        );
+    }
+
+    #[test]
+    fn test_display_copy_message_links() {
+        assert_eq!(
+            Event::CopyMessageLinks(42).to_string(),
+            "CopyMessageLinks(42)"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_avatar_initials() {
+        assert_eq!(
+            Event::ToggleAvatarInitials.to_string(),
+            "ToggleAvatarInitials"
+        );
+    }
+
+    #[test]
+    fn test_display_set_confirm_destructive() {
+        assert_eq!(
+            Event::SetConfirmDestructive(true).to_string(),
+            "SetConfirmDestructive(true)"
+        );
+    }
+
+    #[test]
+    fn test_confirmation_gate_holds_delete() {
+        let mut gate = ConfirmationGate::default();
+        let delete = Event::DeleteMessages(vec![1, 2], true);
+        assert_eq!(gate.filter(delete.clone(), true), None);
+        assert_eq!(gate.pending(), Some(&delete));
+        assert_eq!(gate.confirm(), Some(delete.clone()));
+        assert_eq!(gate.pending(), None);
+        // The confirmed event passes once, then it is held again.
+        assert_eq!(gate.filter(delete.clone(), true), Some(delete.clone()));
+        assert_eq!(gate.filter(delete.clone(), true), None);
+        gate.cancel();
+        assert_eq!(gate.confirm(), None);
+    }
+
+    #[test]
+    fn test_confirmation_gate_passes_through() {
+        let mut gate = ConfirmationGate::default();
+        let delete = Event::DeleteMessages(vec![1], false);
+        assert_eq!(gate.filter(delete.clone(), false), Some(delete));
+        assert_eq!(gate.pending(), None);
+        assert_eq!(
+            gate.filter(Event::GetChatHistory, true),
+            Some(Event::GetChatHistory)
+        );
+    }
+
+    #[test]
+    fn test_display_leave_chat() {
+        assert_eq!(Event::LeaveChat(-100).to_string(), "LeaveChat(-100)");
+    }
+
+    #[test]
+    fn test_confirmation_gate_always_holds_leave() {
+        let mut gate = ConfirmationGate::default();
+        assert_eq!(gate.filter(Event::LeaveChat(-100), false), None);
+        assert_eq!(gate.pending(), Some(&Event::LeaveChat(-100)));
+    }
+
+    #[test]
+    fn test_display_toggle_unsupported_placeholders() {
+        assert_eq!(
+            Event::ToggleUnsupportedPlaceholders.to_string(),
+            "ToggleUnsupportedPlaceholders"
+        );
+    }
+
+    #[test]
+    fn test_display_set_emoji_status() {
+        assert_eq!(Event::SetEmojiStatus(42).to_string(), "SetEmojiStatus(42)");
+        assert_eq!(Event::SetEmojiStatus(0).to_string(), "SetEmojiStatus(0)");
+    }
+
+    #[test]
+    fn test_display_set_scroll_margin() {
+        assert_eq!(Event::SetScrollMargin(3).to_string(), "SetScrollMargin(3)");
+    }
+
+    #[test]
+    fn test_display_toggle_sender_timezone() {
+        assert_eq!(
+            Event::ToggleSenderTimezone.to_string(),
+            "ToggleSenderTimezone"
+        );
+    }
+
+    #[test]
+    fn test_display_open_saved_messages() {
+        assert_eq!(Event::OpenSavedMessages.to_string(), "OpenSavedMessages");
+    }
+
+    #[test]
+    fn test_display_show_live_location_map() {
+        assert_eq!(
+            Event::ShowLiveLocationMap(42).to_string(),
+            "ShowLiveLocationMap(42)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_max_reactions_shown() {
+        assert_eq!(
+            Event::SetMaxReactionsShown(5).to_string(),
+            "SetMaxReactionsShown(5)"
+        );
+    }
+
+    #[test]
+    fn test_display_cycle_selection_style() {
+        assert_eq!(
+            Event::CycleSelectionStyle.to_string(),
+            "CycleSelectionStyle"
+        );
+    }
+
+    #[test]
+    fn test_display_show_quiz_explanation() {
+        assert_eq!(
+            Event::ShowQuizExplanation(42).to_string(),
+            "ShowQuizExplanation(42)"
+        );
+    }
+
+    #[test]
+    fn test_display_open_document() {
+        assert_eq!(Event::OpenDocument(42).to_string(), "OpenDocument(42)");
+    }
+
+    #[test]
+    fn test_display_operation_started() {
+        assert_eq!(
+            Event::OperationStarted("Loading history".to_string()).to_string(),
+            "OperationStarted(Loading history)"
+        );
+    }
+
+    #[test]
+    fn test_display_operation_finished() {
+        assert_eq!(
+            Event::OperationFinished("Searching".to_string()).to_string(),
+            "OperationFinished(Searching)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_draft_reminder() {
+        assert_eq!(
+            Event::SetDraftReminder(42, 1700000000).to_string(),
+            "SetDraftReminder(42, 1700000000)"
+        );
+        assert_eq!(Event::SendDraft(42).to_string(), "SendDraft(42)");
+    }
+
+    #[test]
+    fn test_display_set_tab_behavior() {
+        assert_eq!(
+            Event::SetTabBehavior(TabBehavior::CycleFocus).to_string(),
+            "SetTabBehavior(cycle_focus)"
+        );
+        assert_eq!(
+            Event::SetTabBehavior(TabBehavior::MentionCompletion).to_string(),
+            "SetTabBehavior(mention_completion)"
+        );
+        assert_eq!(
+            Event::SetTabBehavior(TabBehavior::Indent).to_string(),
+            "SetTabBehavior(indent)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_timestamp_granularity() {
+        assert_eq!(
+            Event::SetTimestampGranularity(TimestampGranularity::Minute).to_string(),
+            "SetTimestampGranularity(minute)"
+        );
+        assert_eq!(
+            Event::SetTimestampGranularity(TimestampGranularity::Second).to_string(),
+            "SetTimestampGranularity(second)"
+        );
+        assert_eq!(
+            Event::SetTimestampGranularity(TimestampGranularity::DateTime).to_string(),
+            "SetTimestampGranularity(date_time)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_saved_messages_notepad_mode() {
+        assert_eq!(
+            Event::SetSavedMessagesNotepadMode(true).to_string(),
+            "SetSavedMessagesNotepadMode(true)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_compose_escape_action() {
+        assert_eq!(
+            Event::SetComposeEscapeAction(ComposeEscapeAction::Unfocus).to_string(),
+            "SetComposeEscapeAction(unfocus)"
+        );
+        assert_eq!(
+            Event::SetComposeEscapeAction(ComposeEscapeAction::ClearText).to_string(),
+            "SetComposeEscapeAction(clear_text)"
+        );
+        assert_eq!(
+            Event::SetComposeEscapeAction(ComposeEscapeAction::DiscardReply).to_string(),
+            "SetComposeEscapeAction(discard_reply)"
+        );
+    }
+
+    #[test]
+    fn test_display_show_chat_activity() {
+        assert_eq!(
+            Event::ShowChatActivity(-100).to_string(),
+            "ShowChatActivity(-100)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_read_on_scroll_to() {
+        assert_eq!(
+            Event::SetReadOnScrollTo(true).to_string(),
+            "SetReadOnScrollTo(true)"
+        );
+        assert_eq!(
+            Event::ViewMessage(-100, 42).to_string(),
+            "ViewMessage(-100, 42)"
+        );
+    }
+
+    #[test]
+    fn test_display_show_unread_dashboard() {
+        assert_eq!(
+            Event::ShowUnreadDashboard.to_string(),
+            "ShowUnreadDashboard"
+        );
+        assert_eq!(Event::OpenChat(-100).to_string(), "OpenChat(-100)");
+        assert_eq!(
+            Event::OpenChatAtMessage(-100, 42).to_string(),
+            "OpenChatAtMessage(-100, 42)"
+        );
+    }
+
+    #[test]
+    fn test_display_show_edit_history() {
+        assert_eq!(
+            Event::ShowEditHistory(42).to_string(),
+            "ShowEditHistory(42)"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_emoji_shortcodes() {
+        assert_eq!(
+            Event::ToggleEmojiShortcodes.to_string(),
+            "ToggleEmojiShortcodes"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_view_counts() {
+        assert_eq!(Event::ToggleViewCounts.to_string(), "ToggleViewCounts");
+    }
+
+    #[test]
+    fn test_display_toggle_comment_counts() {
+        assert_eq!(
+            Event::ToggleCommentCounts.to_string(),
+            "ToggleCommentCounts"
+        );
+    }
+
+    #[test]
+    fn test_display_open_comments() {
+        assert_eq!(Event::OpenComments(42).to_string(), "OpenComments(42)");
+    }
+
+    #[test]
+    fn test_display_toggle_channel_signatures() {
+        assert_eq!(
+            Event::ToggleChannelSignatures.to_string(),
+            "ToggleChannelSignatures"
+        );
+    }
+
+    #[test]
+    fn test_display_set_max_concurrent_requests() {
+        assert_eq!(
+            Event::SetMaxConcurrentRequests(4).to_string(),
+            "SetMaxConcurrentRequests(4)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_auto_away_timeout() {
+        assert_eq!(
+            Event::SetAutoAwayTimeout(300).to_string(),
+            "SetAutoAwayTimeout(300)"
+        );
+    }
+
+    #[test]
+    fn test_display_key_round_trip() {
+        let keys = [
+            KeyCode::Char('k'),
+            KeyCode::Char('K'),
+            KeyCode::Char(' '),
+            KeyCode::Char('+'),
+            KeyCode::Enter,
+            KeyCode::PageUp,
+            KeyCode::BackTab,
+            KeyCode::Esc,
+            KeyCode::F(5),
+        ];
+        let modifiers = [
+            KeyModifiers::NONE,
+            KeyModifiers::CONTROL,
+            KeyModifiers::ALT,
+            KeyModifiers::SHIFT,
+            KeyModifiers::SUPER,
+            KeyModifiers::META,
+            KeyModifiers::HYPER,
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+            KeyModifiers::ALT | KeyModifiers::SHIFT | KeyModifiers::SUPER,
+            KeyModifiers::all(),
+        ];
+        for key in keys {
+            for modifiers in modifiers {
+                let event = Event::Key(key, modifiers).normalized();
+                assert_eq!(Event::from_str(&event.to_string()).ok(), Some(event));
+            }
         }
     }
+
+    #[test]
+    fn test_from_str_unicode_key() {
+        assert_eq!(
+            Event::from_str("ctrl+ä").ok(),
+            Some(Event::Key(KeyCode::Char('ä'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            Event::from_str("alt+ñ").ok(),
+            Some(Event::Key(KeyCode::Char('ñ'), KeyModifiers::ALT))
+        );
+        assert_eq!(
+            Event::from_str("€").ok(),
+            Some(Event::Key(KeyCode::Char('€'), KeyModifiers::NONE))
+        );
+        assert!(Event::from_str("ctrl+äö").is_err());
+        assert!(Event::from_str("").is_err());
+    }
+
+    #[test]
+    fn test_from_str_shift_letter() {
+        let shift_a = Event::Key(KeyCode::Char('a'), KeyModifiers::SHIFT);
+        assert_eq!(Event::from_str("shift+a").ok(), Some(shift_a.clone()));
+        assert_eq!(Event::from_str("shift+A").ok(), Some(shift_a.clone()));
+        assert_eq!(Event::from_str("A").ok(), Some(shift_a.clone()));
+        assert_eq!(
+            Event::from_str("ctrl+Ä").ok(),
+            Some(Event::Key(
+                KeyCode::Char('ä'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ))
+        );
+        // The terminal reports the uppercase letter, with or without Shift.
+        assert_eq!(
+            Event::Key(KeyCode::Char('A'), KeyModifiers::SHIFT).normalized(),
+            shift_a
+        );
+        assert_eq!(
+            Event::Key(KeyCode::Char('A'), KeyModifiers::NONE).normalized(),
+            shift_a
+        );
+        assert_ne!(Event::from_str("a").ok(), Some(shift_a));
+    }
+
+    #[test]
+    fn test_display_key_canonical_order() {
+        assert_eq!(
+            Event::Key(
+                KeyCode::Char('k'),
+                KeyModifiers::SHIFT | KeyModifiers::CONTROL
+            )
+            .to_string(),
+            "Ctrl+Shift+k"
+        );
+        assert_eq!(
+            Event::Key(KeyCode::PageDown, KeyModifiers::ALT).to_string(),
+            "Alt+page_down"
+        );
+        assert_eq!(
+            Event::Key(KeyCode::Enter, KeyModifiers::NONE).to_string(),
+            "enter"
+        );
+    }
+
+    #[test]
+    fn test_display_set_message_content_width() {
+        assert_eq!(
+            Event::SetMessageContentWidth(80).to_string(),
+            "SetMessageContentWidth(80)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_history_page_size() {
+        assert_eq!(
+            Event::SetHistoryPageSize(100).to_string(),
+            "SetHistoryPageSize(100)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_compose_auto_clear_timeout() {
+        assert_eq!(
+            Event::SetComposeAutoClearTimeout(120).to_string(),
+            "SetComposeAutoClearTimeout(120)"
+        );
+    }
+
+    #[test]
+    fn test_display_save_draft() {
+        assert_eq!(
+            Event::SaveDraft(-100, "see you at".to_string()).to_string(),
+            "SaveDraft(-100, see you at)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_scrolled_up_new_message_behavior() {
+        assert_eq!(
+            Event::SetScrolledUpNewMessageBehavior(ScrolledUpNewMessageBehavior::JumpButton)
+                .to_string(),
+            "SetScrolledUpNewMessageBehavior(jump_button)"
+        );
+        assert_eq!(
+            Event::SetScrolledUpNewMessageBehavior(ScrolledUpNewMessageBehavior::FlashStatusBar)
+                .to_string(),
+            "SetScrolledUpNewMessageBehavior(flash_status_bar)"
+        );
+        assert_eq!(
+            Event::SetScrolledUpNewMessageBehavior(ScrolledUpNewMessageBehavior::Nothing)
+                .to_string(),
+            "SetScrolledUpNewMessageBehavior(nothing)"
+        );
+    }
+
+    #[test]
+    fn test_display_translate_message() {
+        assert_eq!(
+            Event::TranslateMessage(42, "it".to_string()).to_string(),
+            "TranslateMessage(42, it)"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_translation() {
+        assert_eq!(
+            Event::ToggleTranslation(42).to_string(),
+            "ToggleTranslation(42)"
+        );
+    }
+
+    #[test]
+    fn test_display_forward_messages() {
+        assert_eq!(
+            Event::ForwardMessages(vec![1, 2], 12345, false).to_string(),
+            "ForwardMessages([1, 2], 12345, false)"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_folder_tabs() {
+        assert_eq!(Event::ToggleFolderTabs.to_string(), "ToggleFolderTabs");
+    }
+
+    #[test]
+    fn test_display_toggle_unread_only_view() {
+        assert_eq!(
+            Event::ToggleUnreadOnlyView.to_string(),
+            "ToggleUnreadOnlyView"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_search_bar() {
+        assert_eq!(Event::ToggleSearchBar.to_string(), "ToggleSearchBar");
+        assert_eq!(
+            Event::SearchMessages("rust".to_string()).to_string(),
+            "SearchMessages(rust)"
+        );
+    }
+
+    #[test]
+    fn test_display_select_folder() {
+        assert_eq!(Event::SelectFolder(2).to_string(), "SelectFolder(2)");
+    }
+
+    #[test]
+    fn test_display_toggle_presence_display() {
+        assert_eq!(
+            Event::TogglePresenceDisplay.to_string(),
+            "TogglePresenceDisplay"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_syntax_highlight() {
+        assert_eq!(
+            Event::ToggleSyntaxHighlight.to_string(),
+            "ToggleSyntaxHighlight"
+        );
+    }
+
+    #[test]
+    fn test_display_toggle_big_emoji() {
+        assert_eq!(Event::ToggleBigEmoji.to_string(), "ToggleBigEmoji");
+    }
+
+    #[test]
+    fn test_display_toggle_action_audit() {
+        assert_eq!(Event::ToggleActionAudit.to_string(), "ToggleActionAudit");
+    }
+
+    #[test]
+    fn test_display_show_action_audit() {
+        assert_eq!(Event::ShowActionAudit.to_string(), "ShowActionAudit");
+    }
+
+    #[test]
+    fn test_display_show_chat_polls() {
+        assert_eq!(Event::ShowChatPolls(42).to_string(), "ShowChatPolls(42)");
+    }
+
+    #[test]
+    fn test_display_show_forum_topics() {
+        assert_eq!(
+            Event::ShowForumTopics(-100).to_string(),
+            "ShowForumTopics(-100)"
+        );
+    }
+
+    #[test]
+    fn test_display_open_bot_menu() {
+        assert_eq!(Event::OpenBotMenu(42).to_string(), "OpenBotMenu(42)");
+    }
+
+    #[test]
+    fn test_display_set_reply_preview_mode() {
+        assert_eq!(
+            Event::SetReplyPreviewMode(ReplyPreviewMode::Inline).to_string(),
+            "SetReplyPreviewMode(inline)"
+        );
+        assert_eq!(
+            Event::SetReplyPreviewMode(ReplyPreviewMode::Compact).to_string(),
+            "SetReplyPreviewMode(compact)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_render_interval() {
+        assert_eq!(
+            Event::SetRenderInterval(50).to_string(),
+            "SetRenderInterval(50)"
+        );
+        assert_eq!(
+            Event::SetRenderInterval(0).to_string(),
+            "SetRenderInterval(0)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_chat_title_max_width() {
+        assert_eq!(
+            Event::SetChatTitleMaxWidth(24).to_string(),
+            "SetChatTitleMaxWidth(24)"
+        );
+    }
+
+    #[test]
+    fn test_display_set_double_key_window() {
+        assert_eq!(
+            Event::SetDoubleKeyWindow(300).to_string(),
+            "SetDoubleKeyWindow(300)"
+        );
+    }
+
+    #[test]
+    fn test_display_show_active_sessions() {
+        assert_eq!(Event::ShowActiveSessions.to_string(), "ShowActiveSessions");
+    }
+
+    #[test]
+    fn test_display_terminate_session() {
+        assert_eq!(
+            Event::TerminateSession(42).to_string(),
+            "TerminateSession(42)"
+        );
+    }
+
+    #[test]
+    fn test_display_show_scheduled_messages() {
+        assert_eq!(
+            Event::ShowScheduledMessages(-100).to_string(),
+            "ShowScheduledMessages(-100)"
+        );
+    }
+
+    #[test]
+    fn test_display_send_scheduled_now() {
+        assert_eq!(
+            Event::SendScheduledNow(42).to_string(),
+            "SendScheduledNow(42)"
+        );
+    }
+
+    #[test]
+    fn test_display_preview_formatting() {
+        assert_eq!(
+            Event::PreviewFormatting("*bold*".to_string(), TdParseMode::Markdown).to_string(),
+            "PreviewFormatting(*bold*, markdown)"
+        );
+        assert_eq!(
+            Event::PreviewFormatting("<b>bold</b>".to_string(), TdParseMode::Html).to_string(),
+            "PreviewFormatting(<b>bold</b>, html)"
+        );
+    }
+
+    #[test]
+    fn test_confirmation_gate_always_holds_terminate_session() {
+        let mut gate = ConfirmationGate::default();
+        assert_eq!(gate.filter(Event::TerminateSession(42), false), None);
+        assert_eq!(gate.pending(), Some(&Event::TerminateSession(42)));
+    }
 }
@@ -1,10 +1,14 @@
 pub mod action;
+pub mod action_audit;
 pub mod app_context;
 pub mod app_error;
 pub mod cli;
 pub mod component_name;
+pub mod double_key;
 pub mod event;
 pub mod logger;
+pub mod render_coalescer;
+pub mod shutdown;
 pub mod tui;
 pub mod tui_backend;
 pub mod utils;
@@ -25,7 +29,10 @@ use crate::configs::{
     },
 };
 use crate::logger::Logger;
-use crate::tg::{tg_backend::TgBackend, tg_context::TgContext};
+use crate::tg::{
+    compose_placeholders::compose_placeholders_path, snoozed_chats::snoozed_chats_path,
+    tg_backend::TgBackend, tg_context::TgContext,
+};
 use crate::tui::Tui;
 use crate::tui_backend::TuiBackend;
 use clap::Parser;
@@ -88,6 +95,12 @@ async fn tokio_main() -> Result<(), AppError<()>> {
     telegram_config.use_message_database = std::env::args().count() <= 1;
 
     let tg_context = TgContext::default();
+    if let Ok(path) = snoozed_chats_path() {
+        tg_context.snoozed_chats().load(&path);
+    }
+    if let Ok(path) = compose_placeholders_path() {
+        tg_context.compose_placeholders().load(&path);
+    }
     tracing::info!("Telegram context: {:?}", tg_context);
     let app_context = Arc::new(AppContext::new(
         app_config,
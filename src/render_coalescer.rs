@@ -0,0 +1,80 @@
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Default)]
+/// `RenderCoalescer` merges the renders requested within a render interval
+/// into a single one, so that the chats with many updates do not redraw the
+/// user interface on each of them.
+pub struct RenderCoalescer {
+    /// When the user interface was last rendered, `None` before the first
+    /// render.
+    last_render: Option<Instant>,
+    /// Whether a render has been requested since the last one.
+    pending: bool,
+}
+/// Implementation of the `RenderCoalescer` struct.
+impl RenderCoalescer {
+    /// Ask for a render, it is merged with the other ones requested before
+    /// the interval elapses.
+    pub fn request(&mut self) {
+        self.pending = true;
+    }
+
+    /// Check whether the requested renders are flushed now.
+    ///
+    /// # Arguments
+    /// * `now` - The current time.
+    /// * `interval` - The minimum time between two renders, zero to render
+    ///   on every request.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the user interface has to be rendered.
+    pub fn should_render(&mut self, now: Instant, interval: Duration) -> bool {
+        if !self.pending {
+            return false;
+        }
+        let elapsed = match self.last_render {
+            Some(last_render) => now.duration_since(last_render) >= interval,
+            None => true,
+        };
+        if elapsed {
+            self.last_render = Some(now);
+            self.pending = false;
+        }
+        elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::render_coalescer::RenderCoalescer;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_updates_within_interval_render_once() {
+        let interval = Duration::from_millis(100);
+        let start = Instant::now();
+        let mut coalescer = RenderCoalescer::default();
+        assert!(!coalescer.should_render(start, interval));
+
+        coalescer.request();
+        assert!(coalescer.should_render(start, interval));
+        // The updates queued within the interval wait for the same render.
+        for ms in [10, 20, 50, 99] {
+            coalescer.request();
+            assert!(!coalescer.should_render(start + Duration::from_millis(ms), interval));
+        }
+        assert!(coalescer.should_render(start + Duration::from_millis(100), interval));
+        assert!(!coalescer.should_render(start + Duration::from_millis(150), interval));
+    }
+
+    #[test]
+    fn test_zero_interval_renders_every_request() {
+        let start = Instant::now();
+        let mut coalescer = RenderCoalescer::default();
+        for _ in 0..3 {
+            coalescer.request();
+            assert!(coalescer.should_render(start, Duration::ZERO));
+        }
+        assert!(!coalescer.should_render(start, Duration::ZERO));
+    }
+}
@@ -1,12 +1,45 @@
-use crate::component_name::ComponentName::Prompt;
+use crate::component_name::ComponentName::{Chat, Prompt};
 use crate::{
-    action::Action, app_context::AppContext, app_error::AppError,
-    configs::custom::keymap_custom::ActionBinding, event::Event, tg::tg_backend::TgBackend,
-    tui::Tui, tui_backend::TuiBackend,
+    action::Action,
+    action_audit::{self, action_audit_path, ACTION_AUDIT_MAX_BYTES, ACTION_AUDIT_SHOWN_ENTRIES},
+    app_context::AppContext,
+    app_error::AppError,
+    components::{
+        active_sessions::can_terminate,
+        chat_activity::{activity_sparkline, CHAT_ACTIVITY_BUCKETS},
+        chat_window::actions_after_delete,
+        confirm_popup::destructive_description,
+    },
+    configs::{
+        self, config_type::ConfigType, custom::keymap_custom::ActionBinding,
+        raw::app_raw::SelectionStyle,
+    },
+    event::Event,
+    shutdown::{graceful_shutdown, view_state_path, ViewState},
+    tg::{
+        chat_folders::ALL_CHATS_FOLDER_ID,
+        compose_placeholders::{compose_placeholders_path, ComposePlaceholders},
+        draft_reminders::draft_text,
+        edit_history::text_diff,
+        formatting_preview::FormattingPreview,
+        message_entry::{attach_translation, DateTimeEntry},
+        snoozed_chats::{snoozed_chats_path, SnoozedChats},
+        td_enums::{TdChatList, TdMessageSender},
+        tg_backend::{emoji_status_error, history_page_size_limit, TgBackend},
+    },
+    tui::Tui,
+    tui_backend::TuiBackend,
+    utils::tgt_config_dir,
 };
 use ratatui::layout::Rect;
-use std::{collections::HashMap, io, sync::Arc, time::Instant};
-use tdlib_rs::enums::ChatList;
+use std::{
+    collections::HashMap,
+    future::Future,
+    io,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tdlib_rs::enums::{ChatList, MessageSender};
 use tokio::sync::mpsc::UnboundedSender;
 
 /// Run the main event loop for the application.
@@ -36,6 +69,7 @@ pub async fn run_app(
     tg_backend.handle_authorization_state().await;
     tg_backend.use_quick_ack().await;
     tg_backend.get_me().await;
+    tg_backend.load_time_zones().await;
     tg_backend.load_chats(ChatList::Main, 30).await;
 
     match handle_cli(Arc::clone(&app_context), tg_backend).await {
@@ -55,6 +89,7 @@ pub async fn run_app(
 
     tui_backend.enter()?;
     tui.register_action_handler(app_context.action_tx().clone())?;
+    restore_view_state(&app_context, tg_backend).await?;
 
     // Main loop
     while tg_backend.have_authorization {
@@ -63,7 +98,7 @@ pub async fn run_app(
         handle_app_actions(Arc::clone(&app_context), tui, tui_backend, tg_backend).await?;
 
         if app_context.quit_acquire() {
-            quit_tui(tg_backend, tui_backend).await;
+            quit_tui(&app_context, tg_backend, tui_backend).await;
             tracing::info!("Quitting");
             return Ok(());
         }
@@ -85,6 +120,20 @@ async fn handle_tg_backend_events(
     tg_backend: &mut TgBackend,
 ) -> Result<(), AppError<Action>> {
     if let Some(event) = tg_backend.next().await {
+        let confirm_destructive = app_context.app_config().confirm_destructive;
+        let mut confirmation_gate = app_context.confirmation_gate();
+        let Some(event) = confirmation_gate.filter(event, confirm_destructive) else {
+            if let Some(description) = confirmation_gate
+                .pending()
+                .and_then(|event| destructive_description(event, &app_context.tg_context()))
+            {
+                app_context
+                    .action_tx()
+                    .send(Action::ShowConfirmation(description))?;
+            }
+            return Ok(());
+        };
+        drop(confirmation_gate);
         match event {
             Event::LoadChats(chat_list, limit) => {
                 app_context
@@ -109,6 +158,13 @@ async fn handle_tg_backend_events(
                     .action_tx()
                     .send(Action::DeleteMessages(message_ids, revoke))?;
             }
+            Event::ForwardMessages(message_ids, chat_id, send_copy) => {
+                app_context.action_tx().send(Action::ForwardMessages(
+                    message_ids,
+                    chat_id,
+                    send_copy,
+                ))?;
+            }
             Event::EditMessage(message_id, message) => {
                 // It is important to focus the prompt before editing the message.
                 // Because the actions are sent to the focused component.
@@ -132,6 +188,521 @@ async fn handle_tg_backend_events(
             Event::ViewAllMessages => {
                 app_context.action_tx().send(Action::ViewAllMessages)?;
             }
+            Event::ViewMessage(chat_id, message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::ViewMessage(chat_id, message_id))?;
+            }
+            Event::SetReadOnScrollTo(read_on_scroll_to) => {
+                app_context.app_config().read_on_scroll_to = read_on_scroll_to;
+            }
+            Event::SetJumpToBottomOnSend(jump_to_bottom_on_send) => {
+                app_context.app_config().jump_to_bottom_on_send = jump_to_bottom_on_send;
+            }
+            Event::SetConfirmDestructive(confirm_destructive) => {
+                app_context.app_config().confirm_destructive = confirm_destructive;
+            }
+            Event::LeaveChat(chat_id) => {
+                app_context.action_tx().send(Action::LeaveChat(chat_id))?;
+            }
+            Event::SetScrollMargin(scroll_margin) => {
+                app_context.app_config().scroll_margin = scroll_margin;
+            }
+            Event::SetMaxConcurrentRequests(max_concurrent_requests) => {
+                app_context.app_config().max_concurrent_requests = max_concurrent_requests;
+                app_context
+                    .tg_context()
+                    .request_gate()
+                    .set_max(max_concurrent_requests);
+            }
+            Event::SetAutoAwayTimeout(auto_away_timeout) => {
+                app_context.app_config().auto_away_timeout = auto_away_timeout;
+                if auto_away_timeout <= 0 {
+                    mark_activity(&app_context)?;
+                }
+            }
+            Event::SetEmojiStatus(custom_emoji_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SetEmojiStatus(custom_emoji_id))?;
+            }
+            Event::SetMaxPasteSize(max_paste_size) => {
+                app_context.app_config().max_paste_size = max_paste_size;
+            }
+            Event::SetDeleteLastBehavior(delete_last_behavior) => {
+                app_context.app_config().delete_last_behavior = delete_last_behavior;
+            }
+            Event::CycleSelectionStyle => {
+                let selection_style = app_context.app_config().selection_style.next();
+                app_context.app_config().selection_style = selection_style;
+                save_selection_style(selection_style);
+            }
+            Event::SetMaxReactionsShown(max_reactions_shown) => {
+                app_context.app_config().max_reactions_shown = max_reactions_shown;
+            }
+            Event::SetMessageContentWidth(message_content_width) => {
+                app_context.app_config().message_content_width = message_content_width;
+            }
+            Event::SetHistoryPageSize(history_page_size) => {
+                app_context.app_config().history_page_size =
+                    history_page_size_limit(history_page_size);
+            }
+            Event::SetComposeAutoClearTimeout(compose_auto_clear_timeout) => {
+                app_context.app_config().compose_auto_clear_timeout = compose_auto_clear_timeout;
+            }
+            Event::SetScrolledUpNewMessageBehavior(scrolled_up_new_message_behavior) => {
+                app_context.app_config().scrolled_up_new_message_behavior =
+                    scrolled_up_new_message_behavior;
+            }
+            Event::SetReplyPreviewMode(reply_preview_mode) => {
+                app_context.app_config().reply_preview_mode = reply_preview_mode;
+            }
+            Event::SetRenderInterval(render_interval) => {
+                app_context.app_config().render_interval = render_interval;
+            }
+            Event::SetChatTitleMaxWidth(chat_title_max_width) => {
+                app_context.app_config().chat_title_max_width = chat_title_max_width;
+            }
+            Event::SetDoubleKeyWindow(double_key_window) => {
+                app_context.app_config().double_key_window = double_key_window;
+            }
+            Event::SetBackspaceAction(backspace_action) => {
+                app_context.app_config().backspace_action = backspace_action;
+            }
+            Event::SetHomeEndBehavior(home_end_behavior) => {
+                app_context.app_config().home_end_behavior = home_end_behavior;
+            }
+            Event::SetTimestampGranularity(timestamp_granularity) => {
+                app_context.app_config().timestamp_granularity = timestamp_granularity;
+            }
+            Event::SetTabBehavior(tab_behavior) => {
+                app_context.app_config().tab_behavior = tab_behavior;
+            }
+            Event::SetSavedMessagesNotepadMode(saved_messages_notepad_mode) => {
+                app_context.app_config().saved_messages_notepad_mode = saved_messages_notepad_mode;
+            }
+            Event::SetComposeEscapeAction(compose_escape_action) => {
+                app_context.app_config().compose_escape_action = compose_escape_action;
+            }
+            Event::SetSendRetryPolicy(max_retries, backoff_ms) => {
+                let mut app_config = app_context.app_config();
+                app_config.send_max_retries = max_retries;
+                app_config.send_retry_backoff_ms = backoff_ms;
+            }
+            Event::SendRetry(message_id, attempt) => {
+                app_context
+                    .action_tx()
+                    .send(Action::RetrySend(message_id, attempt))?;
+            }
+            Event::SetDatabaseDirectory(database_dir) => {
+                match app_context
+                    .telegram_config()
+                    .set_database_dir(database_dir.clone())
+                {
+                    Ok(()) => save_database_dir(&database_dir),
+                    Err(e) => tracing::error!("Failed to set the database directory: {e}"),
+                }
+            }
+            Event::ToggleNewMessagesDivider => {
+                app_context
+                    .action_tx()
+                    .send(Action::ToggleNewMessagesDivider)?;
+            }
+            Event::CopyCodeBlock(message_id, block_index) => {
+                app_context
+                    .action_tx()
+                    .send(Action::CopyCodeBlock(message_id, block_index))?;
+            }
+            Event::CopyMessageLinks(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::CopyMessageLinks(message_id))?;
+            }
+            Event::ShowSendError(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::ShowSendError(message_id))?;
+            }
+            Event::CopyVisibleTranscript => {
+                app_context
+                    .action_tx()
+                    .send(Action::CopyVisibleTranscript)?;
+            }
+            Event::NextAlbumItem => {
+                app_context.action_tx().send(Action::NextAlbumItem)?;
+            }
+            Event::PrevAlbumItem => {
+                app_context.action_tx().send(Action::PreviousAlbumItem)?;
+            }
+            Event::NextOwnMessage => {
+                app_context.action_tx().send(Action::NextOwnMessage)?;
+            }
+            Event::PrevOwnMessage => {
+                app_context.action_tx().send(Action::PreviousOwnMessage)?;
+            }
+            Event::ToggleSelectedChatMute => {
+                app_context
+                    .action_tx()
+                    .send(Action::ToggleSelectedChatMute)?;
+            }
+            Event::ToggleHighContrast => {
+                app_context.action_tx().send(Action::ToggleHighContrast)?;
+            }
+            Event::ToggleAvatarInitials => {
+                app_context.action_tx().send(Action::ToggleAvatarInitials)?;
+            }
+            Event::ToggleSenderTimezone => {
+                app_context.action_tx().send(Action::ToggleSenderTimezone)?;
+            }
+            Event::ToggleEmojiShortcodes => {
+                app_context
+                    .action_tx()
+                    .send(Action::ToggleEmojiShortcodes)?;
+            }
+            Event::ToggleChannelSignatures => {
+                app_context
+                    .action_tx()
+                    .send(Action::ToggleChannelSignatures)?;
+            }
+            Event::ToggleViewCounts => {
+                app_context.action_tx().send(Action::ToggleViewCounts)?;
+            }
+            Event::ToggleCommentCounts => {
+                app_context.action_tx().send(Action::ToggleCommentCounts)?;
+            }
+            Event::OpenComments(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::OpenComments(message_id))?;
+            }
+            Event::ToggleFolderTabs => {
+                app_context.action_tx().send(Action::ToggleFolderTabs)?;
+            }
+            Event::ToggleUnreadOnlyView => {
+                app_context.action_tx().send(Action::ToggleUnreadOnlyView)?;
+            }
+            Event::ToggleSearchBar => {
+                app_context.action_tx().send(Action::ToggleSearchBar)?;
+            }
+            Event::SearchMessages(query) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SearchMessages(query))?;
+            }
+            Event::TogglePresenceDisplay => {
+                app_context
+                    .action_tx()
+                    .send(Action::TogglePresenceDisplay)?;
+            }
+            Event::ToggleSyntaxHighlight => {
+                app_context
+                    .action_tx()
+                    .send(Action::ToggleSyntaxHighlight)?;
+            }
+            Event::ToggleBigEmoji => {
+                app_context.action_tx().send(Action::ToggleBigEmoji)?;
+            }
+            Event::ToggleActionAudit => {
+                app_context.action_tx().send(Action::ToggleActionAudit)?;
+            }
+            Event::ShowActionAudit => {
+                app_context.action_tx().send(Action::ActionAudit)?;
+            }
+            Event::SelectFolder(folder_id) => {
+                let selected = app_context.tg_context().chat_folders().select(folder_id);
+                // The chats of the folder are loaded, so that their positions
+                // in the folder are known.
+                if selected && folder_id != ALL_CHATS_FOLDER_ID {
+                    app_context
+                        .action_tx()
+                        .send(Action::LoadChats(TdChatList::Folder(folder_id), 20))?;
+                }
+            }
+            Event::ToggleUnsupportedPlaceholders => {
+                app_context
+                    .action_tx()
+                    .send(Action::ToggleUnsupportedPlaceholders)?;
+            }
+            Event::ToggleMobileLayout => {
+                app_context.action_tx().send(Action::ToggleMobileLayout)?;
+            }
+            Event::OpenReactionPicker(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::OpenReactionPicker(message_id))?;
+            }
+            Event::AddReaction(message_id, emoji) => {
+                app_context
+                    .action_tx()
+                    .send(Action::AddReaction(message_id, emoji))?;
+            }
+            Event::SetSendAs(chat_id, sender_chat_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SetSendAs(chat_id, sender_chat_id))?;
+            }
+            Event::SnoozeChat(chat_id, wake_at) => {
+                let now = chrono::Utc::now().timestamp() as i32;
+                let tg_context = app_context.tg_context();
+                let mut snoozed_chats = tg_context.snoozed_chats();
+                match snoozed_chats.snooze(chat_id, wake_at, now) {
+                    Ok(()) => save_snoozed_chats(&snoozed_chats),
+                    Err(e) => {
+                        tracing::error!("Failed to snooze chat {chat_id}: {e}");
+                        app_context
+                            .action_tx()
+                            .send(Action::StatusMessage(e.to_string()))?;
+                    }
+                }
+            }
+            Event::SetComposePlaceholder(chat_id, text) => {
+                let tg_context = app_context.tg_context();
+                let mut compose_placeholders = tg_context.compose_placeholders();
+                compose_placeholders.set(chat_id, text);
+                save_compose_placeholders(&compose_placeholders);
+            }
+            Event::SetDraftReminder(chat_id, wake_at) => {
+                let now = chrono::Utc::now().timestamp() as i32;
+                let result = app_context
+                    .tg_context()
+                    .draft_reminders()
+                    .set(chat_id, wake_at, now);
+                if let Err(e) = result {
+                    tracing::error!("Failed to set the draft reminder of chat {chat_id}: {e}");
+                    app_context
+                        .action_tx()
+                        .send(Action::StatusMessage(e.to_string()))?;
+                }
+            }
+            Event::SendDraft(chat_id) => {
+                app_context.action_tx().send(Action::SendDraft(chat_id))?;
+            }
+            Event::TranslateMessage(message_id, to_language_code) => {
+                app_context
+                    .action_tx()
+                    .send(Action::TranslateMessage(message_id, to_language_code))?;
+            }
+            Event::ToggleTranslation(message_id) => {
+                let toggled = app_context
+                    .tg_context()
+                    .open_chat_messages()
+                    .iter_mut()
+                    .find(|m| m.id() == message_id)
+                    .is_some_and(|m| m.toggle_translation());
+                if !toggled {
+                    app_context.action_tx().send(Action::StatusMessage(
+                        "The message has not been translated".to_string(),
+                    ))?;
+                }
+            }
+            Event::Quit => {
+                app_context.action_tx().send(Action::Quit)?;
+            }
+            Event::SaveDraft(chat_id, text) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SaveDraft(chat_id, text))?;
+            }
+            Event::FollowEntity(message_id, entity_index) => {
+                let action = app_context
+                    .tg_context()
+                    .open_chat_messages()
+                    .iter()
+                    .find(|m| m.id() == message_id)
+                    .and_then(|m| m.entity_action(entity_index));
+                if let Some(action) = action {
+                    app_context.action_tx().send(action)?;
+                }
+            }
+            Event::ShowCallHistory(chat_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::ShowCallHistory(chat_id))?;
+            }
+            Event::ShowChatPolls(chat_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::ShowChatPolls(chat_id))?;
+            }
+            Event::ShowForumTopics(chat_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::ShowForumTopics(chat_id))?;
+            }
+            Event::OpenBotMenu(chat_id) => {
+                app_context.action_tx().send(Action::OpenBotMenu(chat_id))?;
+            }
+            Event::ShowActiveSessions => {
+                app_context.action_tx().send(Action::ActiveSessions)?;
+            }
+            Event::TerminateSession(session_id) => {
+                let action =
+                    if can_terminate(&app_context.tg_context().active_sessions(), session_id) {
+                        Action::TerminateSession(session_id)
+                    } else {
+                        Action::StatusMessage(
+                            "The current session can not be terminated".to_string(),
+                        )
+                    };
+                app_context.action_tx().send(action)?;
+            }
+            Event::ShowQuizExplanation(message_id) => {
+                let quiz = app_context
+                    .tg_context()
+                    .open_chat_messages()
+                    .iter()
+                    .find(|m| m.id() == message_id)
+                    .and_then(|m| m.quiz().cloned());
+                let action = match quiz {
+                    Some(quiz) => match quiz.explanation() {
+                        Some(explanation) => {
+                            Action::ShowQuizExplanationPanel(explanation.to_string())
+                        }
+                        None if quiz.answered => {
+                            Action::StatusMessage("The quiz has no explanation".to_string())
+                        }
+                        None => Action::StatusMessage(
+                            "Answer the quiz to see its explanation".to_string(),
+                        ),
+                    },
+                    None => Action::StatusMessage("The message is not a quiz".to_string()),
+                };
+                app_context.action_tx().send(action)?;
+            }
+            Event::ShowScheduledMessages(chat_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::ShowScheduledMessages(chat_id))?;
+            }
+            Event::SendScheduledNow(message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::SendScheduledNow(message_id))?;
+            }
+            Event::PreviewFormatting(text, parse_mode) => {
+                app_context
+                    .action_tx()
+                    .send(Action::PreviewFormatting(text, parse_mode))?;
+            }
+            Event::OperationStarted(label) => {
+                // The operation is tracked by the backend while it runs, the
+                // spinner is drawn by `draw_while_pending`.
+                tracing::info!("Operation started: {label}");
+            }
+            Event::OperationFinished(label) => {
+                tracing::info!("Operation finished: {label}");
+            }
+            Event::OpenDocument(message_id) => {
+                let document = app_context
+                    .tg_context()
+                    .open_chat_messages()
+                    .iter()
+                    .find(|m| m.id() == message_id)
+                    .and_then(|m| m.document().cloned());
+                let action = match document {
+                    Some(document) => Action::OpenDocument(document.file_id, document.file_name),
+                    None => Action::StatusMessage("The message is not a document".to_string()),
+                };
+                app_context.action_tx().send(action)?;
+            }
+            Event::ShowUnreadDashboard => {
+                app_context.action_tx().send(Action::UnreadDashboard)?;
+            }
+            Event::OpenChat(chat_id) => {
+                app_context.action_tx().send(Action::OpenChat(chat_id))?;
+            }
+            Event::OpenChatAtMessage(chat_id, message_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::OpenChatAtMessage(chat_id, message_id))?;
+            }
+            Event::ShowEditHistory(message_id) => {
+                let tg_context = app_context.tg_context();
+                let message = tg_context
+                    .open_chat_messages()
+                    .iter()
+                    .find(|m| m.id() == message_id)
+                    .map(|m| (m.is_edited(), m.message_content_to_string()));
+                let action = match message {
+                    Some((true, current)) => {
+                        let diff = tg_context
+                            .edit_history()
+                            .previous(tg_context.open_chat_id(), message_id)
+                            .map(|previous| text_diff(previous, &current));
+                        Action::ShowEditHistoryPanel(current, diff)
+                    }
+                    Some((false, _)) => {
+                        Action::StatusMessage("The message has not been edited".to_string())
+                    }
+                    None => Action::StatusMessage("The message is not loaded".to_string()),
+                };
+                app_context.action_tx().send(action)?;
+            }
+            Event::ShowChatActivity(chat_id) => {
+                let timestamps = app_context.tg_context().loaded_timestamps(chat_id);
+                let mut lines = vec![activity_sparkline(&timestamps, CHAT_ACTIVITY_BUCKETS)];
+                match (timestamps.iter().min(), timestamps.iter().max()) {
+                    (Some(first), Some(last)) => lines.push(format!(
+                        "{} messages from {} to {}",
+                        timestamps.len(),
+                        DateTimeEntry::convert_time(*first as i32),
+                        DateTimeEntry::convert_time(*last as i32)
+                    )),
+                    _ => lines.push("No messages loaded".to_string()),
+                }
+                app_context
+                    .action_tx()
+                    .send(Action::ShowChatActivityPanel(lines))?;
+            }
+            Event::ShowLiveLocationMap(message_id) => {
+                let now = chrono::Utc::now().timestamp() as i32;
+                let lines = app_context
+                    .tg_context()
+                    .open_chat_messages()
+                    .iter()
+                    .find(|m| m.id() == message_id)
+                    .and_then(|m| m.location_panel_lines(now));
+                let action = match lines {
+                    Some(lines) => Action::ShowLocationPanel(lines),
+                    None => Action::StatusMessage("The message is not a location".to_string()),
+                };
+                app_context.action_tx().send(action)?;
+            }
+            Event::OpenSavedMessages => {
+                for action in open_saved_messages_actions(app_context.tg_context().me()) {
+                    app_context.action_tx().send(action)?;
+                }
+            }
+            Event::ShowChatPermissions(chat_id) => {
+                if let Some(permissions) = app_context.tg_context().chat_permissions(chat_id) {
+                    app_context
+                        .action_tx()
+                        .send(Action::ShowChatPermissionsPanel(permissions))?;
+                }
+            }
+            Event::PressKeyboardButton(message_id, row, col) => {
+                let action = app_context
+                    .tg_context()
+                    .open_chat_messages()
+                    .iter()
+                    .find(|m| m.id() == message_id)
+                    .and_then(|m| m.reply_markup())
+                    .and_then(|reply_markup| reply_markup.button_action(message_id, row, col));
+                if let Some(action) = action {
+                    app_context.action_tx().send(action)?;
+                }
+            }
+            Event::OpenSplitView(left_chat_id, right_chat_id) => {
+                app_context
+                    .action_tx()
+                    .send(Action::OpenSplitView(left_chat_id, right_chat_id))?;
+            }
+            Event::CloseSplitView => {
+                app_context.action_tx().send(Action::CloseSplitView)?;
+            }
+            Event::SwapSplitFocus => {
+                app_context.action_tx().send(Action::SwapSplitFocus)?;
+            }
             _ => {}
         }
     }
@@ -155,6 +726,9 @@ async fn handle_tui_backend_events(
     tui_backend: &mut TuiBackend,
 ) -> Result<(), AppError<Action>> {
     if let Some(event) = tui_backend.next().await {
+        if matches!(event, Event::Key(..) | Event::Paste(_)) {
+            mark_activity(&app_context)?;
+        }
         match event {
             Event::Render => app_context.action_tx().send(Action::Render)?,
             Event::Resize(width, height) => app_context
@@ -164,15 +738,29 @@ async fn handle_tui_backend_events(
                 app_context
                     .action_tx()
                     .send(Action::from_key_event(key, modifiers))?;
+                let double_key_window = app_context.app_config().double_key_window;
+                let double = app_context.double_key().press(
+                    Event::Key(key, modifiers).normalized(),
+                    Instant::now(),
+                    double_key_window,
+                );
 
                 // Handle core_window key bindings.
                 if let Some(action_binding) = app_context
                     .keymap_config()
                     .core_window
-                    .get(&Event::Key(key, modifiers))
+                    .get(&Event::Key(key, modifiers).normalized())
                 {
                     match action_binding {
-                        ActionBinding::Single { action, .. } => {
+                        ActionBinding::Single {
+                            action,
+                            double_action,
+                            ..
+                        } => {
+                            let action = match double_action {
+                                Some(double_action) if double => double_action,
+                                _ => action,
+                            };
                             app_context.action_tx().send(action.clone())?;
                             return Ok(());
                         }
@@ -224,7 +812,9 @@ async fn consume_until_single_action(
     let start = Instant::now();
     loop {
         if let Some(event) = tui_backend.next().await {
-            if let Some(ActionBinding::Single { action, .. }) = map_event_action.get(&event) {
+            if let Some(ActionBinding::Single { action, .. }) =
+                map_event_action.get(&event.normalized())
+            {
                 action_tx.send(action.clone()).unwrap();
                 break;
             }
@@ -252,11 +842,34 @@ pub async fn handle_app_actions(
     tg_backend: &mut TgBackend,
 ) -> Result<(), AppError<Action>> {
     while let Ok(action) = app_context.action_rx().try_recv() {
+        // Every action but a render may change what is drawn, as well as the
+        // updates from TDLib. The renders are coalesced by the render
+        // interval, and an idle user interface is not rendered again.
+        // The spinner of the pending operations turns on every render.
+        if !matches!(action, Action::Render)
+            || app_context.tg_context().take_updated()
+            || app_context.tg_context().pending_operations().len() > 0
+        {
+            tui_backend.render_coalescer.request();
+        }
+        if app_context.action_audit() {
+            audit_action(&action);
+        }
         match action {
             Action::Render => {
-                tui_backend.terminal.draw(|f| {
-                    tui.draw(f, f.area()).unwrap();
-                })?;
+                wake_snoozed_chats(&app_context)?;
+                trigger_draft_reminders(&app_context)?;
+                check_idle(&app_context)?;
+                let render_interval =
+                    Duration::from_millis(u64::from(app_context.app_config().render_interval));
+                if tui_backend
+                    .render_coalescer
+                    .should_render(Instant::now(), render_interval)
+                {
+                    tui_backend.terminal.draw(|f| {
+                        tui.draw(f, f.area()).unwrap();
+                    })?;
+                }
             }
             Action::Resize(width, height) => {
                 tui_backend
@@ -271,6 +884,77 @@ pub async fn handle_app_actions(
             Action::Quit => {
                 app_context.quit_store(true);
             }
+            Action::ToggleHighContrast => {
+                app_context.toggle_high_contrast();
+            }
+            Action::ToggleAvatarInitials => {
+                app_context.toggle_avatar_initials();
+            }
+            Action::ToggleSenderTimezone => {
+                app_context.toggle_sender_timezone();
+            }
+            Action::ToggleEmojiShortcodes => {
+                app_context.toggle_emoji_shortcodes();
+            }
+            Action::ToggleChannelSignatures => {
+                app_context.toggle_channel_signatures();
+            }
+            Action::TogglePresenceDisplay => {
+                app_context.toggle_presence_display();
+            }
+            Action::ToggleSyntaxHighlight => {
+                app_context.toggle_syntax_highlight();
+            }
+            Action::ToggleBigEmoji => {
+                app_context.toggle_big_emoji();
+            }
+            Action::ToggleActionAudit => {
+                app_context.toggle_action_audit();
+                let status = if app_context.action_audit() {
+                    "The destructive actions are logged"
+                } else {
+                    "The destructive actions are no longer logged"
+                };
+                app_context
+                    .action_tx()
+                    .send(Action::StatusMessage(status.to_string()))?;
+            }
+            Action::ActionAudit => {
+                let entries = action_audit_path()
+                    .map(|path| action_audit::read_recent(&path, ACTION_AUDIT_SHOWN_ENTRIES))
+                    .unwrap_or_default();
+                app_context
+                    .action_tx()
+                    .send(Action::ShowActionAuditPanel(entries))?;
+            }
+            Action::ToggleFolderTabs => {
+                app_context.toggle_folder_tabs();
+                if !app_context.folder_tabs() {
+                    app_context
+                        .tg_context()
+                        .chat_folders()
+                        .select(ALL_CHATS_FOLDER_ID);
+                }
+            }
+            Action::ToggleUnreadOnlyView => {
+                app_context.toggle_unread_only_view();
+            }
+            Action::ToggleSearchBar => {
+                app_context.toggle_search_bar();
+            }
+            Action::ToggleViewCounts => {
+                app_context.toggle_view_counts();
+            }
+            Action::ToggleCommentCounts => {
+                app_context.toggle_comment_counts();
+            }
+            Action::ToggleUnsupportedPlaceholders => {
+                app_context.toggle_unsupported_placeholders();
+            }
+            Action::ToggleNewMessagesDivider => {
+                let mut app_config = app_context.app_config();
+                app_config.show_new_messages_divider = !app_config.show_new_messages_divider;
+            }
             Action::LoadChats(chat_list, limit) => {
                 tg_backend.load_chats(chat_list.into(), limit).await;
             }
@@ -289,16 +973,68 @@ pub async fn handle_app_actions(
                     .await;
             }
             Action::GetChatHistory => {
-                tg_backend
-                    .get_chat_history(app_context.tg_context().open_chat_id())
-                    .await;
+                let chat_id = app_context.tg_context().open_chat_id();
+                draw_while_pending(
+                    &app_context,
+                    tui,
+                    tui_backend,
+                    tg_backend.get_chat_history(chat_id),
+                )
+                .await?;
+            }
+            Action::LeaveChat(chat_id) => {
+                if tg_backend.leave_chat(chat_id).await.is_ok() {
+                    app_context.tg_context().remove_chat_from_list(chat_id);
+                }
+            }
+            Action::SetEmojiStatus(custom_emoji_id) => {
+                if let Err(e) = tg_backend.set_emoji_status(custom_emoji_id).await {
+                    app_context
+                        .action_tx()
+                        .send(Action::StatusMessage(emoji_status_error(&e)))?;
+                }
             }
             Action::DeleteMessages(ref message_ids, revoke) => {
+                // The deletion has been confirmed, if needed, when the action
+                // is dispatched. The messages are only removed once TDLib
+                // deleted them.
+                let tg_context = app_context.tg_context();
+                if tg_backend
+                    .delete_messages(tg_context.open_chat_id(), message_ids.to_vec(), revoke)
+                    .await
+                    .is_ok()
+                {
+                    for message_id in message_ids.iter() {
+                        tg_context.delete_message(*message_id);
+                    }
+                    let mut remaining = tg_context.open_chat_messages().len();
+                    if remaining == 0 {
+                        // Only the loaded messages are gone, the history is
+                        // loaded again to know whether older ones remain.
+                        tg_context.set_from_message_id(0);
+                        let chat_id = tg_context.open_chat_id();
+                        draw_while_pending(
+                            &app_context,
+                            tui,
+                            tui_backend,
+                            tg_backend.get_chat_history(chat_id),
+                        )
+                        .await?;
+                        remaining = tg_context.open_chat_messages().len();
+                    }
+                    let delete_last_behavior = app_context.app_config().delete_last_behavior;
+                    for action in actions_after_delete(remaining, delete_last_behavior) {
+                        app_context.action_tx().send(action)?;
+                    }
+                }
+            }
+            Action::ForwardMessages(ref message_ids, chat_id, send_copy) => {
                 tg_backend
-                    .delete_messages(
+                    .forward_messages(
+                        chat_id,
                         app_context.tg_context().open_chat_id(),
                         message_ids.to_vec(),
-                        revoke,
+                        send_copy,
                     )
                     .await;
             }
@@ -310,6 +1046,346 @@ pub async fn handle_app_actions(
             Action::ViewAllMessages => {
                 tg_backend.view_all_messages().await;
             }
+            Action::ViewMessage(chat_id, message_id) => {
+                tg_backend.view_message(chat_id, message_id).await;
+            }
+            Action::SetChatMuteFor(chat_id, mute_for) => {
+                tg_backend.set_chat_mute_for(chat_id, mute_for).await;
+            }
+            Action::OpenReactionPicker(message_id) => {
+                let available = tg_backend
+                    .get_message_available_reactions(
+                        app_context.tg_context().open_chat_id(),
+                        message_id,
+                    )
+                    .await;
+                app_context
+                    .action_tx()
+                    .send(Action::ShowReactionPicker(message_id, available))?;
+            }
+            Action::AddReaction(message_id, ref emoji) => {
+                tg_backend
+                    .add_message_reaction(
+                        app_context.tg_context().open_chat_id(),
+                        message_id,
+                        emoji.to_string(),
+                    )
+                    .await;
+            }
+            Action::ShowCallHistory(chat_id) => {
+                let entries = tg_backend.get_call_history(chat_id).await;
+                app_context
+                    .action_tx()
+                    .send(Action::ShowCallHistoryPanel(entries))?;
+            }
+            Action::ShowChatPolls(chat_id) => {
+                let polls = draw_while_pending(
+                    &app_context,
+                    tui,
+                    tui_backend,
+                    tg_backend.search_chat_polls(chat_id),
+                )
+                .await?;
+                app_context
+                    .action_tx()
+                    .send(Action::ShowChatPollsPanel(polls))?;
+            }
+            Action::ShowForumTopics(chat_id) => {
+                let topics = tg_backend.get_forum_topics(chat_id).await;
+                app_context
+                    .action_tx()
+                    .send(Action::ShowForumTopicsPanel(topics))?;
+            }
+            Action::OpenBotMenu(chat_id) => {
+                let user_id = app_context
+                    .tg_context()
+                    .user_of_chat(chat_id)
+                    .map(|user| user.id);
+                let menu = match user_id {
+                    Some(user_id) => tg_backend.get_bot_menu(user_id).await,
+                    None => None,
+                };
+                let action = match menu {
+                    Some(menu) => Action::ShowBotMenuPanel(menu),
+                    None => Action::StatusMessage("The chat has no bot menu".to_string()),
+                };
+                app_context.action_tx().send(action)?;
+            }
+            Action::ActiveSessions => {
+                let sessions = tg_backend.get_active_sessions().await;
+                app_context
+                    .tg_context()
+                    .set_active_sessions(sessions.clone());
+                app_context
+                    .action_tx()
+                    .send(Action::ShowActiveSessionsPanel(sessions))?;
+            }
+            Action::TerminateSession(session_id) => {
+                let status = match tg_backend.terminate_session(session_id).await {
+                    Ok(()) => {
+                        app_context
+                            .tg_context()
+                            .active_sessions()
+                            .retain(|session| session.id != session_id);
+                        "Session terminated".to_string()
+                    }
+                    Err(e) => format!("Failed to terminate the session: {}", e.message),
+                };
+                app_context
+                    .action_tx()
+                    .send(Action::StatusMessage(status))?;
+            }
+            Action::ShowScheduledMessages(chat_id) => {
+                let scheduled = tg_backend.get_chat_scheduled_messages(chat_id).await;
+                app_context
+                    .action_tx()
+                    .send(Action::ShowScheduledMessagesPanel(scheduled))?;
+            }
+            Action::SendScheduledNow(message_id) => {
+                let chat_id = app_context.tg_context().open_chat_id();
+                let status = match tg_backend
+                    .edit_message_scheduling_state(chat_id, message_id, None)
+                    .await
+                {
+                    Ok(()) => {
+                        app_context
+                            .action_tx()
+                            .send(Action::ScheduledMessageSent(message_id))?;
+                        "The scheduled message has been sent".to_string()
+                    }
+                    Err(e) => format!("Failed to send the scheduled message: {}", e.message),
+                };
+                app_context
+                    .action_tx()
+                    .send(Action::StatusMessage(status))?;
+            }
+            Action::PreviewFormatting(ref text, parse_mode) => {
+                let parsed = tg_backend
+                    .parse_text_entities(text.to_string(), parse_mode)
+                    .await
+                    .map_err(|e| e.message);
+                app_context
+                    .action_tx()
+                    .send(Action::ShowFormattingPreviewPanel(
+                        FormattingPreview::from_parsed(parsed),
+                    ))?;
+            }
+            Action::OpenForumTopic(message_thread_id) => {
+                let tg_context = app_context.tg_context();
+                if tg_context.open_topic_id() != message_thread_id {
+                    tg_context.set_open_topic_id(message_thread_id);
+                    tg_context.clear_open_chat_messages();
+                    tg_context.set_from_message_id(0);
+                    let chat_id = tg_context.open_chat_id();
+                    draw_while_pending(
+                        &app_context,
+                        tui,
+                        tui_backend,
+                        tg_backend.get_chat_history(chat_id),
+                    )
+                    .await?;
+                }
+            }
+            Action::OpenComments(message_id) => {
+                let chat_id = app_context.tg_context().open_chat_id();
+                match tg_backend.get_message_thread(chat_id, message_id).await {
+                    Ok((discussion_chat_id, message_thread_id)) => {
+                        // The comments are the thread of the post in the
+                        // discussion group.
+                        let tg_context = app_context.tg_context();
+                        tg_context.open_chat(discussion_chat_id);
+                        tg_context.set_open_topic_id(message_thread_id);
+                        draw_while_pending(
+                            &app_context,
+                            tui,
+                            tui_backend,
+                            tg_backend.get_chat_history(discussion_chat_id),
+                        )
+                        .await?;
+                        app_context
+                            .action_tx()
+                            .send(Action::FocusComponent(Prompt))?;
+                    }
+                    Err(e) => {
+                        app_context.action_tx().send(Action::StatusMessage(format!(
+                            "Failed to open the comments: {}",
+                            e.message
+                        )))?;
+                    }
+                }
+            }
+            Action::OpenUsername(ref username) => {
+                match tg_backend.search_public_chat(username).await {
+                    Ok(chat_id) => {
+                        app_context.tg_context().open_chat(chat_id);
+                        draw_while_pending(
+                            &app_context,
+                            tui,
+                            tui_backend,
+                            tg_backend.get_chat_history(chat_id),
+                        )
+                        .await?;
+                        app_context
+                            .action_tx()
+                            .send(Action::FocusComponent(Prompt))?;
+                    }
+                    Err(_) => {
+                        app_context.action_tx().send(Action::StatusMessage(format!(
+                            "Unknown username @{}",
+                            username
+                        )))?;
+                    }
+                }
+            }
+            Action::UnreadDashboard => {
+                let chats = app_context.tg_context().unread_chats();
+                app_context
+                    .action_tx()
+                    .send(Action::ShowUnreadDashboardPanel(chats))?;
+                // The dashboard is drawn over the chat, which handles its keys.
+                app_context.action_tx().send(Action::FocusComponent(Chat))?;
+            }
+            Action::OpenChatAtMessage(chat_id, message_id) => {
+                let tg_context = app_context.tg_context();
+                tg_context.open_chat(chat_id);
+                // The history is loaded from the message, so that it is the
+                // last one shown.
+                tg_context.set_from_message_id(message_id);
+                tg_context.snoozed_chats().dismiss(chat_id);
+                draw_while_pending(
+                    &app_context,
+                    tui,
+                    tui_backend,
+                    tg_backend.get_chat_history(chat_id),
+                )
+                .await?;
+                app_context
+                    .action_tx()
+                    .send(Action::FocusComponent(Prompt))?;
+            }
+            Action::SearchMessages(ref query) => {
+                let messages = draw_while_pending(
+                    &app_context,
+                    tui,
+                    tui_backend,
+                    tg_backend.search_messages(query),
+                )
+                .await?;
+                app_context
+                    .action_tx()
+                    .send(Action::ShowSearchBarMessages(query.to_string(), messages))?;
+            }
+            Action::GetMe => tg_backend.get_me().await,
+            Action::OpenSavedMessages => {
+                let me = app_context.tg_context().me();
+                if me == 0 {
+                    app_context.action_tx().send(Action::StatusMessage(
+                        "The current user is not resolved yet".to_string(),
+                    ))?;
+                } else if let Ok(chat_id) = tg_backend.create_private_chat(me).await {
+                    // The chat is opened as the ones chosen in the chat list.
+                    app_context.action_tx().send(Action::OpenChat(chat_id))?;
+                }
+            }
+            Action::SearchHashtag(ref hashtag) => {
+                let chat_id = app_context.tg_context().open_chat_id();
+                let results = draw_while_pending(
+                    &app_context,
+                    tui,
+                    tui_backend,
+                    tg_backend.search_chat_messages(chat_id, hashtag),
+                )
+                .await?;
+                app_context
+                    .action_tx()
+                    .send(Action::ShowSearchResults(hashtag.to_string(), results))?;
+            }
+            Action::ChatWindowSendAs => {
+                let senders = tg_backend
+                    .get_chat_available_message_senders(app_context.tg_context().open_chat_id())
+                    .await;
+                app_context
+                    .action_tx()
+                    .send(Action::ShowSendAsPicker(senders))?;
+            }
+            Action::SetSendAs(chat_id, sender_chat_id) => {
+                let tg_context = app_context.tg_context();
+                let sender = TdMessageSender::from_sender_id(sender_chat_id, tg_context.me());
+                tg_context.set_send_as(chat_id, sender);
+                tg_backend
+                    .set_chat_message_sender(chat_id, MessageSender::from(&sender))
+                    .await;
+            }
+            Action::OpenDocument(file_id, ref file_name) => {
+                tg_backend.open_document(file_id, file_name.clone());
+            }
+            Action::SetOnline(true) => tg_backend.online().await,
+            Action::SetOnline(false) => tg_backend.offline().await,
+            Action::SendDraft(chat_id) => {
+                let _ = tg_backend.send_draft(chat_id).await;
+            }
+            Action::SaveDraft(chat_id, text) => {
+                tg_backend.save_draft(chat_id, text).await;
+            }
+            Action::TranslateMessage(message_id, ref to_language_code) => {
+                let chat_id = app_context.tg_context().open_chat_id();
+                match draw_while_pending(
+                    &app_context,
+                    tui,
+                    tui_backend,
+                    tg_backend.translate_message_text(chat_id, message_id, to_language_code),
+                )
+                .await?
+                {
+                    Ok(translation) => {
+                        attach_translation(
+                            &mut app_context.tg_context().open_chat_messages(),
+                            message_id,
+                            translation,
+                        );
+                    }
+                    Err(e) => {
+                        app_context.action_tx().send(Action::StatusMessage(format!(
+                            "Failed to translate the message to {}: {}",
+                            to_language_code, e.message
+                        )))?;
+                    }
+                }
+            }
+            Action::RetrySend(message_id, attempt) => {
+                tg_backend.retry_send(message_id, attempt);
+            }
+            Action::GetCallbackQueryAnswer(message_id, ref data) => {
+                tg_backend
+                    .get_callback_query_answer(
+                        app_context.tg_context().open_chat_id(),
+                        message_id,
+                        data.to_string(),
+                    )
+                    .await;
+            }
+            Action::OpenSplitView(left_chat_id, right_chat_id) => {
+                // The right chat is loaded first and parked on the unfocused
+                // side, then the left chat is opened and receives the input.
+                let tg_context = app_context.tg_context();
+                tg_context.open_chat(right_chat_id);
+                draw_while_pending(
+                    &app_context,
+                    tui,
+                    tui_backend,
+                    tg_backend.get_chat_history(right_chat_id),
+                )
+                .await?;
+                tg_context.park_open_chat();
+                tg_context.open_chat(left_chat_id);
+                draw_while_pending(
+                    &app_context,
+                    tui,
+                    tui_backend,
+                    tg_backend.get_chat_history(left_chat_id),
+                )
+                .await?;
+            }
             _ => {}
         }
 
@@ -318,6 +1394,49 @@ pub async fn handle_app_actions(
     Ok(())
 }
 
+/// The time between two frames drawn while a long operation is pending, it
+/// is the time a frame of the spinner is shown.
+const PENDING_FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Await a request of the backend made by the main loop, the frames are
+/// drawn while a long operation is pending, so that its spinner is shown in
+/// the status bar. The main loop is the only place the terminal is drawn,
+/// and it is blocked until the request returns.
+///
+/// # Arguments
+/// * `app_context` - The application context, with the pending operations.
+/// * `tui` - A mutable reference to the Tui struct.
+/// * `tui_backend` - A mutable reference to the TuiBackend struct.
+/// * `request` - The request of the backend.
+///
+/// # Returns
+/// * `Result<F::Output, AppError>` - The response of the request or an
+///   error if the terminal can not be drawn.
+async fn draw_while_pending<F: Future>(
+    app_context: &AppContext,
+    tui: &mut Tui,
+    tui_backend: &mut TuiBackend,
+    request: F,
+) -> Result<F::Output, AppError<Action>> {
+    tokio::pin!(request);
+    let mut frames = tokio::time::interval(PENDING_FRAME_INTERVAL);
+    loop {
+        tokio::select! {
+            // The request is polled first, so that its operation is started
+            // before the first frame is drawn.
+            biased;
+            output = &mut request => return Ok(output),
+            _ = frames.tick() => {
+                if app_context.tg_context().pending_operations().len() > 0 {
+                    tui_backend.terminal.draw(|f| {
+                        tui.draw(f, f.area()).unwrap();
+                    })?;
+                }
+            }
+        }
+    }
+}
+
 /// An enum to represent the outcome of the handle_cli function.
 enum HandleCliOutcome {
     /// The application should quit.
@@ -400,21 +1519,76 @@ async fn handle_cli(app_context: Arc<AppContext>, tg_backend: &mut TgBackend) ->
 }
 
 /// Quit the tui.
+/// The view state is saved and the drafts queued when quitting are flushed
+/// before the TDLib client is closed, a quit key pressed meanwhile or a
+/// second `Quit` event skips the flush.
 ///
 /// # Arguments
+/// * `app_context` - An Arc wrapped AppContext struct.
 /// * `tg_backend` - A mutable reference to the TgBackend struct.
 /// * `tui_backend` - A mutable reference to the TuiBackend struct.
-async fn quit_tui(tg_backend: &mut TgBackend, tui_backend: &mut TuiBackend) {
+async fn quit_tui(
+    app_context: &Arc<AppContext>,
+    tg_backend: &mut TgBackend,
+    tui_backend: &mut TuiBackend,
+) {
     futures::join!(tg_backend.offline());
-    tg_backend.have_authorization = false;
-    tg_backend.close().await;
+    save_view_state(&ViewState::from_app_context(app_context));
+
+    let mut drafts = Vec::new();
+    let mut forced = false;
+    while let Ok(event) = tg_backend.event_rx.try_recv() {
+        match event {
+            Event::SaveDraft(chat_id, text) => drafts.push((chat_id, text)),
+            Event::Quit => forced = true,
+            _ => {}
+        }
+    }
+    if forced {
+        drafts.clear();
+    }
+    let timeout = Duration::from_millis(app_context.app_config().shutdown_timeout.into());
+    let forced_quit = async {
+        loop {
+            match tui_backend.next().await {
+                Some(event) if is_quit_key(app_context, &event) => break,
+                Some(_) => {}
+                // Nothing can force the quit anymore.
+                None => std::future::pending::<()>().await,
+            }
+        }
+    };
+    graceful_shutdown(tg_backend, drafts, timeout, forced_quit).await;
     tui_backend.exit();
-    tg_backend.handle_authorization_state().await;
 
     // Clear the terminal and move the cursor to the top left corner
     io::Write::write_all(&mut io::stdout().lock(), b"\x1b[2J\x1b[1;1H").unwrap();
 }
 
+/// Check whether an event is a key bound to quit in the core window.
+///
+/// # Arguments
+/// * `app_context` - An Arc wrapped AppContext struct.
+/// * `event` - The event.
+///
+/// # Returns
+/// * `bool` - Whether the event quits.
+fn is_quit_key(app_context: &AppContext, event: &Event) -> bool {
+    let Event::Key(key, modifiers) = event else {
+        return false;
+    };
+    matches!(
+        app_context
+            .keymap_config()
+            .core_window
+            .get(&Event::Key(*key, *modifiers).normalized()),
+        Some(ActionBinding::Single {
+            action: Action::Quit | Action::TryQuit,
+            ..
+        })
+    )
+}
+
 /// Quit the cli.
 ///
 /// # Arguments
@@ -439,3 +1613,267 @@ async fn log_out(tg_backend: &mut TgBackend) {
     // Clear the terminal and move the cursor to the top left corner
     io::Write::write_all(&mut io::stdout().lock(), b"\x1b[2J\x1b[1;1H").unwrap();
 }
+
+/// Wake up the snoozed chats whose wake time has passed, they are moved to the
+/// top of the chat list and a reminder is shown in the status bar.
+///
+/// # Arguments
+/// * `app_context` - An Arc wrapped AppContext struct.
+///
+/// # Returns
+/// * `Result<(), AppError>` - An Ok result or an error.
+fn wake_snoozed_chats(app_context: &AppContext) -> Result<(), AppError<Action>> {
+    let now = chrono::Utc::now().timestamp() as i32;
+    let tg_context = app_context.tg_context();
+    let mut snoozed_chats = tg_context.snoozed_chats();
+    let woken = snoozed_chats.wake(now);
+    if woken.is_empty() {
+        return Ok(());
+    }
+    save_snoozed_chats(&snoozed_chats);
+    drop(snoozed_chats);
+    for chat_id in woken {
+        let title = tg_context
+            .chats()
+            .get(&chat_id)
+            .map_or_else(|| chat_id.to_string(), |chat| chat.title.clone());
+        app_context
+            .action_tx()
+            .send(Action::StatusMessage(format!("⏰ Reminder: {title}")))?;
+    }
+    Ok(())
+}
+
+/// Show again the drafts whose reminder is due, and ask to send them when
+/// `draft_reminder_prompt` is enabled.
+/// The reminders of the chats whose draft has been cleared meanwhile are
+/// dropped.
+///
+/// # Arguments
+/// * `app_context` - An Arc wrapped AppContext struct.
+///
+/// # Returns
+/// * `Result<(), AppError>` - An Ok result or an error.
+fn trigger_draft_reminders(app_context: &AppContext) -> Result<(), AppError<Action>> {
+    let now = chrono::Utc::now().timestamp() as i32;
+    let tg_context = app_context.tg_context();
+    let due = tg_context.draft_reminders().due(now);
+    for chat_id in due {
+        let Some((title, draft)) = tg_context
+            .chats()
+            .get(&chat_id)
+            .and_then(|chat| Some((chat.title.clone(), draft_text(chat)?)))
+        else {
+            continue;
+        };
+        app_context.action_tx().send(Action::StatusMessage(format!(
+            "✏️ Draft reminder: {title}: {draft}"
+        )))?;
+        if app_context.app_config().draft_reminder_prompt {
+            if let Some(event_tx) = tg_context.event_tx().as_ref() {
+                event_tx.send(Event::SendDraft(chat_id)).unwrap();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Record an activity of the user, the user is set online again when it was
+/// away because of the inactivity.
+///
+/// # Arguments
+/// * `app_context` - An Arc wrapped AppContext struct.
+///
+/// # Returns
+/// * `Result<(), AppError>` - An Ok result or an error.
+fn mark_activity(app_context: &AppContext) -> Result<(), AppError<Action>> {
+    let now = chrono::Utc::now().timestamp() as i32;
+    if app_context.tg_context().idle_presence().activity(now) {
+        app_context.action_tx().send(Action::SetOnline(true))?;
+    }
+    Ok(())
+}
+
+/// Set the user offline when it has been idle for the `auto_away_timeout`.
+///
+/// # Arguments
+/// * `app_context` - An Arc wrapped AppContext struct.
+///
+/// # Returns
+/// * `Result<(), AppError>` - An Ok result or an error.
+fn check_idle(app_context: &AppContext) -> Result<(), AppError<Action>> {
+    let now = chrono::Utc::now().timestamp() as i32;
+    let auto_away_timeout = app_context.app_config().auto_away_timeout;
+    if app_context
+        .tg_context()
+        .idle_presence()
+        .idle(now, auto_away_timeout)
+    {
+        app_context.action_tx().send(Action::SetOnline(false))?;
+    }
+    Ok(())
+}
+
+/// Save the snoozed chats in the configuration directory, so that they are
+/// kept across restarts.
+///
+/// # Arguments
+/// * `snoozed_chats` - The snoozed chats.
+fn save_snoozed_chats(snoozed_chats: &SnoozedChats) {
+    let result = snoozed_chats_path()
+        .map_err(AppError::from)
+        .and_then(|path| snoozed_chats.save(&path));
+    if let Err(e) = result {
+        tracing::error!("Failed to save the snoozed chats: {e}");
+    }
+}
+
+/// Restore the view state saved when quitting: the toggled settings, the
+/// open chat and the message it was scrolled to.
+///
+/// # Arguments
+/// * `app_context` - The application context.
+/// * `tg_backend` - A mutable reference to the TgBackend struct.
+///
+/// # Returns
+/// * `Result<(), AppError>` - An Ok result or an error.
+async fn restore_view_state(
+    app_context: &AppContext,
+    tg_backend: &mut TgBackend,
+) -> Result<(), AppError<Action>> {
+    let Some(view_state) = view_state_path()
+        .ok()
+        .and_then(|path| ViewState::load(&path))
+    else {
+        return Ok(());
+    };
+    view_state.apply_settings(app_context);
+    if view_state.open_chat_id == 0 {
+        return Ok(());
+    }
+    let tg_context = app_context.tg_context();
+    tg_context.open_chat(view_state.open_chat_id);
+    tg_context.set_open_topic_id(view_state.open_topic_id);
+    // The history is loaded from the message, so that it is the last one
+    // shown like when quitting.
+    tg_context.set_from_message_id(view_state.scroll_message_id);
+    tg_backend.get_chat_history(view_state.open_chat_id).await;
+    app_context
+        .action_tx()
+        .send(Action::FocusComponent(Prompt))?;
+    Ok(())
+}
+
+/// Save the view state in the configuration directory.
+///
+/// # Arguments
+/// * `view_state` - The view state.
+fn save_view_state(view_state: &ViewState) {
+    let result = view_state_path()
+        .map_err(AppError::from)
+        .and_then(|path| view_state.save(&path));
+    if let Err(e) = result {
+        tracing::error!("Failed to save the view state: {e}");
+    }
+}
+
+/// Save the placeholders of the compose box in the configuration directory,
+/// so that they are kept across restarts.
+///
+/// # Arguments
+/// * `compose_placeholders` - The placeholders of the compose box.
+fn save_compose_placeholders(compose_placeholders: &ComposePlaceholders) {
+    let result = compose_placeholders_path()
+        .map_err(AppError::from)
+        .and_then(|path| compose_placeholders.save(&path));
+    if let Err(e) = result {
+        tracing::error!("Failed to save the compose placeholders: {e}");
+    }
+}
+
+/// Log a destructive action in the audit log, the other actions are
+/// ignored.
+///
+/// # Arguments
+/// * `action` - The action.
+fn audit_action(action: &Action) {
+    let Some(entry) = action_audit::audit_entry(action) else {
+        return;
+    };
+    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    let result = action_audit_path()
+        .and_then(|path| action_audit::append(&path, &timestamp, &entry, ACTION_AUDIT_MAX_BYTES));
+    if let Err(e) = result {
+        tracing::error!("Failed to log the action in the audit log: {e}");
+    }
+}
+
+/// Save the selection style in the configuration file of the application, so
+/// that it is kept across restarts.
+///
+/// # Arguments
+/// * `selection_style` - The selection style.
+fn save_selection_style(selection_style: SelectionStyle) {
+    let result = tgt_config_dir().map_err(AppError::from).and_then(|dir| {
+        configs::persist_toml_value(
+            &dir.join(ConfigType::App.as_default_filename()),
+            "selection_style",
+            &format!("\"{}\"", selection_style),
+        )
+    });
+    if let Err(e) = result {
+        tracing::error!("Failed to save the selection style: {e}");
+    }
+}
+
+/// Get the actions that open the Saved Messages, i.e. the chat of the current
+/// user with itself.
+/// When the current user is not resolved yet it is resolved first.
+///
+/// # Arguments
+/// * `me` - The identifier of the current user, `0` when unknown.
+///
+/// # Returns
+/// * `Vec<Action>` - The actions to perform, in order.
+fn open_saved_messages_actions(me: i64) -> Vec<Action> {
+    if me == 0 {
+        vec![Action::GetMe, Action::OpenSavedMessages]
+    } else {
+        vec![Action::OpenSavedMessages]
+    }
+}
+
+/// Save the database directory in the configuration file of telegram, so
+/// that it is used after a restart.
+///
+/// # Arguments
+/// * `database_dir` - The path of the directory.
+fn save_database_dir(database_dir: &str) {
+    let result = tgt_config_dir().map_err(AppError::from).and_then(|dir| {
+        configs::persist_toml_value(
+            &dir.join(ConfigType::Telegram.as_default_filename()),
+            "database_dir",
+            &configs::toml_string(database_dir),
+        )
+    });
+    if let Err(e) = result {
+        tracing::error!("Failed to save the database directory: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{action::Action, run::open_saved_messages_actions};
+
+    #[test]
+    fn test_open_saved_messages_deferred() {
+        assert_eq!(
+            open_saved_messages_actions(0),
+            vec![Action::GetMe, Action::OpenSavedMessages]
+        );
+        assert_eq!(
+            open_saved_messages_actions(42),
+            vec![Action::OpenSavedMessages]
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use crate::{
+    app_context::AppContext, app_error::AppError, configs, tg::tg_backend::TgBackend,
+    utils::tgt_config_dir,
+};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The name of the file, in the configuration directory, where the view
+/// state is saved when quitting.
+pub const VIEW_STATE_FILENAME: &str = "view_state.toml";
+
+/// Get the path of the file where the view state is saved.
+///
+/// # Returns
+/// The path of the file in the default configuration directory.
+pub fn view_state_path() -> io::Result<PathBuf> {
+    Ok(tgt_config_dir()?.join(VIEW_STATE_FILENAME))
+}
+
+/// A setting toggled from the application: its name, how it is read and how
+/// it is toggled.
+type ToggledSetting = (&'static str, fn(&AppContext) -> bool, fn(&AppContext));
+
+/// The settings saved with the view state.
+const SETTINGS: [ToggledSetting; 15] = [
+    (
+        "high_contrast",
+        AppContext::high_contrast,
+        AppContext::toggle_high_contrast,
+    ),
+    (
+        "avatar_initials",
+        AppContext::avatar_initials,
+        AppContext::toggle_avatar_initials,
+    ),
+    (
+        "unsupported_placeholders",
+        AppContext::unsupported_placeholders,
+        AppContext::toggle_unsupported_placeholders,
+    ),
+    (
+        "sender_timezone",
+        AppContext::sender_timezone,
+        AppContext::toggle_sender_timezone,
+    ),
+    (
+        "emoji_shortcodes",
+        AppContext::emoji_shortcodes,
+        AppContext::toggle_emoji_shortcodes,
+    ),
+    (
+        "view_counts",
+        AppContext::view_counts,
+        AppContext::toggle_view_counts,
+    ),
+    (
+        "comment_counts",
+        AppContext::comment_counts,
+        AppContext::toggle_comment_counts,
+    ),
+    (
+        "channel_signatures",
+        AppContext::channel_signatures,
+        AppContext::toggle_channel_signatures,
+    ),
+    (
+        "folder_tabs",
+        AppContext::folder_tabs,
+        AppContext::toggle_folder_tabs,
+    ),
+    (
+        "unread_only_view",
+        AppContext::unread_only_view,
+        AppContext::toggle_unread_only_view,
+    ),
+    (
+        "search_bar",
+        AppContext::search_bar,
+        AppContext::toggle_search_bar,
+    ),
+    (
+        "presence_display",
+        AppContext::presence_display,
+        AppContext::toggle_presence_display,
+    ),
+    (
+        "syntax_highlight",
+        AppContext::syntax_highlight,
+        AppContext::toggle_syntax_highlight,
+    ),
+    (
+        "big_emoji",
+        AppContext::big_emoji,
+        AppContext::toggle_big_emoji,
+    ),
+    (
+        "action_audit",
+        AppContext::action_audit,
+        AppContext::toggle_action_audit,
+    ),
+];
+
+#[derive(Debug, Deserialize)]
+/// The content of the file where the view state is saved.
+struct ViewStateRaw {
+    /// The open chat and its scroll.
+    view_state: Option<ViewStateSectionRaw>,
+    /// The toggled settings, by name.
+    settings: Option<HashMap<String, bool>>,
+}
+
+#[derive(Debug, Deserialize)]
+/// The `[view_state]` section of the file where the view state is saved.
+struct ViewStateSectionRaw {
+    open_chat_id: Option<i64>,
+    open_topic_id: Option<i64>,
+    scroll_message_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// `ViewState` is what is shown when quitting: the open chat, where it is
+/// scrolled to and the settings toggled while the client was running.
+/// It is restored on the next start.
+pub struct ViewState {
+    /// The identifier of the open chat, `0` when no chat is open.
+    pub open_chat_id: i64,
+    /// The `message_thread_id` of the open forum topic, `0` when the
+    /// messages of all the topics are shown.
+    pub open_topic_id: i64,
+    /// The newest message shown in the open chat, `0` when the most recent
+    /// messages are shown.
+    pub scroll_message_id: i64,
+    /// The toggled settings, by name.
+    pub settings: Vec<(&'static str, bool)>,
+}
+/// Implementation of the `ViewState` struct.
+impl ViewState {
+    /// Get the view state of the application.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context.
+    ///
+    /// # Returns
+    /// * `Self` - The view state.
+    pub fn from_app_context(app_context: &AppContext) -> Self {
+        let tg_context = app_context.tg_context();
+        ViewState {
+            open_chat_id: tg_context.open_chat_id(),
+            open_topic_id: tg_context.open_topic_id(),
+            scroll_message_id: tg_context.scroll_message_id(),
+            settings: SETTINGS
+                .iter()
+                .map(|(name, get, _)| (*name, get(app_context)))
+                .collect(),
+        }
+    }
+
+    /// Load the view state from the given file.
+    /// The unknown settings are skipped.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file.
+    ///
+    /// # Returns
+    /// * `Option<Self>` - The view state, `None` when the file does not exist
+    ///   or cannot be parsed.
+    pub fn load(path: &Path) -> Option<Self> {
+        if !path.exists() {
+            return None;
+        }
+        let raw = match configs::deserialize_to_config::<ViewStateRaw>(path) {
+            Ok(raw) => raw,
+            Err(e) => {
+                tracing::error!("Failed to load the view state: {e}");
+                return None;
+            }
+        };
+        let section = raw.view_state;
+        let settings = raw.settings.unwrap_or_default();
+        Some(ViewState {
+            open_chat_id: section.as_ref().and_then(|s| s.open_chat_id).unwrap_or(0),
+            open_topic_id: section.as_ref().and_then(|s| s.open_topic_id).unwrap_or(0),
+            scroll_message_id: section
+                .as_ref()
+                .and_then(|s| s.scroll_message_id)
+                .unwrap_or(0),
+            settings: SETTINGS
+                .iter()
+                .filter_map(|(name, _, _)| Some((*name, *settings.get(*name)?)))
+                .collect(),
+        })
+    }
+
+    /// Toggle the settings of the application that differ from the saved
+    /// ones.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context.
+    pub fn apply_settings(&self, app_context: &AppContext) {
+        for (name, value) in self.settings.iter() {
+            if let Some((_, get, toggle)) = SETTINGS.iter().find(|(n, _, _)| n == name) {
+                if get(app_context) != *value {
+                    toggle(app_context);
+                }
+            }
+        }
+    }
+
+    /// Save the view state to the given file.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file.
+    ///
+    /// # Returns
+    /// * `Result<(), AppError>` - An Ok result or an error if the file cannot
+    ///   be written.
+    pub fn save(&self, path: &Path) -> Result<(), AppError<()>> {
+        let mut content = String::from("[view_state]\n");
+        // Writing to a `String` cannot fail.
+        let _ = writeln!(content, "open_chat_id = {}", self.open_chat_id);
+        let _ = writeln!(content, "open_topic_id = {}", self.open_topic_id);
+        let _ = writeln!(content, "scroll_message_id = {}", self.scroll_message_id);
+        content.push_str("\n[settings]\n");
+        for (name, value) in self.settings.iter() {
+            let _ = writeln!(content, "{} = {}", name, value);
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// `ShutdownClient` is the client that is flushed and closed when quitting.
+pub trait ShutdownClient {
+    /// Save the text as the draft of the chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `text` - The text of the draft.
+    fn flush_draft(&mut self, chat_id: i64, text: String) -> impl Future<Output = ()>;
+
+    /// Close the client and wait until it is closed.
+    fn close_client(&mut self) -> impl Future<Output = ()>;
+}
+
+/// Implement the `ShutdownClient` trait for the `TgBackend` struct.
+impl ShutdownClient for TgBackend {
+    async fn flush_draft(&mut self, chat_id: i64, text: String) {
+        self.save_draft(chat_id, text).await;
+    }
+
+    async fn close_client(&mut self) {
+        self.have_authorization = false;
+        self.close().await;
+        self.handle_authorization_state().await;
+    }
+}
+
+/// Flush the pending drafts and then close the client.
+/// Each step is bounded by the timeout, so that a hung client does not block
+/// the exit, and the flush is skipped when the quit is forced.
+///
+/// # Arguments
+/// * `client` - The client.
+/// * `drafts` - The pending drafts, by chat id.
+/// * `timeout` - The maximum time of the flush and of the close.
+/// * `forced` - A future that completes when the quit is forced, e.g. by a
+///   second quit.
+pub async fn graceful_shutdown<C: ShutdownClient>(
+    client: &mut C,
+    drafts: Vec<(i64, String)>,
+    timeout: Duration,
+    forced: impl Future<Output = ()>,
+) {
+    let flush = async {
+        for (chat_id, text) in drafts {
+            client.flush_draft(chat_id, text).await;
+        }
+    };
+    tokio::select! {
+        biased;
+        _ = forced => tracing::warn!("Quit forced, the drafts are not flushed"),
+        result = tokio::time::timeout(timeout, flush) => {
+            if result.is_err() {
+                tracing::error!("Timed out flushing the drafts");
+            }
+        }
+    }
+    if tokio::time::timeout(timeout, client.close_client())
+        .await
+        .is_err()
+    {
+        tracing::error!("Timed out closing the TDLib client");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shutdown::{graceful_shutdown, ShutdownClient, ViewState};
+    use std::{future, time::Duration};
+
+    #[derive(Default)]
+    struct StubClient {
+        /// Whether the drafts are never saved, like with a hung TDLib.
+        hung: bool,
+        calls: Vec<String>,
+    }
+    impl ShutdownClient for StubClient {
+        async fn flush_draft(&mut self, chat_id: i64, text: String) {
+            if self.hung {
+                future::pending::<()>().await;
+            }
+            self.calls.push(format!("draft {} {}", chat_id, text));
+        }
+
+        async fn close_client(&mut self) {
+            self.calls.push("close".to_string());
+        }
+    }
+
+    fn drafts() -> Vec<(i64, String)> {
+        vec![(1, "hello".to_string()), (2, "bye".to_string())]
+    }
+
+    #[tokio::test]
+    async fn test_flush_then_close() {
+        let mut client = StubClient::default();
+        graceful_shutdown(
+            &mut client,
+            drafts(),
+            Duration::from_secs(1),
+            future::pending(),
+        )
+        .await;
+        assert_eq!(client.calls, vec!["draft 1 hello", "draft 2 bye", "close"]);
+    }
+
+    #[tokio::test]
+    async fn test_forced_quit_skips_flush() {
+        let mut client = StubClient::default();
+        graceful_shutdown(
+            &mut client,
+            drafts(),
+            Duration::from_secs(1),
+            future::ready(()),
+        )
+        .await;
+        assert_eq!(client.calls, vec!["close"]);
+    }
+
+    #[tokio::test]
+    async fn test_hung_flush_still_closes() {
+        let mut client = StubClient {
+            hung: true,
+            ..StubClient::default()
+        };
+        graceful_shutdown(
+            &mut client,
+            drafts(),
+            Duration::from_millis(10),
+            future::pending(),
+        )
+        .await;
+        assert_eq!(client.calls, vec!["close"]);
+    }
+
+    #[test]
+    fn test_save_and_load_view_state() {
+        let dir = std::env::temp_dir().join(format!(
+            "tgt_test_save_and_load_view_state_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("view_state.toml");
+        let view_state = ViewState {
+            open_chat_id: -100,
+            open_topic_id: 0,
+            scroll_message_id: 42,
+            settings: vec![("view_counts", true), ("big_emoji", false)],
+        };
+        view_state.save(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "[view_state]\nopen_chat_id = -100\nopen_topic_id = 0\nscroll_message_id = 42\n\n\
+             [settings]\nview_counts = true\nbig_emoji = false\n"
+        );
+        assert_eq!(ViewState::load(&path), Some(view_state));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+/// The maximum number of emoji of a message rendered big, like Telegram the
+/// longer sequences of emoji are rendered as a normal text.
+const BIG_EMOJI_MAX: usize = 3;
+
+/// The zero width joiner, that joins several emoji into a single one.
+const ZWJ: char = '\u{200D}';
+
+/// Whether a character starts an emoji.
+fn is_emoji_base(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF
+        | 0x2300..=0x23FF
+        | 0x2600..=0x27BF
+        | 0x2B00..=0x2BFF
+        | 0x2194..=0x21AA
+        | 0x00A9
+        | 0x00AE
+        | 0x203C
+        | 0x2049
+        | 0x2122
+        | 0x2139
+        | 0x3030
+        | 0x303D
+        | 0x3297
+        | 0x3299)
+}
+
+/// Whether a character modifies the emoji before it, e.g. a skin tone or a
+/// variation selector.
+fn is_emoji_modifier(c: char) -> bool {
+    matches!(c as u32,
+        0xFE0E..=0xFE0F
+        | 0x1F3FB..=0x1F3FF
+        | 0x20E3
+        | 0xE0020..=0xE007F)
+}
+
+/// Whether a character is a regional indicator, two of them make a flag.
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c as u32, 0x1F1E6..=0x1F1FF)
+}
+
+/// Split a text made only of emoji into its emoji, each one with its
+/// modifiers and the emoji joined to it. The whitespace around the emoji is
+/// ignored.
+///
+/// # Arguments
+/// * `text` - The text.
+///
+/// # Returns
+/// * `Option<Vec<String>>` - The emoji, or `None` when the text has
+///   something else than emoji.
+pub fn emoji_clusters(text: &str) -> Option<Vec<String>> {
+    let mut clusters: Vec<String> = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut cluster = c.to_string();
+        if c.is_ascii_digit() || c == '#' || c == '*' {
+            // A keycap is a digit, or `#` or `*`, followed by the keycap
+            // combining mark.
+            while let Some(&next) = chars.peek() {
+                if !is_emoji_modifier(next) {
+                    break;
+                }
+                cluster.push(next);
+                chars.next();
+            }
+            if !cluster.ends_with('\u{20E3}') {
+                return None;
+            }
+            clusters.push(cluster);
+            continue;
+        }
+        if !is_emoji_base(c) {
+            return None;
+        }
+        if is_regional_indicator(c) {
+            match chars.next_if(|next| is_regional_indicator(*next)) {
+                Some(next) => cluster.push(next),
+                None => return None,
+            }
+        }
+        while let Some(&next) = chars.peek() {
+            if is_emoji_modifier(next) {
+                cluster.push(next);
+                chars.next();
+            } else if next == ZWJ {
+                cluster.push(next);
+                chars.next();
+                match chars.next_if(|joined| is_emoji_base(*joined)) {
+                    Some(joined) => cluster.push(joined),
+                    None => return None,
+                }
+            } else {
+                break;
+            }
+        }
+        clusters.push(cluster);
+    }
+    Some(clusters)
+}
+
+/// Whether a text is made only of a few emoji, and so it is rendered big.
+/// The emoji are counted as the user sees them, e.g. a family joined by zero
+/// width joiners or a flag are a single emoji.
+///
+/// # Arguments
+/// * `text` - The text.
+///
+/// # Returns
+/// * `bool` - Whether the text has from one to `BIG_EMOJI_MAX` emoji and
+///   nothing else than whitespace.
+pub fn is_emoji_only(text: &str) -> bool {
+    emoji_clusters(text).is_some_and(|clusters| (1..=BIG_EMOJI_MAX).contains(&clusters.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::big_emoji::{emoji_clusters, is_emoji_only};
+
+    #[test]
+    fn test_is_emoji_only_pure_emoji() {
+        assert!(is_emoji_only("😀"));
+        assert!(is_emoji_only("👍🏽🔥"));
+        assert!(is_emoji_only("❤️"));
+        // A family and a flag are a single emoji each.
+        assert!(is_emoji_only("👨‍👩‍👧‍👦🇮🇹1️⃣"));
+        assert_eq!(
+            emoji_clusters("👨‍👩‍👧‍👦🇮🇹").map(|clusters| clusters.len()),
+            Some(2)
+        );
+        // Too many emoji are rendered as a normal text.
+        assert!(!is_emoji_only("😀😀😀😀"));
+    }
+
+    #[test]
+    fn test_is_emoji_only_mixed() {
+        assert!(!is_emoji_only("ok 👍"));
+        assert!(!is_emoji_only("👍!"));
+        assert!(!is_emoji_only("1"));
+        assert!(!is_emoji_only("🇮"));
+        assert!(!is_emoji_only(""));
+    }
+
+    #[test]
+    fn test_is_emoji_only_whitespace_padded() {
+        assert!(is_emoji_only("  🎉 "));
+        assert!(is_emoji_only("🎉 🎂\n"));
+        assert!(!is_emoji_only("   "));
+    }
+}
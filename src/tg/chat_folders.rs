@@ -0,0 +1,200 @@
+use tdlib_rs::{enums::ChatList, types::ChatPosition};
+
+/// The identifier used for the tab that shows all the chats, TDLib never uses
+/// it for a folder.
+pub const ALL_CHATS_FOLDER_ID: i32 = 0;
+
+/// Whether a chat is part of a folder, i.e. it has a position in the chat list
+/// of the folder.
+///
+/// # Arguments
+/// * `positions` - The positions of the chat in the chat lists.
+/// * `folder_id` - The identifier of the folder.
+pub fn is_in_folder(positions: &[ChatPosition], folder_id: i32) -> bool {
+    positions.iter().any(|position| {
+        matches!(&position.list, ChatList::Folder(folder) if folder.chat_folder_id == folder_id)
+    })
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// `ChatFolders` keeps the chat folders of the user, shown as tabs above the
+/// chat list, and the one the chat list is filtered by.
+pub struct ChatFolders {
+    /// The identifiers and the titles of the folders, in the order they are
+    /// shown.
+    folders: Vec<(i32, String)>,
+    /// The identifier of the active folder, `ALL_CHATS_FOLDER_ID` when the
+    /// chat list is not filtered.
+    active: i32,
+}
+/// Implementation of the `ChatFolders` struct.
+impl ChatFolders {
+    /// Replace the folders of the user.
+    /// When the active folder has been deleted all the chats are shown again.
+    ///
+    /// # Arguments
+    /// * `folders` - The identifiers and the titles of the folders.
+    pub fn set_folders(&mut self, folders: Vec<(i32, String)>) {
+        self.folders = folders;
+        if !self.contains(self.active) {
+            self.active = ALL_CHATS_FOLDER_ID;
+        }
+    }
+
+    /// Get the identifiers and the titles of the folders.
+    pub fn folders(&self) -> &[(i32, String)] {
+        &self.folders
+    }
+
+    /// Get the identifier of the active folder.
+    pub fn active(&self) -> i32 {
+        self.active
+    }
+
+    /// Get the position of the active folder among the tabs, the first tab
+    /// shows all the chats.
+    pub fn active_index(&self) -> usize {
+        self.folders
+            .iter()
+            .position(|(id, _)| *id == self.active)
+            .map_or(0, |i| i + 1)
+    }
+
+    /// Filter the chat list by a folder.
+    ///
+    /// # Arguments
+    /// * `folder_id` - The identifier of the folder, `ALL_CHATS_FOLDER_ID` to
+    ///   show all the chats.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the folder exists.
+    pub fn select(&mut self, folder_id: i32) -> bool {
+        if !self.contains(folder_id) {
+            return false;
+        }
+        self.active = folder_id;
+        true
+    }
+
+    /// Get the identifier of the folder after the active one, the tabs wrap
+    /// around.
+    pub fn next_id(&self) -> i32 {
+        let i = (self.active_index() + 1) % (self.folders.len() + 1);
+        self.id_at(i)
+    }
+
+    /// Get the identifier of the folder before the active one, the tabs wrap
+    /// around.
+    pub fn previous_id(&self) -> i32 {
+        let len = self.folders.len() + 1;
+        let i = (self.active_index() + len - 1) % len;
+        self.id_at(i)
+    }
+
+    /// Keep only the chats of the active folder.
+    ///
+    /// # Arguments
+    /// * `chat_ids` - The chats in the order of the chat list.
+    /// * `is_in_folder` - Whether a chat is part of a folder, given the
+    ///   identifiers of the chat and of the folder.
+    ///
+    /// # Returns
+    /// * `Vec<i64>` - The chats of the active folder, in the same order.
+    pub fn filter<F>(&self, chat_ids: Vec<i64>, is_in_folder: F) -> Vec<i64>
+    where
+        F: Fn(i64, i32) -> bool,
+    {
+        if self.active == ALL_CHATS_FOLDER_ID {
+            return chat_ids;
+        }
+        chat_ids
+            .into_iter()
+            .filter(|chat_id| is_in_folder(*chat_id, self.active))
+            .collect()
+    }
+
+    fn contains(&self, folder_id: i32) -> bool {
+        folder_id == ALL_CHATS_FOLDER_ID || self.folders.iter().any(|(id, _)| *id == folder_id)
+    }
+
+    fn id_at(&self, index: usize) -> i32 {
+        index
+            .checked_sub(1)
+            .and_then(|i| self.folders.get(i))
+            .map_or(ALL_CHATS_FOLDER_ID, |(id, _)| *id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::chat_folders::{is_in_folder, ChatFolders, ALL_CHATS_FOLDER_ID};
+    use std::collections::HashMap;
+    use tdlib_rs::{
+        enums::ChatList,
+        types::{ChatListFolder, ChatPosition},
+    };
+
+    fn position(list: ChatList) -> ChatPosition {
+        ChatPosition {
+            list,
+            order: 1,
+            is_pinned: false,
+            source: None,
+        }
+    }
+
+    fn folder(chat_folder_id: i32) -> ChatList {
+        ChatList::Folder(ChatListFolder { chat_folder_id })
+    }
+
+    fn chat_folders() -> ChatFolders {
+        let mut chat_folders = ChatFolders::default();
+        chat_folders.set_folders(vec![(2, "Work".to_string()), (3, "Family".to_string())]);
+        chat_folders
+    }
+
+    #[test]
+    fn test_filter_matches_folder_chats() {
+        let positions = HashMap::from([
+            (1, vec![position(ChatList::Main), position(folder(2))]),
+            (2, vec![position(ChatList::Main)]),
+            (3, vec![position(ChatList::Main), position(folder(3))]),
+            (4, vec![position(folder(2)), position(folder(3))]),
+        ]);
+        let in_folder =
+            |chat_id: i64, folder_id: i32| is_in_folder(&positions[&chat_id], folder_id);
+        let mut chat_folders = chat_folders();
+
+        assert_eq!(
+            chat_folders.filter(vec![1, 2, 3, 4], in_folder),
+            vec![1, 2, 3, 4]
+        );
+        assert!(chat_folders.select(2));
+        assert_eq!(chat_folders.filter(vec![1, 2, 3, 4], in_folder), vec![1, 4]);
+        assert!(chat_folders.select(3));
+        assert_eq!(chat_folders.filter(vec![4, 3, 2, 1], in_folder), vec![4, 3]);
+    }
+
+    #[test]
+    fn test_select_unknown_folder() {
+        let mut chat_folders = chat_folders();
+        assert!(!chat_folders.select(42));
+        assert_eq!(chat_folders.active(), ALL_CHATS_FOLDER_ID);
+
+        // Deleting the active folder shows all the chats again.
+        assert!(chat_folders.select(3));
+        chat_folders.set_folders(vec![(2, "Work".to_string())]);
+        assert_eq!(chat_folders.active(), ALL_CHATS_FOLDER_ID);
+    }
+
+    #[test]
+    fn test_next_and_previous_folder() {
+        let mut chat_folders = chat_folders();
+        assert_eq!(chat_folders.next_id(), 2);
+        assert_eq!(chat_folders.previous_id(), 3);
+        chat_folders.select(3);
+        assert_eq!(chat_folders.active_index(), 2);
+        assert_eq!(chat_folders.next_id(), ALL_CHATS_FOLDER_ID);
+        assert_eq!(chat_folders.previous_id(), 2);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{app_error::AppError, configs, utils::tgt_config_dir};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The name of the file, in the configuration directory, where the
+/// placeholders of the compose box are saved.
+pub const COMPOSE_PLACEHOLDERS_FILENAME: &str = "compose_placeholders.toml";
+
+/// Get the path of the file where the placeholders of the compose box are
+/// saved.
+///
+/// # Returns
+/// The path of the file in the default configuration directory.
+pub fn compose_placeholders_path() -> io::Result<PathBuf> {
+    Ok(tgt_config_dir()?.join(COMPOSE_PLACEHOLDERS_FILENAME))
+}
+
+#[derive(Debug, Deserialize)]
+/// The content of the file where the placeholders are saved.
+struct ComposePlaceholdersRaw {
+    /// The placeholders, by chat id.
+    /// The keys are strings because TOML only allows string keys.
+    compose_placeholders: Option<HashMap<String, String>>,
+}
+
+/// Quote a text as a TOML basic string.
+///
+/// # Arguments
+/// * `text` - The text.
+///
+/// # Returns
+/// * `String` - The quoted text.
+fn toml_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                // Writing to a `String` cannot fail.
+                let _ = write!(quoted, "\\u{:04X}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// `ComposePlaceholders` keeps track of the custom texts shown in the empty
+/// compose box of the chats, e.g. to remind what the chat is used for.
+pub struct ComposePlaceholders {
+    /// The placeholders, by chat id.
+    placeholders: HashMap<i64, String>,
+}
+/// Implementation of the `ComposePlaceholders` struct.
+impl ComposePlaceholders {
+    /// Load the placeholders from the given file.
+    /// The current state is kept when the file does not exist or cannot be
+    /// parsed.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file.
+    pub fn load(&mut self, path: &Path) {
+        if !path.exists() {
+            return;
+        }
+        match configs::deserialize_to_config::<ComposePlaceholdersRaw>(path) {
+            Ok(raw) => {
+                self.placeholders = raw
+                    .compose_placeholders
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|(chat_id, text)| Some((chat_id.parse().ok()?, text)))
+                    .collect();
+            }
+            Err(e) => tracing::error!("Failed to load the compose placeholders: {e}"),
+        }
+    }
+
+    /// Save the placeholders to the given file.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file.
+    ///
+    /// # Returns
+    /// * `Result<(), AppError>` - An Ok result or an error if the file cannot
+    ///   be written.
+    pub fn save(&self, path: &Path) -> Result<(), AppError<()>> {
+        let mut placeholders = self.placeholders.iter().collect::<Vec<_>>();
+        placeholders.sort();
+        let mut content = String::from("[compose_placeholders]\n");
+        for (chat_id, text) in placeholders {
+            // Writing to a `String` cannot fail.
+            let _ = writeln!(content, "\"{}\" = {}", chat_id, toml_string(text));
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Set the placeholder of a chat, an empty text restores the default
+    /// one.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `text` - The placeholder.
+    pub fn set(&mut self, chat_id: i64, text: String) {
+        if text.is_empty() {
+            self.placeholders.remove(&chat_id);
+        } else {
+            self.placeholders.insert(chat_id, text);
+        }
+    }
+
+    /// Get the placeholder of a chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The placeholder, `None` when the chat has none.
+    pub fn get(&self, chat_id: i64) -> Option<&str> {
+        self.placeholders.get(&chat_id).map(|text| text.as_str())
+    }
+
+    /// Get the text shown in the empty compose box of a chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `default` - The text shown when the chat has no placeholder.
+    ///
+    /// # Returns
+    /// * `String` - The placeholder of the chat, or the default one.
+    pub fn resolve(&self, chat_id: i64, default: &str) -> String {
+        self.get(chat_id).unwrap_or(default).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::compose_placeholders::ComposePlaceholders;
+
+    #[test]
+    fn test_resolve_per_chat_with_default() {
+        let mut placeholders = ComposePlaceholders::default();
+        placeholders.set(1, "Reply to the support ticket".to_string());
+        assert_eq!(
+            placeholders.resolve(1, "Type a message"),
+            "Reply to the support ticket"
+        );
+        assert_eq!(placeholders.resolve(2, "Type a message"), "Type a message");
+
+        // An empty text restores the default.
+        placeholders.set(1, String::new());
+        assert_eq!(placeholders.get(1), None);
+        assert_eq!(placeholders.resolve(1, "Type a message"), "Type a message");
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join("tgt_test_compose_placeholders.toml");
+        let mut placeholders = ComposePlaceholders::default();
+        placeholders.set(-100, "Say \"hi\"\nfirst".to_string());
+        placeholders.set(42, "Work only".to_string());
+        placeholders.save(&path).unwrap();
+
+        let mut loaded = ComposePlaceholders::default();
+        loaded.load(&path);
+        assert_eq!(loaded, placeholders);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,117 @@
+use crate::app_error::AppError;
+use std::collections::HashMap;
+use tdlib_rs::{enums::InputMessageContent, types::Chat};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// `DraftReminders` keeps track of the drafts that are shown again at a wake
+/// time, as a lightweight reminder.
+/// The reminders are only kept locally, they are not synchronized with the
+/// other Telegram clients.
+pub struct DraftReminders {
+    /// The wake timestamp of the drafts, by chat id.
+    reminders: HashMap<i64, i32>,
+}
+/// Implementation of the `DraftReminders` struct.
+impl DraftReminders {
+    /// Attach a wake time to the draft of a chat, it replaces the previous
+    /// one.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `wake_at` - The timestamp, in seconds, when the draft is shown again.
+    /// * `now` - The current timestamp, in seconds.
+    ///
+    /// # Returns
+    /// * `Result<(), AppError>` - An Ok result or an error if the wake time is
+    ///   not in the future.
+    pub fn set(&mut self, chat_id: i64, wake_at: i32, now: i32) -> Result<(), AppError<()>> {
+        if wake_at <= now {
+            return Err(AppError::InvalidTimestamp(format!(
+                "{} is not in the future",
+                wake_at
+            )));
+        }
+        self.reminders.insert(chat_id, wake_at);
+        Ok(())
+    }
+
+    /// Remove the reminder of the draft of a chat.
+    pub fn cancel(&mut self, chat_id: i64) {
+        self.reminders.remove(&chat_id);
+    }
+
+    /// Take the reminders whose wake time has passed, each reminder is
+    /// triggered only once.
+    ///
+    /// # Arguments
+    /// * `now` - The current timestamp, in seconds.
+    ///
+    /// # Returns
+    /// * `Vec<i64>` - The chats whose draft is due, from the earliest wake
+    ///   time.
+    pub fn due(&mut self, now: i32) -> Vec<i64> {
+        let mut due = self
+            .reminders
+            .iter()
+            .filter(|(_, wake_at)| **wake_at <= now)
+            .map(|(chat_id, wake_at)| (*wake_at, *chat_id))
+            .collect::<Vec<(i32, i64)>>();
+        due.sort();
+        due.into_iter()
+            .map(|(_, chat_id)| {
+                self.reminders.remove(&chat_id);
+                chat_id
+            })
+            .collect()
+    }
+}
+
+/// Get the text of the draft of a chat.
+///
+/// # Arguments
+/// * `chat` - The chat.
+///
+/// # Returns
+/// * `Option<String>` - The text of the draft, or `None` when the chat has no
+///   draft or it is empty.
+pub fn draft_text(chat: &Chat) -> Option<String> {
+    match chat.draft_message.as_ref()?.input_message_text {
+        InputMessageContent::InputMessageText(ref text) if !text.text.text.is_empty() => {
+            Some(text.text.text.clone())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::draft_reminders::DraftReminders;
+
+    #[test]
+    fn test_set_past_timestamp() {
+        let mut draft_reminders = DraftReminders::default();
+        assert!(draft_reminders.set(1, 100, 100).is_err());
+        assert!(draft_reminders.set(1, 50, 100).is_err());
+        // No reminder has been attached.
+        assert!(draft_reminders.due(i32::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_due_triggers_once() {
+        let mut draft_reminders = DraftReminders::default();
+        draft_reminders.set(2, 300, 100).unwrap();
+        draft_reminders.set(3, 200, 100).unwrap();
+        draft_reminders.set(4, 400, 100).unwrap();
+        draft_reminders.cancel(4);
+
+        assert!(draft_reminders.due(199).is_empty());
+        assert_eq!(draft_reminders.due(300), vec![3, 2]);
+        assert!(draft_reminders.due(300).is_empty());
+        assert!(draft_reminders.due(500).is_empty());
+
+        draft_reminders.set(2, 600, 500).unwrap();
+        draft_reminders.set(2, 700, 500).unwrap();
+        assert!(draft_reminders.due(600).is_empty());
+        assert_eq!(draft_reminders.due(700), vec![2]);
+    }
+}
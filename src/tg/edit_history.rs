@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+/// The number of previous versions kept for each message.
+const MAX_VERSIONS: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A piece of the difference between two versions of a text.
+pub enum DiffSpan {
+    /// Words that are in both versions.
+    Same(String),
+    /// Words that are only in the new version.
+    Added(String),
+    /// Words that are only in the old version.
+    Removed(String),
+}
+
+/// Compute the word by word difference between two versions of a text.
+/// The consecutive words of the same kind are merged in a single span, the
+/// removed words come before the added ones when a word is replaced.
+///
+/// # Arguments
+/// * `old` - The previous version of the text.
+/// * `new` - The current version of the text.
+///
+/// # Returns
+/// * `Vec<DiffSpan>` - The spans, in the order of the text.
+pub fn text_diff(old: &str, new: &str) -> Vec<DiffSpan> {
+    let old = old.split_whitespace().collect::<Vec<&str>>();
+    let new = new.split_whitespace().collect::<Vec<&str>>();
+    // lcs[i][j] is the length of the longest common subsequence of
+    // old[i..] and new[j..].
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut spans: Vec<DiffSpan> = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            push_span(&mut spans, DiffSpan::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            push_span(&mut spans, DiffSpan::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            push_span(&mut spans, DiffSpan::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    for word in &old[i..] {
+        push_span(&mut spans, DiffSpan::Removed(word.to_string()));
+    }
+    for word in &new[j..] {
+        push_span(&mut spans, DiffSpan::Added(word.to_string()));
+    }
+    spans
+}
+
+// Push a span, it is merged with the last one when they are of the same kind.
+fn push_span(spans: &mut Vec<DiffSpan>, span: DiffSpan) {
+    let merged = match (spans.last_mut(), &span) {
+        (Some(DiffSpan::Same(text)), DiffSpan::Same(word))
+        | (Some(DiffSpan::Added(text)), DiffSpan::Added(word))
+        | (Some(DiffSpan::Removed(text)), DiffSpan::Removed(word)) => {
+            text.push(' ');
+            text.push_str(word);
+            true
+        }
+        _ => false,
+    };
+    if !merged {
+        spans.push(span);
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// `EditHistory` keeps the previous versions of the messages edited while
+/// the client is running, Telegram does not provide them.
+pub struct EditHistory {
+    /// The previous versions of the messages, from the oldest, by chat id and
+    /// message id: the identifiers of the messages are unique only in their
+    /// chat.
+    versions: HashMap<(i64, i64), Vec<String>>,
+}
+/// Implementation of the `EditHistory` struct.
+impl EditHistory {
+    /// Record the version of a message that is being replaced by an edit.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat of the message.
+    /// * `message_id` - The identifier of the message.
+    /// * `text` - The text of the message before the edit.
+    pub fn record(&mut self, chat_id: i64, message_id: i64, text: String) {
+        let versions = self.versions.entry((chat_id, message_id)).or_default();
+        if versions.last() != Some(&text) {
+            versions.push(text);
+        }
+        if versions.len() > MAX_VERSIONS {
+            versions.remove(0);
+        }
+    }
+
+    /// Get the version of a message before its last edit, if it was cached.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat of the message.
+    /// * `message_id` - The identifier of the message.
+    pub fn previous(&self, chat_id: i64, message_id: i64) -> Option<&str> {
+        self.versions
+            .get(&(chat_id, message_id))
+            .and_then(|versions| versions.last())
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::edit_history::{text_diff, DiffSpan, EditHistory};
+
+    #[test]
+    fn test_text_diff_additions() {
+        assert_eq!(
+            text_diff("hello world", "hello brave new world"),
+            vec![
+                DiffSpan::Same("hello".to_string()),
+                DiffSpan::Added("brave new".to_string()),
+                DiffSpan::Same("world".to_string()),
+            ]
+        );
+        assert_eq!(
+            text_diff("", "hello"),
+            vec![DiffSpan::Added("hello".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_text_diff_removals() {
+        assert_eq!(
+            text_diff("see you at five today", "see you today"),
+            vec![
+                DiffSpan::Same("see you".to_string()),
+                DiffSpan::Removed("at five".to_string()),
+                DiffSpan::Same("today".to_string()),
+            ]
+        );
+        assert_eq!(
+            text_diff("the cat sat", "the dog sat"),
+            vec![
+                DiffSpan::Same("the".to_string()),
+                DiffSpan::Removed("cat".to_string()),
+                DiffSpan::Added("dog".to_string()),
+                DiffSpan::Same("sat".to_string()),
+            ]
+        );
+        assert_eq!(
+            text_diff("unchanged text", "unchanged text"),
+            vec![DiffSpan::Same("unchanged text".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_edit_history_previous() {
+        let mut edit_history = EditHistory::default();
+        assert_eq!(edit_history.previous(10, 1), None);
+        edit_history.record(10, 1, "first".to_string());
+        edit_history.record(10, 1, "second".to_string());
+        assert_eq!(edit_history.previous(10, 1), Some("second"));
+        assert_eq!(edit_history.previous(10, 2), None);
+        // The same message id in another chat is another message.
+        assert_eq!(edit_history.previous(20, 1), None);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::tg::message_entry::entity_text;
+use ratatui::style::{Modifier, Style};
+use tdlib_rs::{enums::TextEntityType, types::FormattedText};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// `PreviewEntityKind` is how a piece of the composed text is formatted.
+pub enum PreviewEntityKind {
+    Bold,
+    Italic,
+    Underline,
+    Strikethrough,
+    Spoiler,
+    /// An inline code.
+    Code,
+    /// A block of code.
+    Pre,
+    /// A quotation.
+    Quote,
+    /// A link shown as its address.
+    Url,
+    /// A text that links to the address.
+    TextUrl(String),
+    /// A `@username` mention, or a mention of a user without a username.
+    Mention,
+    Hashtag,
+    EmailAddress,
+}
+/// Implementation of the `PreviewEntityKind` enum.
+impl PreviewEntityKind {
+    /// Get the kind of an entity parsed by TDLib, the entities that can not
+    /// be written with the formatting syntax are skipped.
+    ///
+    /// # Arguments
+    /// * `entity_type` - The type of the entity.
+    ///
+    /// # Returns
+    /// * `Option<Self>` - The kind, `None` when it is not previewed.
+    fn from_entity_type(entity_type: &TextEntityType) -> Option<Self> {
+        Some(match entity_type {
+            TextEntityType::Bold => PreviewEntityKind::Bold,
+            TextEntityType::Italic => PreviewEntityKind::Italic,
+            TextEntityType::Underline => PreviewEntityKind::Underline,
+            TextEntityType::Strikethrough => PreviewEntityKind::Strikethrough,
+            TextEntityType::Spoiler => PreviewEntityKind::Spoiler,
+            TextEntityType::Code => PreviewEntityKind::Code,
+            TextEntityType::Pre | TextEntityType::PreCode(_) => PreviewEntityKind::Pre,
+            TextEntityType::BlockQuote => PreviewEntityKind::Quote,
+            TextEntityType::Url => PreviewEntityKind::Url,
+            TextEntityType::TextUrl(text_url) => PreviewEntityKind::TextUrl(text_url.url.clone()),
+            TextEntityType::Mention | TextEntityType::MentionName(_) => PreviewEntityKind::Mention,
+            TextEntityType::Hashtag => PreviewEntityKind::Hashtag,
+            TextEntityType::EmailAddress => PreviewEntityKind::EmailAddress,
+            _ => return None,
+        })
+    }
+
+    /// Get the label of the kind, shown in the legend of the preview.
+    pub fn label(&self) -> String {
+        match self {
+            PreviewEntityKind::Bold => "Bold".to_string(),
+            PreviewEntityKind::Italic => "Italic".to_string(),
+            PreviewEntityKind::Underline => "Underline".to_string(),
+            PreviewEntityKind::Strikethrough => "Strikethrough".to_string(),
+            PreviewEntityKind::Spoiler => "Spoiler".to_string(),
+            PreviewEntityKind::Code => "Code".to_string(),
+            PreviewEntityKind::Pre => "Code block".to_string(),
+            PreviewEntityKind::Quote => "Quote".to_string(),
+            PreviewEntityKind::Url => "Link".to_string(),
+            PreviewEntityKind::TextUrl(url) => format!("Link to {}", url),
+            PreviewEntityKind::Mention => "Mention".to_string(),
+            PreviewEntityKind::Hashtag => "Hashtag".to_string(),
+            PreviewEntityKind::EmailAddress => "Email".to_string(),
+        }
+    }
+
+    /// Get the style the text of the kind is rendered with in the messages.
+    pub fn style(&self) -> Style {
+        let modifier = match self {
+            PreviewEntityKind::Bold => Modifier::BOLD,
+            PreviewEntityKind::Italic | PreviewEntityKind::Quote => Modifier::ITALIC,
+            PreviewEntityKind::Underline
+            | PreviewEntityKind::Url
+            | PreviewEntityKind::TextUrl(_)
+            | PreviewEntityKind::EmailAddress => Modifier::UNDERLINED,
+            PreviewEntityKind::Strikethrough => Modifier::CROSSED_OUT,
+            PreviewEntityKind::Spoiler => Modifier::REVERSED,
+            PreviewEntityKind::Code | PreviewEntityKind::Pre => Modifier::DIM,
+            PreviewEntityKind::Mention | PreviewEntityKind::Hashtag => {
+                Modifier::BOLD | Modifier::UNDERLINED
+            }
+        };
+        Style::default().add_modifier(modifier)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// `PreviewEntity` is a formatted piece of the composed text.
+pub struct PreviewEntity {
+    /// How the piece is formatted.
+    pub kind: PreviewEntityKind,
+    /// The text of the piece, without the formatting syntax.
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// `FormattingPreview` is how a composed text is sent once its formatting
+/// syntax is parsed.
+pub enum FormattingPreview {
+    /// The text was parsed, with the entities detected in it.
+    Parsed {
+        /// The text without the formatting syntax.
+        text: String,
+        /// The formatted pieces of the text, in the order they appear.
+        entities: Vec<PreviewEntity>,
+    },
+    /// The text can not be parsed, e.g. because a character is not escaped.
+    Error(String),
+}
+/// Implementation of the `FormattingPreview` enum.
+impl FormattingPreview {
+    /// Build the preview of a text parsed by TDLib.
+    ///
+    /// # Arguments
+    /// * `parsed` - The formatted text, or the error of the parsing.
+    ///
+    /// # Returns
+    /// * `Self` - The preview.
+    pub fn from_parsed(parsed: Result<FormattedText, String>) -> Self {
+        match parsed {
+            Ok(formatted_text) => FormattingPreview::Parsed {
+                entities: preview_entities(&formatted_text),
+                text: formatted_text.text,
+            },
+            Err(e) => FormattingPreview::Error(e),
+        }
+    }
+}
+
+/// List the formatted pieces of a text, the offsets of the entities are
+/// counted in UTF-16 code units like in the messages.
+///
+/// # Arguments
+/// * `text` - The formatted text.
+///
+/// # Returns
+/// * `Vec<PreviewEntity>` - The formatted pieces, in the order they appear.
+pub fn preview_entities(text: &FormattedText) -> Vec<PreviewEntity> {
+    text.entities
+        .iter()
+        .filter_map(|e| {
+            Some(PreviewEntity {
+                kind: PreviewEntityKind::from_entity_type(&e.r#type)?,
+                text: entity_text(text, e),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::formatting_preview::{
+        preview_entities, FormattingPreview, PreviewEntity, PreviewEntityKind,
+    };
+    use tdlib_rs::{
+        enums::TextEntityType,
+        types::{FormattedText, TextEntity, TextEntityTypeTextUrl},
+    };
+
+    fn entity(offset: i32, length: i32, r#type: TextEntityType) -> TextEntity {
+        TextEntity {
+            offset,
+            length,
+            r#type,
+        }
+    }
+
+    #[test]
+    fn test_preview_lists_detected_entities() {
+        let text = FormattedText {
+            text: "Hi @durov, read the docs #rust".to_string(),
+            entities: vec![
+                entity(0, 2, TextEntityType::Bold),
+                entity(3, 6, TextEntityType::Mention),
+                entity(
+                    20,
+                    4,
+                    TextEntityType::TextUrl(TextEntityTypeTextUrl {
+                        url: "https://docs.rs".to_string(),
+                    }),
+                ),
+                entity(25, 5, TextEntityType::Hashtag),
+                // The bot commands can not be written with the syntax.
+                entity(0, 2, TextEntityType::BotCommand),
+            ],
+        };
+        let preview = |kind, text: &str| PreviewEntity {
+            kind,
+            text: text.to_string(),
+        };
+        assert_eq!(
+            preview_entities(&text),
+            vec![
+                preview(PreviewEntityKind::Bold, "Hi"),
+                preview(PreviewEntityKind::Mention, "@durov"),
+                preview(
+                    PreviewEntityKind::TextUrl("https://docs.rs".to_string()),
+                    "docs"
+                ),
+                preview(PreviewEntityKind::Hashtag, "#rust"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_preview_parse_error() {
+        assert_eq!(
+            FormattingPreview::from_parsed(Err("Can't find end of the entity".to_string())),
+            FormattingPreview::Error("Can't find end of the entity".to_string())
+        );
+        assert_eq!(
+            FormattingPreview::from_parsed(Ok(FormattedText {
+                text: "plain".to_string(),
+                entities: vec![],
+            })),
+            FormattingPreview::Parsed {
+                text: "plain".to_string(),
+                entities: vec![],
+            }
+        );
+    }
+}
@@ -0,0 +1,80 @@
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// `IdlePresence` sets the user offline after a period without activity, and
+/// back online on the next activity.
+pub struct IdlePresence {
+    /// The timestamp, in seconds, of the last activity, `0` before the first
+    /// check.
+    last_activity: i32,
+    /// Whether the user has been set offline because of the inactivity.
+    away: bool,
+}
+/// Implementation of the `IdlePresence` struct.
+impl IdlePresence {
+    /// Record an activity of the user, e.g. a key press.
+    ///
+    /// # Arguments
+    /// * `now` - The current timestamp, in seconds.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the user was away and goes back online.
+    pub fn activity(&mut self, now: i32) -> bool {
+        self.last_activity = now;
+        std::mem::take(&mut self.away)
+    }
+
+    /// Check whether the user has been idle for the timeout.
+    ///
+    /// # Arguments
+    /// * `now` - The current timestamp, in seconds.
+    /// * `timeout` - The inactivity, in seconds, after which the user is
+    ///   away, `0` to never be away.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the user just became away and goes offline, it is
+    ///   `true` only once until the next activity.
+    pub fn idle(&mut self, now: i32, timeout: i32) -> bool {
+        // The idle time is counted from the first check.
+        if self.last_activity == 0 {
+            self.last_activity = now;
+        }
+        if timeout <= 0 || self.away || now - self.last_activity < timeout {
+            return false;
+        }
+        self.away = true;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::idle_presence::IdlePresence;
+
+    #[test]
+    fn test_idle_goes_offline_once() {
+        let mut presence = IdlePresence::default();
+        assert!(!presence.idle(1000, 60));
+        assert!(!presence.idle(1059, 60));
+        assert!(presence.idle(1060, 60));
+        // The user is set offline only once per idle period.
+        assert!(!presence.idle(1200, 60));
+    }
+
+    #[test]
+    fn test_activity_goes_back_online() {
+        let mut presence = IdlePresence::default();
+        assert!(!presence.activity(1000));
+        assert!(presence.idle(1060, 60));
+        assert!(presence.activity(1100));
+        assert!(!presence.idle(1159, 60));
+        assert!(presence.idle(1160, 60));
+    }
+
+    #[test]
+    fn test_idle_disabled() {
+        let mut presence = IdlePresence::default();
+        presence.activity(1000);
+        assert!(!presence.idle(100_000, 0));
+        // The user was never away.
+        assert!(!presence.activity(100_001));
+    }
+}
@@ -1,12 +1,20 @@
+use crate::action::Action;
 use crate::app_context::AppContext;
-use chrono::{DateTime, Local};
+use crate::components::avatar::avatar_span;
+use crate::configs::raw::app_raw::{ReplyPreviewMode, TimestampGranularity};
+use chrono::{DateTime, FixedOffset, Local, TimeZone};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span, Text};
 use std::time::{Duration, UNIX_EPOCH};
-use tdlib_rs::enums::{MessageContent, MessageReplyTo, MessageSender};
-use tdlib_rs::types::FormattedText;
+use tdlib_rs::enums::{
+    MessageContent, MessageReplyTo, MessageSender, PollType, ReactionType, TextEntityType,
+};
+use tdlib_rs::types::{FormattedText, MessageInteractionInfo, Poll, TextEntity};
 
-use super::td_enums::{TdMessageReplyTo, TdMessageSender};
+use super::big_emoji::{emoji_clusters, is_emoji_only};
+use super::send_error::{SendError, SendErrorKind};
+use super::syntax_highlight::{is_supported, tokenize, TokenKind};
+use super::td_enums::{TdMessageReplyTo, TdMessageSender, TdReplyMarkup, TdTextEntity};
 
 #[derive(Debug, Default, Clone)]
 pub struct DateTimeEntry {
@@ -15,32 +23,591 @@ pub struct DateTimeEntry {
 impl DateTimeEntry {
     pub fn convert_time(timestamp: i32) -> String {
         let d = UNIX_EPOCH + Duration::from_secs(timestamp as u64);
-        let datetime = DateTime::<Local>::from(d);
-        if datetime.date_naive() == Local::now().date_naive() {
-            return datetime.format("%H:%M").to_string();
+        Self::format_datetime(
+            DateTime::<Local>::from(d),
+            Local::now(),
+            TimestampGranularity::Minute,
+        )
+    }
+
+    /// Convert the timestamp to the time of the sender.
+    /// When the time zone of the sender is unknown the local time is used,
+    /// followed by an indicator.
+    ///
+    /// # Arguments
+    /// * `timestamp` - The timestamp, in seconds.
+    /// * `utc_offset` - The offset from UTC of the time zone of the sender, in
+    ///   seconds, if known.
+    /// * `now` - The current timestamp, in seconds.
+    /// * `granularity` - How precise the time is.
+    ///
+    /// # Returns
+    /// * `String` - The time of the sender followed by the offset of its time
+    ///   zone, or the local time followed by `(local)`.
+    pub fn convert_time_in(
+        timestamp: i32,
+        utc_offset: Option<i32>,
+        now: i32,
+        granularity: TimestampGranularity,
+    ) -> String {
+        match utc_offset.and_then(FixedOffset::east_opt) {
+            Some(offset) => {
+                let datetime = offset.timestamp_opt(timestamp as i64, 0).unwrap();
+                let now = offset.timestamp_opt(now as i64, 0).unwrap();
+                format!(
+                    "{} {}",
+                    Self::format_datetime(datetime, now, granularity),
+                    datetime.format("%:z")
+                )
+            }
+            None => {
+                let datetime = Local.timestamp_opt(timestamp as i64, 0).unwrap();
+                let now = Local.timestamp_opt(now as i64, 0).unwrap();
+                format!(
+                    "{} (local)",
+                    Self::format_datetime(datetime, now, granularity)
+                )
+            }
         }
-        if datetime.date_naive() == (Local::now() - chrono::Duration::days(1)).date_naive() {
-            return datetime.format("Yesterday %H:%M").to_string();
+    }
+
+    fn format_datetime<Tz: TimeZone>(
+        datetime: DateTime<Tz>,
+        now: DateTime<Tz>,
+        granularity: TimestampGranularity,
+    ) -> String
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        let time = match granularity {
+            TimestampGranularity::Minute => "%H:%M",
+            TimestampGranularity::Second | TimestampGranularity::DateTime => "%H:%M:%S",
+        };
+        if granularity != TimestampGranularity::DateTime {
+            if datetime.date_naive() == now.date_naive() {
+                return datetime.format(time).to_string();
+            }
+            if datetime.date_naive() == (now - chrono::Duration::days(1)).date_naive() {
+                return datetime.format(&format!("Yesterday {}", time)).to_string();
+            }
         }
-        datetime.format("%Y-%m-%d %H:%M").to_string() // :%S
+        datetime.format(&format!("%Y-%m-%d {}", time)).to_string()
     }
 
     pub fn get_span_styled(&self, app_context: &AppContext) -> Span {
+        let granularity = app_context.app_config().timestamp_granularity;
+        Span::styled(
+            format_with_granularity(self.timestamp as i64, granularity),
+            app_context.style_timestamp(),
+        )
+    }
+
+    /// Get the time of the sender as a styled span.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context, used for the style.
+    /// * `utc_offset` - The offset from UTC of the time zone of the sender, in
+    ///   seconds, if known.
+    pub fn get_span_styled_in(&self, app_context: &AppContext, utc_offset: Option<i32>) -> Span {
+        let now = chrono::Utc::now().timestamp() as i32;
+        let granularity = app_context.app_config().timestamp_granularity;
         Span::styled(
-            Self::convert_time(self.timestamp),
+            Self::convert_time_in(self.timestamp, utc_offset, now, granularity),
             app_context.style_timestamp(),
         )
     }
 }
 
+/// Format a timestamp in the local time, as precisely as requested.
+///
+/// # Arguments
+/// * `ts` - The timestamp, in seconds.
+/// * `g` - How precise the time is.
+///
+/// # Returns
+/// * `String` - The local time, with the date unless the granularity is not
+///   `DateTime` and the time is today or yesterday.
+pub fn format_with_granularity(ts: i64, g: TimestampGranularity) -> String {
+    let datetime = Local.timestamp_opt(ts, 0).unwrap();
+    DateTimeEntry::format_datetime(datetime, Local::now(), g)
+}
+
 #[derive(Debug, Clone)]
 pub struct MessageEntry {
     id: i64,
     sender_id: TdMessageSender,
     message_content: Vec<Line<'static>>,
+    /// The text of the message, kept when it has code blocks of the known
+    /// languages to highlight them with the styles of the theme when it is
+    /// drawn, `None` when the message has no such code block.
+    highlighted_text: Option<FormattedText>,
     reply_to: Option<TdMessageReplyTo>,
     timestamp: DateTimeEntry,
     is_edited: bool,
+    reply_markup: Option<TdReplyMarkup>,
+    media_album_id: i64,
+    /// The identifier of the message thread, i.e. of the forum topic, the
+    /// message belongs to.
+    message_thread_id: i64,
+    caption: Vec<Line<'static>>,
+    send_failed: bool,
+    /// The error of the last attempt to send the message, if it failed.
+    send_error: Option<SendError>,
+    /// The translation of the text of the message, if it was asked.
+    translation: Option<String>,
+    /// Whether the translation is shown beneath the original text.
+    translation_shown: bool,
+    reactions: Vec<MessageReactionEntry>,
+    /// Whether the message is a post of a channel, only the posts show their
+    /// view count.
+    is_channel_post: bool,
+    view_count: i32,
+    /// The number of comments of a channel post, `None` when the channel has
+    /// no discussion group.
+    comment_count: Option<i32>,
+    /// The signature of the author of a channel post, empty when the post is
+    /// not signed.
+    author_signature: String,
+    entities: Vec<TdTextEntity>,
+    code_blocks: Vec<CodeBlock>,
+    /// The links of the text or of the caption of the message.
+    links: Vec<String>,
+    /// The name of the type of the content when it cannot be rendered.
+    unsupported: Option<String>,
+    location: Option<LocationEntry>,
+    quiz: Option<QuizEntry>,
+    document: Option<DocumentEntry>,
+}
+
+/// `MessageReactionEntry` is an emoji reaction added to a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageReactionEntry {
+    /// The emoji of the reaction.
+    pub emoji: String,
+    /// The number of times the reaction was added.
+    pub total_count: i32,
+    /// Whether the reaction was added by the current user.
+    pub is_chosen: bool,
+}
+
+/// Format a count compactly, e.g. `1.2K` for `1234`.
+/// The count is truncated to one decimal, so it is never shown higher than it
+/// is.
+///
+/// # Arguments
+/// * `n` - The count.
+///
+/// # Returns
+/// * `String` - The formatted count.
+pub fn format_count(n: i64) -> String {
+    const UNITS: [(i64, &str); 3] = [(1_000_000_000, "B"), (1_000_000, "M"), (1_000, "K")];
+    for (unit, suffix) in UNITS {
+        if n >= unit {
+            let tenths = n / (unit / 10);
+            return if tenths % 10 == 0 {
+                format!("{}{}", tenths / 10, suffix)
+            } else {
+                format!("{}.{}{}", tenths / 10, tenths % 10, suffix)
+            };
+        }
+    }
+    n.to_string()
+}
+
+/// Get the reactions to show under a message, the most used first.
+///
+/// # Arguments
+/// * `reactions` - The reactions of the message.
+/// * `cap` - The maximum number of reactions to show, `0` to show all.
+///
+/// # Returns
+/// * `(Vec<MessageReactionEntry>, usize)` - The reactions to show and the
+///   number of the reactions that are not shown.
+pub fn top_reactions(
+    reactions: &[MessageReactionEntry],
+    cap: usize,
+) -> (Vec<MessageReactionEntry>, usize) {
+    let mut reactions = reactions.to_vec();
+    // The sort is stable, the reactions used the same number of times keep
+    // their order.
+    reactions.sort_by(|a, b| b.total_count.cmp(&a.total_count));
+    if cap == 0 || reactions.len() <= cap {
+        return (reactions, 0);
+    }
+    let hidden = reactions.len() - cap;
+    reactions.truncate(cap);
+    (reactions, hidden)
+}
+
+/// Render the placeholder shown in place of a message whose content cannot
+/// be rendered.
+///
+/// # Arguments
+/// * `content_type` - The name of the type of the content, e.g. `giveaway`.
+///
+/// # Returns
+/// * `String` - The placeholder.
+pub fn render_unsupported(content_type: &str) -> String {
+    format!("[unsupported: {}]", content_type)
+}
+
+/// `QuizEntry` is the explanation of a quiz poll.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuizEntry {
+    /// The text shown after the quiz is answered, it can be empty.
+    pub explanation: String,
+    /// Whether the current user answered the quiz.
+    pub answered: bool,
+}
+/// Implementation of the `QuizEntry` struct.
+impl QuizEntry {
+    /// Get the explanation of the quiz, it is kept hidden until the quiz is
+    /// answered so that it does not reveal the correct answer.
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The explanation, or `None` when the quiz is not
+    ///   answered yet or it has no explanation.
+    pub fn explanation(&self) -> Option<&str> {
+        (self.answered && !self.explanation.is_empty()).then_some(self.explanation.as_str())
+    }
+}
+
+/// The live period of the live locations shared until they are stopped.
+const LIVE_PERIOD_INDEFINITE: i32 = 0x7FFFFFFF;
+
+/// `LocationEntry` is the location of a location message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocationEntry {
+    /// The latitude of the location, in degrees.
+    pub latitude: f64,
+    /// The longitude of the location, in degrees.
+    pub longitude: f64,
+    /// For how long the location is shared, in seconds since the message was
+    /// sent, it is `0` for the static locations.
+    pub live_period: i32,
+}
+
+/// `DocumentEntry` is the file of a document message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentEntry {
+    /// The identifier of the file, used to download it.
+    pub file_id: i32,
+    /// The original name of the file.
+    pub file_name: String,
+}
+
+/// Get the glyph shown before the name of a document, from its MIME type or,
+/// when the MIME type is not known, from the extension of its name.
+///
+/// # Arguments
+/// * `mime` - The MIME type of the document, it can be empty.
+/// * `filename` - The name of the document.
+///
+/// # Returns
+/// * `char` - The glyph of the type of the document, `📄` for unknown types.
+pub fn file_type_glyph(mime: &str, filename: &str) -> char {
+    let mime = mime.to_lowercase();
+    match mime.split('/').next().unwrap_or_default() {
+        "image" => return '🖼',
+        "audio" => return '🎵',
+        "video" => return '🎬',
+        "text" => return '📝',
+        _ => {}
+    }
+    let extension = filename
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase())
+        .unwrap_or_default();
+    match (mime.as_str(), extension.as_str()) {
+        ("application/pdf", _) | (_, "pdf") => '📕',
+        (
+            "application/zip"
+            | "application/gzip"
+            | "application/x-tar"
+            | "application/x-7z-compressed"
+            | "application/x-rar-compressed"
+            | "application/vnd.rar",
+            _,
+        )
+        | (_, "zip" | "gz" | "tgz" | "tar" | "xz" | "bz2" | "7z" | "rar") => '📦',
+        (_, "xls" | "xlsx" | "ods" | "csv") => '📊',
+        (_, "ppt" | "pptx" | "odp" | "key") => '📽',
+        (_, "png" | "jpg" | "jpeg" | "gif" | "webp" | "bmp" | "svg") => '🖼',
+        (_, "mp3" | "ogg" | "flac" | "wav" | "m4a" | "opus") => '🎵',
+        (_, "mp4" | "mkv" | "webm" | "avi" | "mov") => '🎬',
+        ("application/json" | "application/xml", _)
+        | (
+            _,
+            "txt" | "md" | "rs" | "py" | "js" | "ts" | "c" | "h" | "cpp" | "go" | "java" | "sh"
+            | "toml" | "json" | "yaml" | "yml" | "xml" | "html" | "css" | "log",
+        ) => '📝',
+        _ => '📄',
+    }
+}
+
+/// Format the size of a file with the largest unit that keeps it above `1`.
+///
+/// # Arguments
+/// * `size` - The size of the file, in bytes.
+///
+/// # Returns
+/// * `String` - The size, e.g. `1.5 MB`.
+pub fn format_file_size(size: i64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if size < 1024 {
+        return format!("{} B", size.max(0));
+    }
+    let mut value = size as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Compute for how long a live location is still shared.
+///
+/// # Arguments
+/// * `date` - The timestamp, in seconds, when the location was sent.
+/// * `live_period` - For how long the location is shared, in seconds, `0`
+///   for the static locations.
+/// * `now` - The current timestamp, in seconds.
+///
+/// # Returns
+/// * `Option<i32>` - The remaining time, in seconds, or `None` when the
+///   location is static or expired. It is `i32::MAX` for the locations shared
+///   until they are stopped.
+pub fn live_location_remaining(date: i32, live_period: i32, now: i32) -> Option<i32> {
+    if live_period == 0 {
+        return None;
+    }
+    if live_period == LIVE_PERIOD_INDEFINITE {
+        return Some(i32::MAX);
+    }
+    let remaining = date.saturating_add(live_period).saturating_sub(now);
+    (remaining > 0).then_some(remaining)
+}
+
+/// Describe the remaining time of a live location.
+///
+/// # Arguments
+/// * `remaining` - The remaining time, in seconds.
+///
+/// # Returns
+/// * `String` - The remaining time, rounded up to the minute.
+fn format_remaining(remaining: i32) -> String {
+    if remaining == i32::MAX {
+        return "until stopped".to_string();
+    }
+    let minutes = (remaining as i64 + 59) / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m left", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m left", minutes)
+    }
+}
+
+/// `CodeBlock` is a preformatted block of code of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The programming language of the code, if it is known.
+    pub language: Option<String>,
+    /// The code, without the framing.
+    pub content: String,
+}
+
+/// Extract the code blocks of a formatted text, i.e. the text of its
+/// `textEntityTypePre` and `textEntityTypePreCode` entities.
+/// The inline `textEntityTypeCode` entities are not blocks and are skipped.
+///
+/// # Arguments
+/// * `text` - The formatted text.
+///
+/// # Returns
+/// * `Vec<CodeBlock>` - The code blocks, in the order they appear in the text.
+pub fn code_blocks(text: &FormattedText) -> Vec<CodeBlock> {
+    text.entities
+        .iter()
+        .filter_map(|e| {
+            let language = match &e.r#type {
+                TextEntityType::Pre => None,
+                TextEntityType::PreCode(pre_code) if pre_code.language.is_empty() => None,
+                TextEntityType::PreCode(pre_code) => Some(pre_code.language.clone()),
+                _ => return None,
+            };
+            Some(CodeBlock {
+                language,
+                content: entity_text(text, e),
+            })
+        })
+        .collect()
+}
+
+/// Get the part of a text between two offsets counted in UTF-16 code units,
+/// the unit of the offsets of the entities of TDLib.
+///
+/// # Arguments
+/// * `text` - The text.
+/// * `start` - The offset of the first code unit.
+/// * `end` - The offset after the last code unit.
+///
+/// # Returns
+/// * `String` - The characters that start between the two offsets.
+fn utf16_slice(text: &str, start: usize, end: usize) -> String {
+    let mut position = 0;
+    text.chars()
+        .filter(|c| {
+            let c_position = position;
+            position += c.len_utf16();
+            (start..end).contains(&c_position)
+        })
+        .collect()
+}
+
+/// Get the text an entity of a formatted text applies to.
+///
+/// # Arguments
+/// * `text` - The formatted text.
+/// * `entity` - The entity, its offset and its length are counted in UTF-16
+///   code units.
+///
+/// # Returns
+/// * `String` - The text of the entity.
+pub fn entity_text(text: &FormattedText, entity: &TextEntity) -> String {
+    let start = entity.offset as usize;
+    utf16_slice(&text.text, start, start + entity.length as usize)
+}
+
+/// Extract the links of a formatted text, i.e. the text of its
+/// `textEntityTypeUrl` entities and the targets of its `textEntityTypeTextUrl`
+/// entities.
+///
+/// # Arguments
+/// * `text` - The formatted text.
+///
+/// # Returns
+/// * `Vec<String>` - The links, in the order they appear in the text.
+pub fn text_links(text: &FormattedText) -> Vec<String> {
+    text.entities
+        .iter()
+        .filter_map(|e| match &e.r#type {
+            TextEntityType::Url => Some(entity_text(text, e)),
+            TextEntityType::TextUrl(text_url) => Some(text_url.url.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Get the preview of the message a reply replies to, drawn between the
+/// header and the body of the reply.
+///
+/// # Arguments
+/// * `mode` - How the replied message is previewed.
+/// * `sender` - The sender of the replied message, already styled.
+/// * `quoted` - The lines of the replied message, ignored by the compact
+///   mode.
+/// * `label_style` - The style of the label before the sender.
+///
+/// # Returns
+/// * `Vec<Line<'static>>` - The lines of the preview.
+pub fn reply_preview_lines(
+    mode: ReplyPreviewMode,
+    sender: Span<'static>,
+    quoted: Vec<Line<'static>>,
+    label_style: Style,
+) -> Vec<Line<'static>> {
+    match mode {
+        ReplyPreviewMode::Inline => {
+            let mut lines = vec![Line::from(vec![
+                Span::styled("↩️ Reply to: ", label_style),
+                sender,
+            ])];
+            lines.extend(quoted);
+            lines
+        }
+        ReplyPreviewMode::Compact => {
+            vec![Line::from(vec![Span::styled("↩ ", label_style), sender])]
+        }
+    }
+}
+
+/// Attach a translation to a message of a chat.
+///
+/// # Arguments
+/// * `msgs` - The messages of the chat.
+/// * `message_id` - The identifier of the translated message.
+/// * `translation` - The translated text.
+///
+/// # Returns
+/// * `bool` - Whether the message is among the messages of the chat.
+pub fn attach_translation(msgs: &mut [MessageEntry], message_id: i64, translation: String) -> bool {
+    match msgs.iter_mut().find(|m| m.id() == message_id) {
+        Some(message) => {
+            message.set_translation(translation);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Frame a code block with a border, labelled with its language.
+///
+/// # Arguments
+/// * `block` - The code block.
+///
+/// # Returns
+/// * `String` - The framed code block, one line of the frame each line.
+fn frame_code_block(block: &CodeBlock) -> String {
+    let mut framed = match &block.language {
+        Some(language) => format!("┌─ {} ─", language),
+        None => "┌──".to_string(),
+    };
+    for line in block.content.lines() {
+        framed.push_str("\n│ ");
+        framed.push_str(line);
+    }
+    framed.push_str("\n└──");
+    framed
+}
+
+/// `AlbumGroup` is a group of consecutive messages that belong to the same
+/// album and are rendered as a single block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlbumGroup {
+    /// The identifier of the album, it is `0` when the message is not part of
+    /// an album.
+    pub media_album_id: i64,
+    /// The indices of the messages of the group, in the same order as the
+    /// messages they are computed from.
+    pub indices: Vec<usize>,
+}
+
+/// Group the consecutive messages with the same `media_album_id`.
+/// The messages that are not part of an album are in a group of their own.
+///
+/// # Arguments
+/// * `msgs` - The messages to group.
+///
+/// # Returns
+/// * `Vec<AlbumGroup>` - The groups, in the same order as the messages.
+pub fn group_album(msgs: &[MessageEntry]) -> Vec<AlbumGroup> {
+    let mut groups: Vec<AlbumGroup> = Vec::new();
+    for (i, message) in msgs.iter().enumerate() {
+        match groups.last_mut() {
+            Some(group)
+                if message.media_album_id != 0
+                    && group.media_album_id == message.media_album_id =>
+            {
+                group.indices.push(i);
+            }
+            _ => groups.push(AlbumGroup {
+                media_album_id: message.media_album_id,
+                indices: vec![i],
+            }),
+        }
+    }
+    groups
 }
 
 impl MessageEntry {
@@ -52,6 +619,89 @@ impl MessageEntry {
         &self.timestamp
     }
 
+    pub fn reply_markup(&self) -> Option<&TdReplyMarkup> {
+        self.reply_markup.as_ref()
+    }
+
+    pub fn media_album_id(&self) -> i64 {
+        self.media_album_id
+    }
+
+    pub fn message_thread_id(&self) -> i64 {
+        self.message_thread_id
+    }
+
+    pub fn entities(&self) -> &[TdTextEntity] {
+        &self.entities
+    }
+
+    /// The action to perform when the entity at `entity_index` is followed.
+    /// It returns `None` when the message has no such entity.
+    pub fn entity_action(&self, entity_index: usize) -> Option<Action> {
+        self.entities
+            .get(entity_index)
+            .map(TdTextEntity::follow_action)
+    }
+
+    /// The content of the code block at `block_index`.
+    /// It returns `None` when the message has no such block.
+    pub fn code_block_content(&self, block_index: usize) -> Option<&str> {
+        self.code_blocks
+            .get(block_index)
+            .map(|block| block.content.as_str())
+    }
+
+    pub fn links(&self) -> &[String] {
+        &self.links
+    }
+
+    pub fn reactions(&self) -> &[MessageReactionEntry] {
+        &self.reactions
+    }
+
+    pub fn set_interaction_info(&mut self, interaction_info: Option<&MessageInteractionInfo>) {
+        self.reactions = Self::message_reactions(interaction_info);
+        self.view_count = interaction_info.map_or(0, |info| info.view_count);
+        self.comment_count = Self::message_comment_count(interaction_info);
+    }
+
+    /// Get the signature of the author of the post.
+    ///
+    /// # Arguments
+    /// * `channel_signatures` - Whether the signatures are shown.
+    ///
+    /// # Returns
+    /// * `Option<&str>` - The signature, `None` when the post is not signed or
+    ///   the signatures are hidden.
+    pub fn signature(&self, channel_signatures: bool) -> Option<&str> {
+        if channel_signatures && !self.author_signature.is_empty() {
+            Some(&self.author_signature)
+        } else {
+            None
+        }
+    }
+
+    pub fn view_count(&self) -> Option<i32> {
+        if self.is_channel_post {
+            Some(self.view_count)
+        } else {
+            None
+        }
+    }
+
+    /// Get the number of comments of the post.
+    ///
+    /// # Returns
+    /// * `Option<i32>` - The number of comments, `None` when the message is
+    ///   not a post of a channel with a discussion group.
+    pub fn comment_count(&self) -> Option<i32> {
+        if self.is_channel_post {
+            self.comment_count
+        } else {
+            None
+        }
+    }
+
     pub fn sender_id(&self) -> i64 {
         match self.sender_id {
             TdMessageSender::User(user_id) => user_id,
@@ -69,12 +719,114 @@ impl MessageEntry {
 
     pub fn set_message_content(&mut self, content: &MessageContent) {
         self.message_content = Self::message_content_lines(content);
+        self.highlighted_text = Self::message_highlighted_text(content);
+        // The translation of the previous text is stale.
+        self.translation = None;
+        self.translation_shown = false;
+        self.entities = Self::message_entities(content);
+        self.code_blocks = Self::message_code_blocks(content);
+        self.links = Self::message_links(content);
+        self.unsupported = Self::message_unsupported(content);
+        self.location = Self::message_location(content);
+        self.quiz = Self::message_quiz(content);
+        self.document = Self::message_document(content);
+        self.caption = Self::message_caption_lines(content);
+    }
+
+    pub fn quiz(&self) -> Option<&QuizEntry> {
+        self.quiz.as_ref()
+    }
+
+    pub fn location(&self) -> Option<LocationEntry> {
+        self.location
+    }
+
+    pub fn document(&self) -> Option<&DocumentEntry> {
+        self.document.as_ref()
+    }
+
+    /// Get for how long the location of the message is still shared.
+    ///
+    /// # Arguments
+    /// * `now` - The current timestamp, in seconds.
+    ///
+    /// # Returns
+    /// * `Option<i32>` - The remaining time, in seconds, or `None` when the
+    ///   message is not a live location or it expired.
+    pub fn live_location_remaining(&self, now: i32) -> Option<i32> {
+        let location = self.location?;
+        live_location_remaining(self.timestamp.timestamp, location.live_period, now)
+    }
+
+    /// Get the lines of the panel that shows the location of the message
+    /// with the links to open it on a map.
+    ///
+    /// # Arguments
+    /// * `now` - The current timestamp, in seconds.
+    ///
+    /// # Returns
+    /// * `Option<Vec<String>>` - The lines, or `None` when the message is not
+    ///   a location.
+    pub fn location_panel_lines(&self, now: i32) -> Option<Vec<String>> {
+        let LocationEntry {
+            latitude,
+            longitude,
+            ..
+        } = self.location?;
+        let status = match self.live_location_remaining(now) {
+            Some(remaining) => format!("Live, {}", format_remaining(remaining)),
+            None => "Static".to_string(),
+        };
+        Some(vec![
+            format!("📍 {:.5}, {:.5}", latitude, longitude),
+            status,
+            format!(
+                "https://www.openstreetmap.org/?mlat={lat}&mlon={lon}#map=16/{lat}/{lon}",
+                lat = latitude,
+                lon = longitude
+            ),
+            format!("geo:{},{}", latitude, longitude),
+        ])
+    }
+
+    pub fn is_edited(&self) -> bool {
+        self.is_edited
     }
 
     pub fn set_is_edited(&mut self, is_edited: bool) {
         self.is_edited = is_edited;
     }
 
+    pub fn set_send_failed(&mut self, send_failed: bool) {
+        self.send_failed = send_failed;
+    }
+
+    pub fn send_error(&self) -> Option<&SendError> {
+        self.send_error.as_ref()
+    }
+
+    pub fn translation(&self) -> Option<&str> {
+        self.translation.as_deref()
+    }
+
+    /// Set the translation of the message, it is shown.
+    pub fn set_translation(&mut self, translation: String) {
+        self.translation = Some(translation);
+        self.translation_shown = true;
+    }
+
+    /// Show or hide the translation of the message.
+    ///
+    /// # Returns
+    /// * `bool` - Whether the message has a translation to toggle.
+    pub fn toggle_translation(&mut self) -> bool {
+        if self.translation.is_none() {
+            return false;
+        }
+        self.translation_shown = !self.translation_shown;
+        true
+    }
+
     pub fn get_text_styled(
         &self,
         myself: bool,
@@ -83,6 +835,67 @@ impl MessageEntry {
         name_style: Style,
         content_style: Style,
         wrap_width: i32,
+    ) -> Text {
+        let mut body = match &self.unsupported {
+            Some(content_type) if app_context.unsupported_placeholders() => {
+                vec![Line::styled(
+                    render_unsupported(content_type),
+                    content_style.add_modifier(Modifier::ITALIC),
+                )]
+            }
+            _ => match self.get_big_emoji_lines(content_style) {
+                Some(lines) if app_context.big_emoji() => lines,
+                _ => self.get_lines_styled_with_style(
+                    content_style,
+                    wrap_width,
+                    app_context.syntax_highlight().then_some(app_context),
+                ),
+            },
+        };
+        // The expired live locations are rendered as the static ones.
+        let now = chrono::Utc::now().timestamp() as i32;
+        if let Some(remaining) = self.live_location_remaining(now) {
+            body.insert(
+                0,
+                Line::styled(
+                    format!("📡 Live location · {}", format_remaining(remaining)),
+                    content_style.add_modifier(Modifier::BOLD),
+                ),
+            );
+        }
+        body.extend(self.get_caption_lines_styled_with_style(content_style, wrap_width));
+        body.extend(self.get_translation_lines(content_style, wrap_width));
+        body.extend(self.get_reply_markup_lines(content_style));
+        body.extend(
+            self.get_reactions_lines(content_style, app_context.app_config().max_reactions_shown),
+        );
+        if let Some(signature) = self.signature(app_context.channel_signatures()) {
+            body.push(Line::styled(
+                format!("— {}", signature),
+                content_style.add_modifier(Modifier::ITALIC),
+            ));
+        }
+        if app_context.view_counts() {
+            body.extend(self.get_view_count_lines(content_style));
+        }
+        if app_context.comment_counts() {
+            body.extend(self.get_comment_count_lines(content_style));
+        }
+        body.extend(self.get_send_error_lines(content_style));
+        self.get_text_styled_with_body(myself, app_context, is_unread, name_style, wrap_width, body)
+    }
+
+    /// Get the header and the reply of the message followed by the given body.
+    /// It is used to render a message whose body is not its own content, like
+    /// an album whose items are rendered under the header of the first one.
+    pub fn get_text_styled_with_body(
+        &self,
+        myself: bool,
+        app_context: &AppContext,
+        is_unread: bool, // When myself is false, is_unread is useless
+        name_style: Style,
+        wrap_width: i32,
+        body: Vec<Line<'static>>,
     ) -> Text {
         let (message_reply_name, message_reply_content) = if myself {
             (
@@ -96,85 +909,82 @@ impl MessageEntry {
             )
         };
 
+        let reply_preview_mode = app_context.app_config().reply_preview_mode;
         let reply_text = match &self.reply_to {
             Some(reply_to) => match reply_to {
                 TdMessageReplyTo::Message(message) => {
                     if app_context.tg_context().open_chat_id() == message.chat_id {
-                        let mut entry = Text::default();
-                        entry.extend(vec![Line::from(vec![
-                            Span::styled(
-                                "↩️ Reply to: ",
-                                app_context.style_chat_message_reply_text(),
-                            ),
-                            Span::styled(
-                                app_context
-                                    .tg_context()
-                                    .try_name_from_chats_or_users(
-                                        match app_context
-                                            .tg_context()
-                                            .open_chat_messages()
-                                            .iter()
-                                            .find(|m| m.id() == message.message_id)
-                                        {
-                                            Some(m) => m.sender_id(),
-                                            None => -1,
-                                        },
-                                    )
-                                    .unwrap_or_default(),
-                                message_reply_name,
-                            ),
-                        ])]);
-                        entry.extend(
-                            match app_context
+                        let replied = app_context
+                            .tg_context()
+                            .open_chat_messages()
+                            .iter()
+                            .find(|m| m.id() == message.message_id)
+                            .map(|m| {
+                                (
+                                    m.sender_id(),
+                                    m.get_lines_styled_with_style(
+                                        message_reply_content,
+                                        wrap_width,
+                                        None,
+                                    ),
+                                )
+                            });
+                        let (sender_id, quoted) = replied.unwrap_or((-1, vec![Line::from("")]));
+                        let sender = Span::styled(
+                            app_context
                                 .tg_context()
-                                .open_chat_messages()
-                                .iter()
-                                .find(|m| m.id() == message.message_id)
-                            {
-                                Some(m) => {
-                                    m.get_lines_styled_with_style(message_reply_content, wrap_width)
-                                }
-                                None => vec![Line::from("")],
-                            },
+                                .try_name_from_chats_or_users(sender_id)
+                                .unwrap_or_default(),
+                            message_reply_name,
                         );
-                        Some(entry)
+                        Some(reply_preview_lines(
+                            reply_preview_mode,
+                            sender,
+                            quoted,
+                            app_context.style_chat_message_reply_text(),
+                        ))
                     } else {
                         None
                     }
                 }
-                TdMessageReplyTo::Story(_) => {
-                    let mut entry = Text::default();
-                    entry.extend(vec![Line::from(vec![
-                        Span::styled("↩️ Reply to: ", app_context.style_chat_message_reply_text()),
-                        Span::styled("Story", message_reply_name),
-                    ])]);
-                    Some(entry)
-                }
+                TdMessageReplyTo::Story(_) => Some(reply_preview_lines(
+                    reply_preview_mode,
+                    Span::styled("Story", message_reply_name),
+                    vec![],
+                    app_context.style_chat_message_reply_text(),
+                )),
             },
             None => None,
         };
 
+        let sender_name = match self.sender_id {
+            TdMessageSender::User(user_id) => app_context
+                .tg_context()
+                .try_name_from_chats_or_users(user_id)
+                .unwrap_or_default(),
+            TdMessageSender::Chat(chat_id) => app_context
+                .tg_context()
+                .name_from_chats(chat_id)
+                .unwrap_or_default(),
+        };
+        let avatar = if app_context.avatar_initials() {
+            avatar_span(self.sender_id(), &sender_name)
+        } else {
+            Span::default()
+        };
+
         let mut entry = Text::default();
         entry.extend(vec![Line::from(vec![
-            Span::styled(
-                match self.sender_id {
-                    TdMessageSender::User(user_id) => app_context
-                        .tg_context()
-                        .try_name_from_chats_or_users(user_id)
-                        .unwrap_or_default(),
-                    TdMessageSender::Chat(chat_id) => app_context
-                        .tg_context()
-                        .name_from_chats(chat_id)
-                        .unwrap_or_default(),
-                },
-                name_style,
-            ),
+            avatar,
+            Span::styled(sender_name, name_style),
             Span::raw(" "),
             Span::raw(if self.is_edited { "✏️" } else { "" }),
             Span::raw(" "),
             Span::raw(match myself {
                 true => {
-                    if is_unread {
+                    if self.send_failed {
+                        "❌"
+                    } else if is_unread {
                         "📤"
                     } else {
                         "👀"
@@ -183,27 +993,387 @@ impl MessageEntry {
                 false => "",
             }),
             Span::raw(" "),
-            self.timestamp.get_span_styled(app_context),
+            match self.sender_id {
+                TdMessageSender::User(user_id) if app_context.sender_timezone() && !myself => {
+                    let utc_offset = app_context.tg_context().user_utc_offset(user_id);
+                    self.timestamp.get_span_styled_in(app_context, utc_offset)
+                }
+                _ => self.timestamp.get_span_styled(app_context),
+            },
         ])]);
         entry.extend(reply_text.unwrap_or_default());
-        entry.extend(self.get_lines_styled_with_style(content_style, wrap_width));
+        entry.extend(body);
         entry
     }
 
+    fn get_reply_markup_lines(&self, content_style: Style) -> Vec<Line<'static>> {
+        let button_style = content_style.add_modifier(Modifier::REVERSED);
+        match &self.reply_markup {
+            Some(reply_markup) => reply_markup
+                .button_texts()
+                .iter()
+                .map(|row| {
+                    Line::from(
+                        row.iter()
+                            .enumerate()
+                            .flat_map(|(i, text)| {
+                                vec![
+                                    Span::raw(if i == 0 { "" } else { " " }),
+                                    Span::styled(format!(" {} ", text), button_style),
+                                ]
+                            })
+                            .collect::<Vec<Span>>(),
+                    )
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    fn get_reactions_lines(&self, content_style: Style, cap: usize) -> Vec<Line<'static>> {
+        if self.reactions.is_empty() {
+            return vec![];
+        }
+        let chosen_style = content_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        let (reactions, hidden) = top_reactions(&self.reactions, cap);
+        let mut line = Line::from(
+            reactions
+                .iter()
+                .enumerate()
+                .flat_map(|(i, reaction)| {
+                    vec![
+                        Span::raw(if i == 0 { "" } else { " " }),
+                        Span::styled(
+                            format!("{} {}", reaction.emoji, reaction.total_count),
+                            if reaction.is_chosen {
+                                chosen_style
+                            } else {
+                                content_style
+                            },
+                        ),
+                    ]
+                })
+                .collect::<Vec<Span>>(),
+        );
+        if hidden > 0 {
+            line.push_span(Span::styled(format!(" +{}", hidden), content_style));
+        }
+        vec![line]
+    }
+
+    fn get_view_count_lines(&self, content_style: Style) -> Vec<Line<'static>> {
+        match self.view_count() {
+            Some(view_count) if view_count > 0 => vec![Line::styled(
+                format!("👁 {}", format_count(view_count.into())),
+                content_style.add_modifier(Modifier::DIM),
+            )],
+            _ => vec![],
+        }
+    }
+
+    fn get_comment_count_lines(&self, content_style: Style) -> Vec<Line<'static>> {
+        match self.comment_count() {
+            Some(comment_count) => vec![Line::styled(
+                format!(
+                    "💬 {} {}",
+                    format_count(comment_count.into()),
+                    if comment_count == 1 {
+                        "comment"
+                    } else {
+                        "comments"
+                    }
+                ),
+                content_style.add_modifier(Modifier::DIM),
+            )],
+            None => vec![],
+        }
+    }
+
+    // The translation is set apart from the original text by its marker.
+    /// Get the content of the message spaced out and emphasized, when it is
+    /// made only of a few emoji.
+    fn get_big_emoji_lines(&self, content_style: Style) -> Option<Vec<Line<'static>>> {
+        let text = self.message_content_to_string();
+        if !is_emoji_only(&text) {
+            return None;
+        }
+        let emoji = emoji_clusters(&text)?.join("  ");
+        Some(vec![
+            Line::from(""),
+            Line::styled(
+                format!(" {} ", emoji),
+                content_style.add_modifier(Modifier::BOLD),
+            ),
+            Line::from(""),
+        ])
+    }
+
+    fn get_translation_lines(&self, content_style: Style, wrap_width: i32) -> Vec<Line<'static>> {
+        match &self.translation {
+            Some(translation) if self.translation_shown => {
+                let mut lines = vec![Line::styled(
+                    "🌐 Translation",
+                    content_style.add_modifier(Modifier::DIM),
+                )];
+                lines.extend(Self::lines_styled_with_style(
+                    &Self::from_span_to_lines(Span::raw(translation.clone())),
+                    content_style.add_modifier(Modifier::ITALIC),
+                    wrap_width,
+                ));
+                lines
+            }
+            _ => vec![],
+        }
+    }
+
+    // The transient failures are retried, so they are shown dimmed while the
+    // permanent ones stand out.
+    fn get_send_error_lines(&self, content_style: Style) -> Vec<Line<'static>> {
+        match &self.send_error {
+            Some(error) => {
+                let (symbol, style) = match error.kind() {
+                    SendErrorKind::Transient => (
+                        "⏳",
+                        content_style.add_modifier(Modifier::DIM | Modifier::ITALIC),
+                    ),
+                    SendErrorKind::Permanent => ("⛔", content_style.add_modifier(Modifier::BOLD)),
+                };
+                vec![Line::styled(format!("{} {}", symbol, error.note()), style)]
+            }
+            None => vec![],
+        }
+    }
+
+    // The replies are only counted for the posts of the channels with a
+    // discussion group, where they are the comments.
+    fn message_comment_count(interaction_info: Option<&MessageInteractionInfo>) -> Option<i32> {
+        interaction_info
+            .and_then(|info| info.reply_info.as_ref())
+            .map(|reply_info| reply_info.reply_count)
+    }
+
+    // Only the emoji reactions are kept, the custom emoji can not be
+    // rendered in the terminal.
+    fn message_reactions(
+        interaction_info: Option<&MessageInteractionInfo>,
+    ) -> Vec<MessageReactionEntry> {
+        interaction_info
+            .and_then(|info| info.reactions.as_ref())
+            .map(|reactions| {
+                reactions
+                    .reactions
+                    .iter()
+                    .filter_map(|reaction| match &reaction.r#type {
+                        ReactionType::Emoji(emoji) => Some(MessageReactionEntry {
+                            emoji: emoji.emoji.clone(),
+                            total_count: reaction.total_count,
+                            is_chosen: reaction.is_chosen,
+                        }),
+                        ReactionType::CustomEmoji(_) => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // Only the entities that can be followed are kept.
+    fn message_entities(content: &MessageContent) -> Vec<TdTextEntity> {
+        let MessageContent::MessageText(m) = content else {
+            return vec![];
+        };
+        m.text
+            .entities
+            .iter()
+            .filter_map(|e| {
+                let text = entity_text(&m.text, e);
+                match &e.r#type {
+                    tdlib_rs::enums::TextEntityType::Mention => Some(TdTextEntity::Mention(
+                        text.trim_start_matches('@').to_string(),
+                    )),
+                    tdlib_rs::enums::TextEntityType::Hashtag => Some(TdTextEntity::Hashtag(text)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    fn message_code_blocks(content: &MessageContent) -> Vec<CodeBlock> {
+        match content {
+            MessageContent::MessageText(m) => code_blocks(&m.text),
+            _ => vec![],
+        }
+    }
+
+    fn message_links(content: &MessageContent) -> Vec<String> {
+        match content {
+            MessageContent::MessageText(m) => text_links(&m.text),
+            MessageContent::MessageAudio(m) => text_links(&m.caption),
+            MessageContent::MessagePhoto(m) => text_links(&m.caption),
+            MessageContent::MessageVideo(m) => text_links(&m.caption),
+            MessageContent::MessageAnimation(m) => text_links(&m.caption),
+            MessageContent::MessageVoiceNote(m) => text_links(&m.caption),
+            MessageContent::MessageDocument(m) => text_links(&m.caption),
+            _ => vec![],
+        }
+    }
+
+    // The contents rendered by `message_content_lines` are supported, the
+    // name of the type of the others is kept for the placeholder.
+    fn message_unsupported(content: &MessageContent) -> Option<String> {
+        let content_type = match content {
+            MessageContent::MessageText(_)
+            | MessageContent::MessageAnimatedEmoji(_)
+            | MessageContent::MessageAudio(_)
+            | MessageContent::MessagePhoto(_)
+            | MessageContent::MessageSticker(_)
+            | MessageContent::MessageVideo(_)
+            | MessageContent::MessageAnimation(_)
+            | MessageContent::MessageVoiceNote(_)
+            | MessageContent::MessageDocument(_)
+            | MessageContent::MessageLocation(_)
+            | MessageContent::MessagePoll(_) => return None,
+            MessageContent::MessageCall(_) => "call",
+            MessageContent::MessageContact(_) => "contact",
+            MessageContent::MessageDice(_) => "dice",
+            MessageContent::MessageGame(_) => "game",
+            MessageContent::MessageInvoice(_) => "invoice",
+            MessageContent::MessageStory(_) => "story",
+            MessageContent::MessageVenue(_) => "venue",
+            MessageContent::MessageVideoNote(_) => "video note",
+            MessageContent::MessageExpiredPhoto => "expired photo",
+            MessageContent::MessageExpiredVideo => "expired video",
+            MessageContent::MessageUnsupported => "unsupported",
+            _ => "message",
+        };
+        Some(content_type.to_string())
+    }
+
+    fn message_highlighted_text(content: &MessageContent) -> Option<FormattedText> {
+        let MessageContent::MessageText(m) = content else {
+            return None;
+        };
+        m.text
+            .entities
+            .iter()
+            .any(|e| match &e.r#type {
+                TextEntityType::PreCode(pre_code) => is_supported(&pre_code.language),
+                _ => false,
+            })
+            .then(|| m.text.clone())
+    }
+
     fn message_content_lines(content: &MessageContent) -> Vec<Line<'static>> {
         match content {
             MessageContent::MessageText(m) => Self::format_message_content(&m.text),
+            MessageContent::MessageAnimatedEmoji(m) => vec![Line::from(m.emoji.clone())],
             MessageContent::MessageAudio(_) => vec![Line::from("🎵 Audio")],
             MessageContent::MessagePhoto(_) => vec![Line::from("📷 Photo")],
             MessageContent::MessageSticker(_) => vec![Line::from("🎨 Sticker")],
             MessageContent::MessageVideo(_) => vec![Line::from("🎥 Video")],
             MessageContent::MessageAnimation(_) => vec![Line::from("🎞️ Animation")],
             MessageContent::MessageVoiceNote(_) => vec![Line::from("🎤 Voice Note")],
-            MessageContent::MessageDocument(_) => vec![Line::from("📄 Document")],
+            MessageContent::MessageDocument(m) => vec![Line::from(format!(
+                "{} {} · {}",
+                file_type_glyph(&m.document.mime_type, &m.document.file_name),
+                m.document.file_name,
+                format_file_size(m.document.document.size)
+            ))],
+            MessageContent::MessageLocation(m) => vec![Line::from(format!(
+                "📍 {:.5}, {:.5}",
+                m.location.latitude, m.location.longitude
+            ))],
+            MessageContent::MessagePoll(m) => Self::poll_lines(&m.poll),
             _ => vec![Line::from("")],
         }
     }
 
+    // The results are shown once the current user answered or the poll is
+    // closed, the explanation of a quiz only once it is answered.
+    fn poll_lines(poll: &Poll) -> Vec<Line<'static>> {
+        let answered = poll.options.iter().any(|option| option.is_chosen);
+        let show_results = answered || poll.is_closed;
+        let correct_option_id = match &poll.r#type {
+            PollType::Quiz(quiz) => Some(quiz.correct_option_id),
+            PollType::Regular(_) => None,
+        };
+        let mut lines = vec![Line::from(format!("📊 {}", poll.question))];
+        for (i, option) in poll.options.iter().enumerate() {
+            let mark = if show_results && correct_option_id == Some(i as i32) {
+                "✓"
+            } else if option.is_chosen {
+                "●"
+            } else {
+                "○"
+            };
+            lines.push(Line::from(if show_results {
+                format!("{} {} — {}%", mark, option.text, option.vote_percentage)
+            } else {
+                format!("{} {}", mark, option.text)
+            }));
+        }
+        if let Some(explanation) = Self::message_quiz_of_poll(poll)
+            .as_ref()
+            .and_then(QuizEntry::explanation)
+        {
+            lines.push(Line::from(format!("💡 {}", explanation)));
+        }
+        lines
+    }
+
+    fn message_quiz(content: &MessageContent) -> Option<QuizEntry> {
+        match content {
+            MessageContent::MessagePoll(m) => Self::message_quiz_of_poll(&m.poll),
+            _ => None,
+        }
+    }
+
+    fn message_quiz_of_poll(poll: &Poll) -> Option<QuizEntry> {
+        match &poll.r#type {
+            PollType::Quiz(quiz) => Some(QuizEntry {
+                explanation: quiz.explanation.text.clone(),
+                answered: poll.options.iter().any(|option| option.is_chosen),
+            }),
+            PollType::Regular(_) => None,
+        }
+    }
+
+    fn message_document(content: &MessageContent) -> Option<DocumentEntry> {
+        match content {
+            MessageContent::MessageDocument(m) => Some(DocumentEntry {
+                file_id: m.document.document.id,
+                file_name: m.document.file_name.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn message_location(content: &MessageContent) -> Option<LocationEntry> {
+        match content {
+            MessageContent::MessageLocation(m) => Some(LocationEntry {
+                latitude: m.location.latitude,
+                longitude: m.location.longitude,
+                live_period: m.live_period,
+            }),
+            _ => None,
+        }
+    }
+
+    fn message_caption_lines(content: &MessageContent) -> Vec<Line<'static>> {
+        let caption = match content {
+            MessageContent::MessageAudio(m) => &m.caption,
+            MessageContent::MessagePhoto(m) => &m.caption,
+            MessageContent::MessageVideo(m) => &m.caption,
+            MessageContent::MessageAnimation(m) => &m.caption,
+            MessageContent::MessageVoiceNote(m) => &m.caption,
+            MessageContent::MessageDocument(m) => &m.caption,
+            _ => return vec![],
+        };
+        if caption.text.is_empty() {
+            return vec![];
+        }
+        Self::format_message_content(caption)
+    }
+
     fn from_span_to_lines(span: Span) -> Vec<Line<'static>> {
         span.content
             .split('\n')
@@ -234,14 +1404,51 @@ impl MessageEntry {
         }
     }
 
+    /// Get the lines of the content of the message, wrapped and styled.
+    ///
+    /// # Arguments
+    /// * `content_style` - The style of the content.
+    /// * `wrap_width` - The width at which the content is wrapped.
+    /// * `syntax_highlight` - The application context, used for the styles of
+    ///   the highlighted code blocks, `None` to not highlight them.
+    ///
+    /// # Returns
+    /// * `Vec<Line>` - The lines of the content.
     pub fn get_lines_styled_with_style(
         &self,
         content_style: Style,
         wrap_width: i32,
+        syntax_highlight: Option<&AppContext>,
+    ) -> Vec<Line<'static>> {
+        match (&self.highlighted_text, syntax_highlight) {
+            (Some(text), Some(app_context)) => Self::lines_styled_with_style(
+                &Self::format_message_content_in(text, Some(app_context)),
+                content_style,
+                wrap_width,
+            ),
+            _ => Self::lines_styled_with_style(&self.message_content, content_style, wrap_width),
+        }
+    }
+
+    pub fn get_caption_lines_styled_with_style(
+        &self,
+        content_style: Style,
+        wrap_width: i32,
+    ) -> Vec<Line<'static>> {
+        if self.caption.is_empty() {
+            return vec![];
+        }
+        Self::lines_styled_with_style(&self.caption, content_style, wrap_width)
+    }
+
+    fn lines_styled_with_style(
+        content: &[Line<'static>],
+        content_style: Style,
+        wrap_width: i32,
     ) -> Vec<Line<'static>> {
         if wrap_width == -1 {
             // No wrap
-            self.message_content
+            content
                 .iter()
                 .map(|l| {
                     l.iter()
@@ -259,19 +1466,20 @@ impl MessageEntry {
             let mut lines = Vec::new();
             let mut current_line = Line::default();
             let mut current_line_length = 0;
-            // for span in self.message_content.iter().flat_map(|l| l.iter()) {
-            for span in self.message_content.iter().flat_map(|l| l.iter()) {
-                for c in span.content.chars() {
-                    if c == ' ' && current_line_length >= wrap_width {
-                        lines.push(current_line);
-                        current_line = Line::default();
-                        current_line_length = 0;
+            for line in content.iter() {
+                for span in line.iter() {
+                    for c in span.content.chars() {
+                        if c == ' ' && current_line_length >= wrap_width {
+                            lines.push(current_line);
+                            current_line = Line::default();
+                            current_line_length = 0;
+                        }
+                        current_line.spans.push(Span::styled(
+                            c.to_string(),
+                            Self::merge_two_style(span.style, content_style),
+                        ));
+                        current_line_length += 1;
                     }
-                    current_line.spans.push(Span::styled(
-                        c.to_string(),
-                        Self::merge_two_style(span.style, content_style),
-                    ));
-                    current_line_length += 1;
                 }
                 lines.push(current_line);
                 current_line = Line::default();
@@ -282,6 +1490,48 @@ impl MessageEntry {
     }
 
     fn format_message_content(message: &FormattedText) -> Vec<Line<'static>> {
+        Self::format_message_content_in(message, None)
+    }
+
+    /// Frame a code block of a known language, coloring its tokens.
+    ///
+    /// # Arguments
+    /// * `language` - The language of the code block.
+    /// * `code` - The code.
+    /// * `style` - The style of each kind of token.
+    ///
+    /// # Returns
+    /// * `Vec<Line<'static>>` - The lines of the framed code block.
+    fn highlighted_code_block_lines(
+        language: &str,
+        code: &str,
+        style: &dyn Fn(TokenKind) -> Style,
+    ) -> Vec<Line<'static>> {
+        let frame_style = Style::default().add_modifier(Modifier::DIM);
+        let gutter = || Span::styled("│ ", frame_style);
+        let mut lines = vec![Line::styled(format!("┌─ {} ─", language), frame_style)];
+        let mut current_line = Line::from(gutter());
+        for token in tokenize(language, code) {
+            for (i, part) in token.text.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(std::mem::replace(&mut current_line, Line::from(gutter())));
+                }
+                if !part.is_empty() {
+                    current_line
+                        .spans
+                        .push(Span::styled(part.to_string(), style(token.kind)));
+                }
+            }
+        }
+        lines.push(current_line);
+        lines.push(Line::styled("└──", frame_style));
+        lines
+    }
+
+    fn format_message_content_in(
+        message: &FormattedText,
+        highlight: Option<&AppContext>,
+    ) -> Vec<Line<'static>> {
         let text = &message.text;
         let entities = &message.entities;
 
@@ -289,39 +1539,42 @@ impl MessageEntry {
             return Self::from_span_to_lines(Span::raw(text));
         }
 
+        // The highlighted code blocks are made of several spans on each line,
+        // so they are added as lines, after the spans before them.
+        let mut lines = Vec::new();
         let mut message_vec = Vec::new();
         entities.iter().for_each(|e| {
             let offset = e.offset as usize;
             let length = e.length as usize;
-            message_vec.push(Span::raw(text.chars().take(offset).collect::<String>()));
+            message_vec.push(Span::raw(utf16_slice(text, 0, offset)));
             match &e.r#type {
                 tdlib_rs::enums::TextEntityType::Italic => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::ITALIC),
                     ));
                 }
                 tdlib_rs::enums::TextEntityType::Bold => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::BOLD),
                     ));
                 }
                 tdlib_rs::enums::TextEntityType::Underline => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::UNDERLINED),
                     ));
                 }
                 tdlib_rs::enums::TextEntityType::Strikethrough => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::CROSSED_OUT),
                     ));
                 }
                 tdlib_rs::enums::TextEntityType::Url => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::UNDERLINED),
                     ));
                 }
@@ -333,25 +1586,25 @@ impl MessageEntry {
                 }
                 tdlib_rs::enums::TextEntityType::EmailAddress => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::UNDERLINED),
                     ));
                 }
                 tdlib_rs::enums::TextEntityType::Mention => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::BOLD),
+                        entity_text(message, e),
+                        Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
                     ));
                 }
                 tdlib_rs::enums::TextEntityType::Hashtag => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::BOLD),
+                        entity_text(message, e),
+                        Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
                     ));
                 }
                 tdlib_rs::enums::TextEntityType::PhoneNumber => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::UNDERLINED),
                     ));
                 }
@@ -364,37 +1617,59 @@ impl MessageEntry {
                 }
                 tdlib_rs::enums::TextEntityType::Code => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::DIM),
                     ));
                 }
                 tdlib_rs::enums::TextEntityType::Pre => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        frame_code_block(&CodeBlock {
+                            language: None,
+                            content: entity_text(message, e),
+                        }),
                         Style::default().add_modifier(Modifier::DIM),
                     ));
                 }
-                tdlib_rs::enums::TextEntityType::PreCode(_pre_code) => {
-                    message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
-                        Style::default().add_modifier(Modifier::DIM),
-                    ));
+                tdlib_rs::enums::TextEntityType::PreCode(pre_code) => {
+                    match highlight.filter(|_| is_supported(&pre_code.language)) {
+                        Some(app_context) => {
+                            lines.extend(
+                                message_vec
+                                    .drain(..)
+                                    .filter(|s: &Span| !s.content.is_empty())
+                                    .flat_map(Self::from_span_to_lines),
+                            );
+                            lines.extend(Self::highlighted_code_block_lines(
+                                &pre_code.language,
+                                &entity_text(message, e),
+                                &|kind: TokenKind| kind.style(app_context),
+                            ));
+                        }
+                        None => message_vec.push(Span::styled(
+                            frame_code_block(&CodeBlock {
+                                language: Some(pre_code.language.clone())
+                                    .filter(|language| !language.is_empty()),
+                                content: entity_text(message, e),
+                            }),
+                            Style::default().add_modifier(Modifier::DIM),
+                        )),
+                    }
                 }
                 tdlib_rs::enums::TextEntityType::Cashtag => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::BOLD),
                     ));
                 }
                 tdlib_rs::enums::TextEntityType::BankCardNumber => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::UNDERLINED),
                     ));
                 }
                 tdlib_rs::enums::TextEntityType::BlockQuote => {
                     message_vec.push(Span::styled(
-                        text.chars().skip(offset).take(length).collect::<String>(),
+                        entity_text(message, e),
                         Style::default().add_modifier(Modifier::DIM),
                     ));
                 }
@@ -403,12 +1678,63 @@ impl MessageEntry {
                 tdlib_rs::enums::TextEntityType::CustomEmoji(_) => {}
                 tdlib_rs::enums::TextEntityType::BotCommand => {}
             }
-            message_vec.push(Span::raw(
-                text.chars().skip(offset + length).collect::<String>(),
-            ));
+            message_vec.push(Span::raw(utf16_slice(text, offset + length, usize::MAX)));
         });
 
-        Self::from_spans_to_lines(message_vec)
+        if lines.is_empty() {
+            return Self::from_spans_to_lines(message_vec);
+        }
+        lines.extend(
+            message_vec
+                .into_iter()
+                .filter(|s| !s.content.is_empty())
+                .flat_map(Self::from_span_to_lines),
+        );
+        lines
+    }
+}
+#[cfg(test)]
+impl MessageEntry {
+    /// Create an empty message with the given id, it is used to build
+    /// synthetic chats in the tests.
+    pub fn with_id(id: i64) -> Self {
+        Self {
+            id,
+            sender_id: TdMessageSender::User(1),
+            message_content: vec![],
+            highlighted_text: None,
+            reply_to: None,
+            timestamp: DateTimeEntry::default(),
+            is_edited: false,
+            reply_markup: None,
+            media_album_id: 0,
+            message_thread_id: 0,
+            caption: vec![],
+            send_failed: false,
+            send_error: None,
+            translation: None,
+            translation_shown: false,
+            reactions: vec![],
+            is_channel_post: false,
+            view_count: 0,
+            comment_count: None,
+            author_signature: String::new(),
+            entities: vec![],
+            code_blocks: vec![],
+            links: vec![],
+            unsupported: None,
+            location: None,
+            quiz: None,
+            document: None,
+        }
+    }
+
+    /// Create an empty message with the given id sent by the given user.
+    pub fn with_sender(id: i64, user_id: i64) -> Self {
+        Self {
+            sender_id: TdMessageSender::User(user_id),
+            ..Self::with_id(id)
+        }
     }
 }
 impl From<&tdlib_rs::types::Message> for MessageEntry {
@@ -420,6 +1746,7 @@ impl From<&tdlib_rs::types::Message> for MessageEntry {
                 MessageSender::Chat(chat) => TdMessageSender::Chat(chat.chat_id),
             },
             message_content: Self::message_content_lines(&message.content),
+            highlighted_text: Self::message_highlighted_text(&message.content),
             reply_to: match &message.reply_to {
                 Some(reply) => match reply {
                     MessageReplyTo::Message(message) => {
@@ -433,6 +1760,608 @@ impl From<&tdlib_rs::types::Message> for MessageEntry {
                 timestamp: message.date,
             },
             is_edited: message.edit_date != 0,
+            reply_markup: message
+                .reply_markup
+                .as_ref()
+                .and_then(TdReplyMarkup::from_reply_markup),
+            media_album_id: message.media_album_id,
+            message_thread_id: message.message_thread_id,
+            caption: Self::message_caption_lines(&message.content),
+            send_failed: false,
+            send_error: SendError::from_sending_state(message.sending_state.as_ref()),
+            translation: None,
+            translation_shown: false,
+            reactions: Self::message_reactions(message.interaction_info.as_ref()),
+            is_channel_post: message.is_channel_post,
+            view_count: message
+                .interaction_info
+                .as_ref()
+                .map_or(0, |info| info.view_count),
+            comment_count: Self::message_comment_count(message.interaction_info.as_ref()),
+            author_signature: message.author_signature.clone(),
+            entities: Self::message_entities(&message.content),
+            code_blocks: Self::message_code_blocks(&message.content),
+            links: Self::message_links(&message.content),
+            unsupported: Self::message_unsupported(&message.content),
+            location: Self::message_location(&message.content),
+            quiz: Self::message_quiz(&message.content),
+            document: Self::message_document(&message.content),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::Action;
+    use crate::configs::raw::app_raw::{ReplyPreviewMode, TimestampGranularity};
+    use crate::tg::message_entry::{
+        attach_translation, code_blocks, entity_text, file_type_glyph, format_count,
+        format_file_size, format_with_granularity, frame_code_block, group_album,
+        live_location_remaining, render_unsupported, reply_preview_lines, text_links,
+        top_reactions, AlbumGroup, CodeBlock, DateTimeEntry, MessageEntry, MessageReactionEntry,
+        QuizEntry,
+    };
+    use crate::tg::syntax_highlight::TokenKind;
+    use crate::tg::td_enums::TdTextEntity;
+    use chrono::{Local, TimeZone};
+    use ratatui::style::{Modifier, Style};
+    use ratatui::text::{Line, Span};
+    use tdlib_rs::enums::{MessageContent, TextEntityType};
+    use tdlib_rs::types::{
+        FormattedText, TextEntity, TextEntityTypePreCode, TextEntityTypeTextUrl,
+    };
+
+    fn message_entry(id: i64, media_album_id: i64) -> MessageEntry {
+        MessageEntry {
+            media_album_id,
+            ..MessageEntry::with_id(id)
+        }
+    }
+
+    #[test]
+    fn test_group_album_by_album_id() {
+        let messages = vec![
+            message_entry(6, 0),
+            message_entry(5, 100),
+            message_entry(4, 100),
+            message_entry(3, 100),
+            message_entry(2, 200),
+            message_entry(1, 200),
+        ];
+        assert_eq!(
+            group_album(&messages),
+            vec![
+                AlbumGroup {
+                    media_album_id: 0,
+                    indices: vec![0],
+                },
+                AlbumGroup {
+                    media_album_id: 100,
+                    indices: vec![1, 2, 3],
+                },
+                AlbumGroup {
+                    media_album_id: 200,
+                    indices: vec![4, 5],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_album_without_albums() {
+        let messages = vec![message_entry(2, 0), message_entry(1, 0)];
+        assert_eq!(
+            group_album(&messages),
+            vec![
+                AlbumGroup {
+                    media_album_id: 0,
+                    indices: vec![0],
+                },
+                AlbumGroup {
+                    media_album_id: 0,
+                    indices: vec![1],
+                },
+            ]
+        );
+        assert!(group_album(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_group_album_not_consecutive() {
+        let messages = vec![
+            message_entry(3, 100),
+            message_entry(2, 0),
+            message_entry(1, 100),
+        ];
+        assert_eq!(group_album(&messages).len(), 3);
+    }
+
+    #[test]
+    fn test_entity_action_by_index() {
+        let message = MessageEntry {
+            entities: vec![
+                TdTextEntity::Hashtag("#rust".to_string()),
+                TdTextEntity::Mention("durov".to_string()),
+            ],
+            ..MessageEntry::with_id(1)
+        };
+        assert_eq!(
+            message.entity_action(0),
+            Some(Action::SearchHashtag("#rust".to_string()))
+        );
+        assert_eq!(
+            message.entity_action(1),
+            Some(Action::OpenUsername("durov".to_string()))
+        );
+        assert_eq!(message.entity_action(2), None);
+    }
+
+    fn code_text() -> FormattedText {
+        // An inline code, a Rust block and an unlabelled block.
+        FormattedText {
+            text: "See x: fn main() {}\nend ls -la".to_string(),
+            entities: vec![
+                TextEntity {
+                    offset: 4,
+                    length: 1,
+                    r#type: TextEntityType::Code,
+                },
+                TextEntity {
+                    offset: 7,
+                    length: 16,
+                    r#type: TextEntityType::PreCode(TextEntityTypePreCode {
+                        language: "rust".to_string(),
+                    }),
+                },
+                TextEntity {
+                    offset: 24,
+                    length: 6,
+                    r#type: TextEntityType::Pre,
+                },
+            ],
         }
     }
+
+    #[test]
+    fn test_attach_translation() {
+        let mut messages = vec![
+            MessageEntry::with_id(3),
+            MessageEntry::with_id(2),
+            MessageEntry::with_id(1),
+        ];
+        assert!(attach_translation(&mut messages, 2, "Hello".to_string()));
+        assert_eq!(messages[0].translation(), None);
+        assert_eq!(messages[1].translation(), Some("Hello"));
+        assert_eq!(messages[2].translation(), None);
+        // The message is not loaded anymore.
+        assert!(!attach_translation(&mut messages, 4, "Bye".to_string()));
+
+        assert!(messages[1].toggle_translation());
+        assert!(!messages[1].translation_shown);
+        assert!(messages[1].toggle_translation());
+        assert!(messages[1].translation_shown);
+        assert!(!messages[0].toggle_translation());
+    }
+
+    #[test]
+    fn test_reply_preview_lines_per_mode() {
+        let quoted = vec![Line::from("See you at 8"), Line::from("Bring the cake")];
+        let texts = |mode: ReplyPreviewMode| {
+            reply_preview_lines(mode, Span::raw("Alice"), quoted.clone(), Style::default())
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(
+            texts(ReplyPreviewMode::Inline),
+            vec!["↩️ Reply to: Alice", "See you at 8", "Bring the cake"]
+        );
+        assert_eq!(texts(ReplyPreviewMode::Compact), vec!["↩ Alice"]);
+    }
+
+    #[test]
+    fn test_big_emoji_lines() {
+        let entry = |text: &str| MessageEntry {
+            message_content: vec![Line::from(text.to_string())],
+            ..MessageEntry::with_id(1)
+        };
+        let lines = entry(" 👍🏽🔥 ")
+            .get_big_emoji_lines(Style::default())
+            .unwrap();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].to_string(), " 👍🏽  🔥 ");
+        assert!(entry("ok 👍")
+            .get_big_emoji_lines(Style::default())
+            .is_none());
+    }
+
+    #[test]
+    fn test_highlighted_code_block_lines() {
+        let style = |kind: TokenKind| match kind {
+            TokenKind::Keyword => Style::default().add_modifier(Modifier::BOLD),
+            _ => Style::default(),
+        };
+        let lines = MessageEntry::highlighted_code_block_lines("rust", "fn main() {}\nend", &style);
+        let texts = lines
+            .iter()
+            .map(|line| line.iter().map(|s| s.content.clone()).collect::<String>())
+            .collect::<Vec<String>>();
+        assert_eq!(texts, vec!["┌─ rust ─", "│ fn main() {}", "│ end", "└──"]);
+        // The keyword is styled apart from the rest of the line.
+        assert_eq!(lines[1].spans[1].content, "fn");
+        assert_eq!(
+            lines[1].spans[1].style,
+            Style::default().add_modifier(Modifier::BOLD)
+        );
+    }
+
+    #[test]
+    fn test_code_blocks_identification() {
+        assert_eq!(
+            code_blocks(&code_text()),
+            vec![
+                CodeBlock {
+                    language: Some("rust".to_string()),
+                    content: "fn main() {}\nend".to_string(),
+                },
+                CodeBlock {
+                    language: None,
+                    content: "ls -la".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            frame_code_block(&code_blocks(&code_text())[0]),
+            "┌─ rust ─\n│ fn main() {}\n│ end\n└──"
+        );
+    }
+
+    #[test]
+    fn test_text_links() {
+        let text = FormattedText {
+            text: "Docs at https://docs.rs and the book, mail me@example.com or see http://a.b"
+                .to_string(),
+            entities: vec![
+                TextEntity {
+                    offset: 8,
+                    length: 15,
+                    r#type: TextEntityType::Url,
+                },
+                TextEntity {
+                    offset: 32,
+                    length: 4,
+                    r#type: TextEntityType::TextUrl(TextEntityTypeTextUrl {
+                        url: "https://doc.rust-lang.org/book".to_string(),
+                    }),
+                },
+                TextEntity {
+                    offset: 43,
+                    length: 14,
+                    r#type: TextEntityType::EmailAddress,
+                },
+                TextEntity {
+                    offset: 65,
+                    length: 10,
+                    r#type: TextEntityType::Url,
+                },
+            ],
+        };
+        assert_eq!(
+            text_links(&text),
+            vec![
+                "https://docs.rs".to_string(),
+                "https://doc.rust-lang.org/book".to_string(),
+                "http://a.b".to_string(),
+            ]
+        );
+        assert!(text_links(&code_text()).is_empty());
+    }
+
+    #[test]
+    fn test_entity_text_counts_utf16_code_units() {
+        // The emoji before the mention takes two UTF-16 code units.
+        let text = FormattedText {
+            text: "👍 @durov é #rust".to_string(),
+            entities: vec![],
+        };
+        let entity = |offset, length| TextEntity {
+            offset,
+            length,
+            r#type: TextEntityType::Mention,
+        };
+        assert_eq!(entity_text(&text, &entity(3, 6)), "@durov");
+        assert_eq!(entity_text(&text, &entity(12, 5)), "#rust");
+        assert_eq!(entity_text(&text, &entity(0, 2)), "👍");
+        assert_eq!(entity_text(&text, &entity(30, 2)), "");
+    }
+
+    #[test]
+    fn test_code_block_content_by_index() {
+        let message = MessageEntry {
+            code_blocks: code_blocks(&code_text()),
+            ..MessageEntry::with_id(1)
+        };
+        assert_eq!(message.code_block_content(0), Some("fn main() {}\nend"));
+        assert_eq!(message.code_block_content(1), Some("ls -la"));
+        assert_eq!(message.code_block_content(2), None);
+    }
+
+    #[test]
+    fn test_render_unsupported_content_types() {
+        for (content, placeholder) in [
+            (
+                MessageContent::MessageExpiredPhoto,
+                "[unsupported: expired photo]",
+            ),
+            (
+                MessageContent::MessageExpiredVideo,
+                "[unsupported: expired video]",
+            ),
+            (
+                MessageContent::MessageUnsupported,
+                "[unsupported: unsupported]",
+            ),
+        ] {
+            let content_type = MessageEntry::message_unsupported(&content).unwrap();
+            assert_eq!(render_unsupported(&content_type), placeholder);
+        }
+    }
+
+    #[test]
+    fn test_convert_time_in_sender_timezone() {
+        // 2024-01-02 10:30 UTC.
+        let timestamp = 1704191400;
+        assert_eq!(
+            DateTimeEntry::convert_time_in(
+                timestamp,
+                Some(2 * 3600),
+                timestamp,
+                TimestampGranularity::Minute
+            ),
+            "12:30 +02:00"
+        );
+        assert_eq!(
+            DateTimeEntry::convert_time_in(
+                timestamp,
+                Some(-5 * 3600),
+                timestamp,
+                TimestampGranularity::Minute
+            ),
+            "05:30 -05:00"
+        );
+        // The day changes in the time zone of the sender.
+        assert_eq!(
+            DateTimeEntry::convert_time_in(
+                timestamp,
+                Some(14 * 3600),
+                timestamp + 2 * 86400,
+                TimestampGranularity::Minute
+            ),
+            "2024-01-03 00:30 +14:00"
+        );
+    }
+
+    #[test]
+    fn test_convert_time_in_unknown_timezone() {
+        let timestamp = 1704191400;
+        let local = DateTimeEntry::format_datetime(
+            Local.timestamp_opt(timestamp as i64, 0).unwrap(),
+            Local.timestamp_opt(timestamp as i64, 0).unwrap(),
+            TimestampGranularity::Minute,
+        );
+        assert_eq!(
+            DateTimeEntry::convert_time_in(
+                timestamp,
+                None,
+                timestamp,
+                TimestampGranularity::Minute
+            ),
+            format!("{} (local)", local)
+        );
+        // An offset that is out of range falls back to the local time too.
+        assert_eq!(
+            DateTimeEntry::convert_time_in(
+                timestamp,
+                Some(100000),
+                timestamp,
+                TimestampGranularity::Minute
+            ),
+            format!("{} (local)", local)
+        );
+    }
+
+    #[test]
+    fn test_format_with_granularity() {
+        // 2024-01-02 10:30:45 UTC, the seconds do not depend on the time zone.
+        let timestamp = 1704191445;
+        let minute = format_with_granularity(timestamp, TimestampGranularity::Minute);
+        assert_eq!(minute.len(), "2024-01-02 10:30".len());
+        assert!(minute.starts_with("2024-01-0"));
+        let second = format_with_granularity(timestamp, TimestampGranularity::Second);
+        assert_eq!(second.len(), "2024-01-02 10:30:45".len());
+        assert!(second.starts_with(&minute) && second.ends_with(":45"));
+        let date_time = format_with_granularity(timestamp, TimestampGranularity::DateTime);
+        assert_eq!(date_time, second);
+
+        // Only the `DateTime` granularity shows the date of today.
+        let now = chrono::Utc::now().timestamp();
+        assert_eq!(
+            format_with_granularity(now, TimestampGranularity::Minute).len(),
+            "10:30".len()
+        );
+        assert_eq!(
+            format_with_granularity(now, TimestampGranularity::Second).len(),
+            "10:30:45".len()
+        );
+        assert_eq!(
+            format_with_granularity(now, TimestampGranularity::DateTime).len(),
+            "2024-01-02 10:30:45".len()
+        );
+    }
+
+    #[test]
+    fn test_live_location_remaining() {
+        // A static location is never live.
+        assert_eq!(live_location_remaining(100, 0, 100), None);
+        // An active live location.
+        assert_eq!(live_location_remaining(100, 900, 400), Some(600));
+        // An expired live location is rendered as a static one.
+        assert_eq!(live_location_remaining(100, 900, 1000), None);
+        assert_eq!(live_location_remaining(100, 900, 5000), None);
+        // A live location shared until it is stopped never expires.
+        assert_eq!(
+            live_location_remaining(100, 0x7FFFFFFF, i32::MAX),
+            Some(i32::MAX)
+        );
+    }
+
+    fn reaction(emoji: &str, total_count: i32) -> MessageReactionEntry {
+        MessageReactionEntry {
+            emoji: emoji.to_string(),
+            total_count,
+            is_chosen: false,
+        }
+    }
+
+    #[test]
+    fn test_top_reactions_cap() {
+        let reactions = vec![
+            reaction("👍", 3),
+            reaction("🔥", 10),
+            reaction("😂", 1),
+            reaction("❤", 3),
+        ];
+        let (shown, hidden) = top_reactions(&reactions, 2);
+        assert_eq!(shown, vec![reaction("🔥", 10), reaction("👍", 3)]);
+        assert_eq!(hidden, 2);
+        let (shown, hidden) = top_reactions(&reactions, 4);
+        assert_eq!(shown.len(), 4);
+        assert_eq!(hidden, 0);
+    }
+
+    #[test]
+    fn test_top_reactions_show_all() {
+        let reactions = vec![reaction("👍", 1), reaction("🔥", 2)];
+        let (shown, hidden) = top_reactions(&reactions, 0);
+        assert_eq!(shown, vec![reaction("🔥", 2), reaction("👍", 1)]);
+        assert_eq!(hidden, 0);
+        assert_eq!(top_reactions(&[], 3), (vec![], 0));
+    }
+
+    #[test]
+    fn test_quiz_explanation_gated_on_answer() {
+        let mut quiz = QuizEntry {
+            explanation: "Rust 1.0 was released in 2015".to_string(),
+            answered: false,
+        };
+        assert_eq!(quiz.explanation(), None);
+        quiz.answered = true;
+        assert_eq!(quiz.explanation(), Some("Rust 1.0 was released in 2015"));
+        quiz.explanation = String::new();
+        assert_eq!(quiz.explanation(), None);
+    }
+
+    #[test]
+    fn test_file_type_glyph_from_mime() {
+        assert_eq!(file_type_glyph("image/png", "photo"), '🖼');
+        assert_eq!(file_type_glyph("audio/mpeg", "song.bin"), '🎵');
+        assert_eq!(file_type_glyph("video/mp4", "clip"), '🎬');
+        assert_eq!(file_type_glyph("application/pdf", "paper"), '📕');
+        assert_eq!(file_type_glyph("application/zip", "backup"), '📦');
+        assert_eq!(file_type_glyph("text/plain", "notes"), '📝');
+    }
+
+    #[test]
+    fn test_file_type_glyph_from_extension() {
+        assert_eq!(file_type_glyph("", "Report.PDF"), '📕');
+        assert_eq!(
+            file_type_glyph("application/octet-stream", "src.tar.gz"),
+            '📦'
+        );
+        assert_eq!(file_type_glyph("", "budget.xlsx"), '📊');
+        assert_eq!(file_type_glyph("", "slides.pptx"), '📽');
+        assert_eq!(file_type_glyph("", "main.rs"), '📝');
+    }
+
+    #[test]
+    fn test_file_type_glyph_unknown() {
+        assert_eq!(
+            file_type_glyph("application/octet-stream", "firmware.bin"),
+            '📄'
+        );
+        assert_eq!(file_type_glyph("", "README"), '📄');
+        assert_eq!(file_type_glyph("", ""), '📄');
+    }
+
+    #[test]
+    fn test_format_file_size() {
+        assert_eq!(format_file_size(0), "0 B");
+        assert_eq!(format_file_size(1023), "1023 B");
+        assert_eq!(format_file_size(1536), "1.5 KB");
+        assert_eq!(format_file_size(5 * 1024 * 1024), "5.0 MB");
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1_000), "1K");
+        assert_eq!(format_count(1_234), "1.2K");
+        assert_eq!(format_count(1_299), "1.2K");
+        assert_eq!(format_count(56_700), "56.7K");
+        assert_eq!(format_count(999_999), "999.9K");
+        assert_eq!(format_count(1_000_000), "1M");
+        assert_eq!(format_count(3_450_000), "3.4M");
+        assert_eq!(format_count(2_000_000_000), "2B");
+    }
+
+    #[test]
+    fn test_signature_shown_only_when_enabled() {
+        let signed = MessageEntry {
+            is_channel_post: true,
+            author_signature: "Pavel Durov".to_string(),
+            ..MessageEntry::with_id(1)
+        };
+        assert_eq!(signed.signature(true), Some("Pavel Durov"));
+        assert_eq!(signed.signature(false), None);
+
+        let unsigned = MessageEntry {
+            is_channel_post: true,
+            ..MessageEntry::with_id(2)
+        };
+        assert_eq!(unsigned.signature(true), None);
+        assert_eq!(unsigned.signature(false), None);
+    }
+
+    #[test]
+    fn test_comment_count_only_with_discussion_group() {
+        let discussed = MessageEntry {
+            is_channel_post: true,
+            comment_count: Some(3),
+            ..MessageEntry::with_id(1)
+        };
+        assert_eq!(
+            discussed.get_comment_count_lines(Style::default()),
+            vec![Line::styled(
+                "💬 3 comments",
+                Style::default().add_modifier(Modifier::DIM)
+            )]
+        );
+
+        let not_discussed = MessageEntry {
+            is_channel_post: true,
+            ..MessageEntry::with_id(2)
+        };
+        assert!(not_discussed
+            .get_comment_count_lines(Style::default())
+            .is_empty());
+        // The replies of the messages in a group are not comments.
+        let not_a_post = MessageEntry {
+            comment_count: Some(3),
+            ..MessageEntry::with_id(3)
+        };
+        assert!(not_a_post
+            .get_comment_count_lines(Style::default())
+            .is_empty());
+    }
 }
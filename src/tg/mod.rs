@@ -1,5 +1,17 @@
+pub mod big_emoji;
+pub mod chat_folders;
+pub mod compose_placeholders;
+pub mod draft_reminders;
+pub mod edit_history;
+pub mod formatting_preview;
+pub mod idle_presence;
 pub mod message_entry;
 pub mod ordered_chat;
+pub mod pending_operations;
+pub mod request_gate;
+pub mod send_error;
+pub mod snoozed_chats;
+pub mod syntax_highlight;
 pub mod td_enums;
 pub mod tg_backend;
 pub mod tg_context;
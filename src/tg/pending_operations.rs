@@ -0,0 +1,79 @@
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// `PendingOperations` keeps track of the long operations that are running,
+/// they are shown with a spinner in the status bar until they finish.
+/// The same operation can be running more than once at the same time, e.g.
+/// two searches, so each start is matched by its own finish.
+pub struct PendingOperations {
+    /// The labels of the running operations, from the oldest one.
+    operations: Vec<String>,
+}
+/// Implementation of the `PendingOperations` struct.
+impl PendingOperations {
+    /// Track an operation that started.
+    ///
+    /// # Arguments
+    /// * `label` - The label of the operation, e.g. `Loading history`.
+    pub fn start(&mut self, label: String) {
+        self.operations.push(label);
+    }
+
+    /// Stop tracking an operation that finished.
+    /// A finish without a matching start is ignored.
+    ///
+    /// # Arguments
+    /// * `label` - The label of the operation.
+    pub fn finish(&mut self, label: &str) {
+        if let Some(i) = self.operations.iter().rposition(|op| op == label) {
+            self.operations.remove(i);
+        }
+    }
+
+    /// Get the label of the most recent running operation.
+    pub fn current(&self) -> Option<&str> {
+        self.operations.last().map(String::as_str)
+    }
+
+    /// Get the number of the running operations.
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::pending_operations::PendingOperations;
+
+    #[test]
+    fn test_overlapping_operations() {
+        let mut pending = PendingOperations::default();
+        assert_eq!(pending.len(), 0);
+        assert_eq!(pending.current(), None);
+
+        pending.start("Loading history".to_string());
+        pending.start("Searching".to_string());
+        pending.start("Searching".to_string());
+        assert_eq!(pending.len(), 3);
+        assert_eq!(pending.current(), Some("Searching"));
+
+        pending.finish("Searching");
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending.current(), Some("Searching"));
+
+        pending.finish("Searching");
+        assert_eq!(pending.current(), Some("Loading history"));
+
+        pending.finish("Loading history");
+        assert_eq!(pending.len(), 0);
+    }
+
+    #[test]
+    fn test_finish_without_start() {
+        let mut pending = PendingOperations::default();
+        pending.start("Downloading".to_string());
+        pending.finish("Searching");
+        assert_eq!(pending.len(), 1);
+        pending.finish("Downloading");
+        pending.finish("Downloading");
+        assert_eq!(pending.len(), 0);
+    }
+}
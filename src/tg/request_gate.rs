@@ -0,0 +1,139 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
+use tokio::sync::Notify;
+
+#[derive(Debug, Default)]
+/// `RequestGate` bounds the number of TDLib requests in flight, the other
+/// requests wait until one of them completes.
+/// The requests of the main loop pass the gate as well as the downloads and
+/// the resends, that are spawned on their own tasks and run alongside them.
+pub struct RequestGate {
+    /// The number of requests in flight.
+    in_flight: Mutex<usize>,
+    /// The maximum number of requests in flight, `0` means no limit.
+    max: AtomicUsize,
+    /// Notified when a request completes or the limit changes.
+    released: Notify,
+}
+/// Implementation of the `RequestGate` struct.
+impl RequestGate {
+    /// Set the maximum number of requests in flight, the waiting requests
+    /// are started if the new limit allows it.
+    ///
+    /// # Arguments
+    /// * `max` - The maximum number of requests in flight, `0` means no
+    ///   limit.
+    pub fn set_max(&self, max: usize) {
+        self.max.store(max, Ordering::Relaxed);
+        self.released.notify_waiters();
+    }
+
+    /// Start a request if the limit allows it.
+    ///
+    /// # Returns
+    /// * `Option<RequestPermit>` - The permit of the request, it must be kept
+    ///   until the request completes, or `None` when the request has to wait.
+    pub fn try_acquire(&self) -> Option<RequestPermit<'_>> {
+        let max = self.max.load(Ordering::Relaxed);
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if max != 0 && *in_flight >= max {
+            return None;
+        }
+        *in_flight += 1;
+        Some(RequestPermit { gate: self })
+    }
+
+    /// Wait until the limit allows to start a request.
+    ///
+    /// # Returns
+    /// * `RequestPermit` - The permit of the request, it must be kept until
+    ///   the request completes.
+    pub async fn acquire(&self) -> RequestPermit<'_> {
+        loop {
+            // The notification is registered before checking the limit, so a
+            // request completed in between is not missed.
+            let released = self.released.notified();
+            if let Some(permit) = self.try_acquire() {
+                return permit;
+            }
+            released.await;
+        }
+    }
+}
+
+#[derive(Debug)]
+/// `RequestPermit` is held by a request in flight, the request is completed
+/// when it is dropped.
+pub struct RequestPermit<'a> {
+    /// The gate the request passed.
+    gate: &'a RequestGate,
+}
+/// Implement the `Drop` trait for `RequestPermit`.
+impl Drop for RequestPermit<'_> {
+    fn drop(&mut self) {
+        *self.gate.in_flight.lock().unwrap() -= 1;
+        self.gate.released.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::request_gate::RequestGate;
+
+    #[test]
+    fn test_excess_requests_wait() {
+        let gate = RequestGate::default();
+        gate.set_max(2);
+        let first = gate.try_acquire();
+        let second = gate.try_acquire();
+        assert!(first.is_some() && second.is_some());
+        // The third request waits instead of being sent.
+        assert!(gate.try_acquire().is_none());
+
+        drop(first);
+        let third = gate.try_acquire();
+        assert!(third.is_some());
+        assert!(gate.try_acquire().is_none());
+
+        // Raising the limit lets the waiting requests through.
+        gate.set_max(3);
+        assert!(gate.try_acquire().is_some());
+        drop(second);
+        drop(third);
+        let permits = (0..5).map_while(|_| gate.try_acquire()).collect::<Vec<_>>();
+        assert_eq!(permits.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_then_resumes() {
+        let gate = RequestGate::default();
+        gate.set_max(1);
+        let first = gate.try_acquire();
+        let second = gate.acquire();
+        tokio::pin!(second);
+        // The second request waits while the first one is in flight.
+        assert!(futures::poll!(&mut second).is_pending());
+        assert!(futures::poll!(&mut second).is_pending());
+
+        // It resumes once the first request completes.
+        drop(first);
+        let second = match futures::poll!(&mut second) {
+            std::task::Poll::Ready(permit) => permit,
+            std::task::Poll::Pending => panic!("The waiting request did not resume"),
+        };
+        assert!(gate.try_acquire().is_none());
+        drop(second);
+        assert!(gate.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_no_limit() {
+        let gate = RequestGate::default();
+        let permits = (0..100)
+            .map_while(|_| gate.try_acquire())
+            .collect::<Vec<_>>();
+        assert_eq!(permits.len(), 100);
+    }
+}
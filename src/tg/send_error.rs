@@ -0,0 +1,139 @@
+use tdlib_rs::enums::MessageSendingState;
+
+/// The maximum number of characters of the reason shown under a message that
+/// failed to send, the full error is shown on request.
+const NOTE_MAX_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// `SendErrorKind` tells whether sending the message again may succeed.
+pub enum SendErrorKind {
+    /// The failure depends on the network or on the load of the servers, the
+    /// message can be sent again later.
+    Transient,
+    /// The message is refused, sending it again fails the same way.
+    Permanent,
+}
+
+/// Classify the error returned by TDLib when a message fails to send.
+/// The flood waits, the timeouts and the internal errors of the servers are
+/// transient, all the other errors are permanent.
+///
+/// # Arguments
+/// * `code` - The code of the error.
+/// * `msg` - The message of the error.
+///
+/// # Returns
+/// * `SendErrorKind` - The kind of the error.
+pub fn classify_send_error(code: i32, msg: &str) -> SendErrorKind {
+    let msg = msg.to_uppercase();
+    if code == 429
+        || code >= 500
+        || msg.contains("FLOOD_WAIT")
+        || msg.contains("TIMEOUT")
+        || msg.contains("NETWORK")
+    {
+        SendErrorKind::Transient
+    } else {
+        SendErrorKind::Permanent
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// `SendError` is the error of a message that failed to send.
+pub struct SendError {
+    /// The code of the error.
+    pub code: i32,
+    /// The message of the error.
+    pub message: String,
+}
+/// Implementation of the `SendError` struct.
+impl SendError {
+    /// Get the error of a message from its sending state.
+    ///
+    /// # Arguments
+    /// * `sending_state` - The sending state of the message.
+    ///
+    /// # Returns
+    /// * `Option<Self>` - The error, or `None` when the message did not fail
+    ///   to send.
+    pub fn from_sending_state(sending_state: Option<&MessageSendingState>) -> Option<Self> {
+        match sending_state? {
+            MessageSendingState::Failed(failed) => Some(SendError {
+                code: failed.error.code,
+                message: failed.error.message.clone(),
+            }),
+            MessageSendingState::Pending(_) => None,
+        }
+    }
+
+    /// Get the kind of the error.
+    pub fn kind(&self) -> SendErrorKind {
+        classify_send_error(self.code, &self.message)
+    }
+
+    /// Get the short reason shown under the message.
+    pub fn note(&self) -> String {
+        if self.message.chars().count() <= NOTE_MAX_CHARS {
+            return self.message.clone();
+        }
+        let mut note: String = self.message.chars().take(NOTE_MAX_CHARS - 1).collect();
+        note.push('…');
+        note
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::send_error::{classify_send_error, SendError, SendErrorKind};
+
+    #[test]
+    fn test_classify_send_error_transient() {
+        assert_eq!(
+            classify_send_error(429, "Too Many Requests: retry after 12"),
+            SendErrorKind::Transient
+        );
+        assert_eq!(
+            classify_send_error(400, "FLOOD_WAIT_30"),
+            SendErrorKind::Transient
+        );
+        assert_eq!(
+            classify_send_error(500, "Internal Server Error"),
+            SendErrorKind::Transient
+        );
+        assert_eq!(
+            classify_send_error(-1, "Request timeout"),
+            SendErrorKind::Transient
+        );
+    }
+
+    #[test]
+    fn test_classify_send_error_permanent() {
+        assert_eq!(
+            classify_send_error(400, "MESSAGE_TOO_LONG"),
+            SendErrorKind::Permanent
+        );
+        assert_eq!(
+            classify_send_error(403, "CHAT_WRITE_FORBIDDEN"),
+            SendErrorKind::Permanent
+        );
+        assert_eq!(
+            classify_send_error(400, "USER_IS_BLOCKED"),
+            SendErrorKind::Permanent
+        );
+    }
+
+    #[test]
+    fn test_note_is_truncated() {
+        let error = SendError {
+            code: 400,
+            message: "Bad Request: the message is longer than the allowed length".to_string(),
+        };
+        assert_eq!(error.note().chars().count(), 40);
+        assert!(error.note().ends_with('…'));
+        let error = SendError {
+            code: 403,
+            message: "CHAT_WRITE_FORBIDDEN".to_string(),
+        };
+        assert_eq!(error.note(), "CHAT_WRITE_FORBIDDEN");
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{app_error::AppError, configs, utils::tgt_config_dir};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The name of the file, in the configuration directory, where the snoozed
+/// chats are saved.
+pub const SNOOZED_CHATS_FILENAME: &str = "snoozed_chats.toml";
+
+/// Get the path of the file where the snoozed chats are saved.
+///
+/// # Returns
+/// The path of the file in the default configuration directory.
+pub fn snoozed_chats_path() -> io::Result<PathBuf> {
+    Ok(tgt_config_dir()?.join(SNOOZED_CHATS_FILENAME))
+}
+
+#[derive(Debug, Deserialize)]
+/// The content of the file where the snoozed chats are saved.
+struct SnoozedChatsRaw {
+    /// The wake timestamp of the snoozed chats, by chat id.
+    /// The keys are strings because TOML only allows string keys.
+    snoozed_chats: Option<HashMap<String, i32>>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// `SnoozedChats` keeps track of the chats hidden from the chat list until a
+/// wake time, and of the chats that woke up and are shown at the top of the
+/// list as a reminder.
+pub struct SnoozedChats {
+    /// The wake timestamp of the snoozed chats, by chat id.
+    snoozed: HashMap<i64, i32>,
+    /// The chats that woke up, from the most recent one.
+    woken: Vec<i64>,
+}
+/// Implementation of the `SnoozedChats` struct.
+impl SnoozedChats {
+    /// Load the snoozed chats from the given file.
+    /// The current state is kept when the file does not exist or cannot be
+    /// parsed.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file.
+    pub fn load(&mut self, path: &Path) {
+        if !path.exists() {
+            return;
+        }
+        match configs::deserialize_to_config::<SnoozedChatsRaw>(path) {
+            Ok(raw) => {
+                self.snoozed = raw
+                    .snoozed_chats
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|(chat_id, wake_at)| Some((chat_id.parse().ok()?, wake_at)))
+                    .collect();
+            }
+            Err(e) => tracing::error!("Failed to load the snoozed chats: {e}"),
+        }
+    }
+
+    /// Save the snoozed chats to the given file.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file.
+    ///
+    /// # Returns
+    /// * `Result<(), AppError>` - An Ok result or an error if the file cannot
+    ///   be written.
+    pub fn save(&self, path: &Path) -> Result<(), AppError<()>> {
+        let mut snoozed = self.snoozed.iter().collect::<Vec<_>>();
+        snoozed.sort();
+        let mut content = String::from("[snoozed_chats]\n");
+        for (chat_id, wake_at) in snoozed {
+            // Writing to a `String` cannot fail.
+            let _ = writeln!(content, "\"{}\" = {}", chat_id, wake_at);
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Hide a chat from the chat list until the given time.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `wake_at` - The timestamp, in seconds, when the chat is shown again.
+    /// * `now` - The current timestamp, in seconds.
+    ///
+    /// # Returns
+    /// * `Result<(), AppError>` - An Ok result or an error if the wake time is
+    ///   not in the future.
+    pub fn snooze(&mut self, chat_id: i64, wake_at: i32, now: i32) -> Result<(), AppError<()>> {
+        if wake_at <= now {
+            return Err(AppError::InvalidTimestamp(format!(
+                "{} is not in the future",
+                wake_at
+            )));
+        }
+        self.woken.retain(|id| *id != chat_id);
+        self.snoozed.insert(chat_id, wake_at);
+        Ok(())
+    }
+
+    /// Wake up the chats whose wake time has passed, so that they are shown
+    /// at the top of the chat list.
+    ///
+    /// # Arguments
+    /// * `now` - The current timestamp, in seconds.
+    ///
+    /// # Returns
+    /// * `Vec<i64>` - The chats that woke up, from the earliest wake time.
+    pub fn wake(&mut self, now: i32) -> Vec<i64> {
+        let mut woken = self
+            .snoozed
+            .iter()
+            .filter(|(_, wake_at)| **wake_at <= now)
+            .map(|(chat_id, wake_at)| (*wake_at, *chat_id))
+            .collect::<Vec<(i32, i64)>>();
+        woken.sort();
+        let woken = woken
+            .into_iter()
+            .map(|(_, chat_id)| chat_id)
+            .collect::<Vec<i64>>();
+        for chat_id in woken.iter() {
+            self.snoozed.remove(chat_id);
+            self.woken.insert(0, *chat_id);
+        }
+        woken
+    }
+
+    /// Whether the chat is hidden from the chat list.
+    pub fn is_snoozed(&self, chat_id: i64) -> bool {
+        self.snoozed.contains_key(&chat_id)
+    }
+
+    /// Whether the chat woke up and is shown as a reminder.
+    pub fn is_woken(&self, chat_id: i64) -> bool {
+        self.woken.contains(&chat_id)
+    }
+
+    /// Stop showing the chat as a reminder, e.g. because it has been opened.
+    pub fn dismiss(&mut self, chat_id: i64) {
+        self.woken.retain(|id| *id != chat_id);
+    }
+
+    /// Order the chats of the chat list, the snoozed chats are removed and
+    /// the chats that woke up are moved to the top.
+    ///
+    /// # Arguments
+    /// * `chat_ids` - The chats in the order of the chat list.
+    ///
+    /// # Returns
+    /// * `Vec<i64>` - The chats in the order they are shown.
+    pub fn order(&self, chat_ids: Vec<i64>) -> Vec<i64> {
+        let mut ordered = self
+            .woken
+            .iter()
+            .copied()
+            .filter(|chat_id| chat_ids.contains(chat_id))
+            .collect::<Vec<i64>>();
+        ordered.extend(
+            chat_ids
+                .into_iter()
+                .filter(|chat_id| !self.is_snoozed(*chat_id) && !self.is_woken(*chat_id)),
+        );
+        ordered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::snoozed_chats::SnoozedChats;
+
+    #[test]
+    fn test_snooze_past_timestamp() {
+        let mut snoozed_chats = SnoozedChats::default();
+        assert!(snoozed_chats.snooze(1, 100, 100).is_err());
+        assert!(snoozed_chats.snooze(1, 50, 100).is_err());
+        assert!(!snoozed_chats.is_snoozed(1));
+    }
+
+    #[test]
+    fn test_wake_resurfaces_to_top() {
+        let mut snoozed_chats = SnoozedChats::default();
+        snoozed_chats.snooze(2, 200, 100).unwrap();
+        snoozed_chats.snooze(3, 300, 100).unwrap();
+        assert_eq!(snoozed_chats.order(vec![1, 2, 3, 4]), vec![1, 4]);
+
+        assert!(snoozed_chats.wake(199).is_empty());
+        assert_eq!(snoozed_chats.wake(250), vec![2]);
+        assert_eq!(snoozed_chats.order(vec![1, 2, 3, 4]), vec![2, 1, 4]);
+
+        assert_eq!(snoozed_chats.wake(300), vec![3]);
+        assert_eq!(snoozed_chats.order(vec![1, 2, 3, 4]), vec![3, 2, 1, 4]);
+
+        snoozed_chats.dismiss(3);
+        assert_eq!(snoozed_chats.order(vec![1, 2, 3, 4]), vec![2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join("tgt_test_snoozed_chats.toml");
+        let mut snoozed_chats = SnoozedChats::default();
+        snoozed_chats.snooze(-100, 200, 100).unwrap();
+        snoozed_chats.snooze(42, 300, 100).unwrap();
+        snoozed_chats.save(&path).unwrap();
+
+        let mut loaded = SnoozedChats::default();
+        loaded.load(&path);
+        assert!(loaded.is_snoozed(-100));
+        assert!(loaded.is_snoozed(42));
+        assert_eq!(loaded.wake(250), vec![-100]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,326 @@
+use crate::app_context::AppContext;
+use ratatui::style::Style;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "False", "finally", "for", "from", "global", "if", "import", "in", "is",
+    "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "True", "try", "while",
+    "with", "yield",
+];
+
+const JAVASCRIPT_KEYWORDS: &[&str] = &[
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "null",
+    "return",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "yield",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// `TokenKind` is the class of a token of a code block, each class is colored
+/// differently.
+pub enum TokenKind {
+    /// A reserved word of the language.
+    Keyword,
+    /// A string or a character literal, with its quotes.
+    String,
+    /// A number literal.
+    Number,
+    /// A comment, up to the end of the line.
+    Comment,
+    /// Anything else, e.g. the identifiers, the punctuation and the spaces.
+    Plain,
+}
+/// Implementation of the `TokenKind` enum.
+impl TokenKind {
+    /// Get the style of the tokens of the kind, from the theme.
+    ///
+    /// # Arguments
+    /// * `app_context` - The application context, used for the styles.
+    pub fn style(&self, app_context: &AppContext) -> Style {
+        match self {
+            TokenKind::Keyword => app_context.style_chat_code_keyword(),
+            TokenKind::String => app_context.style_chat_code_string(),
+            TokenKind::Number => app_context.style_chat_code_number(),
+            TokenKind::Comment => app_context.style_chat_code_comment(),
+            TokenKind::Plain => Style::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// `Token` is a piece of a code block with a single class.
+pub struct Token {
+    /// The class of the token.
+    pub kind: TokenKind,
+    /// The text of the token.
+    pub text: String,
+}
+
+/// The syntax of a language known to the tokenizer.
+struct Syntax {
+    /// The reserved words.
+    keywords: &'static [&'static str],
+    /// The prefix of the comments, they end with the line.
+    comment: &'static str,
+    /// Whether the single quote starts a character literal, instead of a
+    /// string, it is also used by the lifetimes of Rust.
+    char_literals: bool,
+}
+
+/// Get the syntax of a language from the hint of a code block.
+///
+/// # Arguments
+/// * `lang` - The language of the code block, e.g. `rust` or `py`.
+///
+/// # Returns
+/// * `Option<Syntax>` - The syntax, or `None` when the language is unknown.
+fn syntax(lang: &str) -> Option<Syntax> {
+    match lang.to_lowercase().as_str() {
+        "rust" | "rs" => Some(Syntax {
+            keywords: RUST_KEYWORDS,
+            comment: "//",
+            char_literals: true,
+        }),
+        "python" | "py" => Some(Syntax {
+            keywords: PYTHON_KEYWORDS,
+            comment: "#",
+            char_literals: false,
+        }),
+        "javascript" | "js" | "typescript" | "ts" => Some(Syntax {
+            keywords: JAVASCRIPT_KEYWORDS,
+            comment: "//",
+            char_literals: false,
+        }),
+        _ => None,
+    }
+}
+
+/// Whether the code blocks of a language are highlighted.
+///
+/// # Arguments
+/// * `lang` - The language of the code block.
+pub fn is_supported(lang: &str) -> bool {
+    syntax(lang).is_some()
+}
+
+/// Get the length of the character literal at the start of the code, e.g.
+/// `'a'`, `'\n'` or `'\u{1F600}'`.
+///
+/// # Arguments
+/// * `chars` - The code, from the opening quote.
+///
+/// # Returns
+/// * `Option<usize>` - The length of the literal with its quotes, `None` when
+///   no closing quote follows a single character or escape, e.g. for a
+///   lifetime.
+fn char_literal_len(chars: &[char]) -> Option<usize> {
+    let end = match chars.get(1)? {
+        '\\' if chars.get(2) == Some(&'u') => 4 + chars[3..].iter().position(|c| *c == '}')?,
+        '\\' => 3,
+        _ => 2,
+    };
+    (chars.get(end) == Some(&'\'')).then_some(end + 1)
+}
+
+/// Split a code block into tokens to color.
+/// The code of an unknown language is a single plain token.
+///
+/// # Arguments
+/// * `lang` - The language of the code block.
+/// * `code` - The code.
+///
+/// # Returns
+/// * `Vec<Token>` - The tokens, that joined together give back the code.
+pub fn tokenize(lang: &str, code: &str) -> Vec<Token> {
+    let Some(syntax) = syntax(lang) else {
+        return vec![Token {
+            kind: TokenKind::Plain,
+            text: code.to_string(),
+        }];
+    };
+    let chars: Vec<char> = code.chars().collect();
+    let comment: Vec<char> = syntax.comment.chars().collect();
+    let mut tokens: Vec<Token> = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+        let kind = if chars[i..].starts_with(&comment) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            TokenKind::Comment
+        } else if c == '\'' && syntax.char_literals {
+            match char_literal_len(&chars[i..]) {
+                Some(len) => {
+                    i += len;
+                    TokenKind::String
+                }
+                // The quote of a lifetime is plain.
+                None => {
+                    i += 1;
+                    TokenKind::Plain
+                }
+            }
+        } else if c == '"' || c == '\'' {
+            i += 1;
+            while i < chars.len() && chars[i] != c && chars[i] != '\n' {
+                // The escaped quotes do not close the string.
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            // An unterminated string ends with the line.
+            i = i.min(chars.len());
+            if i < chars.len() && chars[i] == c {
+                i += 1;
+            }
+            TokenKind::String
+        } else if c.is_ascii_digit() {
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            TokenKind::Number
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if syntax.keywords.contains(&word.as_str()) {
+                TokenKind::Keyword
+            } else {
+                TokenKind::Plain
+            }
+        } else {
+            i += 1;
+            TokenKind::Plain
+        };
+        let text: String = chars[start..i].iter().collect();
+        match tokens.last_mut() {
+            // The consecutive plain tokens are merged, there is no need to
+            // style them separately.
+            Some(last) if kind == TokenKind::Plain && last.kind == TokenKind::Plain => {
+                last.text.push_str(&text)
+            }
+            _ => tokens.push(Token { kind, text }),
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::syntax_highlight::{is_supported, tokenize, Token, TokenKind};
+
+    fn token(kind: TokenKind, text: &str) -> Token {
+        Token {
+            kind,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_tokenize_rust() {
+        assert_eq!(
+            tokenize("rust", "let x = \"a\\\"b\"; // 42\nfn"),
+            vec![
+                token(TokenKind::Keyword, "let"),
+                token(TokenKind::Plain, " x = "),
+                token(TokenKind::String, "\"a\\\"b\""),
+                token(TokenKind::Plain, "; "),
+                token(TokenKind::Comment, "// 42"),
+                token(TokenKind::Plain, "\n"),
+                token(TokenKind::Keyword, "fn"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_rust_lifetimes() {
+        assert_eq!(
+            tokenize("rust", "fn f<'a>(s: &'a str) { '\\n'; 'x' }"),
+            vec![
+                token(TokenKind::Keyword, "fn"),
+                token(TokenKind::Plain, " f<'a>(s: &'a str) { "),
+                token(TokenKind::String, "'\\n'"),
+                token(TokenKind::Plain, "; "),
+                token(TokenKind::String, "'x'"),
+                token(TokenKind::Plain, " }"),
+            ]
+        );
+        assert_eq!(
+            tokenize("rust", "'\\u{1F600}'"),
+            vec![token(TokenKind::String, "'\\u{1F600}'")]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_python() {
+        assert_eq!(
+            tokenize("py", "def f(n): return n * 2.5 # twice"),
+            vec![
+                token(TokenKind::Keyword, "def"),
+                token(TokenKind::Plain, " f(n): "),
+                token(TokenKind::Keyword, "return"),
+                token(TokenKind::Plain, " n * "),
+                token(TokenKind::Number, "2.5"),
+                token(TokenKind::Plain, " "),
+                token(TokenKind::Comment, "# twice"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_unknown_language() {
+        assert!(!is_supported("brainfuck"));
+        assert_eq!(
+            tokenize("brainfuck", "let x = 1"),
+            vec![token(TokenKind::Plain, "let x = 1")]
+        );
+        // The identifiers that contain a keyword are not keywords.
+        assert_eq!(
+            tokenize("js", "letter"),
+            vec![token(TokenKind::Plain, "letter")]
+        );
+    }
+}
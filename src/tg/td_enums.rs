@@ -1,6 +1,20 @@
+use std::fmt::{self, Display, Formatter};
 use std::hash::Hash;
 
-use tdlib_rs::{enums::ChatList, types::ChatListFolder};
+use tdlib_rs::{
+    enums::{
+        CallDiscardReason, ChatList, ChatMemberStatus, InlineKeyboardButtonType, MessageContent,
+        MessageSchedulingState, MessageSender, ReplyMarkup, TextParseMode, UserStatus,
+    },
+    types::{
+        BotCommand, BotInfo, BotMenuButton, ChatAdministratorRights, ChatListFolder,
+        ChatPermissions, ForumTopic, Message, MessageSenderChat, MessageSenderUser, Poll, Session,
+        TextParseModeMarkdown,
+    },
+};
+
+use crate::action::Action;
+use crate::tg::message_entry::{DateTimeEntry, MessageEntry};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum TdMessageSender {
@@ -8,6 +22,48 @@ pub enum TdMessageSender {
     Chat(i64),
 }
 
+impl TdMessageSender {
+    /// The sender with the given identifier, it is the current user when
+    /// `sender_id` is `me` and a chat otherwise.
+    pub fn from_sender_id(sender_id: i64, me: i64) -> Self {
+        if sender_id == me {
+            TdMessageSender::User(me)
+        } else {
+            TdMessageSender::Chat(sender_id)
+        }
+    }
+
+    /// The identifier of the user or of the chat.
+    pub fn id(&self) -> i64 {
+        match self {
+            TdMessageSender::User(user_id) => *user_id,
+            TdMessageSender::Chat(chat_id) => *chat_id,
+        }
+    }
+}
+
+impl From<&MessageSender> for TdMessageSender {
+    fn from(sender: &MessageSender) -> Self {
+        match sender {
+            MessageSender::User(user) => TdMessageSender::User(user.user_id),
+            MessageSender::Chat(chat) => TdMessageSender::Chat(chat.chat_id),
+        }
+    }
+}
+
+impl From<&TdMessageSender> for MessageSender {
+    fn from(sender: &TdMessageSender) -> Self {
+        match sender {
+            TdMessageSender::User(user_id) => {
+                MessageSender::User(MessageSenderUser { user_id: *user_id })
+            }
+            TdMessageSender::Chat(chat_id) => {
+                MessageSender::Chat(MessageSenderChat { chat_id: *chat_id })
+            }
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum TdChatList {
     Main,
@@ -95,3 +151,847 @@ impl From<TdChatList> for ChatList {
         }
     }
 }
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TdReplyMarkup {
+    /// A custom keyboard sent by a bot, pressing a button sends its text
+    ShowKeyboard(Vec<Vec<String>>),
+    /// A keyboard attached to the message, pressing a button answers to the bot
+    InlineKeyboard(Vec<Vec<TdInlineKeyboardButton>>),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TdInlineKeyboardButton {
+    /// The text of the button
+    pub text: String,
+    /// The data sent to the bot when the button is pressed; it is `None` when the button is not a
+    /// callback button
+    pub callback_data: Option<String>,
+}
+
+impl TdReplyMarkup {
+    /// Convert a reply markup received from Telegram.
+    /// It returns `None` for the reply markups that do not have buttons.
+    pub fn from_reply_markup(reply_markup: &ReplyMarkup) -> Option<Self> {
+        match reply_markup {
+            ReplyMarkup::ShowKeyboard(keyboard) => Some(TdReplyMarkup::ShowKeyboard(
+                keyboard
+                    .rows
+                    .iter()
+                    .map(|row| row.iter().map(|button| button.text.clone()).collect())
+                    .collect(),
+            )),
+            ReplyMarkup::InlineKeyboard(keyboard) => Some(TdReplyMarkup::InlineKeyboard(
+                keyboard
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|button| TdInlineKeyboardButton {
+                                text: button.text.clone(),
+                                callback_data: match &button.r#type {
+                                    InlineKeyboardButtonType::Callback(callback) => {
+                                        Some(callback.data.clone())
+                                    }
+                                    _ => None,
+                                },
+                            })
+                            .collect()
+                    })
+                    .collect(),
+            )),
+            ReplyMarkup::RemoveKeyboard(_) | ReplyMarkup::ForceReply(_) => None,
+        }
+    }
+
+    /// The text of the buttons, row by row.
+    pub fn button_texts(&self) -> Vec<Vec<&str>> {
+        match self {
+            TdReplyMarkup::ShowKeyboard(rows) => rows
+                .iter()
+                .map(|row| row.iter().map(String::as_str).collect())
+                .collect(),
+            TdReplyMarkup::InlineKeyboard(rows) => rows
+                .iter()
+                .map(|row| row.iter().map(|button| button.text.as_str()).collect())
+                .collect(),
+        }
+    }
+
+    /// The action to perform when the button at `row` and `col` of the
+    /// message `message_id` is pressed.
+    /// It returns `None` when there is no such button or when the button
+    /// can not be handled.
+    pub fn button_action(&self, message_id: i64, row: usize, col: usize) -> Option<Action> {
+        match self {
+            TdReplyMarkup::ShowKeyboard(rows) => rows
+                .get(row)?
+                .get(col)
+                .map(|text| Action::SendMessage(text.clone(), None)),
+            TdReplyMarkup::InlineKeyboard(rows) => {
+                let data = rows.get(row)?.get(col)?.callback_data.clone()?;
+                Some(Action::GetCallbackQueryAnswer(message_id, data))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TdBotCommand {
+    /// The text of the command, without the leading slash
+    pub command: String,
+    /// The description of the command
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TdBotMenu {
+    /// A menu button that opens a Web App, only its URL is shown because the
+    /// web view can not be rendered in the terminal
+    WebApp { text: String, url: String },
+    /// The commands of the bot, choosing one sends it to the bot
+    Commands(Vec<TdBotCommand>),
+}
+
+impl TdBotMenu {
+    /// Convert the menu button and the commands of a bot.
+    /// The bots without a menu button show their commands, it returns `None`
+    /// when there are none.
+    pub fn from_menu_button(
+        menu_button: Option<&BotMenuButton>,
+        commands: &[BotCommand],
+    ) -> Option<Self> {
+        match menu_button {
+            Some(button) => Some(TdBotMenu::WebApp {
+                text: button.text.clone(),
+                url: button.url.clone(),
+            }),
+            None if commands.is_empty() => None,
+            None => Some(TdBotMenu::Commands(
+                commands
+                    .iter()
+                    .map(|command| TdBotCommand {
+                        command: command.command.clone(),
+                        description: command.description.clone(),
+                    })
+                    .collect(),
+            )),
+        }
+    }
+
+    /// Convert the menu of a bot from its full information.
+    pub fn from_bot_info(bot_info: &BotInfo) -> Option<Self> {
+        Self::from_menu_button(bot_info.menu_button.as_ref(), &bot_info.commands)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TdUserStatus {
+    /// The user is online
+    Online,
+    /// The user is offline, with the timestamp of when it was last online
+    Offline(i32),
+    /// The user was online recently, the exact time is hidden
+    Recently,
+    /// The user was online within the last week
+    LastWeek,
+    /// The user was online within the last month
+    LastMonth,
+    /// The user has never changed its status, or a long time ago
+    Empty,
+}
+
+impl From<&UserStatus> for TdUserStatus {
+    fn from(status: &UserStatus) -> Self {
+        match status {
+            UserStatus::Online(_) => TdUserStatus::Online,
+            UserStatus::Offline(offline) => TdUserStatus::Offline(offline.was_online),
+            UserStatus::Recently(_) => TdUserStatus::Recently,
+            UserStatus::LastWeek(_) => TdUserStatus::LastWeek,
+            UserStatus::LastMonth(_) => TdUserStatus::LastMonth,
+            UserStatus::Empty => TdUserStatus::Empty,
+        }
+    }
+}
+
+/// Describe the presence of a user, as shown next to the title of a private
+/// chat.
+///
+/// # Arguments
+/// * `status` - The status of the user.
+///
+/// # Returns
+/// * `String` - `online` or when the user was last seen.
+pub fn format_last_seen(status: &TdUserStatus) -> String {
+    match status {
+        TdUserStatus::Online => "online".to_string(),
+        TdUserStatus::Offline(was_online) => {
+            format!("last seen {}", DateTimeEntry::convert_time(*was_online))
+        }
+        TdUserStatus::Recently => "last seen recently".to_string(),
+        TdUserStatus::LastWeek => "last seen within a week".to_string(),
+        TdUserStatus::LastMonth => "last seen within a month".to_string(),
+        TdUserStatus::Empty => "last seen a long time ago".to_string(),
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TdCallOutcome {
+    /// The call was answered and then hung up
+    Completed,
+    /// The call was not answered
+    Missed,
+    /// The call was declined by the receiver
+    Declined,
+}
+
+impl From<&CallDiscardReason> for TdCallOutcome {
+    fn from(discard_reason: &CallDiscardReason) -> Self {
+        match discard_reason {
+            CallDiscardReason::Missed => TdCallOutcome::Missed,
+            CallDiscardReason::Declined => TdCallOutcome::Declined,
+            _ => TdCallOutcome::Completed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TdCallEntry {
+    /// The date of the call, in seconds since the epoch
+    pub date: i32,
+    /// Whether the call was started by the current user
+    pub is_outgoing: bool,
+    /// Whether the call was a video call
+    pub is_video: bool,
+    /// The duration of the call, in seconds
+    pub duration: i32,
+    /// How the call ended
+    pub outcome: TdCallOutcome,
+}
+
+impl TdCallEntry {
+    /// Convert a call message received from Telegram.
+    /// It returns `None` for the messages that are not calls.
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match &message.content {
+            MessageContent::MessageCall(call) => Some(TdCallEntry {
+                date: message.date,
+                is_outgoing: message.is_outgoing,
+                is_video: call.is_video,
+                duration: call.duration,
+                outcome: TdCallOutcome::from(&call.discard_reason),
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TdPollEntry {
+    /// The identifier of the poll message
+    pub message_id: i64,
+    /// The question of the poll
+    pub question: String,
+    /// Whether the poll is closed, it does not accept votes anymore
+    pub is_closed: bool,
+    /// Whether the current user has voted
+    pub voted: bool,
+}
+
+impl TdPollEntry {
+    /// Convert a poll received from Telegram.
+    pub fn new(message_id: i64, poll: &Poll) -> Self {
+        TdPollEntry {
+            message_id,
+            question: poll.question.clone(),
+            is_closed: poll.is_closed,
+            voted: poll.options.iter().any(|option| option.is_chosen),
+        }
+    }
+
+    /// Convert a poll message received from Telegram.
+    /// It returns `None` for the messages that are not polls.
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match &message.content {
+            MessageContent::MessagePoll(m) => Some(TdPollEntry::new(message.id, &m.poll)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TdSchedulingState {
+    /// The message is sent at the given date, as a Unix timestamp
+    SendAtDate(i32),
+    /// The message is sent when the peer comes online
+    SendWhenOnline,
+}
+
+impl From<&MessageSchedulingState> for TdSchedulingState {
+    fn from(state: &MessageSchedulingState) -> Self {
+        match state {
+            MessageSchedulingState::SendAtDate(state) => {
+                TdSchedulingState::SendAtDate(state.send_date)
+            }
+            MessageSchedulingState::SendWhenOnline => TdSchedulingState::SendWhenOnline,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TdScheduledMessage {
+    /// The identifier of the scheduled message
+    pub message_id: i64,
+    /// The content of the message as a text
+    pub text: String,
+    /// When the message is sent
+    pub state: TdSchedulingState,
+}
+
+impl TdScheduledMessage {
+    /// Convert a scheduled message received from Telegram.
+    /// It returns `None` for the messages that are not scheduled.
+    pub fn from_message(message: &Message) -> Option<Self> {
+        let state = message.scheduling_state.as_ref()?;
+        Some(TdScheduledMessage {
+            message_id: message.id,
+            text: MessageEntry::from(message).message_content_to_string(),
+            state: TdSchedulingState::from(state),
+        })
+    }
+
+    /// Whether the date the message had to be sent at has passed, e.g.
+    /// because the sending failed.
+    ///
+    /// # Arguments
+    /// * `now` - The current time, as a Unix timestamp.
+    pub fn is_past_due(&self, now: i32) -> bool {
+        matches!(self.state, TdSchedulingState::SendAtDate(send_date) if send_date <= now)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TdForumTopic {
+    /// The identifier of the message thread of the topic
+    pub message_thread_id: i64,
+    /// The name of the topic
+    pub name: String,
+    /// The number of unread messages of the topic
+    pub unread_count: i32,
+    /// Whether the topic is closed, only the administrators can write in it
+    pub is_closed: bool,
+}
+
+impl From<&ForumTopic> for TdForumTopic {
+    fn from(topic: &ForumTopic) -> Self {
+        TdForumTopic {
+            message_thread_id: topic.info.message_thread_id,
+            name: topic.info.name.clone(),
+            unread_count: topic.unread_count,
+            is_closed: topic.info.is_closed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TdSession {
+    /// The identifier of the session
+    pub id: i64,
+    /// Whether it is the session of this client
+    pub is_current: bool,
+    /// The name and the version of the application of the session
+    pub application: String,
+    /// The model, the platform and the system version of the device
+    pub device: String,
+    /// The location of the session, guessed from its IP address
+    pub location: String,
+    /// When the session was last used, as a Unix timestamp
+    pub last_active_date: i32,
+}
+
+impl From<&Session> for TdSession {
+    fn from(session: &Session) -> Self {
+        let join = |parts: &[&str]| {
+            parts
+                .iter()
+                .filter(|part| !part.is_empty())
+                .copied()
+                .collect::<Vec<&str>>()
+                .join(" ")
+        };
+        TdSession {
+            id: session.id,
+            is_current: session.is_current,
+            application: join(&[&session.application_name, &session.application_version]),
+            device: join(&[
+                &session.device_model,
+                &session.platform,
+                &session.system_version,
+            ]),
+            location: session.location.clone(),
+            last_active_date: session.last_active_date,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TdFoundMessage {
+    /// The identifier of the chat of the message
+    pub chat_id: i64,
+    /// The identifier of the message
+    pub message_id: i64,
+    /// The title of the chat of the message
+    pub chat_title: String,
+    /// The name of the sender followed by the text of the message
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TdChatPermissions {
+    /// What the current user can do in the chat, with whether it is allowed
+    pub entries: Vec<(String, bool)>,
+    /// The minimum delay between the messages sent to the chat, in seconds
+    pub slow_mode_delay: i32,
+}
+
+impl TdChatPermissions {
+    /// Summarize the permissions of the current user in a chat.
+    /// The permissions of the chat are narrowed by the status of the user:
+    /// the owner can do everything, the administrators what their rights
+    /// allow, a restricted member only what is allowed both to it and to the
+    /// members of the chat, and a user that is not a member nothing.
+    pub fn new(
+        permissions: &ChatPermissions,
+        status: Option<&ChatMemberStatus>,
+        slow_mode_delay: i32,
+    ) -> Self {
+        let permissions = match status {
+            Some(ChatMemberStatus::Creator(_)) => Self::all(true),
+            Some(ChatMemberStatus::Administrator(administrator)) => {
+                Self::administrator(&administrator.rights)
+            }
+            Some(ChatMemberStatus::Restricted(restricted)) => {
+                Self::intersection(permissions, &restricted.permissions)
+            }
+            Some(ChatMemberStatus::Left) | Some(ChatMemberStatus::Banned(_)) => Self::all(false),
+            Some(ChatMemberStatus::Member) | None => permissions.clone(),
+        };
+        let entries = [
+            ("Send messages", permissions.can_send_basic_messages),
+            ("Send photos", permissions.can_send_photos),
+            ("Send videos", permissions.can_send_videos),
+            ("Send audios", permissions.can_send_audios),
+            ("Send documents", permissions.can_send_documents),
+            ("Send voice notes", permissions.can_send_voice_notes),
+            ("Send video notes", permissions.can_send_video_notes),
+            ("Send polls", permissions.can_send_polls),
+            (
+                "Send stickers and GIFs",
+                permissions.can_send_other_messages,
+            ),
+            ("Add link previews", permissions.can_add_web_page_previews),
+            ("Change chat info", permissions.can_change_info),
+            ("Invite users", permissions.can_invite_users),
+            ("Pin messages", permissions.can_pin_messages),
+            ("Manage topics", permissions.can_manage_topics),
+        ];
+        TdChatPermissions {
+            entries: entries
+                .into_iter()
+                .map(|(label, allowed)| (label.to_string(), allowed))
+                .collect(),
+            slow_mode_delay,
+        }
+    }
+
+    fn all(allowed: bool) -> ChatPermissions {
+        ChatPermissions {
+            can_send_basic_messages: allowed,
+            can_send_audios: allowed,
+            can_send_documents: allowed,
+            can_send_photos: allowed,
+            can_send_videos: allowed,
+            can_send_video_notes: allowed,
+            can_send_voice_notes: allowed,
+            can_send_polls: allowed,
+            can_send_other_messages: allowed,
+            can_add_web_page_previews: allowed,
+            can_change_info: allowed,
+            can_invite_users: allowed,
+            can_pin_messages: allowed,
+            can_manage_topics: allowed,
+        }
+    }
+
+    /// The administrators are not bound by the permissions of the members,
+    /// the actions that need a right are allowed only when it is given.
+    fn administrator(rights: &ChatAdministratorRights) -> ChatPermissions {
+        ChatPermissions {
+            can_change_info: rights.can_change_info,
+            can_invite_users: rights.can_invite_users,
+            can_pin_messages: rights.can_pin_messages,
+            can_manage_topics: rights.can_manage_topics,
+            ..Self::all(true)
+        }
+    }
+
+    fn intersection(a: &ChatPermissions, b: &ChatPermissions) -> ChatPermissions {
+        ChatPermissions {
+            can_send_basic_messages: a.can_send_basic_messages && b.can_send_basic_messages,
+            can_send_audios: a.can_send_audios && b.can_send_audios,
+            can_send_documents: a.can_send_documents && b.can_send_documents,
+            can_send_photos: a.can_send_photos && b.can_send_photos,
+            can_send_videos: a.can_send_videos && b.can_send_videos,
+            can_send_video_notes: a.can_send_video_notes && b.can_send_video_notes,
+            can_send_voice_notes: a.can_send_voice_notes && b.can_send_voice_notes,
+            can_send_polls: a.can_send_polls && b.can_send_polls,
+            can_send_other_messages: a.can_send_other_messages && b.can_send_other_messages,
+            can_add_web_page_previews: a.can_add_web_page_previews && b.can_add_web_page_previews,
+            can_change_info: a.can_change_info && b.can_change_info,
+            can_invite_users: a.can_invite_users && b.can_invite_users,
+            can_pin_messages: a.can_pin_messages && b.can_pin_messages,
+            can_manage_topics: a.can_manage_topics && b.can_manage_topics,
+        }
+    }
+
+    /// The actions that the current user cannot do in the chat.
+    pub fn restricted(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(_, allowed)| !allowed)
+            .map(|(label, _)| label.as_str())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum TdTextEntity {
+    /// A `@username` mention, the username is stored without the `@`
+    Mention(String),
+    /// A `#hashtag`, the hashtag is stored with the `#`
+    Hashtag(String),
+}
+
+impl TdTextEntity {
+    /// The action to perform when the entity is followed: a mention opens the
+    /// chat of the username and a hashtag searches it in the open chat.
+    pub fn follow_action(&self) -> Action {
+        match self {
+            TdTextEntity::Mention(username) => Action::OpenUsername(username.clone()),
+            TdTextEntity::Hashtag(hashtag) => Action::SearchHashtag(hashtag.clone()),
+        }
+    }
+}
+
+impl Display for TdTextEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TdTextEntity::Mention(username) => write!(f, "@{}", username),
+            TdTextEntity::Hashtag(hashtag) => write!(f, "{}", hashtag),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TdParseMode {
+    /// The MarkdownV2 syntax of Telegram, e.g. `*bold*` and `[text](url)`
+    Markdown,
+    /// The subset of HTML supported by Telegram, e.g. `<b>bold</b>`
+    Html,
+}
+
+impl From<TdParseMode> for TextParseMode {
+    fn from(parse_mode: TdParseMode) -> Self {
+        match parse_mode {
+            TdParseMode::Markdown => TextParseMode::Markdown(TextParseModeMarkdown { version: 2 }),
+            TdParseMode::Html => TextParseMode::Html,
+        }
+    }
+}
+
+impl Display for TdParseMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TdParseMode::Markdown => write!(f, "markdown"),
+            TdParseMode::Html => write!(f, "html"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::Action;
+    use crate::tg::message_entry::DateTimeEntry;
+    use crate::tg::td_enums::{
+        format_last_seen, TdBotCommand, TdBotMenu, TdCallOutcome, TdChatPermissions,
+        TdInlineKeyboardButton, TdPollEntry, TdReplyMarkup, TdScheduledMessage, TdSchedulingState,
+        TdTextEntity, TdUserStatus,
+    };
+    use tdlib_rs::enums::{CallDiscardReason, ChatMemberStatus, PollType};
+    use tdlib_rs::types::{
+        BotCommand, BotMenuButton, ChatAdministratorRights, ChatMemberStatusAdministrator,
+        ChatMemberStatusRestricted, ChatPermissions, Poll, PollOption, PollTypeRegular,
+    };
+
+    fn poll(is_closed: bool, chosen: &[bool]) -> Poll {
+        Poll {
+            id: 1,
+            question: "Lunch?".to_string(),
+            options: chosen
+                .iter()
+                .map(|is_chosen| PollOption {
+                    text: "Pizza".to_string(),
+                    voter_count: 0,
+                    vote_percentage: 0,
+                    is_chosen: *is_chosen,
+                    is_being_chosen: false,
+                })
+                .collect(),
+            total_voter_count: 0,
+            recent_voter_ids: vec![],
+            is_anonymous: false,
+            r#type: PollType::Regular(PollTypeRegular {
+                allow_multiple_answers: false,
+            }),
+            open_period: 0,
+            close_date: 0,
+            is_closed,
+        }
+    }
+
+    #[test]
+    fn test_button_action_show_keyboard() {
+        let markup = TdReplyMarkup::ShowKeyboard(vec![
+            vec!["Yes".to_string(), "No".to_string()],
+            vec!["Cancel".to_string()],
+        ]);
+        assert_eq!(
+            markup.button_action(42, 0, 1),
+            Some(Action::SendMessage("No".to_string(), None))
+        );
+        assert_eq!(
+            markup.button_action(42, 1, 0),
+            Some(Action::SendMessage("Cancel".to_string(), None))
+        );
+        assert_eq!(markup.button_action(42, 1, 1), None);
+        assert_eq!(markup.button_action(42, 2, 0), None);
+    }
+
+    #[test]
+    fn test_button_action_inline_keyboard() {
+        let markup = TdReplyMarkup::InlineKeyboard(vec![vec![
+            TdInlineKeyboardButton {
+                text: "Like".to_string(),
+                callback_data: Some("bGlrZQ==".to_string()),
+            },
+            TdInlineKeyboardButton {
+                text: "Open".to_string(),
+                callback_data: None,
+            },
+        ]]);
+        assert_eq!(
+            markup.button_action(42, 0, 0),
+            Some(Action::GetCallbackQueryAnswer(42, "bGlrZQ==".to_string()))
+        );
+        assert_eq!(markup.button_action(42, 0, 1), None);
+        assert_eq!(markup.button_action(42, 1, 0), None);
+    }
+
+    #[test]
+    fn test_button_texts() {
+        let markup = TdReplyMarkup::InlineKeyboard(vec![vec![TdInlineKeyboardButton {
+            text: "Like".to_string(),
+            callback_data: None,
+        }]]);
+        assert_eq!(markup.button_texts(), vec![vec!["Like"]]);
+    }
+
+    #[test]
+    fn test_call_outcome_from_discard_reason() {
+        assert_eq!(
+            TdCallOutcome::from(&CallDiscardReason::HungUp),
+            TdCallOutcome::Completed
+        );
+        assert_eq!(
+            TdCallOutcome::from(&CallDiscardReason::Disconnected),
+            TdCallOutcome::Completed
+        );
+        assert_eq!(
+            TdCallOutcome::from(&CallDiscardReason::Missed),
+            TdCallOutcome::Missed
+        );
+        assert_eq!(
+            TdCallOutcome::from(&CallDiscardReason::Declined),
+            TdCallOutcome::Declined
+        );
+    }
+
+    #[test]
+    fn test_poll_entry_from_poll() {
+        let state = |entry: TdPollEntry| (entry.is_closed, entry.voted);
+        assert_eq!(
+            state(TdPollEntry::new(1, &poll(false, &[false, false]))),
+            (false, false)
+        );
+        assert_eq!(
+            state(TdPollEntry::new(1, &poll(false, &[false, true]))),
+            (false, true)
+        );
+        assert_eq!(
+            state(TdPollEntry::new(1, &poll(true, &[false, false]))),
+            (true, false)
+        );
+        // A closed poll keeps the vote of the user.
+        assert_eq!(
+            state(TdPollEntry::new(1, &poll(true, &[true, false]))),
+            (true, true)
+        );
+    }
+
+    #[test]
+    fn test_scheduled_message_past_due() {
+        let scheduled = |state| TdScheduledMessage {
+            message_id: 1,
+            text: "Happy birthday!".to_string(),
+            state,
+        };
+        assert!(scheduled(TdSchedulingState::SendAtDate(100)).is_past_due(100));
+        assert!(!scheduled(TdSchedulingState::SendAtDate(101)).is_past_due(100));
+        assert!(!scheduled(TdSchedulingState::SendWhenOnline).is_past_due(100));
+    }
+
+    #[test]
+    fn test_format_last_seen() {
+        assert_eq!(format_last_seen(&TdUserStatus::Online), "online");
+        assert_eq!(
+            format_last_seen(&TdUserStatus::Recently),
+            "last seen recently"
+        );
+        assert_eq!(
+            format_last_seen(&TdUserStatus::LastWeek),
+            "last seen within a week"
+        );
+        assert_eq!(
+            format_last_seen(&TdUserStatus::LastMonth),
+            "last seen within a month"
+        );
+        assert_eq!(
+            format_last_seen(&TdUserStatus::Empty),
+            "last seen a long time ago"
+        );
+        assert_eq!(
+            format_last_seen(&TdUserStatus::Offline(1_700_000_000)),
+            format!("last seen {}", DateTimeEntry::convert_time(1_700_000_000))
+        );
+    }
+
+    #[test]
+    fn test_text_entity_follow_action() {
+        assert_eq!(
+            TdTextEntity::Mention("durov".to_string()).follow_action(),
+            Action::OpenUsername("durov".to_string())
+        );
+        assert_eq!(
+            TdTextEntity::Hashtag("#rust".to_string()).follow_action(),
+            Action::SearchHashtag("#rust".to_string())
+        );
+    }
+
+    #[test]
+    fn test_chat_permissions_restricted() {
+        let chat_permissions = ChatPermissions {
+            can_send_basic_messages: true,
+            can_send_photos: true,
+            can_send_polls: true,
+            can_invite_users: true,
+            ..Default::default()
+        };
+        let status = ChatMemberStatus::Restricted(ChatMemberStatusRestricted {
+            is_member: true,
+            restricted_until_date: 0,
+            permissions: ChatPermissions {
+                can_send_basic_messages: true,
+                can_invite_users: true,
+                ..Default::default()
+            },
+        });
+        let permissions = TdChatPermissions::new(&chat_permissions, Some(&status), 30);
+        assert_eq!(permissions.slow_mode_delay, 30);
+        assert!(permissions
+            .entries
+            .contains(&("Send messages".to_string(), true)));
+        assert!(permissions
+            .entries
+            .contains(&("Invite users".to_string(), true)));
+        let restricted = permissions.restricted();
+        assert!(restricted.contains(&"Send photos"));
+        assert!(restricted.contains(&"Send polls"));
+        assert!(restricted.contains(&"Pin messages"));
+        assert!(!restricted.contains(&"Send messages"));
+    }
+
+    #[test]
+    fn test_chat_permissions_status() {
+        let chat_permissions = ChatPermissions::default();
+        let left = TdChatPermissions::new(&chat_permissions, Some(&ChatMemberStatus::Left), 0);
+        assert_eq!(left.restricted().len(), left.entries.len());
+        let member = TdChatPermissions::new(&chat_permissions, None, 0);
+        assert_eq!(member.restricted().len(), member.entries.len());
+
+        // An administrator can send everything, but only has the rights it
+        // was given.
+        let status = ChatMemberStatus::Administrator(ChatMemberStatusAdministrator {
+            rights: ChatAdministratorRights {
+                can_pin_messages: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let administrator = TdChatPermissions::new(&chat_permissions, Some(&status), 0);
+        assert_eq!(
+            administrator.restricted(),
+            vec!["Change chat info", "Invite users", "Manage topics"]
+        );
+    }
+
+    #[test]
+    fn test_bot_menu_commands() {
+        let commands = [
+            BotCommand {
+                command: "start".to_string(),
+                description: "Start the bot".to_string(),
+            },
+            BotCommand {
+                command: "help".to_string(),
+                description: "Show the help".to_string(),
+            },
+        ];
+        assert_eq!(
+            TdBotMenu::from_menu_button(None, &commands),
+            Some(TdBotMenu::Commands(vec![
+                TdBotCommand {
+                    command: "start".to_string(),
+                    description: "Start the bot".to_string(),
+                },
+                TdBotCommand {
+                    command: "help".to_string(),
+                    description: "Show the help".to_string(),
+                },
+            ]))
+        );
+        assert_eq!(TdBotMenu::from_menu_button(None, &[]), None);
+
+        // The menu button replaces the commands.
+        let button = BotMenuButton {
+            text: "Shop".to_string(),
+            url: "https://shop.example".to_string(),
+        };
+        assert_eq!(
+            TdBotMenu::from_menu_button(Some(&button), &commands),
+            Some(TdBotMenu::WebApp {
+                text: "Shop".to_string(),
+                url: "https://shop.example".to_string(),
+            })
+        );
+    }
+}
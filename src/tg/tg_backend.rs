@@ -1,20 +1,33 @@
 use crate::action::Action;
 use crate::event::Event;
+use crate::utils::open_with_default_app;
 use crate::{app_context::AppContext, tg::ordered_chat::OrderedChat};
 use std::collections::{BTreeSet, VecDeque};
+use std::future::Future;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, MutexGuard};
+use std::time::Duration;
 use tdlib_rs::enums::{
-    self, AuthorizationState, ChatList, InputMessageContent, InputMessageReplyTo, LogStream,
-    Messages, OptionValue, Update, User,
+    self, AuthorizationState, AvailableReactions, CallbackQueryPayload, ChatList,
+    InputMessageContent, InputMessageReplyTo, LogStream, MessageContent, MessageSender, Messages,
+    OptionValue, ReactionType, Stickers, TimeZones, Update, User,
 };
 use tdlib_rs::functions;
-use tdlib_rs::types::{Chat, ChatPosition, InputMessageText, LogStreamFile, OptionValueBoolean};
+use tdlib_rs::types::{
+    CallbackQueryPayloadData, Chat, ChatPosition, DraftMessage, EmojiStatus, InputMessageText,
+    LogStreamFile, OptionValueBoolean, ReactionTypeEmoji,
+};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::task::JoinHandle;
 
 use super::message_entry::MessageEntry;
-use super::td_enums::TdMessageReplyToMessage;
+use super::td_enums::{
+    TdBotMenu, TdCallEntry, TdForumTopic, TdFoundMessage, TdMessageReplyToMessage, TdMessageSender,
+    TdParseMode, TdPollEntry, TdScheduledMessage, TdSession,
+};
+
+/// The maximum number of messages TDLib returns for a page of the history.
+const MAX_HISTORY_PAGE_SIZE: i32 = 100;
 
 pub struct TgBackend {
     pub handle_updates: JoinHandle<()>,
@@ -40,6 +53,10 @@ impl TgBackend {
         let can_quit = Arc::new(AtomicBool::new(false));
         let full_chats_list = false;
         app_context.tg_context().set_event_tx(event_tx.clone());
+        app_context
+            .tg_context()
+            .request_gate()
+            .set_max(app_context.app_config().max_concurrent_requests);
         tracing::info!("Created TDLib client with client_id: {}", client_id);
 
         Ok(Self {
@@ -56,21 +73,83 @@ impl TgBackend {
         })
     }
 
+    /// Send a TDLib request once the `max_concurrent_requests` limit allows
+    /// it.
+    ///
+    /// # Arguments
+    /// * `request` - The request, it is sent when it is awaited.
+    ///
+    /// # Returns
+    /// * `F::Output` - The response of the request.
+    async fn request<F: Future>(&self, request: F) -> F::Output {
+        let tg_context = self.app_context.tg_context();
+        let _permit = tg_context.request_gate().acquire().await;
+        request.await
+    }
+
+    /// Send a long TDLib request, the operation is shown with a spinner in
+    /// the status bar until it finishes.
+    /// The operation is tracked directly, the started and finished events
+    /// are only handled by the main loop once the request returns.
+    ///
+    /// # Arguments
+    /// * `label` - The label of the operation, e.g. `Loading history`.
+    /// * `request` - The request, it is sent when it is awaited.
+    ///
+    /// # Returns
+    /// * `F::Output` - The response of the request.
+    async fn operation<F: Future>(&self, label: &str, request: F) -> F::Output {
+        let tg_context = self.app_context.tg_context();
+        tg_context.pending_operations().start(label.to_string());
+        let _ = self
+            .event_tx
+            .send(Event::OperationStarted(label.to_string()));
+        let output = self.request(request).await;
+        tg_context.pending_operations().finish(label);
+        let _ = self
+            .event_tx
+            .send(Event::OperationFinished(label.to_string()));
+        output
+    }
+
     pub async fn get_me(&mut self) {
         match functions::get_me(self.client_id).await {
             Ok(User::User(me)) => {
                 self.app_context.tg_context().set_me(me.id);
+                let custom_emoji_id = me.emoji_status.map_or(0, |s| s.custom_emoji_id);
+                let emoji = self.get_custom_emoji(custom_emoji_id).await;
+                self.app_context.tg_context().set_emoji_status(emoji);
             }
             Err(error) => tracing::error!("Failed to get me: {error:?}"),
         }
     }
 
+    pub async fn load_time_zones(&mut self) {
+        match functions::get_time_zones(self.client_id).await {
+            Ok(TimeZones::TimeZones(time_zones)) => {
+                *self.app_context.tg_context().time_zones() = time_zones
+                    .time_zones
+                    .into_iter()
+                    .map(|time_zone| (time_zone.id, time_zone.utc_time_offset))
+                    .collect();
+            }
+            Err(error) => tracing::error!("Failed to get time zones: {error:?}"),
+        }
+    }
+
     pub async fn load_chats(&mut self, chat_list: ChatList, limit: i32) {
         if self.full_chats_list {
             return;
         }
 
-        if let Err(e) = functions::load_chats(Some(chat_list), limit, self.client_id).await {
+        if let Err(e) = self
+            .request(functions::load_chats(
+                Some(chat_list),
+                limit,
+                self.client_id,
+            ))
+            .await
+        {
             tracing::error!("Failed to load chats: {e:?}");
             self.full_chats_list = true;
         }
@@ -86,7 +165,14 @@ impl TgBackend {
         &self,
         username: String,
     ) -> Result<tdlib_rs::types::Chats, tdlib_rs::types::Error> {
-        match functions::search_chats(username.clone(), 50, self.client_id).await {
+        match self
+            .request(functions::search_chats(
+                username.clone(),
+                50,
+                self.client_id,
+            ))
+            .await
+        {
             Ok(tdlib_rs::enums::Chats::Chats(chats)) => Ok(chats),
             Err(e) => {
                 tracing::error!("Failed to search chats: {:?} with query: {}", e, username);
@@ -95,6 +181,191 @@ impl TgBackend {
         }
     }
 
+    pub async fn set_chat_message_sender(&self, chat_id: i64, sender: MessageSender) {
+        match self
+            .request(functions::set_chat_message_sender(
+                chat_id,
+                sender,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(_) => tracing::info!("Chat {} message sender set", chat_id),
+            Err(e) => tracing::error!("Failed to set chat message sender: {e:?}"),
+        }
+    }
+
+    pub async fn get_chat_available_message_senders(
+        &self,
+        chat_id: i64,
+    ) -> Vec<(TdMessageSender, String)> {
+        match self
+            .request(functions::get_chat_available_message_senders(
+                chat_id,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(enums::ChatMessageSenders::ChatMessageSenders(senders)) => {
+                let tg_context = self.app_context.tg_context();
+                senders
+                    .senders
+                    .iter()
+                    .map(|sender| {
+                        let sender = TdMessageSender::from(&sender.sender);
+                        let name = match sender {
+                            TdMessageSender::User(user_id) => {
+                                tg_context.try_name_from_chats_or_users(user_id)
+                            }
+                            TdMessageSender::Chat(chat_id) => tg_context.name_from_chats(chat_id),
+                        };
+                        (sender, name.unwrap_or_default())
+                    })
+                    .collect()
+            }
+            Err(e) => {
+                tracing::error!("Failed to get chat available message senders: {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    pub async fn search_public_chat(&self, username: &str) -> Result<i64, tdlib_rs::types::Error> {
+        match self
+            .request(functions::search_public_chat(
+                username.to_string(),
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(enums::Chat::Chat(chat)) => Ok(chat.id),
+            Err(e) => {
+                tracing::error!(
+                    "Failed to search public chat: {:?} with username: {}",
+                    e,
+                    username
+                );
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn create_private_chat(&self, user_id: i64) -> Result<i64, tdlib_rs::types::Error> {
+        match self
+            .request(functions::create_private_chat(
+                user_id,
+                false,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(enums::Chat::Chat(chat)) => Ok(chat.id),
+            Err(e) => {
+                tracing::error!("Failed to create private chat with {}: {:?}", user_id, e);
+                Err(e)
+            }
+        }
+    }
+
+    // The found messages are returned one line each, with the name of the
+    // sender before the text.
+    pub async fn search_chat_messages(&self, chat_id: i64, query: &str) -> Vec<String> {
+        match self
+            .operation(
+                "Searching",
+                functions::search_chat_messages(
+                    chat_id,
+                    query.to_string(),
+                    None,
+                    0,
+                    0,
+                    50,
+                    None,
+                    0,
+                    0,
+                    self.client_id,
+                ),
+            )
+            .await
+        {
+            Ok(enums::FoundChatMessages::FoundChatMessages(found)) => {
+                let tg_context = self.app_context.tg_context();
+                found
+                    .messages
+                    .iter()
+                    .map(|message| {
+                        let message = MessageEntry::from(message);
+                        format!(
+                            "{}: {}",
+                            tg_context
+                                .try_name_from_chats_or_users(message.sender_id())
+                                .unwrap_or_default(),
+                            message.message_content_to_string()
+                        )
+                    })
+                    .collect()
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to search chat messages: {:?} with query: {}",
+                    e,
+                    query
+                );
+                vec![]
+            }
+        }
+    }
+
+    // The messages are searched in all the chats, the most recent ones
+    // first.
+    pub async fn search_messages(&self, query: &str) -> Vec<TdFoundMessage> {
+        match self
+            .operation(
+                "Searching",
+                functions::search_messages(
+                    None,
+                    query.to_string(),
+                    String::new(),
+                    20,
+                    None,
+                    0,
+                    0,
+                    self.client_id,
+                ),
+            )
+            .await
+        {
+            Ok(enums::FoundMessages::FoundMessages(found)) => {
+                let tg_context = self.app_context.tg_context();
+                found
+                    .messages
+                    .iter()
+                    .map(|message| {
+                        let entry = MessageEntry::from(message);
+                        TdFoundMessage {
+                            chat_id: message.chat_id,
+                            message_id: message.id,
+                            chat_title: tg_context
+                                .name_from_chats(message.chat_id)
+                                .unwrap_or_default(),
+                            text: format!(
+                                "{}: {}",
+                                tg_context
+                                    .try_name_from_chats_or_users(entry.sender_id())
+                                    .unwrap_or_default(),
+                                entry.message_content_to_string()
+                            ),
+                        }
+                    })
+                    .collect()
+            }
+            Err(e) => {
+                tracing::error!("Failed to search messages: {:?} with query: {}", e, query);
+                vec![]
+            }
+        }
+    }
+
     pub async fn close(&self) {
         match functions::close(self.client_id).await {
             Ok(me) => tracing::info!("TDLib client closed: {:?}", me),
@@ -103,23 +374,49 @@ impl TgBackend {
     }
 
     pub async fn view_all_messages(&self) {
-        if let Err(e) = functions::view_messages(
-            self.app_context.tg_context().open_chat_id(),
-            self.app_context.tg_context().unread_messages(),
-            None,
-            true,
-            self.client_id,
-        )
-        .await
+        if let Err(e) = self
+            .request(functions::view_messages(
+                self.app_context.tg_context().open_chat_id(),
+                self.app_context.tg_context().unread_messages(),
+                None,
+                true,
+                self.client_id,
+            ))
+            .await
         {
             tracing::error!("Failed to view all messages: {e:?}");
         }
     }
 
+    pub async fn view_message(&self, chat_id: i64, message_id: i64) {
+        if let Err(e) = self
+            .request(functions::view_messages(
+                chat_id,
+                vec![message_id],
+                None,
+                true,
+                self.client_id,
+            ))
+            .await
+        {
+            tracing::error!("Failed to view message {message_id}: {e:?}");
+        }
+    }
+
     #[allow(clippy::await_holding_lock)]
     // By default telegram send us only one message the first time
     pub async fn prepare_to_get_chat_history(&mut self, chat_id: i64) {
-        match functions::get_chat_history(chat_id, 0, 0, 100, false, self.client_id).await {
+        match self
+            .request(functions::get_chat_history(
+                chat_id,
+                0,
+                0,
+                100,
+                false,
+                self.client_id,
+            ))
+            .await
+        {
             Ok(_m) => {}
             Err(_e) => {}
         }
@@ -131,19 +428,45 @@ impl TgBackend {
         let mut mut_open_chat_messages_len =
             self.app_context.tg_context().open_chat_messages().len();
         let win_size = 100;
+        let page_size = history_page_size_limit(self.app_context.app_config().history_page_size);
+
+        // When a forum topic is open only the messages of its thread are loaded.
+        let open_topic_id = if chat_id == self.app_context.tg_context().open_chat_id() {
+            self.app_context.tg_context().open_topic_id()
+        } else {
+            0
+        };
 
         while mut_open_chat_messages_len < start_open_chat_messages_len + win_size {
             let from_message_id = self.app_context.tg_context().from_message_id();
-            match functions::get_chat_history(
-                chat_id,
-                from_message_id,
-                0,
-                50,
-                false,
-                self.client_id,
-            )
-            .await
-            {
+            let history = if open_topic_id != 0 {
+                self.operation(
+                    "Loading history",
+                    functions::get_message_thread_history(
+                        chat_id,
+                        open_topic_id,
+                        from_message_id,
+                        0,
+                        page_size,
+                        self.client_id,
+                    ),
+                )
+                .await
+            } else {
+                self.operation(
+                    "Loading history",
+                    functions::get_chat_history(
+                        chat_id,
+                        from_message_id,
+                        0,
+                        page_size,
+                        false,
+                        self.client_id,
+                    ),
+                )
+                .await
+            };
+            match history {
                 Ok(Messages::Messages(messages)) => {
                     if messages.messages.is_empty() {
                         tracing::info!("No more messages to get");
@@ -198,7 +521,29 @@ impl TgBackend {
         });
         let reply_to: Option<InputMessageReplyTo> =
             reply_to.map(|reply_to| InputMessageReplyTo::Message((&reply_to).into()));
-        match functions::send_message(chat_id, 0, reply_to, None, text, self.client_id).await {
+        let current_sender = self
+            .app_context
+            .tg_context()
+            .chats()
+            .get(&chat_id)
+            .and_then(|chat| chat.message_sender_id.clone());
+        if let Some(sender) = message_sender_to_attach(
+            self.app_context.tg_context().send_as(chat_id),
+            current_sender.as_ref(),
+        ) {
+            self.set_chat_message_sender(chat_id, sender).await;
+        }
+        match self
+            .request(functions::send_message(
+                chat_id,
+                0,
+                reply_to,
+                None,
+                text,
+                self.client_id,
+            ))
+            .await
+        {
             Ok(tdlib_rs::enums::Message::Message(message)) => Ok(message),
             Err(e) => {
                 tracing::error!("Failed to send message: {e:?}");
@@ -207,6 +552,154 @@ impl TgBackend {
         }
     }
 
+    /// Download a document and open it with the default application of the
+    /// system, the outcome is shown in the status bar.
+    /// The download is spawned on its own task so that a large file does not
+    /// block the main loop.
+    ///
+    /// # Arguments
+    /// * `file_id` - The identifier of the file.
+    /// * `file_name` - The name of the file, shown in the status bar.
+    pub fn open_document(&self, file_id: i32, file_name: String) {
+        let tg_context = self.app_context.tg_context();
+        let action_tx = self.app_context.action_tx().clone();
+        let event_tx = self.event_tx.clone();
+        let client_id = self.client_id;
+        tokio::spawn(async move {
+            tg_context
+                .pending_operations()
+                .start("Downloading".to_string());
+            let _ = event_tx.send(Event::OperationStarted("Downloading".to_string()));
+            let permit = tg_context.request_gate().acquire().await;
+            let response = functions::download_file(file_id, 1, 0, 0, true, client_id).await;
+            drop(permit);
+            tg_context.pending_operations().finish("Downloading");
+            let _ = event_tx.send(Event::OperationFinished("Downloading".to_string()));
+            let status = match response {
+                Ok(enums::File::File(file)) => match open_with_default_app(&file.local.path) {
+                    Ok(()) => format!("Opened {}", file_name),
+                    Err(e) => format!("Failed to open {}: {}", file_name, e),
+                },
+                Err(e) => {
+                    tracing::error!("Failed to download file {file_id}: {e:?}");
+                    format!("Failed to download {}: {}", file_name, e.message)
+                }
+            };
+            let _ = action_tx.send(Action::StatusMessage(status));
+        });
+    }
+
+    /// Send the draft of a chat as a message, the draft is cleared once it is
+    /// sent.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    pub async fn send_draft(&self, chat_id: i64) -> Result<(), tdlib_rs::types::Error> {
+        let draft = self
+            .app_context
+            .tg_context()
+            .chats()
+            .get(&chat_id)
+            .and_then(|chat| chat.draft_message.clone());
+        let Some(draft) = draft else {
+            return Ok(());
+        };
+        let content = match draft.input_message_text {
+            InputMessageContent::InputMessageText(text) => {
+                InputMessageContent::InputMessageText(InputMessageText {
+                    clear_draft: true,
+                    ..text
+                })
+            }
+            content => content,
+        };
+        match self
+            .request(functions::send_message(
+                chat_id,
+                0,
+                draft.reply_to,
+                None,
+                content,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!("Failed to send the draft of chat {chat_id}: {e:?}");
+                Err(e)
+            }
+        }
+    }
+
+    /// Save a text as the draft of a chat, it replaces the previous draft.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    /// * `text` - The text of the draft.
+    pub async fn save_draft(&self, chat_id: i64, text: String) {
+        let draft = DraftMessage {
+            reply_to: None,
+            date: chrono::Utc::now().timestamp() as i32,
+            input_message_text: InputMessageContent::InputMessageText(InputMessageText {
+                text: tdlib_rs::types::FormattedText {
+                    text,
+                    entities: vec![],
+                },
+                link_preview_options: None,
+                clear_draft: false,
+            }),
+        };
+        match self
+            .request(functions::set_chat_draft_message(
+                chat_id,
+                0,
+                Some(draft),
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(_) => tracing::info!("Draft of chat {chat_id} saved"),
+            Err(e) => tracing::error!("Failed to save the draft of chat {chat_id}: {e:?}"),
+        }
+    }
+
+    /// Translate the text of a message.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat of the message.
+    /// * `message_id` - The identifier of the message.
+    /// * `to_language_code` - The code of the language to translate to.
+    ///
+    /// # Returns
+    /// * `Result<String, Error>` - The translated text, or the error when the
+    ///   language is not supported or the message can not be translated.
+    pub async fn translate_message_text(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        to_language_code: &str,
+    ) -> Result<String, tdlib_rs::types::Error> {
+        match self
+            .operation(
+                "Translating",
+                functions::translate_message_text(
+                    chat_id,
+                    message_id,
+                    to_language_code.to_string(),
+                    self.client_id,
+                ),
+            )
+            .await
+        {
+            Ok(enums::FormattedText::FormattedText(text)) => Ok(text.text),
+            Err(e) => {
+                tracing::error!("Failed to translate message {message_id}: {e:?}");
+                Err(e)
+            }
+        }
+    }
+
     pub async fn send_message_edited(&self, message_id: i64, message: String) {
         let text = InputMessageContent::InputMessageText(InputMessageText {
             text: tdlib_rs::types::FormattedText {
@@ -216,26 +709,511 @@ impl TgBackend {
             link_preview_options: None,
             clear_draft: true,
         });
-        match functions::edit_message_text(
-            self.app_context.tg_context().open_chat_id(),
-            message_id,
-            text,
-            self.client_id,
-        )
-        .await
+        match self
+            .request(functions::edit_message_text(
+                self.app_context.tg_context().open_chat_id(),
+                message_id,
+                text,
+                self.client_id,
+            ))
+            .await
         {
             Ok(_) => tracing::info!("Message edited"),
             Err(e) => tracing::error!("Failed to edit message: {e:?}"),
         }
     }
 
-    pub async fn delete_messages(&self, chat_id: i64, message_ids: Vec<i64>, revoke: bool) {
-        match functions::delete_messages(chat_id, message_ids, revoke, self.client_id).await {
-            Ok(_) => tracing::info!("Messages deleted"),
-            Err(e) => tracing::error!("Failed to delete messages: {e:?}"),
+    pub async fn delete_messages(
+        &self,
+        chat_id: i64,
+        message_ids: Vec<i64>,
+        revoke: bool,
+    ) -> Result<(), tdlib_rs::types::Error> {
+        match self
+            .request(functions::delete_messages(
+                chat_id,
+                message_ids,
+                revoke,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(()) => {
+                tracing::info!("Messages deleted");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Failed to delete messages: {e:?}");
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn forward_messages(
+        &self,
+        chat_id: i64,
+        from_chat_id: i64,
+        message_ids: Vec<i64>,
+        send_copy: bool,
+    ) {
+        match self
+            .request(functions::forward_messages(
+                chat_id,
+                0,
+                from_chat_id,
+                message_ids,
+                None,
+                send_copy,
+                false,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(_) => tracing::info!("Messages forwarded to {}", chat_id),
+            Err(e) => tracing::error!("Failed to forward messages: {e:?}"),
+        }
+    }
+
+    pub async fn leave_chat(&self, chat_id: i64) -> Result<(), tdlib_rs::types::Error> {
+        match self
+            .request(functions::leave_chat(chat_id, self.client_id))
+            .await
+        {
+            Ok(()) => {
+                tracing::info!("Chat {} left", chat_id);
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Failed to leave chat: {e:?}");
+                Err(e)
+            }
         }
     }
 
+    pub async fn set_emoji_status(
+        &self,
+        custom_emoji_id: i64,
+    ) -> Result<(), tdlib_rs::types::Error> {
+        match self
+            .request(functions::set_emoji_status(
+                emoji_status(custom_emoji_id),
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(()) => {
+                tracing::info!("Emoji status set to {}", custom_emoji_id);
+                let emoji = self.get_custom_emoji(custom_emoji_id).await;
+                self.app_context.tg_context().set_emoji_status(emoji);
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Failed to set emoji status: {e:?}");
+                Err(e)
+            }
+        }
+    }
+
+    /// Get the emoji that corresponds to a custom emoji, `None` when the
+    /// identifier is `0` or the custom emoji cannot be found.
+    async fn get_custom_emoji(&self, custom_emoji_id: i64) -> Option<String> {
+        if custom_emoji_id == 0 {
+            return None;
+        }
+        match self
+            .request(functions::get_custom_emoji_stickers(
+                vec![custom_emoji_id],
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(Stickers::Stickers(stickers)) => {
+                stickers.stickers.into_iter().next().map(|s| s.emoji)
+            }
+            Err(e) => {
+                tracing::error!("Failed to get custom emoji: {e:?}");
+                None
+            }
+        }
+    }
+
+    pub async fn set_chat_mute_for(&self, chat_id: i64, mute_for: i32) {
+        let notification_settings = match self.app_context.tg_context().chats().get(&chat_id) {
+            Some(chat) => {
+                let mut notification_settings = chat.notification_settings.clone();
+                notification_settings.use_default_mute_for = false;
+                notification_settings.mute_for = mute_for;
+                notification_settings
+            }
+            None => return,
+        };
+        match self
+            .request(functions::set_chat_notification_settings(
+                chat_id,
+                notification_settings,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(_) => tracing::info!("Chat {} muted for {} seconds", chat_id, mute_for),
+            Err(e) => tracing::error!("Failed to set chat notification settings: {e:?}"),
+        }
+    }
+
+    pub async fn get_callback_query_answer(&self, chat_id: i64, message_id: i64, data: String) {
+        let payload = CallbackQueryPayload::Data(CallbackQueryPayloadData { data });
+        match self
+            .request(functions::get_callback_query_answer(
+                chat_id,
+                message_id,
+                payload,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(enums::CallbackQueryAnswer::CallbackQueryAnswer(answer)) => {
+                tracing::info!("Callback query answer: {}", answer.text)
+            }
+            Err(e) => tracing::error!("Failed to get callback query answer: {e:?}"),
+        }
+    }
+
+    // Only the last page of the history is searched, the calls are expected
+    // to be recent.
+    pub async fn get_call_history(&self, chat_id: i64) -> Vec<TdCallEntry> {
+        match self
+            .request(functions::get_chat_history(
+                chat_id,
+                0,
+                0,
+                100,
+                false,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(Messages::Messages(messages)) => messages
+                .messages
+                .iter()
+                .flatten()
+                .filter_map(TdCallEntry::from_message)
+                .collect(),
+            Err(e) => {
+                tracing::error!("Failed to get call history: {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    // The current session is listed first, then the most recently used.
+    pub async fn get_active_sessions(&self) -> Vec<TdSession> {
+        match self
+            .request(functions::get_active_sessions(self.client_id))
+            .await
+        {
+            Ok(enums::Sessions::Sessions(sessions)) => {
+                let mut sessions = sessions
+                    .sessions
+                    .iter()
+                    .map(TdSession::from)
+                    .collect::<Vec<TdSession>>();
+                sessions.sort_by_key(|s| (!s.is_current, std::cmp::Reverse(s.last_active_date)));
+                sessions
+            }
+            Err(e) => {
+                tracing::error!("Failed to get active sessions: {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    pub async fn terminate_session(&self, session_id: i64) -> Result<(), tdlib_rs::types::Error> {
+        match self
+            .request(functions::terminate_session(session_id, self.client_id))
+            .await
+        {
+            Ok(()) => {
+                tracing::info!("Session {} terminated", session_id);
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Failed to terminate session: {e:?}");
+                Err(e)
+            }
+        }
+    }
+
+    // The text is parsed like TDLib parses the formatted messages, it is not
+    // sent.
+    pub async fn parse_text_entities(
+        &self,
+        text: String,
+        parse_mode: TdParseMode,
+    ) -> Result<tdlib_rs::types::FormattedText, tdlib_rs::types::Error> {
+        match self
+            .request(functions::parse_text_entities(
+                text,
+                parse_mode.into(),
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(enums::FormattedText::FormattedText(formatted_text)) => Ok(formatted_text),
+            Err(e) => {
+                tracing::error!("Failed to parse text entities: {e:?}");
+                Err(e)
+            }
+        }
+    }
+
+    // The thread of a channel post is in the discussion group of the
+    // channel, its chat and thread identifiers are returned.
+    pub async fn get_message_thread(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+    ) -> Result<(i64, i64), tdlib_rs::types::Error> {
+        match self
+            .request(functions::get_message_thread(
+                chat_id,
+                message_id,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(enums::MessageThreadInfo::MessageThreadInfo(thread)) => {
+                Ok((thread.chat_id, thread.message_thread_id))
+            }
+            Err(e) => {
+                tracing::error!("Failed to get message thread: {e:?}");
+                Err(e)
+            }
+        }
+    }
+
+    // The scheduled messages are returned the next to be sent first.
+    pub async fn get_chat_scheduled_messages(&self, chat_id: i64) -> Vec<TdScheduledMessage> {
+        match self
+            .request(functions::get_chat_scheduled_messages(
+                chat_id,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(Messages::Messages(messages)) => messages
+                .messages
+                .iter()
+                .flatten()
+                .filter_map(TdScheduledMessage::from_message)
+                .collect(),
+            Err(e) => {
+                tracing::error!("Failed to get chat scheduled messages: {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    // A `None` scheduling state sends the scheduled message immediately.
+    pub async fn edit_message_scheduling_state(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+        scheduling_state: Option<enums::MessageSchedulingState>,
+    ) -> Result<(), tdlib_rs::types::Error> {
+        match self
+            .request(functions::edit_message_scheduling_state(
+                chat_id,
+                message_id,
+                scheduling_state,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                tracing::error!("Failed to edit message scheduling state: {e:?}");
+                Err(e)
+            }
+        }
+    }
+
+    // The users that are not bots have no menu.
+    pub async fn get_bot_menu(&self, user_id: i64) -> Option<TdBotMenu> {
+        match self
+            .request(functions::get_user_full_info(user_id, self.client_id))
+            .await
+        {
+            Ok(enums::UserFullInfo::UserFullInfo(user_full_info)) => user_full_info
+                .bot_info
+                .as_ref()
+                .and_then(TdBotMenu::from_bot_info),
+            Err(e) => {
+                tracing::error!("Failed to get user full info: {e:?}");
+                None
+            }
+        }
+    }
+
+    // The first 100 topics are enough for the panel, the forums rarely have
+    // more of them.
+    pub async fn get_forum_topics(&self, chat_id: i64) -> Vec<TdForumTopic> {
+        match self
+            .request(functions::get_forum_topics(
+                chat_id,
+                String::new(),
+                0,
+                0,
+                0,
+                100,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(enums::ForumTopics::ForumTopics(topics)) => {
+                topics.topics.iter().map(TdForumTopic::from).collect()
+            }
+            Err(e) => {
+                tracing::error!("Failed to get forum topics: {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    // Only the most recent polls are searched, like the other messages found
+    // in a chat.
+    pub async fn search_chat_polls(&self, chat_id: i64) -> Vec<TdPollEntry> {
+        match self
+            .operation(
+                "Searching",
+                functions::search_chat_messages(
+                    chat_id,
+                    String::new(),
+                    None,
+                    0,
+                    0,
+                    50,
+                    Some(enums::SearchMessagesFilter::Poll),
+                    0,
+                    0,
+                    self.client_id,
+                ),
+            )
+            .await
+        {
+            Ok(enums::FoundChatMessages::FoundChatMessages(found)) => found
+                .messages
+                .iter()
+                .filter_map(TdPollEntry::from_message)
+                .collect(),
+            Err(e) => {
+                tracing::error!("Failed to search chat polls: {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    // Only the emoji reactions are returned, the custom emoji can not be
+    // rendered in the terminal.
+    pub async fn get_message_available_reactions(
+        &self,
+        chat_id: i64,
+        message_id: i64,
+    ) -> Vec<String> {
+        match self
+            .request(functions::get_message_available_reactions(
+                chat_id,
+                message_id,
+                8,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(AvailableReactions::AvailableReactions(available_reactions)) => {
+                let mut emoji: Vec<String> = Vec::new();
+                for reaction in available_reactions
+                    .top_reactions
+                    .iter()
+                    .chain(available_reactions.recent_reactions.iter())
+                    .chain(available_reactions.popular_reactions.iter())
+                {
+                    if let ReactionType::Emoji(reaction) = &reaction.r#type {
+                        if !emoji.contains(&reaction.emoji) {
+                            emoji.push(reaction.emoji.clone());
+                        }
+                    }
+                }
+                emoji
+            }
+            Err(e) => {
+                tracing::error!("Failed to get message available reactions: {e:?}");
+                vec![]
+            }
+        }
+    }
+
+    pub async fn add_message_reaction(&self, chat_id: i64, message_id: i64, emoji: String) {
+        let reaction_type = ReactionType::Emoji(ReactionTypeEmoji { emoji });
+        match self
+            .request(functions::add_message_reaction(
+                chat_id,
+                message_id,
+                reaction_type,
+                false,
+                true,
+                self.client_id,
+            ))
+            .await
+        {
+            Ok(_) => tracing::info!("Reaction added"),
+            Err(e) => tracing::error!("Failed to add reaction: {e:?}"),
+        }
+    }
+
+    // The resend is spawned on its own task so that waiting for the backoff
+    // does not block the main loop.
+    pub fn retry_send(&self, message_id: i64, attempt: u32) {
+        let tg_context = self.app_context.tg_context();
+        let Some(chat_id) = tg_context.take_failed_send(message_id) else {
+            return;
+        };
+        let (max_retries, backoff_ms) = {
+            let app_config = self.app_context.app_config();
+            (
+                app_config.send_max_retries,
+                app_config.send_retry_backoff_ms,
+            )
+        };
+        let Some(backoff) = send_retry_backoff(attempt, max_retries, backoff_ms) else {
+            tracing::error!(
+                "Message {} failed to send after {} retries",
+                message_id,
+                max_retries
+            );
+            tg_context.mark_send_failed(message_id);
+            return;
+        };
+        let client_id = self.client_id;
+        tokio::spawn(async move {
+            tokio::time::sleep(backoff).await;
+            let permit = tg_context.request_gate().acquire().await;
+            let response =
+                functions::resend_messages(chat_id, vec![message_id], None, client_id).await;
+            drop(permit);
+            match response {
+                Ok(Messages::Messages(messages)) => {
+                    tracing::info!("Message {} resent, attempt {}", message_id, attempt);
+                    for message in messages.messages.into_iter().flatten() {
+                        tg_context.set_send_attempts(message.id, attempt);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to resend message: {e:?}");
+                    tg_context.mark_send_failed(message_id);
+                }
+            }
+        });
+    }
+
     pub async fn use_quick_ack(&self) {
         match functions::set_option(
             String::from("use_quick_ack"),
@@ -339,6 +1317,8 @@ impl TgBackend {
                 telegram_config.api_hash.clone()
             }
         };
+        // TDLib reads its parameters once per client, a new database
+        // directory is used after a restart.
         let database_dir = telegram_config.database_dir.clone();
         let use_file_database = telegram_config.use_file_database;
         let use_chat_info_database = telegram_config.use_chat_info_database;
@@ -535,6 +1515,23 @@ impl TgBackend {
                     match update.clone() {
                         Update::MessageSendSucceeded(update_message) => {
                             tracing::info!("Message sent: {:?}", update_message);
+                            tg_context.take_send_attempts(update_message.old_message_id);
+                        }
+                        Update::MessageSendFailed(update_message) => {
+                            tracing::error!("Message failed to send: {:?}", update_message.error);
+                            let message = update_message.message;
+                            let attempt =
+                                tg_context.take_send_attempts(update_message.old_message_id) + 1;
+                            tg_context.replace_message(
+                                update_message.old_message_id,
+                                MessageEntry::from(&message),
+                            );
+                            tg_context.set_failed_send(message.id, message.chat_id);
+                            if let Some(event_tx) = tg_context.event_tx().as_ref() {
+                                event_tx
+                                    .send(Event::SendRetry(message.id, attempt))
+                                    .unwrap();
+                            }
                         }
                         Update::MessageSendAcknowledged(update_message) => {
                             tg_context.set_last_acknowledged_message_id(update_message.message_id);
@@ -651,8 +1648,30 @@ impl TgBackend {
                                     }
                                     None => update_dequeue.push_back(update),
                                 }
+                            } else if let enums::ChatList::Folder(_) = update_chat.position.list {
+                                // The positions in the folders are only used to
+                                // filter the chat list, they are not indexed.
+                                match tg_context.chats().get_mut(&update_chat.chat_id) {
+                                    Some(chat) => {
+                                        chat.positions
+                                            .retain(|p| p.list != update_chat.position.list);
+                                        if update_chat.position.order != 0 {
+                                            chat.positions.push(update_chat.position);
+                                        }
+                                    }
+                                    None => update_dequeue.push_back(update),
+                                }
                             }
                         }
+                        Update::ChatFolders(update_chat_folders) => {
+                            tg_context.chat_folders().set_folders(
+                                update_chat_folders
+                                    .chat_folders
+                                    .into_iter()
+                                    .map(|folder| (folder.id, folder.title))
+                                    .collect(),
+                            );
+                        }
                         Update::ChatReadInbox(update_chat) => {
                             match tg_context.chats().get_mut(&update_chat.chat_id) {
                                 Some(chat) => {
@@ -850,19 +1869,58 @@ impl TgBackend {
                                 tg_context
                                     .open_chat_messages()
                                     .insert(0, MessageEntry::from(&message));
+                            } else if tg_context.split_chat_id() == chat_id {
+                                tg_context
+                                    .split_chat_messages()
+                                    .insert(0, MessageEntry::from(&message));
                             }
                         }
                         Update::MessageEdited(_) => {}
+                        Update::MessageInteractionInfo(update_message) => {
+                            if tg_context.open_chat_id() == update_message.chat_id {
+                                for m in tg_context.open_chat_messages().iter_mut() {
+                                    if m.id() == update_message.message_id {
+                                        m.set_interaction_info(
+                                            update_message.interaction_info.as_ref(),
+                                        );
+                                    }
+                                }
+                            }
+                        }
                         Update::MessageContent(message) => {
                             if tg_context.open_chat_id() == message.chat_id {
                                 for m in tg_context.open_chat_messages().iter_mut() {
                                     if m.id() == message.message_id {
+                                        // A moving live location is not an edit.
+                                        let is_edit = !matches!(
+                                            message.new_content,
+                                            MessageContent::MessageLocation(_)
+                                        );
+                                        if is_edit {
+                                            tg_context.edit_history().record(
+                                                message.chat_id,
+                                                m.id(),
+                                                m.message_content_to_string(),
+                                            );
+                                        }
                                         m.set_message_content(&message.new_content);
-                                        m.set_is_edited(true);
+                                        if is_edit {
+                                            m.set_is_edited(true);
+                                        }
                                     }
                                 }
                             }
                         }
+                        Update::MessageLiveLocationViewed(update_message) => {
+                            // The new position of a live location arrives with
+                            // `updateMessageContent`, its remaining time is
+                            // computed when the message is rendered.
+                            tracing::debug!(
+                                "Live location viewed: {} in chat {}",
+                                update_message.message_id,
+                                update_message.chat_id
+                            );
+                        }
                         Update::DeleteMessages(update_delete_messages) => {
                             if tg_context.open_chat_id() == update_delete_messages.chat_id {
                                 let mut i = 0;
@@ -886,6 +1944,7 @@ impl TgBackend {
                             // tracing::info!("Unhandled update: {:?}", update);
                         }
                     }
+                    tg_context.set_updated();
                 }
             }
         });
@@ -898,3 +1957,202 @@ fn ask_user(string: &str) -> String {
     std::io::stdin().read_line(&mut input).unwrap();
     input.trim().to_string()
 }
+
+/// Compute the time to wait before retrying to send a message.
+/// The backoff doubles at every attempt.
+///
+/// # Arguments
+/// * `attempt` - The number of the retry attempt, starting from 1.
+/// * `max_retries` - The maximum number of retries.
+/// * `backoff_ms` - The time, in milliseconds, to wait before the first retry.
+///
+/// # Returns
+/// * `Option<Duration>` - The time to wait, or `None` when the retries are
+///   exhausted.
+pub fn send_retry_backoff(attempt: u32, max_retries: u32, backoff_ms: u32) -> Option<Duration> {
+    if attempt == 0 || attempt > max_retries {
+        return None;
+    }
+    let factor = 2u64.saturating_pow(attempt - 1);
+    Some(Duration::from_millis(
+        u64::from(backoff_ms).saturating_mul(factor),
+    ))
+}
+
+/// Get the number of messages to request for a page of the history.
+/// TDLib rejects the values outside of the range from 1 to 100.
+///
+/// # Arguments
+/// * `history_page_size` - The configured number of messages of a page.
+///
+/// # Returns
+/// * `i32` - The number of messages of a page, between 1 and 100.
+pub fn history_page_size_limit(history_page_size: i32) -> i32 {
+    history_page_size.clamp(1, MAX_HISTORY_PAGE_SIZE)
+}
+
+/// Get the sender to set as the default one of a chat before sending a
+/// message to it.
+///
+/// # Arguments
+/// * `chosen` - The identity chosen to send the messages to the chat.
+/// * `current` - The default sender of the chat.
+///
+/// # Returns
+/// * `Option<MessageSender>` - The chosen sender, or `None` when no identity
+///   was chosen or it is already the default one.
+pub fn message_sender_to_attach(
+    chosen: Option<TdMessageSender>,
+    current: Option<&MessageSender>,
+) -> Option<MessageSender> {
+    let chosen = chosen?;
+    if current.map(TdMessageSender::from) == Some(chosen) {
+        return None;
+    }
+    Some(MessageSender::from(&chosen))
+}
+
+/// Get the emoji status to set from the identifier of a custom emoji.
+///
+/// # Arguments
+/// * `custom_emoji_id` - The identifier of the custom emoji, `0` to clear the
+///   emoji status.
+///
+/// # Returns
+/// * `Option<EmojiStatus>` - The emoji status, that never expires, or `None`
+///   to clear it.
+pub fn emoji_status(custom_emoji_id: i64) -> Option<EmojiStatus> {
+    if custom_emoji_id == 0 {
+        return None;
+    }
+    Some(EmojiStatus {
+        custom_emoji_id,
+        expiration_date: 0,
+    })
+}
+
+/// Describe the failure to set the emoji status, it is shown to the user.
+/// Only the Telegram Premium users can set an emoji status.
+///
+/// # Arguments
+/// * `error` - The error returned by TDLib.
+///
+/// # Returns
+/// * `String` - The description of the failure.
+pub fn emoji_status_error(error: &tdlib_rs::types::Error) -> String {
+    if error.message.contains("PREMIUM") {
+        "Setting an emoji status requires Telegram Premium".to_string()
+    } else {
+        format!("Failed to set emoji status: {}", error.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tg::td_enums::TdMessageSender;
+    use crate::tg::tg_backend::{
+        emoji_status, emoji_status_error, history_page_size_limit, message_sender_to_attach,
+        send_retry_backoff,
+    };
+    use std::time::Duration;
+    use tdlib_rs::enums::MessageSender;
+    use tdlib_rs::types::{EmojiStatus, Error, MessageSenderChat, MessageSenderUser};
+
+    #[test]
+    fn test_send_retry_backoff_doubles() {
+        assert_eq!(
+            send_retry_backoff(1, 3, 500),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            send_retry_backoff(2, 3, 500),
+            Some(Duration::from_millis(1000))
+        );
+        assert_eq!(
+            send_retry_backoff(3, 3, 500),
+            Some(Duration::from_millis(2000))
+        );
+    }
+
+    #[test]
+    fn test_history_page_size_limit() {
+        assert_eq!(history_page_size_limit(i32::MIN), 1);
+        assert_eq!(history_page_size_limit(0), 1);
+        assert_eq!(history_page_size_limit(1), 1);
+        assert_eq!(history_page_size_limit(50), 50);
+        assert_eq!(history_page_size_limit(100), 100);
+        assert_eq!(history_page_size_limit(101), 100);
+        assert_eq!(history_page_size_limit(i32::MAX), 100);
+    }
+
+    #[test]
+    fn test_send_retry_backoff_exhausted() {
+        assert_eq!(send_retry_backoff(4, 3, 500), None);
+        assert_eq!(send_retry_backoff(1, 0, 500), None);
+        assert_eq!(send_retry_backoff(0, 3, 500), None);
+    }
+
+    #[test]
+    fn test_send_retry_backoff_saturates() {
+        assert_eq!(
+            send_retry_backoff(u32::MAX, u32::MAX, u32::MAX),
+            Some(Duration::from_millis(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn test_message_sender_to_attach_chosen() {
+        let chosen = Some(TdMessageSender::Chat(-100));
+        let current = MessageSender::User(MessageSenderUser { user_id: 1 });
+        assert_eq!(
+            message_sender_to_attach(chosen, Some(&current)),
+            Some(MessageSender::Chat(MessageSenderChat { chat_id: -100 }))
+        );
+        assert_eq!(
+            message_sender_to_attach(chosen, None),
+            Some(MessageSender::Chat(MessageSenderChat { chat_id: -100 }))
+        );
+    }
+
+    #[test]
+    fn test_message_sender_to_attach_unchanged() {
+        let current = MessageSender::Chat(MessageSenderChat { chat_id: -100 });
+        assert_eq!(
+            message_sender_to_attach(Some(TdMessageSender::Chat(-100)), Some(&current)),
+            None
+        );
+        assert_eq!(message_sender_to_attach(None, Some(&current)), None);
+    }
+
+    #[test]
+    fn test_emoji_status_clear() {
+        assert_eq!(emoji_status(0), None);
+        assert_eq!(
+            emoji_status(42),
+            Some(EmojiStatus {
+                custom_emoji_id: 42,
+                expiration_date: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_emoji_status_error_premium() {
+        let error = Error {
+            code: 400,
+            message: "PREMIUM_ACCOUNT_REQUIRED".to_string(),
+        };
+        assert_eq!(
+            emoji_status_error(&error),
+            "Setting an emoji status requires Telegram Premium"
+        );
+        let error = Error {
+            code: 400,
+            message: "EMOJI_INVALID".to_string(),
+        };
+        assert_eq!(
+            emoji_status_error(&error),
+            "Failed to set emoji status: EMOJI_INVALID"
+        );
+    }
+}
@@ -1,16 +1,31 @@
 use super::message_entry::MessageEntry;
-use crate::tg::message_entry::DateTimeEntry;
+use crate::tg::chat_folders::{is_in_folder, ChatFolders};
+use crate::tg::compose_placeholders::ComposePlaceholders;
+use crate::tg::draft_reminders::DraftReminders;
+use crate::tg::edit_history::EditHistory;
+use crate::tg::idle_presence::IdlePresence;
+use crate::tg::pending_operations::PendingOperations;
+use crate::tg::request_gate::RequestGate;
+use crate::tg::snoozed_chats::SnoozedChats;
+use crate::tg::td_enums::{
+    format_last_seen, TdChatPermissions, TdMessageSender, TdSession, TdUserStatus,
+};
 use crate::{
-    app_error::AppError, components::chat_list_window::ChatListEntry, event::Event,
+    app_error::AppError,
+    components::{
+        chat_list_window::ChatListEntry,
+        unread_dashboard::{sort_by_unread_count, UnreadChat},
+    },
+    event::Event,
     tg::ordered_chat::OrderedChat,
 };
-use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::{
     collections::{BTreeSet, HashMap},
     sync::{Mutex, MutexGuard},
 };
 use tdlib_rs::{
-    enums::ChatType,
+    enums::{ChatMemberStatus, ChatType},
     types::{
         BasicGroup, BasicGroupFullInfo, Chat, SecretChat, Supergroup, SupergroupFullInfo, User,
         UserFullInfo,
@@ -40,9 +55,18 @@ pub struct TgContext {
     // in the chat window.
     open_chat_messages: Mutex<Vec<MessageEntry>>,
     open_chat_user: Mutex<Option<User>>,
+    // The forum topic the messages of the open chat are filtered by, `0`
+    // to show all of them.
+    open_topic_id: AtomicI64,
+    // The newest message shown by the chat window, `0` when the most recent
+    // messages are shown. It is saved with the view state.
+    scroll_message_id: AtomicI64,
 
     last_acknowledged_message_id: AtomicI64,
 
+    /// Whether an update from TDLib has been handled since the last render.
+    updated: AtomicBool,
+
     /// The message id from which to start loading the chat history.
     from_message_id: AtomicI64,
 
@@ -50,6 +74,58 @@ pub struct TgContext {
     reply_message_id: AtomicI64,
     /// reply message text
     reply_message_text: Mutex<String>,
+
+    /// The chat parked on the unfocused side of the split view.
+    /// It is `0` when the split view is closed.
+    split_chat_id: AtomicI64,
+    /// The messages of the chat parked on the unfocused side of the split
+    /// view.
+    split_chat_messages: Mutex<Vec<MessageEntry>>,
+
+    /// The number of send retries already made, by the id of the message
+    /// that is being resent.
+    send_attempts: Mutex<HashMap<i64, u32>>,
+    /// The chat of the messages that failed to send and are waiting to be
+    /// retried, by message id.
+    failed_sends: Mutex<HashMap<i64, i64>>,
+
+    /// The identity chosen to send the messages, by chat id.
+    send_as: Mutex<HashMap<i64, TdMessageSender>>,
+
+    /// The chats hidden from the chat list until a wake time.
+    snoozed_chats: Mutex<SnoozedChats>,
+
+    /// The texts shown in the empty compose box, by chat id.
+    compose_placeholders: Mutex<ComposePlaceholders>,
+
+    /// The drafts that are shown again at a wake time.
+    draft_reminders: Mutex<DraftReminders>,
+
+    /// The previous versions of the messages edited while the client is
+    /// running.
+    edit_history: Mutex<EditHistory>,
+
+    /// The limit of the TDLib requests in flight.
+    request_gate: RequestGate,
+
+    /// The long operations that are running, shown in the status bar.
+    pending_operations: Mutex<PendingOperations>,
+
+    /// The inactivity of the user, used to set the user offline when idle.
+    idle_presence: Mutex<IdlePresence>,
+
+    /// The chat folders of the user and the one the chat list is filtered by.
+    chat_folders: Mutex<ChatFolders>,
+
+    /// The emoji of the custom emoji status of the user, if any.
+    emoji_status: Mutex<Option<String>>,
+
+    /// The offset from UTC, in seconds, of the time zones by their identifier.
+    time_zones: Mutex<HashMap<String, i32>>,
+
+    /// The sessions of the account listed the last time, used to describe
+    /// and to protect them.
+    active_sessions: Mutex<Vec<TdSession>>,
 }
 
 impl TgContext {
@@ -71,6 +147,30 @@ impl TgContext {
     pub fn chats_index(&self) -> MutexGuard<'_, BTreeSet<OrderedChat>> {
         self.chats_index.lock().unwrap()
     }
+    pub fn snoozed_chats(&self) -> MutexGuard<'_, SnoozedChats> {
+        self.snoozed_chats.lock().unwrap()
+    }
+    pub fn compose_placeholders(&self) -> MutexGuard<'_, ComposePlaceholders> {
+        self.compose_placeholders.lock().unwrap()
+    }
+    pub fn draft_reminders(&self) -> MutexGuard<'_, DraftReminders> {
+        self.draft_reminders.lock().unwrap()
+    }
+    pub fn edit_history(&self) -> MutexGuard<'_, EditHistory> {
+        self.edit_history.lock().unwrap()
+    }
+    pub fn request_gate(&self) -> &RequestGate {
+        &self.request_gate
+    }
+    pub fn pending_operations(&self) -> MutexGuard<'_, PendingOperations> {
+        self.pending_operations.lock().unwrap()
+    }
+    pub fn idle_presence(&self) -> MutexGuard<'_, IdlePresence> {
+        self.idle_presence.lock().unwrap()
+    }
+    pub fn chat_folders(&self) -> MutexGuard<'_, ChatFolders> {
+        self.chat_folders.lock().unwrap()
+    }
     pub fn users_full_info(&self) -> MutexGuard<'_, HashMap<i64, UserFullInfo>> {
         self.users_full_info.lock().unwrap()
     }
@@ -101,6 +201,12 @@ impl TgContext {
     pub fn last_acknowledged_message_id(&self) -> i64 {
         self.last_acknowledged_message_id.load(Ordering::Relaxed)
     }
+    pub fn split_chat_id(&self) -> i64 {
+        self.split_chat_id.load(Ordering::Relaxed)
+    }
+    pub fn split_chat_messages(&self) -> MutexGuard<'_, Vec<MessageEntry>> {
+        self.split_chat_messages.lock().unwrap()
+    }
 
     pub fn set_open_chat_user(&self, user: Option<User>) {
         *self.open_chat_user() = user;
@@ -110,10 +216,63 @@ impl TgContext {
         self.open_chat_id.store(chat_id, Ordering::Relaxed);
     }
 
+    pub fn open_topic_id(&self) -> i64 {
+        self.open_topic_id.load(Ordering::Relaxed)
+    }
+
+    pub fn set_open_topic_id(&self, message_thread_id: i64) {
+        self.open_topic_id
+            .store(message_thread_id, Ordering::Relaxed);
+    }
+
+    pub fn scroll_message_id(&self) -> i64 {
+        self.scroll_message_id.load(Ordering::Relaxed)
+    }
+
+    pub fn set_scroll_message_id(&self, message_id: i64) {
+        self.scroll_message_id.store(message_id, Ordering::Relaxed);
+    }
+
     pub fn clear_open_chat_messages(&self) {
         *self.open_chat_messages() = Vec::new();
     }
 
+    // This is used to open a chat starting from an empty history.
+    pub fn open_chat(&self, chat_id: i64) {
+        self.set_open_chat_user(self.user_of_chat(chat_id));
+        self.set_open_chat_id(chat_id);
+        self.set_open_topic_id(0);
+        self.clear_open_chat_messages();
+        self.set_from_message_id(0);
+    }
+
+    // This is used to move the open chat on the unfocused side of the split
+    // view.
+    pub fn park_open_chat(&self) {
+        self.split_chat_id
+            .store(self.open_chat_id(), Ordering::Relaxed);
+        *self.split_chat_messages() = std::mem::take(&mut *self.open_chat_messages());
+    }
+
+    // This is used to switch the open chat with the one parked on the
+    // unfocused side of the split view.
+    pub fn swap_split_chat(&self) {
+        let open_chat_id = self.open_chat_id();
+        self.set_open_chat_id(self.split_chat_id());
+        self.split_chat_id.store(open_chat_id, Ordering::Relaxed);
+        std::mem::swap(
+            &mut *self.open_chat_messages(),
+            &mut *self.split_chat_messages(),
+        );
+        self.set_from_message_id(self.open_chat_messages().last().map_or(0, |m| m.id()));
+        self.set_open_chat_user(self.user_of_chat(self.open_chat_id()));
+    }
+
+    pub fn clear_split_chat(&self) {
+        self.split_chat_id.store(0, Ordering::Relaxed);
+        *self.split_chat_messages() = Vec::new();
+    }
+
     pub fn set_from_message_id(&self, from_message_id: i64) {
         self.from_message_id
             .store(from_message_id, Ordering::Relaxed);
@@ -123,11 +282,76 @@ impl TgContext {
         self.me.store(me, Ordering::Relaxed);
     }
 
+    pub fn emoji_status(&self) -> MutexGuard<'_, Option<String>> {
+        self.emoji_status.lock().unwrap()
+    }
+
+    pub fn time_zones(&self) -> MutexGuard<'_, HashMap<String, i32>> {
+        self.time_zones.lock().unwrap()
+    }
+
+    pub fn active_sessions(&self) -> MutexGuard<'_, Vec<TdSession>> {
+        self.active_sessions.lock().unwrap()
+    }
+
+    pub fn set_active_sessions(&self, sessions: Vec<TdSession>) {
+        *self.active_sessions() = sessions;
+    }
+
+    /// Get a session of the account listed the last time.
+    ///
+    /// # Arguments
+    /// * `session_id` - The identifier of the session.
+    pub fn active_session(&self, session_id: i64) -> Option<TdSession> {
+        self.active_sessions()
+            .iter()
+            .find(|session| session.id == session_id)
+            .cloned()
+    }
+
+    /// Get the offset from UTC of the time zone of a user.
+    /// The time zone is known only when the user has set the opening hours
+    /// of its business.
+    ///
+    /// # Arguments
+    /// * `user_id` - The identifier of the user.
+    ///
+    /// # Returns
+    /// * `Option<i32>` - The offset from UTC, in seconds, if known.
+    pub fn user_utc_offset(&self, user_id: i64) -> Option<i32> {
+        let time_zone_id = self
+            .users_full_info()
+            .get(&user_id)?
+            .business_info
+            .as_ref()?
+            .opening_hours
+            .as_ref()?
+            .time_zone_id
+            .clone();
+        self.time_zones().get(&time_zone_id).copied()
+    }
+
+    pub fn set_emoji_status(&self, emoji_status: Option<String>) {
+        *self.emoji_status() = emoji_status;
+    }
+
     pub fn set_last_acknowledged_message_id(&self, message_id: i64) {
         self.last_acknowledged_message_id
             .store(message_id, Ordering::Relaxed);
     }
 
+    /// Mark that an update from TDLib has been handled, so the user interface
+    /// has to be rendered again.
+    pub fn set_updated(&self) {
+        self.updated.store(true, Ordering::Release);
+    }
+
+    /// Check whether an update from TDLib has been handled since the last
+    /// call, and clear it.
+    pub fn take_updated(&self) -> bool {
+        self.updated.swap(false, Ordering::AcqRel)
+    }
+
     pub fn set_event_tx(&self, event_tx: UnboundedSender<Event>) {
         *self.event_tx() = Some(event_tx);
     }
@@ -143,23 +367,70 @@ impl TgContext {
         open_chat_messages.retain(|message| message.id() != message_id);
     }
 
-    pub fn open_chat_user_status(&self) -> String {
-        if let Some(user) = self.open_chat_user().as_ref() {
-            return match &user.status {
-                tdlib_rs::enums::UserStatus::Empty => "Empty".to_string(),
-                tdlib_rs::enums::UserStatus::Online(_) => "Online".to_string(),
-                tdlib_rs::enums::UserStatus::Offline(offline) => {
-                    format!(
-                        "Last seen {}",
-                        DateTimeEntry::convert_time(offline.was_online)
-                    )
-                }
-                tdlib_rs::enums::UserStatus::Recently(_) => "Last seen recently ".to_string(),
-                tdlib_rs::enums::UserStatus::LastWeek(_) => "Last seen LastWeek".to_string(),
-                tdlib_rs::enums::UserStatus::LastMonth(_) => "Last seen LastMonth ".to_string(),
-            };
+    // This is used to keep the displayed messages in sync when TDLib
+    // changes the id of a message, e.g. when it fails to send.
+    pub fn replace_message(&self, old_message_id: i64, message: MessageEntry) {
+        for mut messages in [self.open_chat_messages(), self.split_chat_messages()] {
+            if let Some(m) = messages.iter_mut().find(|m| m.id() == old_message_id) {
+                *m = message.clone();
+            }
+        }
+    }
+
+    // This is used to flag the messages whose send retries are exhausted.
+    pub fn mark_send_failed(&self, message_id: i64) {
+        for mut messages in [self.open_chat_messages(), self.split_chat_messages()] {
+            if let Some(m) = messages.iter_mut().find(|m| m.id() == message_id) {
+                m.set_send_failed(true);
+            }
         }
-        "".to_string()
+    }
+
+    pub fn send_as(&self, chat_id: i64) -> Option<TdMessageSender> {
+        self.send_as.lock().unwrap().get(&chat_id).copied()
+    }
+
+    pub fn set_send_as(&self, chat_id: i64, sender: TdMessageSender) {
+        self.send_as.lock().unwrap().insert(chat_id, sender);
+    }
+
+    pub fn take_send_attempts(&self, message_id: i64) -> u32 {
+        self.send_attempts
+            .lock()
+            .unwrap()
+            .remove(&message_id)
+            .unwrap_or_default()
+    }
+
+    pub fn set_send_attempts(&self, message_id: i64, attempts: u32) {
+        self.send_attempts
+            .lock()
+            .unwrap()
+            .insert(message_id, attempts);
+    }
+
+    pub fn set_failed_send(&self, message_id: i64, chat_id: i64) {
+        self.failed_sends
+            .lock()
+            .unwrap()
+            .insert(message_id, chat_id);
+    }
+
+    pub fn take_failed_send(&self, message_id: i64) -> Option<i64> {
+        self.failed_sends.lock().unwrap().remove(&message_id)
+    }
+
+    // The open chat user is a copy taken when the chat is opened, the status
+    // kept up to date by the updates is the one of the users.
+    pub fn open_chat_user_status(&self) -> String {
+        let Some(open_chat_user) = self.open_chat_user().clone() else {
+            return "".to_string();
+        };
+        let users = self.users();
+        let status = users
+            .get(&open_chat_user.id)
+            .map_or(&open_chat_user.status, |user| &user.status);
+        format_last_seen(&TdUserStatus::from(status))
     }
 
     pub fn unread_messages(&self) -> Vec<i64> {
@@ -208,6 +479,124 @@ impl TgContext {
         None
     }
 
+    /// Get the timestamps of the loaded messages of a chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat, the open chat or the one on
+    ///   the other side of the split view.
+    ///
+    /// # Returns
+    /// * `Vec<i64>` - The timestamps, in seconds, empty when the history of
+    ///   the chat is not loaded.
+    pub fn loaded_timestamps(&self, chat_id: i64) -> Vec<i64> {
+        let messages = if chat_id == self.open_chat_id() {
+            self.open_chat_messages()
+        } else if chat_id == self.split_chat_id() {
+            self.split_chat_messages()
+        } else {
+            return vec![];
+        };
+        messages
+            .iter()
+            .map(|message| message.timestamp().timestamp as i64)
+            .collect()
+    }
+
+    /// Get the usernames of the senders of the messages of the open chat, they
+    /// are used to complete the mentions.
+    ///
+    /// # Returns
+    /// * `Vec<String>` - The sorted usernames, without the `@`.
+    pub fn open_chat_usernames(&self) -> Vec<String> {
+        let users = self.users();
+        let mut usernames = self
+            .open_chat_messages()
+            .iter()
+            .filter_map(|message| users.get(&message.sender_id()))
+            .filter_map(|user| user.usernames.as_ref()?.active_usernames.first().cloned())
+            .collect::<Vec<String>>();
+        usernames.sort();
+        usernames.dedup();
+        usernames
+    }
+
+    pub fn set_chat_mute_for(&self, chat_id: i64, mute_for: i32) {
+        if let Some(chat) = self.chats().get_mut(&chat_id) {
+            chat.notification_settings.use_default_mute_for = false;
+            chat.notification_settings.mute_for = mute_for;
+        }
+    }
+
+    pub fn user_of_chat(&self, chat_id: i64) -> Option<User> {
+        let chat_type = self.chats().get(&chat_id).map(|c| c.r#type.clone());
+        if let Some(ChatType::Private(p)) = chat_type {
+            return self.users().get(&p.user_id).cloned();
+        }
+        None
+    }
+
+    /// Whether the current user is the owner of the group or the channel.
+    pub fn is_chat_owner(&self, chat_id: i64) -> bool {
+        let chat_type = self.chats().get(&chat_id).map(|chat| chat.r#type.clone());
+        match chat_type {
+            Some(ChatType::BasicGroup(bg)) => self
+                .basic_groups()
+                .get(&bg.basic_group_id)
+                .is_some_and(|basic_group| {
+                    matches!(basic_group.status, ChatMemberStatus::Creator(_))
+                }),
+            Some(ChatType::Supergroup(sg)) => self
+                .supergroups()
+                .get(&sg.supergroup_id)
+                .is_some_and(|supergroup| {
+                    matches!(supergroup.status, ChatMemberStatus::Creator(_))
+                }),
+            _ => false,
+        }
+    }
+
+    /// Summarize what the current user can do in a chat.
+    ///
+    /// # Arguments
+    /// * `chat_id` - The identifier of the chat.
+    ///
+    /// # Returns
+    /// * `Option<TdChatPermissions>` - The permissions of the current user and
+    ///   the slow mode delay, `None` when the chat is unknown.
+    pub fn chat_permissions(&self, chat_id: i64) -> Option<TdChatPermissions> {
+        let (permissions, chat_type) = self
+            .chats()
+            .get(&chat_id)
+            .map(|chat| (chat.permissions.clone(), chat.r#type.clone()))?;
+        let (status, slow_mode_delay) = match chat_type {
+            ChatType::BasicGroup(bg) => (
+                self.basic_groups()
+                    .get(&bg.basic_group_id)
+                    .map(|basic_group| basic_group.status.clone()),
+                0,
+            ),
+            ChatType::Supergroup(sg) => (
+                self.supergroups()
+                    .get(&sg.supergroup_id)
+                    .map(|supergroup| supergroup.status.clone()),
+                self.supergroups_full_info()
+                    .get(&sg.supergroup_id)
+                    .map_or(0, |full_info| full_info.slow_mode_delay),
+            ),
+            _ => (None, 0),
+        };
+        Some(TdChatPermissions::new(
+            &permissions,
+            status.as_ref(),
+            slow_mode_delay,
+        ))
+    }
+
+    /// Remove a chat from the chat list, e.g. because it has been left.
+    pub fn remove_chat_from_list(&self, chat_id: i64) {
+        self.chats_index().retain(|chat| chat.chat_id != chat_id);
+    }
+
     pub fn name_from_chats(&self, chat_id: i64) -> Option<String> {
         if let Some(chat) = self.chats().get(&chat_id) {
             return Some(chat.title.clone());
@@ -230,19 +619,57 @@ impl TgContext {
         None
     }
 
+    /// Get the chats with unread messages, sorted by their number of unread
+    /// messages.
+    ///
+    /// # Returns
+    /// * `Vec<UnreadChat>` - The chats, with the text of their last message.
+    pub fn unread_chats(&self) -> Vec<UnreadChat> {
+        // The chats index is locked first, as in `get_chats_index`.
+        let chats_index = self.chats_index();
+        let chats = self.chats();
+        let unread_chats = chats_index
+            .iter()
+            .filter_map(|ordered_chat| chats.get(&ordered_chat.chat_id))
+            .filter(|chat| chat.unread_count > 0)
+            .map(|chat| UnreadChat {
+                chat_id: chat.id,
+                title: chat.title.clone(),
+                unread_count: chat.unread_count,
+                preview: chat
+                    .last_message
+                    .as_ref()
+                    .map(|message| MessageEntry::from(message).message_content_to_string())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        sort_by_unread_count(unread_chats)
+    }
+
     pub fn get_chats_index(&self) -> Result<Option<Vec<ChatListEntry>>, AppError<Event>> {
         let chats_index = self.chats_index();
         let chats = self.chats();
+        let snoozed_chats = self.snoozed_chats();
+        let chat_ids = snoozed_chats.order(chats_index.iter().map(|c| c.chat_id).collect());
+        // Only the chats of the main list that are part of the active folder
+        // are shown.
+        let chat_ids = self.chat_folders().filter(chat_ids, |chat_id, folder_id| {
+            chats
+                .get(&chat_id)
+                .is_some_and(|chat| is_in_folder(&chat.positions, folder_id))
+        });
         let mut chat_list: Vec<ChatListEntry> = Vec::new();
-        for ord_chat in chats_index.iter() {
+        for chat_id in chat_ids {
             let mut chat_list_item = ChatListEntry::new();
-            chat_list_item.set_chat_id(ord_chat.chat_id);
-            if let Some(chat) = chats.get(&ord_chat.chat_id) {
-                chat_list_item.set_is_marked_as_unread(chat.unread_count > 0);
+            chat_list_item.set_chat_id(chat_id);
+            chat_list_item.set_is_reminder(snoozed_chats.is_woken(chat_id));
+            if let Some(chat) = chats.get(&chat_id) {
+                chat_list_item.set_is_marked_as_unread(chat.is_marked_as_unread);
                 chat_list_item.set_chat_name(chat.title.clone());
                 chat_list_item.set_last_read_inbox_message_id(chat.last_read_inbox_message_id);
                 chat_list_item.set_last_read_outbox_message_id(chat.last_read_outbox_message_id);
                 chat_list_item.set_unread_count(chat.unread_count);
+                chat_list_item.set_is_muted(chat.notification_settings.mute_for > 0);
                 if let Some(chat_message) = &chat.last_message {
                     chat_list_item.set_last_message(MessageEntry::from(chat_message));
                 }
@@ -1,5 +1,5 @@
 use {
-    crate::{app_context::AppContext, event::Event},
+    crate::{app_context::AppContext, event::Event, render_coalescer::RenderCoalescer},
     crossterm::{
         cursor,
         event::{
@@ -43,6 +43,9 @@ pub struct TuiBackend {
     /// A boolean flag that represents whether the paste mode is enabled or
     /// not.
     pub paste: bool,
+    /// The coalescer of the renders, it merges the updates received within
+    /// the render interval into a single render.
+    pub render_coalescer: RenderCoalescer,
 }
 
 impl TuiBackend {
@@ -70,6 +73,7 @@ impl TuiBackend {
             frame_rate,
             mouse,
             paste,
+            render_coalescer: RenderCoalescer::default(),
         })
     }
     /// Enter the user interface and start processing events.
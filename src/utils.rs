@@ -32,6 +32,33 @@ pub fn tgt_config_dir() -> io::Result<PathBuf> {
     Ok(tgt_dir()?.join("config"))
 }
 
+/// Open a file with the default application of the system.
+///
+/// # Arguments
+/// * `path` - The path of the file.
+///
+/// # Returns
+/// * `io::Result<()>` - An Ok result or an error if the application cannot
+///   be started.
+pub fn open_with_default_app(path: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    command
+        .arg(path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
 /// Fail with an error message and exit the application.
 ///
 /// # Arguments